@@ -1,10 +1,8 @@
 use clap::Args;
-use sdml_core::store::InMemoryModuleCache;
 use sdml_errors::{diagnostics::StandardStreamReporter, Error};
 use sdml_generate::convert::doc::{
     org_mode::DocumentationGenerator, BookConfig, DocumentationWriter,
 };
-use sdml_parse::load::FsModuleLoader;
 use std::{path::PathBuf, process::ExitCode};
 
 // ------------------------------------------------------------------------------------------------
@@ -31,8 +29,8 @@ impl super::Command for Command {
         let mut generator = DocumentationGenerator::default();
 
         let reporter = StandardStreamReporter::default();
-        let mut loader = FsModuleLoader::default().with_reporter(Box::new(reporter));
-        let mut cache = InMemoryModuleCache::default().with_stdlib();
+        let mut loader = super::module_loader(Box::new(reporter))?;
+        let mut cache = super::module_cache()?;
 
         generator.write_book(&mut loader, &mut cache, config)?;
 
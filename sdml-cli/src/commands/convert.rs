@@ -4,7 +4,7 @@ use clap::{Args, ValueEnum};
 use sdml_core::model::modules::Module;
 use sdml_core::{
     load::ModuleLoader,
-    store::{InMemoryModuleCache, ModuleStore},
+    store::{disk::DiskModuleCache, ModuleStore},
 };
 use sdml_errors::Error;
 use sdml_generate::convert::{json, rdf, sexpr};
@@ -59,7 +59,7 @@ pub(crate) enum ConvertFormat {
 
 impl super::Command for Command {
     fn execute(&self) -> Result<ExitCode, Error> {
-        call_with_module!(self, |module: &Module, cache: &InMemoryModuleCache, _| {
+        call_with_module!(self, |module: &Module, cache: &DiskModuleCache, _| {
             let mut output = self.files.output.clone();
             let mut writer = output.lock();
 
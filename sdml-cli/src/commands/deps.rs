@@ -3,7 +3,7 @@ use std::process::ExitCode;
 use clap::{Args, ValueEnum};
 use sdml_core::{
     load::ModuleLoader,
-    store::{InMemoryModuleCache, ModuleStore},
+    store::{disk::DiskModuleCache, ModuleStore},
 };
 use sdml_errors::Error;
 use sdml_generate::{
@@ -74,6 +74,16 @@ use sdml_generate::{
 /// └── xsd
 /// ```
 ///
+/// The `--highlight-cycles` argument will mark any import that is part of an import cycle, in
+/// the text tree these are suffixed with `(cycle)` and in the graph they are colored red.
+///
+/// ```text
+/// ❯ sdml deps --depth 2 --highlight-cycles rdf
+/// rdf
+/// └── rdfs (cycle)
+///     └── rdf (cycle)
+/// ```
+///
 #[derive(Args, Debug)]
 pub(crate) struct Command {
     #[arg(short = 'f', long)]
@@ -86,6 +96,10 @@ pub(crate) struct Command {
     #[arg(default_value = "0")]
     depth: usize,
 
+    /// Highlight imports that are part of an import cycle
+    #[arg(short = 'c', long, default_value = "false")]
+    highlight_cycles: bool,
+
     #[command(flatten)]
     files: super::FileArgs,
 }
@@ -108,10 +122,11 @@ pub(crate) enum OutputFormat {
 impl super::Command for Command {
     #[allow(clippy::redundant_closure_call)]
     fn execute(&self) -> Result<ExitCode, Error> {
-        call_with_module!(self, |module, cache: &InMemoryModuleCache, _| {
+        call_with_module!(self, |module, cache: &DiskModuleCache, _| {
             let options: DependencyViewOptions = DependencyViewOptions::default()
                 .with_depth(self.depth)
-                .with_representation(self.output_format.into());
+                .with_representation(self.output_format.into())
+                .with_highlight_cycles(self.highlight_cycles);
             let mut generator = DependencyViewGenerator::default();
             let mut output = self.files.output.clone();
             let mut writer = output.lock();
@@ -0,0 +1,236 @@
+use clap::{Args, ValueEnum};
+use sdml_core::diff::compatibility::Compatibility;
+use sdml_core::diff::{Change, ModuleDiff};
+use sdml_core::load::ModuleLoader;
+use sdml_core::model::modules::Module;
+use sdml_core::store::ModuleStore;
+use sdml_errors::diagnostics::StandardStreamReporter;
+use sdml_errors::Error;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// Compare two versions of a module.
+///
+/// This command compares the definitions, members, variants, annotations, imports, and header of
+/// two module files and lists the differences. Layout, comments, and the order of definitions are
+/// ignored.
+///
+/// ```text
+/// ❯ sdml diff rentals-1.0.sdm rentals-1.1.sdm
+/// ~ module version info changed from 1.0 to 1.1
+/// + import dc
+/// + member Customer.email -> {0..1} xsd:string
+/// ~ member Booking.days cardinality changed from {1..1} to {1..5} (potentially breaking)
+/// - variant Status.Cancelled (breaking)
+/// ```
+///
+/// - Text (default) :: One change per line, prefixed with `+` for additions, `-` for removals,
+///   and `~` for other changes.
+///
+/// - JSON :: A JSON object with the module names and a list of change objects.
+///
+/// - Markdown :: A report with sections for added, removed, and changed elements.
+///
+/// Each change is classified as breaking, potentially breaking, or non-breaking for consumers of
+/// the module. With `--check` the command fails if the module's version info has not increased
+/// along with the module, or if its version URI has not increased along with a breaking change.
+///
+/// ```text
+/// ❯ sdml diff --check rentals-1.0.sdm rentals-1.1.sdm
+/// ~ member Booking.days cardinality changed from {1..1} to {1..5} (potentially breaking)
+/// - variant Status.Cancelled (breaking)
+/// Error: the module has breaking changes but its version info has not increased
+/// Error: the module has breaking changes but its version URI has not increased
+/// ```
+///
+#[derive(Args, Debug)]
+pub(crate) struct Command {
+    #[arg(short = 'f', long)]
+    #[arg(value_enum)]
+    #[arg(default_value_t = OutputFormat::Text)]
+    output_format: OutputFormat,
+
+    /// File name to write to, or '-' to write to stdout
+    #[arg(short, long)]
+    #[clap(value_parser, default_value = "-")]
+    output: clio::Output,
+
+    /// Fail if the module header version has not increased as the changes require
+    #[arg(short, long)]
+    check: bool,
+
+    /// The old version of the module
+    old: PathBuf,
+
+    /// The new version of the module
+    new: PathBuf,
+}
+
+/// The output format of the differences
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum OutputFormat {
+    /// JSON object
+    Json,
+    /// Markdown report
+    Markdown,
+    /// Plain text, one change per line
+    Text,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl super::Command for Command {
+    fn execute(&self) -> Result<ExitCode, Error> {
+        let Some(old) = load_module(&self.old)? else {
+            return Ok(ExitCode::FAILURE);
+        };
+        let Some(new) = load_module(&self.new)? else {
+            return Ok(ExitCode::FAILURE);
+        };
+
+        let diff = ModuleDiff::compare(&old, &new);
+
+        let mut output = self.output.clone();
+        let mut writer = output.lock();
+        match self.output_format {
+            OutputFormat::Text => write_text(&diff, &mut writer)?,
+            OutputFormat::Json => write_json(&diff, &mut writer)?,
+            OutputFormat::Markdown => write_markdown(&diff, &self.old, &self.new, &mut writer)?,
+        }
+
+        if self.check {
+            let missing = diff.missing_version_changes();
+            for field in &missing {
+                eprintln!(
+                    "Error: the module has {} changes but its {field} has not increased",
+                    diff.compatibility()
+                );
+            }
+            if !missing.is_empty() {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+// Each version is loaded into its own store as both will usually have the same module name.
+fn load_module(file: &Path) -> Result<Option<Module>, Error> {
+    let mut cache = super::module_cache()?;
+    let mut loader = super::module_loader(Box::<StandardStreamReporter>::default())?;
+    match loader.load_from_file(file.to_path_buf(), &mut cache, false) {
+        Ok(name) => Ok(cache.get(&name).cloned()),
+        Err(Error::LanguageValidationError { source: _ }) => {
+            loader.reporter_done(None)?;
+            Ok(None)
+        }
+        Err(err @ Error::IoError { source: _ }) => {
+            println!(
+                "Error: the input file `{}` could not be found, or read.",
+                file.display()
+            );
+            Err(err)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+fn write_text(diff: &ModuleDiff, writer: &mut impl Write) -> Result<(), Error> {
+    for change in diff.changes() {
+        match change.compatibility() {
+            Compatibility::NonBreaking => writeln!(writer, "{change}")?,
+            compatibility => writeln!(writer, "{change} ({compatibility})")?,
+        }
+    }
+    Ok(())
+}
+
+// The serialized changes are extended with their classification.
+fn write_json(diff: &ModuleDiff, writer: &mut impl Write) -> Result<(), Error> {
+    let mut value = serde_json::to_value(diff).map_err(std::io::Error::other)?;
+    if let Some(object) = value.as_object_mut() {
+        if let Some(changes) = object.get_mut("changes").and_then(|v| v.as_array_mut()) {
+            for (value, change) in changes.iter_mut().zip(diff.changes()) {
+                if let Some(value) = value.as_object_mut() {
+                    let _ = value.insert(
+                        "compatibility".to_string(),
+                        serde_json::to_value(change.compatibility())
+                            .map_err(std::io::Error::other)?,
+                    );
+                }
+            }
+        }
+        let _ = object.insert(
+            "compatibility".to_string(),
+            serde_json::to_value(diff.compatibility()).map_err(std::io::Error::other)?,
+        );
+    }
+    serde_json::to_writer_pretty(&mut *writer, &value).map_err(std::io::Error::other)?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+fn write_markdown(
+    diff: &ModuleDiff,
+    old: &Path,
+    new: &Path,
+    writer: &mut impl Write,
+) -> Result<(), Error> {
+    writeln!(
+        writer,
+        "# Changes to module `{}`\n\nComparing `{}` with `{}`.\n",
+        diff.new_module(),
+        old.display(),
+        new.display()
+    )?;
+    if diff.is_empty() {
+        writeln!(writer, "No changes.")?;
+        return Ok(());
+    }
+    writeln!(
+        writer,
+        "Overall these changes are **{}**.\n",
+        diff.compatibility()
+    )?;
+    for heading in ["Added", "Removed", "Changed"] {
+        let changes: Vec<&Change> = diff
+            .changes()
+            .filter(|change| change_heading(change) == heading)
+            .collect();
+        if !changes.is_empty() {
+            writeln!(writer, "## {heading}\n")?;
+            for change in changes {
+                match change.compatibility() {
+                    Compatibility::NonBreaking => writeln!(writer, "* {}", change.description())?,
+                    compatibility => {
+                        writeln!(writer, "* {} (**{compatibility}**)", change.description())?
+                    }
+                }
+            }
+            writeln!(writer)?;
+        }
+    }
+    Ok(())
+}
+
+fn change_heading(change: &Change) -> &'static str {
+    if change.is_addition() {
+        "Added"
+    } else if change.is_removal() {
+        "Removed"
+    } else {
+        "Changed"
+    }
+}
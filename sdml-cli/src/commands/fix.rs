@@ -0,0 +1,253 @@
+use clap::{Args, ValueEnum};
+use sdml_core::load::ModuleLoader;
+use sdml_core::model::identifiers::Identifier;
+use sdml_core::model::values::LanguageTag;
+use sdml_core::refactor::fix::{Fix, FixKind};
+use sdml_core::store::ModuleStore;
+use sdml_errors::diagnostics::reporter::CollectingReporter;
+use sdml_errors::diagnostics::StandardStreamReporter;
+use sdml_errors::Error;
+use std::collections::BTreeMap;
+use std::fs::write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// Apply automatic fixes to the modules in a set of files.
+///
+/// The modules are read from the files and directories given, directories are searched for SDML
+/// files, and validated. Diagnostics that can be fixed automatically are fixed by changing the
+/// module source files in place, all comments and layout are preserved.
+///
+/// - preferred-case :: Rename definitions, members, and variants to their preferred case, all
+///   references to a renamed definition are also changed.
+/// - duplicate-import :: Remove duplicate module and member imports.
+/// - missing-import :: Import modules that are referenced by qualified identifiers but not
+///   imported, if they can be found on the search path.
+/// - language-tag :: Add a language tag to strings used as labels, definitions, and other text
+///   annotations.
+///
+/// ```text
+/// ❯ sdml fix models
+/// models/rentals.sdm: 5 fixes
+/// ```
+///
+/// Use `--dry-run` to write the changes as a unified diff without changing any files.
+///
+#[derive(Args, Debug)]
+pub(crate) struct Command {
+    /// Only apply these kinds of fix
+    #[arg(long, value_enum, value_delimiter = ',')]
+    only: Vec<FixKindArg>,
+
+    /// The language tag added to strings without one
+    #[arg(long, default_value = "en")]
+    #[arg(value_parser = LanguageTag::from_str)]
+    language: LanguageTag,
+
+    /// Write the changes as a unified diff without changing any files
+    #[arg(short = 'n', long)]
+    dry_run: bool,
+
+    /// Module files, or directories containing module files, to fix
+    #[arg(default_value = ".")]
+    paths: Vec<PathBuf>,
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum FixKindArg {
+    /// Rename identifiers to their preferred case
+    PreferredCase,
+    /// Remove duplicate imports
+    DuplicateImport,
+    /// Add imports for referenced modules
+    MissingImport,
+    /// Add language tags to text strings
+    LanguageTag,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl super::Command for Command {
+    fn execute(&self) -> Result<ExitCode, Error> {
+        let reporter = CollectingReporter::default();
+        let super::ModuleFiles {
+            cache,
+            loader,
+            files,
+        } = match super::load_module_files(&self.paths, Box::new(reporter.clone()))? {
+            Ok(module_files) => module_files,
+            Err(loader) => {
+                // report the errors that prevent fixing.
+                let loader = loader.with_reporter(Box::<StandardStreamReporter>::default());
+                for diagnostic in reporter.take_suggested_diagnostics() {
+                    loader.report_suggested(&diagnostic)?;
+                }
+                loader.reporter_done(None)?;
+                return Ok(ExitCode::FAILURE);
+            }
+        };
+        let files: BTreeMap<Identifier, PathBuf> = files.into_iter().collect();
+        let module_names: Vec<Identifier> = files.keys().cloned().collect();
+        for name in &module_names {
+            if let Some(module) = cache.get(name) {
+                module.validate(&cache, &loader, false);
+            }
+        }
+
+        let mut fix = Fix::default().with_language(self.language.clone());
+        if !self.only.is_empty() {
+            fix = fix.only(self.only.iter().map(|kind| FixKind::from(*kind)));
+        }
+        let plan = fix.plan(
+            &module_names,
+            &reporter.take_suggested_diagnostics(),
+            &cache,
+            &loader,
+        )?;
+
+        for module_name in plan.module_names() {
+            let Some(file) = files.get(module_name) else {
+                continue;
+            };
+            let Some(source) = cache
+                .get(module_name)
+                .and_then(|module| module.file_id())
+                .and_then(|file_id| loader.get_source(*file_id))
+            else {
+                continue;
+            };
+            let fixed = plan.apply(module_name, source.as_ref());
+            if self.dry_run {
+                print!("{}", unified_diff(file, source.as_ref(), &fixed));
+            } else {
+                write(file, fixed)?;
+                println!(
+                    "{}: {} fixes",
+                    file.display(),
+                    plan.fixes(module_name).len()
+                );
+            }
+        }
+        if plan.is_empty() && !self.dry_run {
+            println!("No fixes required");
+        }
+        for skipped in plan.skipped() {
+            eprintln!("Warning: not fixed, {skipped}");
+        }
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+impl From<FixKindArg> for FixKind {
+    fn from(value: FixKindArg) -> Self {
+        match value {
+            FixKindArg::PreferredCase => Self::PreferredCase,
+            FixKindArg::DuplicateImport => Self::DuplicateImport,
+            FixKindArg::MissingImport => Self::MissingImport,
+            FixKindArg::LanguageTag => Self::LanguageTag,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+const DIFF_CONTEXT: usize = 3;
+
+fn unified_diff(file: &Path, old: &str, new: &str) -> String {
+    // each line with its line number in the old and new text, 0 if not present.
+    let mut lines: Vec<(char, &str, usize, usize)> = Default::default();
+    let (mut old_line, mut new_line) = (1, 1);
+    for line in ::diff::lines(old, new) {
+        match line {
+            ::diff::Result::Left(text) => {
+                lines.push(('-', text, old_line, 0));
+                old_line += 1;
+            }
+            ::diff::Result::Both(text, _) => {
+                lines.push((' ', text, old_line, new_line));
+                old_line += 1;
+                new_line += 1;
+            }
+            ::diff::Result::Right(text) => {
+                lines.push(('+', text, 0, new_line));
+                new_line += 1;
+            }
+        }
+    }
+
+    // `lines` ignores a missing newline on the last line, so a last line shared by both texts
+    // is changed if only one of them has the newline.
+    let old_missing = !old.is_empty() && !old.ends_with('\n');
+    let new_missing = !new.is_empty() && !new.ends_with('\n');
+    if old_missing != new_missing {
+        if let Some((' ', text, old_line, new_line)) = lines.last().copied() {
+            lines.pop();
+            lines.push(('-', text, old_line, 0));
+            lines.push(('+', text, 0, new_line));
+        }
+    }
+    let (old_last, new_last) = (old_line - 1, new_line - 1);
+
+    let changes: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (tag, _, _, _))| *tag != ' ')
+        .map(|(i, _)| i)
+        .collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    let mut hunks: Vec<(usize, usize)> = Default::default();
+    for i in changes {
+        let start = i.saturating_sub(DIFF_CONTEXT);
+        let end = (i + DIFF_CONTEXT + 1).min(lines.len());
+        match hunks.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut result = if file.is_relative() {
+        format!("--- a/{0}\n+++ b/{0}\n", file.display())
+    } else {
+        format!("--- {0}\n+++ {0}\n", file.display())
+    };
+    for (start, end) in hunks {
+        let hunk = &lines[start..end];
+        let old_count = hunk.iter().filter(|(tag, _, _, _)| *tag != '+').count();
+        let new_count = hunk.iter().filter(|(tag, _, _, _)| *tag != '-').count();
+        let old_start = hunk
+            .iter()
+            .find(|(tag, _, _, _)| *tag != '+')
+            .map(|(_, _, line, _)| *line)
+            .unwrap_or_else(|| lines[..start].iter().filter(|l| l.0 != '+').count());
+        let new_start = hunk
+            .iter()
+            .find(|(tag, _, _, _)| *tag != '-')
+            .map(|(_, _, _, line)| *line)
+            .unwrap_or_else(|| lines[..start].iter().filter(|l| l.0 != '-').count());
+        result.push_str(&format!(
+            "@@ -{old_start},{old_count} +{new_start},{new_count} @@\n"
+        ));
+        for (tag, text, old_line, new_line) in hunk {
+            result.push_str(&format!("{tag}{text}\n"));
+            if (old_missing && *tag != '+' && *old_line == old_last)
+                || (new_missing && *tag != '-' && *new_line == new_last)
+            {
+                result.push_str("\\ No newline at end of file\n");
+            }
+        }
+    }
+    result
+}
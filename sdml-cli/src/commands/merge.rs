@@ -0,0 +1,119 @@
+use clap::Args;
+use sdml_core::compose::Merge;
+use sdml_core::load::ModuleLoader;
+use sdml_core::model::identifiers::Identifier;
+use sdml_core::store::ModuleStore;
+use sdml_errors::diagnostics::StandardStreamReporter;
+use sdml_errors::Error;
+use sdml_generate::convert::source::{SourceGenerator, SourceGeneratorOptions};
+use sdml_generate::Generator;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+use url::Url;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// Merge a set of modules into a single new module.
+///
+/// The modules are read from the files and directories given, directories are searched for SDML
+/// files, and merged in that order. References between the merged modules become local
+/// references, imports of the merged modules are removed, and all other imports and the module
+/// annotations are combined without duplicates. The merged module is written as SDML source.
+///
+/// Where two modules have a definition with the same name and the same shape the definitions are
+/// merged, combining their annotations. If the definitions have different shapes they are
+/// reported as conflicts and no module is written.
+///
+/// ```text
+/// ❯ sdml merge -n rentals_all -b https://example.org/rentals/all/ models/rentals models/billing.sdm
+/// module rentals_all <https://example.org/rentals/all/> is
+///
+///   import [ skos xsd ]
+///   ...
+/// end
+/// ```
+///
+#[derive(Args, Debug)]
+pub(crate) struct Command {
+    /// The name of the merged module
+    #[arg(short, long)]
+    #[arg(value_parser = Identifier::from_str)]
+    name: Identifier,
+
+    /// The base URI of the merged module
+    #[arg(short, long)]
+    #[arg(value_parser = Url::parse)]
+    base_uri: Option<Url>,
+
+    /// The version information string of the merged module
+    #[arg(long)]
+    version_info: Option<String>,
+
+    /// The version URI of the merged module
+    #[arg(long)]
+    #[arg(value_parser = Url::parse)]
+    version_uri: Option<Url>,
+
+    /// Set the number of spaces for indentation
+    #[arg(short = 's', long)]
+    #[arg(default_value = "2")]
+    indent_spaces: usize,
+
+    /// File name to write to, or '-' to write to stdout
+    #[arg(short, long)]
+    #[clap(value_parser, default_value = "-")]
+    output: clio::Output,
+
+    /// Module files, or directories containing module files, to merge
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl super::Command for Command {
+    fn execute(&self) -> Result<ExitCode, Error> {
+        let Ok(super::ModuleFiles {
+            cache,
+            loader,
+            files,
+        }) = super::load_module_files(&self.paths, Box::<StandardStreamReporter>::default())?
+        else {
+            return Ok(ExitCode::FAILURE);
+        };
+
+        let mut merge = Merge::new(self.name.clone());
+        if let Some(base_uri) = &self.base_uri {
+            merge = merge.with_base_uri(base_uri.clone());
+        }
+        if let Some(version_info) = &self.version_info {
+            merge = merge.with_version_info(version_info);
+        }
+        if let Some(version_uri) = &self.version_uri {
+            merge = merge.with_version_uri(version_uri.clone());
+        }
+        let merged = merge.merge(files.iter().filter_map(|(name, _)| cache.get(name)))?;
+
+        if merged.has_conflicts() {
+            for conflict in merged.conflicts() {
+                loader.report(&conflict.to_diagnostic())?;
+            }
+            loader.reporter_done(Some(self.name.to_string()))?;
+            return Ok(ExitCode::FAILURE);
+        }
+
+        let options = SourceGeneratorOptions::default().with_indentation(self.indent_spaces);
+        let mut generator = SourceGenerator::default();
+        let mut output = self.output.clone();
+        let mut writer = output.lock();
+
+        generator.generate_with_options(merged.module(), &cache, options, None, &mut writer)?;
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
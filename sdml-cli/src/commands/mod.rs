@@ -1,6 +1,18 @@
 use clap::{Args, Subcommand};
+use sdml_core::load::ModuleLoader;
 use sdml_core::model::identifiers::Identifier;
+use sdml_core::store::disk::DiskModuleCache;
+use sdml_errors::diagnostics::Reporter;
 use sdml_errors::Error;
+use sdml_parse::lint::LintConfig;
+use sdml_parse::load::{
+    FsModuleLoader, FsModuleResolver, ResolverChain, SDML_FILE_EXTENSION, SDML_FILE_EXTENSION_LONG,
+};
+use std::collections::BTreeSet;
+use std::fs::read_dir;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use std::{process::ExitCode, str::FromStr};
 use tracing::trace;
 
@@ -15,8 +27,8 @@ macro_rules! call_with_module {
     };
     ($cmd: expr, $reporter:expr, $callback_fn: expr) => {
         let (module_name, cache, mut loader) = {
-            let mut cache = ::sdml_core::store::InMemoryModuleCache::default().with_stdlib();
-            let mut loader = ::sdml_parse::load::FsModuleLoader::default().with_reporter($reporter);
+            let mut cache = $crate::commands::module_cache()?;
+            let mut loader = $crate::commands::module_loader($reporter)?;
             let module_name = if let Some(module_name) = &$cmd.files.module {
                 loader.load(
                     module_name,
@@ -70,12 +82,22 @@ pub(crate) trait Command {
 pub(crate) enum Commands {
     Convert(convert::Command),
     Deps(deps::Command),
+    Diff(diff::Command),
     Doc(doc::Command),
     DocBook(book::Command),
     Draw(draw::Command),
+    Fix(fix::Command),
     Generate(generate::Command),
     Highlight(highlight::Command),
+    Merge(merge::Command),
+    Package(package::Command),
+    Query(query::Command),
+    Refactor(refactor::Command),
+    Sparql(sparql::Command),
+    Split(split::Command),
     Tags(tags::Command),
+    Transform(transform::Command),
+    Unused(unused::Command),
     Validate(validate::Command),
     Versions(versions::Command),
     View(view::Command),
@@ -101,10 +123,148 @@ pub(crate) struct FileArgs {
     input: clio::Input,
 }
 
+///
+/// A set of module files loaded by [`load_module_files`], with the cache and loader used so that
+/// module sources remain available.
+///
+pub(crate) struct ModuleFiles {
+    cache: DiskModuleCache,
+    loader: FsModuleLoader,
+    files: Vec<(Identifier, PathBuf)>,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Public Functions
 // ------------------------------------------------------------------------------------------------
 
+///
+/// Set the directory used to persist parsed modules between invocations, this is set once from
+/// the global `--cache-dir` option.
+///
+pub(crate) fn set_cache_directory(directory: Option<PathBuf>) {
+    let _ = CACHE_DIRECTORY.set(directory);
+}
+
+///
+/// Construct the module store used by all commands, this persists parsed modules only if a cache
+/// directory has been set.
+///
+pub(crate) fn module_cache() -> Result<DiskModuleCache, Error> {
+    let cache = match CACHE_DIRECTORY.get() {
+        Some(Some(directory)) => DiskModuleCache::open(directory)?,
+        _ => DiskModuleCache::default(),
+    };
+    Ok(cache
+        .with_tool_version(format!(
+            "sdml-cli/{}+grammar/{}",
+            env!("CARGO_PKG_VERSION"),
+            tree_sitter_sdml::GRAMMAR_VERSION
+        ))
+        .with_stdlib())
+}
+
+///
+/// Construct the module loader used by commands that validate modules, diagnostics are passed to
+/// `reporter` after applying the discovered lint configuration, if any.
+///
+pub(crate) fn module_loader(reporter: Box<dyn Reporter>) -> Result<FsModuleLoader, Error> {
+    let loader = FsModuleLoader::default().with_reporter(reporter);
+    Ok(match LintConfig::discover()? {
+        Some(lint_config) => loader.with_lint_config(lint_config),
+        None => loader,
+    })
+}
+
+///
+/// Load all the module files in `paths`, where directories are searched for SDML files, in path
+/// order. Modules in the set are found by name when imported by other modules in the set. If any
+/// file fails validation the diagnostics are reported to `reporter` and the loader is returned as
+/// the error so that its sources remain available.
+///
+pub(crate) fn load_module_files(
+    paths: &[PathBuf],
+    reporter: Box<dyn Reporter>,
+) -> Result<Result<ModuleFiles, FsModuleLoader>, Error> {
+    let module_files = find_module_files(paths)?;
+
+    let mut resolver = FsModuleResolver::default();
+    let directories: BTreeSet<&Path> = module_files
+        .iter()
+        .filter_map(|file| file.parent())
+        .collect();
+    for directory in directories {
+        resolver.prepend_to_search_path(directory);
+    }
+    let mut cache = module_cache()?;
+    let mut loader =
+        FsModuleLoader::new(ResolverChain::empty().with_resolver(resolver)).with_reporter(reporter);
+
+    let mut files: Vec<(Identifier, PathBuf)> = Default::default();
+    for file in module_files {
+        match loader.load_from_file(file.clone(), &mut cache, true) {
+            Ok(name) => files.push((name, file)),
+            Err(Error::LanguageValidationError { source: _ }) => {
+                loader.reporter_done(None)?;
+                return Ok(Err(loader));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(Ok(ModuleFiles {
+        cache,
+        loader,
+        files,
+    }))
+}
+
+///
+/// Write `rows` to `writer` as a plain text table under `header`, each column is padded to the
+/// width of its widest value.
+///
+pub(crate) fn write_table<S: AsRef<str>>(
+    header: &[S],
+    rows: &[Vec<String>],
+    writer: &mut impl Write,
+) -> Result<(), Error> {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.as_ref().chars().count()).collect();
+    for row in rows {
+        for (width, value) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(value.chars().count());
+        }
+    }
+
+    let write_row = |writer: &mut dyn Write, values: &[&str]| -> Result<(), Error> {
+        let line = values
+            .iter()
+            .zip(widths.iter())
+            .map(|(value, width)| format!("{value:width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        writeln!(writer, "{}", line.trim_end())?;
+        Ok(())
+    };
+
+    write_row(
+        writer,
+        &header.iter().map(AsRef::as_ref).collect::<Vec<_>>(),
+    )?;
+    for row in rows {
+        write_row(writer, &row.iter().map(String::as_str).collect::<Vec<_>>())?;
+    }
+    Ok(())
+}
+
+///
+/// Return `value` as a CSV field, quoted if it contains a separator, quote, or line break.
+///
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Private Macros
 // ------------------------------------------------------------------------------------------------
@@ -113,6 +273,8 @@ pub(crate) struct FileArgs {
 // Private Types
 // ------------------------------------------------------------------------------------------------
 
+static CACHE_DIRECTORY: OnceLock<Option<PathBuf>> = OnceLock::new();
+
 // ------------------------------------------------------------------------------------------------
 // Implementations
 // ------------------------------------------------------------------------------------------------
@@ -122,11 +284,21 @@ impl Command for Commands {
         trace!("Commands::execute self: {self:?}");
         match self {
             Commands::Highlight(cmd) => cmd.execute(),
+            Commands::Merge(cmd) => cmd.execute(),
+            Commands::Fix(cmd) => cmd.execute(),
+            Commands::Package(cmd) => cmd.execute(),
+            Commands::Query(cmd) => cmd.execute(),
+            Commands::Refactor(cmd) => cmd.execute(),
+            Commands::Sparql(cmd) => cmd.execute(),
+            Commands::Split(cmd) => cmd.execute(),
             Commands::Generate(cmd) => cmd.execute(),
             Commands::Doc(cmd) => cmd.execute(),
             Commands::DocBook(cmd) => cmd.execute(),
             Commands::Deps(cmd) => cmd.execute(),
+            Commands::Diff(cmd) => cmd.execute(),
             Commands::Tags(cmd) => cmd.execute(),
+            Commands::Transform(cmd) => cmd.execute(),
+            Commands::Unused(cmd) => cmd.execute(),
             Commands::Convert(cmd) => cmd.execute(),
             Commands::Draw(cmd) => cmd.execute(),
             Commands::View(cmd) => cmd.execute(),
@@ -136,6 +308,41 @@ impl Command for Commands {
     }
 }
 
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn find_module_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>, Error> {
+    fn is_module_file(path: &Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext == SDML_FILE_EXTENSION || ext == SDML_FILE_EXTENSION_LONG)
+    }
+
+    fn find_in(path: &Path, files: &mut Vec<PathBuf>) -> Result<(), Error> {
+        if path.is_dir() {
+            let mut entries = read_dir(path)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()?;
+            entries.sort();
+            for entry in entries {
+                if entry.is_dir() || is_module_file(&entry) {
+                    find_in(&entry, files)?;
+                }
+            }
+        } else {
+            files.push(path.to_path_buf());
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::default();
+    for path in paths {
+        find_in(path, &mut files)?;
+    }
+    Ok(files)
+}
+
 // ------------------------------------------------------------------------------------------------
 // Modules
 // ------------------------------------------------------------------------------------------------
@@ -143,11 +350,21 @@ impl Command for Commands {
 mod book;
 mod convert;
 mod deps;
+mod diff;
 mod doc;
 mod draw;
+mod fix;
 mod generate;
 mod highlight;
+mod merge;
+mod package;
+mod query;
+mod refactor;
+mod sparql;
+mod split;
 mod tags;
+mod transform;
+mod unused;
 mod validate;
 mod versions;
 mod view;
@@ -0,0 +1,134 @@
+use clap::{Args, Subcommand};
+use sdml_errors::{diagnostics::StandardStreamReporter, Error};
+use sdml_parse::load::SDML_CATALOG_FILE_NAME;
+use sdml_parse::package::{
+    PackageLock, PackageManifest, SDML_PACKAGE_FILE_NAME, SDML_PACKAGE_LOCK_FILE_NAME,
+    SDML_VENDOR_DIRECTORY_NAME,
+};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// Build, lock, and vendor model packages.
+///
+/// A model package is described by a manifest file, `sdml-package.json`, that lists the package's
+/// modules, version, exported modules, and dependencies on other packages with version ranges.
+/// Dependencies are always local, either a package directory or a package archive.
+///
+/// - Build :: Write a package archive, `{name}-{version}.zip`, containing the manifest, the module
+///   files, and a generated `sdml-catalog.json`.
+///
+/// ```text
+/// ❯ sdml package build
+/// Package written to ./rentals-1.2.0.zip
+/// ```
+///
+/// - Lock :: Resolve all dependencies and write their exact versions, and checksums, to the
+///   lockfile `sdml-package.lock`.
+///
+/// ```text
+/// ❯ sdml package lock
+/// common 1.0.3
+/// Lockfile written to ./sdml-package.lock
+/// ```
+///
+/// - Vendor :: Copy the modules of all dependencies into the `vendor` directory and write an
+///   `sdml-catalog.json` so that the standard resolver finds them. If a lockfile exists the
+///   dependencies must match it, use `--update` to ignore the existing lockfile and write a new
+///   one.
+///
+/// ```text
+/// ❯ sdml package vendor
+/// common 1.0.3
+/// Catalog written to ./sdml-catalog.json
+/// ```
+///
+#[derive(Args, Debug)]
+pub(crate) struct Command {
+    /// The package manifest file, or the directory containing it
+    #[arg(short, long)]
+    #[arg(default_value = SDML_PACKAGE_FILE_NAME)]
+    manifest: PathBuf,
+
+    #[command(subcommand)]
+    action: Action,
+}
+
+#[derive(Subcommand, Debug)]
+enum Action {
+    /// Build a package archive
+    Build {
+        /// The archive file to write, by default `{name}-{version}.zip` in the package directory
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Resolve dependencies and write a lockfile
+    Lock,
+    /// Copy dependencies into a local directory and write a catalog
+    Vendor {
+        /// The directory, relative to the package, to copy dependencies into
+        #[arg(short, long)]
+        #[arg(default_value = SDML_VENDOR_DIRECTORY_NAME)]
+        directory: PathBuf,
+
+        /// Ignore any existing lockfile, and replace it
+        #[arg(short, long)]
+        update: bool,
+    },
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl super::Command for Command {
+    fn execute(&self) -> Result<ExitCode, Error> {
+        let manifest = PackageManifest::load_from(&self.manifest)?;
+        let lock_file = manifest.loaded_from().join(SDML_PACKAGE_LOCK_FILE_NAME);
+
+        match &self.action {
+            Action::Build { output } => {
+                let archive = manifest.build(output.as_deref())?;
+                println!("Package written to {}", archive.display());
+            }
+            Action::Lock => {
+                let lock = manifest.lock()?;
+                print_packages(&lock);
+                lock.save_to(&lock_file)?;
+                println!("Lockfile written to {}", lock_file.display());
+            }
+            Action::Vendor { directory, update } => {
+                let existing = if !update && lock_file.is_file() {
+                    Some(PackageLock::load_from(&lock_file)?)
+                } else {
+                    None
+                };
+                let mut loader = super::module_loader(Box::<StandardStreamReporter>::default())?;
+                let catalog = manifest.vendor(directory, existing.as_ref(), &mut loader)?;
+                let lock = match existing {
+                    Some(lock) => lock,
+                    None => {
+                        let lock = manifest.lock()?;
+                        lock.save_to(&lock_file)?;
+                        lock
+                    }
+                };
+                print_packages(&lock);
+                let catalog_file = manifest.loaded_from().join(SDML_CATALOG_FILE_NAME);
+                catalog.save_to(&catalog_file)?;
+                println!("Catalog written to {}", catalog_file.display());
+            }
+        }
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+fn print_packages(lock: &PackageLock) {
+    for package in lock.packages() {
+        println!("{} {}", package.name(), package.version());
+    }
+}
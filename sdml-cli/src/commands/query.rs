@@ -0,0 +1,136 @@
+use clap::{Args, ValueEnum};
+use sdml_core::load::ModuleLoader;
+use sdml_core::model::modules::Module;
+use sdml_core::model::HasName;
+use sdml_core::query::{Query, QueryRow};
+use sdml_core::store::{disk::DiskModuleCache, ModuleStore};
+use sdml_errors::Error;
+use std::io::Write;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// Query the definitions, members, and annotations of a module.
+///
+/// The query is run over the module and all the modules it imports, except the standard library
+/// modules. A query is a path of steps separated by `/`, where each step selects a kind of model
+/// element and may be followed by filters in square brackets; the first step selects elements
+/// anywhere in the modules, each following step selects from the children of the elements
+/// selected before.
+///
+/// - Selectors :: `module`, `definition`, a definition keyword such as `entity` or `structure`,
+///   `member`, `variant`, `annotation`, `constraint`, or `*` for any element.
+/// - Filters :: `[@name]` if the element has the annotation `name`, `[@name = value]`, or
+///   `[field op value]` where field is one of `name`, `module`, `kind`, `type`, `cardinality`,
+///   or `value`, and op is one of `=`, `!=`, or `~` (contains). A filter may be negated as
+///   `[!...]`.
+///
+/// ```text
+/// ❯ sdml query 'entity[!@skos:definition]' -i rentals.sdm
+/// path              kind    type  cardinality  value
+/// rentals:Customer  entity
+///
+/// ❯ sdml query 'structure/member[type = xsd:date]' -f csv rentals
+/// path,kind,type,cardinality,value
+/// rentals:Address.moved,member,xsd:date,{1..1},
+/// ```
+///
+#[derive(Args, Debug)]
+pub(crate) struct Command {
+    /// The query to run over the loaded modules
+    #[arg(value_parser = Query::from_str)]
+    query: Query,
+
+    #[arg(short = 'f', long)]
+    #[arg(value_enum)]
+    #[arg(default_value_t = OutputFormat::Table)]
+    output_format: OutputFormat,
+
+    #[command(flatten)]
+    files: super::FileArgs,
+}
+
+/// Format to write query results in
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum OutputFormat {
+    /// An aligned text table
+    Table,
+    /// A JSON array of result objects
+    Json,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+const COLUMNS: [&str; 5] = ["path", "kind", "type", "cardinality", "value"];
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl super::Command for Command {
+    fn execute(&self) -> Result<ExitCode, Error> {
+        call_with_module!(self, |_: &Module, cache: &DiskModuleCache, _| {
+            let mut modules: Vec<&Module> = cache
+                .modules()
+                .filter(|module| !module.is_library_module())
+                .collect();
+            modules.sort_by(|lhs, rhs| lhs.name().cmp(rhs.name()));
+
+            let rows = self.query.execute(modules, cache)?;
+
+            let mut output = self.files.output.clone();
+            let mut writer = output.lock();
+
+            match self.output_format {
+                OutputFormat::Table => write_table(&rows, &mut writer)?,
+                OutputFormat::Json => {
+                    serde_json::to_writer_pretty(&mut writer, &rows)
+                        .map_err(std::io::Error::other)?;
+                    writeln!(writer)?;
+                }
+                OutputFormat::Csv => write_csv(&rows, &mut writer)?,
+            }
+
+            Ok(ExitCode::SUCCESS)
+        });
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn columns(row: &QueryRow) -> [String; 5] {
+    [
+        row.path(),
+        row.kind().clone(),
+        row.target_type().cloned().unwrap_or_default(),
+        row.cardinality().cloned().unwrap_or_default(),
+        row.value().cloned().unwrap_or_default(),
+    ]
+}
+
+fn write_table(rows: &[QueryRow], writer: &mut impl Write) -> Result<(), Error> {
+    let rows: Vec<Vec<String>> = rows.iter().map(|row| columns(row).to_vec()).collect();
+    super::write_table(&COLUMNS, &rows, writer)
+}
+
+fn write_csv(rows: &[QueryRow], writer: &mut impl Write) -> Result<(), Error> {
+    writeln!(writer, "{}", COLUMNS.join(","))?;
+    for row in rows {
+        let line = columns(row)
+            .iter()
+            .map(|value| super::csv_field(value))
+            .collect::<Vec<_>>()
+            .join(",");
+        writeln!(writer, "{line}")?;
+    }
+    Ok(())
+}
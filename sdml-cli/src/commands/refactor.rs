@@ -0,0 +1,128 @@
+use clap::{Args, Subcommand};
+use sdml_core::load::ModuleLoader;
+use sdml_core::model::identifiers::Identifier;
+use sdml_core::refactor::rename::{Rename, RenameTarget};
+use sdml_core::store::ModuleStore;
+use sdml_errors::diagnostics::StandardStreamReporter;
+use sdml_errors::Error;
+use std::collections::BTreeMap;
+use std::fs::write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// Refactor the modules in a set of files.
+///
+/// Refactorings change the module source files in place, only the text that must change is
+/// replaced and all comments and layout are preserved. The set of modules is read from the
+/// files and directories given, directories are searched for SDML files. Any modules these
+/// import are also loaded, although only the files given are changed.
+///
+/// - Rename :: Rename a definition, `module:Definition`, or a member or variant of a definition,
+///   `module:Definition.member`. Renaming a definition changes all references to it, qualified
+///   and unqualified, member imports, and annotation property names.
+///
+/// ```text
+/// ❯ sdml refactor rename rentals:Customer Client models
+/// models/billing.sdm: 3 changes
+/// models/rentals.sdm: 4 changes
+/// ```
+///
+/// Use `--dry-run` to list the changes without changing any files.
+///
+#[derive(Args, Debug)]
+pub(crate) struct Command {
+    #[command(subcommand)]
+    action: Action,
+}
+
+#[derive(Subcommand, Debug)]
+enum Action {
+    /// Rename a definition, member, or variant
+    Rename {
+        /// The definition, `module:Definition`, or member, `module:Definition.member`, to rename
+        #[arg(value_parser = RenameTarget::from_str)]
+        target: RenameTarget,
+
+        /// The new name
+        #[arg(value_parser = Identifier::from_str)]
+        new_name: Identifier,
+
+        /// Module files, or directories containing module files, to change
+        #[arg(default_value = ".")]
+        paths: Vec<PathBuf>,
+
+        /// List the changes without writing any files
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+    },
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl super::Command for Command {
+    fn execute(&self) -> Result<ExitCode, Error> {
+        match &self.action {
+            Action::Rename {
+                target,
+                new_name,
+                paths,
+                dry_run,
+            } => {
+                let Ok(super::ModuleFiles {
+                    cache,
+                    loader,
+                    files,
+                }) = super::load_module_files(paths, Box::<StandardStreamReporter>::default())?
+                else {
+                    return Ok(ExitCode::FAILURE);
+                };
+                let files: BTreeMap<Identifier, PathBuf> = files.into_iter().collect();
+
+                let plan = Rename::new(target.clone(), new_name.clone()).plan(&cache)?;
+                if plan.is_empty() {
+                    println!("No changes required");
+                    return Ok(ExitCode::SUCCESS);
+                }
+
+                for module_name in plan.module_names() {
+                    let Some(file) = files.get(module_name) else {
+                        eprintln!(
+                            "Warning: the module `{module_name}` was loaded as an import and has not been changed"
+                        );
+                        continue;
+                    };
+                    let edits = plan.edits(module_name);
+                    if *dry_run {
+                        println!("{}: {} changes", file.display(), edits.len());
+                        for edit in edits {
+                            println!("  {edit}");
+                        }
+                    } else {
+                        let source = cache
+                            .get(module_name)
+                            .and_then(|module| module.file_id())
+                            .and_then(|file_id| loader.get_source(*file_id))
+                            .expect("Error: module source not found in loader");
+                        write(file, plan.apply(module_name, source.as_ref()))?;
+                        println!("{}: {} changes", file.display(), edits.len());
+                    }
+                }
+                if plan.missing_spans() > 0 {
+                    eprintln!(
+                        "Warning: {} references have no source location and have not been changed",
+                        plan.missing_spans()
+                    );
+                }
+            }
+        }
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
@@ -0,0 +1,205 @@
+use clap::{Args, ValueEnum};
+use sdml_core::load::ModuleLoader;
+use sdml_core::model::modules::Module;
+use sdml_core::store::{disk::DiskModuleCache, ModuleStore};
+use sdml_errors::Error;
+use sdml_rdf::generate::store_to_graph;
+use sdml_rdf::sparql::{Query, QueryResults, Solution, Term};
+use serde_json::{json, Map, Value};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// Run a SPARQL query over the RDF representation of a module.
+///
+/// The query is run over the union graph of the module, all the modules it imports, and the
+/// standard library modules. The `SELECT`, `ASK`, and `CONSTRUCT` query forms are supported,
+/// with basic graph patterns, `OPTIONAL`, `UNION`, `MINUS`, `FILTER`, and the solution modifiers
+/// `ORDER BY`, `LIMIT`, and `OFFSET`.
+///
+/// - Table :: aligned columns of terms in N-Triples syntax, or `true`/`false` for `ASK`.
+/// - CSV :: the SPARQL 1.1 CSV results format.
+/// - JSON :: the SPARQL 1.1 JSON results format.
+///
+/// The results of a `CONSTRUCT` query are always written as N-Triples.
+///
+/// ```text
+/// ❯ cat labels.rq
+/// PREFIX skos: <http://www.w3.org/2004/02/skos/>
+/// SELECT ?module ?label WHERE { ?module skos:prefLabel ?label } ORDER BY ?label
+///
+/// ❯ sdml sparql -q labels.rq -i rentals.sdm
+/// module                                 label
+/// <https://example.org/rentals/rentals>  "Rentals"@en
+/// ```
+///
+#[derive(Args, Debug)]
+pub(crate) struct Command {
+    /// File containing the SPARQL query to run
+    #[arg(short, long)]
+    query: PathBuf,
+
+    #[arg(short = 'f', long)]
+    #[arg(value_enum)]
+    #[arg(default_value_t = OutputFormat::Table)]
+    output_format: OutputFormat,
+
+    #[command(flatten)]
+    files: super::FileArgs,
+}
+
+/// Format to write query results in
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum OutputFormat {
+    /// An aligned text table
+    Table,
+    /// SPARQL 1.1 CSV results
+    Csv,
+    /// SPARQL 1.1 JSON results
+    Json,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl super::Command for Command {
+    fn execute(&self) -> Result<ExitCode, Error> {
+        let query = Query::from_str(&std::fs::read_to_string(&self.query)?)?;
+
+        call_with_module!(self, |_: &Module, cache: &DiskModuleCache, _| {
+            let graph = store_to_graph(cache)?;
+            let results = query.execute(&graph)?;
+
+            let mut output = self.files.output.clone();
+            let mut writer = output.lock();
+
+            match (results, self.output_format) {
+                (QueryResults::Graph(graph), _) => {
+                    for statement in graph.statements() {
+                        if let (Some(subject), Some(object)) = (
+                            Term::from_subject(statement.subject()),
+                            Term::from_object(statement.object()),
+                        ) {
+                            writeln!(
+                                writer,
+                                "{subject} {} {object} .",
+                                Term::Iri(statement.predicate().clone())
+                            )?;
+                        }
+                    }
+                }
+                (QueryResults::Boolean(value), OutputFormat::Json) => {
+                    write_json(&json!({ "head": {}, "boolean": value }), &mut writer)?
+                }
+                (QueryResults::Boolean(value), _) => writeln!(writer, "{value}")?,
+                (
+                    QueryResults::Solutions {
+                        variables,
+                        solutions,
+                    },
+                    format,
+                ) => match format {
+                    OutputFormat::Table => write_table(&variables, &solutions, &mut writer)?,
+                    OutputFormat::Csv => write_csv(&variables, &solutions, &mut writer)?,
+                    OutputFormat::Json => write_json(
+                        &json!({
+                            "head": { "vars": variables },
+                            "results": {
+                                "bindings": solutions.iter().map(solution_to_json).collect::<Vec<_>>()
+                            }
+                        }),
+                        &mut writer,
+                    )?,
+                },
+            }
+
+            Ok(ExitCode::SUCCESS)
+        });
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn write_table(
+    variables: &[String],
+    solutions: &[Solution],
+    writer: &mut impl Write,
+) -> Result<(), Error> {
+    let rows: Vec<Vec<String>> = solutions
+        .iter()
+        .map(|solution| {
+            variables
+                .iter()
+                .map(|variable| {
+                    solution
+                        .get(variable)
+                        .map(Term::to_string)
+                        .unwrap_or_default()
+                })
+                .collect()
+        })
+        .collect();
+    super::write_table(variables, &rows, writer)
+}
+
+fn write_csv(
+    variables: &[String],
+    solutions: &[Solution],
+    writer: &mut impl Write,
+) -> Result<(), Error> {
+    writeln!(writer, "{}", variables.join(","))?;
+    for solution in solutions {
+        let line = variables
+            .iter()
+            .map(|variable| match solution.get(variable) {
+                Some(term @ Term::BlankNode(_)) => term.to_string(),
+                Some(term) => super::csv_field(term.value()),
+                None => String::new(),
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        writeln!(writer, "{line}")?;
+    }
+    Ok(())
+}
+
+fn solution_to_json(solution: &Solution) -> Value {
+    let bindings: Map<String, Value> = solution
+        .iter()
+        .map(|(variable, term)| {
+            let value = match term {
+                Term::Iri(iri) => json!({ "type": "uri", "value": iri.as_str() }),
+                Term::BlankNode(label) => json!({ "type": "bnode", "value": label }),
+                Term::Literal {
+                    lexical_form,
+                    data_type,
+                    language,
+                } => {
+                    let mut value = json!({ "type": "literal", "value": lexical_form });
+                    if let Some(language) = language {
+                        value["xml:lang"] = json!(language);
+                    } else if let Some(data_type) = data_type {
+                        value["datatype"] = json!(data_type.as_str());
+                    }
+                    value
+                }
+            };
+            (variable.clone(), value)
+        })
+        .collect();
+    Value::Object(bindings)
+}
+
+fn write_json(value: &Value, writer: &mut impl Write) -> Result<(), Error> {
+    serde_json::to_writer_pretty(&mut *writer, value).map_err(std::io::Error::other)?;
+    writeln!(writer)?;
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use clap::Args;
+use sdml_core::compose::Split;
+use sdml_core::load::ModuleLoader;
+use sdml_core::model::identifiers::IdentifierReference;
+use sdml_core::model::modules::Module;
+use sdml_core::model::{HasBody, HasName};
+use sdml_core::store::{disk::DiskModuleCache, ModuleStore};
+use sdml_errors::diagnostics::UseColor;
+use sdml_errors::Error;
+use sdml_generate::color::set_colorize;
+use sdml_generate::convert::source::{SourceGenerator, SourceGeneratorOptions};
+use sdml_generate::Generator;
+use sdml_parse::load::SDML_FILE_EXTENSION;
+use std::fs::{create_dir_all, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// Split a module into a number of new modules.
+///
+/// By default the definitions of the module are split by kind, into modules named for the
+/// original module and the kind of definition, for example `rentals_entity`. With
+/// `--by-annotation` definitions are split by the value of an annotation property instead, and
+/// definitions without the annotation remain in a module with the original name.
+///
+/// References between definitions that are split into different modules become qualified, and the
+/// necessary imports are added. Each new module is written as SDML source to a file in the output
+/// directory, named for the module; the files written are listed on the output.
+///
+/// ```text
+/// ❯ sdml split --by-annotation dc:subject -d split -i rentals.sdm
+/// split/rentals.sdm: 2 definitions
+/// split/rentals_billing.sdm: 3 definitions
+/// split/rentals_fleet.sdm: 4 definitions
+/// ```
+///
+/// Use `--dry-run` to list the new modules without writing any files.
+///
+#[derive(Args, Debug)]
+pub(crate) struct Command {
+    /// Split by the value of this annotation property, rather than by kind
+    #[arg(short = 'a', long)]
+    #[arg(value_parser = IdentifierReference::from_str)]
+    by_annotation: Option<IdentifierReference>,
+
+    /// Directory to write the new module files into
+    #[arg(short = 'd', long)]
+    #[arg(default_value = ".")]
+    output_dir: PathBuf,
+
+    /// Set the number of spaces for indentation
+    #[arg(short = 's', long)]
+    #[arg(default_value = "2")]
+    indent_spaces: usize,
+
+    /// List the new modules without writing any files
+    #[arg(short = 'n', long)]
+    dry_run: bool,
+
+    #[command(flatten)]
+    files: super::FileArgs,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl super::Command for Command {
+    fn execute(&self) -> Result<ExitCode, Error> {
+        call_with_module!(self, |module: &Module, cache: &DiskModuleCache, _| {
+            let split = match &self.by_annotation {
+                Some(property) => Split::by_annotation(property.clone()),
+                None => Split::by_kind(),
+            };
+            let parts = split.split(module)?;
+
+            let mut output = self.files.output.clone();
+            let mut writer = output.lock();
+
+            if !self.dry_run {
+                create_dir_all(&self.output_dir)?;
+                // module files are always written without color.
+                set_colorize(UseColor::Never);
+            }
+            for part in parts {
+                let file = self
+                    .output_dir
+                    .join(format!("{}.{SDML_FILE_EXTENSION}", part.name()));
+                if !self.dry_run {
+                    let options =
+                        SourceGeneratorOptions::default().with_indentation(self.indent_spaces);
+                    let mut generator = SourceGenerator::default();
+                    generator.generate_with_options(
+                        &part,
+                        cache,
+                        options,
+                        None,
+                        &mut File::create(&file)?,
+                    )?;
+                }
+                writeln!(
+                    writer,
+                    "{}: {} definitions",
+                    file.display(),
+                    part.body().definitions_len()
+                )?;
+            }
+
+            Ok(ExitCode::SUCCESS)
+        });
+    }
+}
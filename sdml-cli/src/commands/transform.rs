@@ -0,0 +1,115 @@
+use std::process::ExitCode;
+
+use clap::{Args, ValueEnum};
+use sdml_core::model::modules::Module;
+use sdml_core::model::walk::passes::{
+    InlineImports, ModulePass, PassChain, QualifyReferences, StripAnnotations,
+};
+use sdml_core::store::disk::DiskModuleCache;
+use sdml_core::{load::ModuleLoader, store::ModuleStore};
+use sdml_errors::Error;
+use sdml_generate::convert::source::{SourceGenerator, SourceGeneratorOptions};
+use sdml_generate::Generator;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// Transform a module and write the resulting source.
+///
+/// This command applies one or more transformation passes, in the order given, to a module and
+/// writes the transformed module as SDML source. The module file itself is not changed.
+///
+/// - Strip Annotations :: Remove all annotations from the module, its definitions, members, and
+///   variants. Use `--keep-constraints` to only remove annotation properties.
+///
+/// - Qualify References :: Replace unqualified references to local definitions, or imported
+///   members, with qualified references.
+///
+/// - Inline Imports :: Copy definitions referenced from other, non-library, modules into the
+///   module, removing member imports of them.
+///
+/// ```text
+/// ❯ sdml transform -p qualify-references -i examples/example.sdm
+/// module example <https://example.com/api> is
+///
+///   import [ dc xsd ]
+///
+///   datatype Uuid <- sdml:string is
+///     @xsd:pattern = "[0-9a-f]{8}-([0-9a-f]{4}-){3}[0-9a-f]{12}"
+///   end
+///
+///   entity Example is
+///     identity example_id -> example:Uuid
+///     version -> example:Uuid
+///     name -> sdml:string is
+///       @dc:description = "the name of this thing"@en
+///     end
+///   end
+///
+/// end
+/// ```
+///
+#[derive(Args, Debug)]
+pub(crate) struct Command {
+    /// A transformation pass to apply, may be repeated
+    #[arg(short, long = "pass", required = true)]
+    #[arg(value_enum)]
+    passes: Vec<TransformPass>,
+
+    /// When stripping annotations keep any constraints
+    #[arg(short, long)]
+    keep_constraints: bool,
+
+    /// Set the number of spaces for indentation
+    #[arg(short = 's', long)]
+    #[arg(default_value = "2")]
+    indent_spaces: usize,
+
+    #[command(flatten)]
+    files: super::FileArgs,
+}
+
+/// Transformation to apply to the module
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum TransformPass {
+    /// Remove annotations
+    StripAnnotations,
+    /// Qualify references to local and imported definitions
+    QualifyReferences,
+    /// Copy imported definitions into the module
+    InlineImports,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl super::Command for Command {
+    fn execute(&self) -> Result<ExitCode, Error> {
+        call_with_module!(self, |module: &Module, cache: &DiskModuleCache, _| {
+            let mut passes = PassChain::default();
+            for pass in &self.passes {
+                match pass {
+                    TransformPass::StripAnnotations => passes.push(
+                        StripAnnotations::default().with_keep_constraints(self.keep_constraints),
+                    ),
+                    TransformPass::QualifyReferences => passes.push(QualifyReferences::default()),
+                    TransformPass::InlineImports => passes.push(InlineImports::new(cache)),
+                }
+            }
+
+            let mut module = module.clone();
+            passes.apply(&mut module)?;
+
+            let options = SourceGeneratorOptions::default().with_indentation(self.indent_spaces);
+            let mut generator = SourceGenerator::default();
+            let mut output = self.files.output.clone();
+            let mut writer = output.lock();
+
+            generator.generate_with_options(&module, cache, options, None, &mut writer)?;
+
+            Ok(ExitCode::SUCCESS)
+        });
+    }
+}
@@ -0,0 +1,125 @@
+use clap::Args;
+use sdml_core::model::check::usage::{
+    module_set, prune_module, validate_module_usage, Reachability,
+};
+use sdml_core::model::identifiers::IdentifierReference;
+use sdml_core::model::{modules::Module, HasName};
+use sdml_core::store::disk::DiskModuleCache;
+use sdml_core::{load::ModuleLoader, store::ModuleStore};
+use sdml_errors::diagnostics::{SeverityFilter, StandardStreamReporter};
+use sdml_errors::Error;
+use sdml_generate::convert::source::{SourceGenerator, SourceGeneratorOptions};
+use sdml_generate::Generator;
+use sdml_parse::load::FsModuleLoader;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// Find unused imports and unreachable definitions.
+///
+/// This command checks a module, and every non-library module it imports, for imports that are
+/// never referenced (`W0308`), including member imports such as `import xsd:string`. An import is
+/// used if a definition references a type or annotation property through it, or if the module
+/// itself has an annotation property through it.
+///
+/// With one or more `--root` definitions the command also reports every definition, in any module
+/// of the set, that cannot be reached by following the types and annotation properties referenced
+/// from the roots (`I0512`). Unqualified roots are definitions in the module being checked.
+///
+/// ```text
+/// ❯ sdml unused --root Customer --root rentals_fleet:Vehicle -i rentals.sdm
+/// note[I0512]: this definition is not reachable from any root definition
+///    ┌─ rentals.sdm:41:13
+///    │
+/// 41 │   structure LegacyAddress is
+///    │             ^^^^^^^^^^^^^ this definition
+///    │
+///    = help: reference this definition from a root, or remove it with `sdml unused --prune`
+/// ```
+///
+/// The `prune` option writes the module being checked as SDML source, without its unreachable
+/// definitions and without any imports the remaining definitions do not use, rather than
+/// reporting diagnostics. The module file itself is not changed.
+///
+/// ```text
+/// ❯ sdml unused --prune --root Customer -i rentals.sdm -o rentals_pruned.sdm
+/// ```
+///
+#[derive(Args, Debug)]
+pub(crate) struct Command {
+    /// A root definition, unqualified names are in the module being checked
+    #[arg(short = 'r', long = "root")]
+    #[arg(value_parser = IdentifierReference::from_str)]
+    roots: Vec<IdentifierReference>,
+
+    /// Write the module without unused imports and unreachable definitions
+    #[arg(short, long)]
+    prune: bool,
+
+    /// Set the number of spaces for indentation
+    #[arg(short = 's', long)]
+    #[arg(default_value = "2")]
+    indent_spaces: usize,
+
+    #[command(flatten)]
+    files: super::FileArgs,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl super::Command for Command {
+    fn execute(&self) -> Result<ExitCode, Error> {
+        let reporter = StandardStreamReporter::default().with_severity_filter(SeverityFilter::Note);
+        call_with_module!(
+            self,
+            Box::new(reporter),
+            |module: &Module, cache: &DiskModuleCache, loader: &mut FsModuleLoader| {
+                let reachability = if self.roots.is_empty() {
+                    None
+                } else {
+                    let reachability =
+                        Reachability::from_roots(self.roots.iter().cloned(), module.name(), cache);
+                    let mut found_all = true;
+                    for root in reachability.unresolved_roots() {
+                        println!("Error: the root definition `{root}` could not be found.");
+                        found_all = false;
+                    }
+                    if !found_all {
+                        return Ok(ExitCode::FAILURE);
+                    }
+                    Some(reachability)
+                };
+
+                if self.prune {
+                    let pruned = prune_module(module, reachability.as_ref());
+                    let options =
+                        SourceGeneratorOptions::default().with_indentation(self.indent_spaces);
+                    let mut generator = SourceGenerator::default();
+                    let mut output = self.files.output.clone();
+                    let mut writer = output.lock();
+
+                    generator.generate_with_options(&pruned, cache, options, None, &mut writer)?;
+
+                    return Ok(ExitCode::SUCCESS);
+                }
+
+                for module in module_set(module, cache) {
+                    validate_module_usage(module, reachability.as_ref(), loader)?;
+                }
+                let reports = loader.reporter_done(Some(module.name().to_string()))?;
+
+                if reports.total() > 0 {
+                    Ok(ExitCode::FAILURE)
+                } else {
+                    println!("No unused imports or unreachable definitions found.");
+                    Ok(ExitCode::SUCCESS)
+                }
+            }
+        );
+    }
+}
@@ -1,14 +1,25 @@
 use clap::{Args, ValueEnum};
-use sdml_core::model::check::terms::{default_term_set, validate_module_terms};
+use sdml_core::model::check::cycles::validate_module_import_cycles;
+use sdml_core::model::check::lint::LintRegistry;
+use sdml_core::model::check::terms::{default_term_set, validate_module_terms, TermSet};
+use sdml_core::model::identifiers::Identifier;
 use sdml_core::model::{modules::Module, HasName};
+use sdml_core::store::disk::DiskModuleCache;
 use sdml_core::{load::ModuleLoader, store::ModuleStore};
 use sdml_errors::diagnostics::{
-    reporter::{CompactStreamReporter, Reporter, StandardStreamReporter},
+    reporter::{
+        CompactStreamReporter, JsonLinesReporter, Reporter, SarifReporter, StandardStreamReporter,
+    },
     SeverityFilter,
 };
 use sdml_errors::Error;
+use sdml_generate::actions::report::{DocumentReporter, ReportFormat};
+use sdml_parse::lint::load_term_set;
 use sdml_parse::load::FsModuleLoader;
+use std::collections::HashSet;
+use std::path::PathBuf;
 use std::process::ExitCode;
+use std::str::FromStr;
 
 // ------------------------------------------------------------------------------------------------
 // Public Types
@@ -58,6 +69,16 @@ use std::process::ExitCode;
 /// note,examples/errors/i0506.sdm,3,13,3,26,I0506,identifier not using preferred casing
 /// ```
 ///
+/// The `format` option selects `standard`, `short`, `sarif` (a SARIF 2.1.0 log), or `json` (one
+/// object per line) diagnostics; the `report` option writes a standalone `html` or `markdown`
+/// report instead.
+///
+/// The `lint` option also runs the standard lint rules, such as unused imports (`W0308`). A lint
+/// configuration file, `sdml-lint.json`, in the current directory or any of its parents may turn
+/// codes on or off, change their severity, and choose identifier case styles and deprecated
+/// terms. The `terms` option adds deprecated term sets, and import cycles are reported unless all
+/// modules in the cycle are named with `allow-cycle`.
+///
 #[derive(Args, Debug)]
 pub(crate) struct Command {
     #[arg(short = 'l', long)]
@@ -69,10 +90,43 @@ pub(crate) struct Command {
     #[arg(short = 'c', long, default_value = "false")]
     check_constraints: bool,
 
-    /// Enable the short form (CSV) output
+    /// Enable the short form (CSV) output, the same as `--format short`
     #[arg(short = 's', long, default_value = "false")]
+    #[arg(conflicts_with = "format")]
     short_form: bool,
 
+    /// The format of diagnostic output
+    #[arg(short = 'f', long)]
+    #[arg(value_enum)]
+    #[arg(default_value_t = DiagnosticFormat::Standard)]
+    format: DiagnosticFormat,
+
+    /// Write a standalone report, rather than diagnostics, to the output file
+    #[arg(long)]
+    #[arg(value_enum)]
+    #[arg(conflicts_with_all = ["format", "short_form"])]
+    report: Option<DiagnosticReport>,
+
+    /// Report codes in `sdml:suppress` annotations that did not suppress any diagnostic
+    #[arg(long, default_value = "false")]
+    report_unused_suppressions: bool,
+
+    /// A deprecated term set, JSON or an SDML module, merged with the default set
+    #[arg(long)]
+    terms: Vec<PathBuf>,
+
+    /// Do not include the default, or configured, deprecated term set
+    #[arg(long, default_value = "false")]
+    no_default_terms: bool,
+
+    /// Run the standard lint rules
+    #[arg(long, default_value = "false")]
+    lint: bool,
+
+    /// A module allowed to be part of an import cycle
+    #[arg(long, value_parser = Identifier::from_str)]
+    allow_cycle: Vec<Identifier>,
+
     #[command(flatten)]
     files: super::FileArgs,
 }
@@ -96,33 +150,123 @@ pub(crate) enum DiagnosticLevel {
     All,
 }
 
+/// Set the format of diagnostic messages
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum DiagnosticFormat {
+    /// Human-readable diagnostics with source excerpts
+    Standard,
+    /// One diagnostic per line, comma separated
+    Short,
+    /// A SARIF 2.1.0 log
+    Sarif,
+    /// One JSON object per diagnostic
+    Json,
+}
+
+/// Set the kind of standalone report
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum DiagnosticReport {
+    /// An HTML page with highlighted source excerpts
+    Html,
+    /// A Markdown document
+    Markdown,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Implementations
 // ------------------------------------------------------------------------------------------------
 
 impl super::Command for Command {
     fn execute(&self) -> Result<ExitCode, Error> {
-        let reporter: Box<dyn Reporter> = if self.short_form {
-            Box::<CompactStreamReporter>::default()
+        let format = if self.short_form {
+            DiagnosticFormat::Short
+        } else {
+            self.format
+        };
+        // the filter is set before loading so that parser diagnostics are also reported.
+        let filter: SeverityFilter = self.level.into();
+        let document = self.report.map(|report| {
+            DocumentReporter::new(self.files.output.clone(), report.into())
+                .with_severity_filter(filter)
+        });
+        let reporter: Box<dyn Reporter> = if let Some(document) = &document {
+            Box::new(document.clone())
         } else {
-            Box::<StandardStreamReporter>::default()
+            match format {
+                DiagnosticFormat::Standard => {
+                    Box::new(StandardStreamReporter::default().with_severity_filter(filter))
+                }
+                DiagnosticFormat::Short => {
+                    Box::new(CompactStreamReporter::default().with_severity_filter(filter))
+                }
+                DiagnosticFormat::Json => {
+                    Box::new(JsonLinesReporter::default().with_severity_filter(filter))
+                }
+                DiagnosticFormat::Sarif => Box::new(
+                    SarifReporter::default()
+                        .with_tool(env!("CARGO_BIN_NAME"), env!("CARGO_PKG_VERSION"))
+                        .with_severity_filter(filter),
+                ),
+            }
         };
         call_with_module!(
             self,
             reporter,
-            |module: &Module, cache, loader: &mut FsModuleLoader| {
-                loader.set_severity_filter(self.level.into());
+            |module: &Module, cache: &DiskModuleCache, loader: &mut FsModuleLoader| {
                 module.validate(cache, loader, self.check_constraints);
 
-                let term_set = default_term_set()?;
-                validate_module_terms(module, &term_set, loader);
+                let allowed_cycles: HashSet<Identifier> =
+                    self.allow_cycle.iter().cloned().collect();
+                validate_module_import_cycles(module, cache, loader, &allowed_cycles)?;
+
+                let mut term_sets = Vec::default();
+                if !self.no_default_terms {
+                    let term_set = match loader.lint_config().cloned() {
+                        Some(lint_config) => lint_config.deprecated_terms(loader)?,
+                        None => None,
+                    };
+                    term_sets.push(match term_set {
+                        Some(term_set) => term_set,
+                        None => default_term_set()?,
+                    });
+                }
+                for path in &self.terms {
+                    term_sets.push(load_term_set(path, loader)?);
+                }
+                if let Some(term_set) = TermSet::merged(term_sets) {
+                    validate_module_terms(module, &term_set, loader);
+                }
+
+                if self.lint {
+                    LintRegistry::standard().check_and_report(module, cache, loader)?;
+                }
+
+                if self.report_unused_suppressions {
+                    if let Some(file_id) = module.file_id() {
+                        loader.report_unused_suppressions(*file_id)?;
+                    }
+                }
+
+                if let Some(document) = &document {
+                    for module in cache.modules() {
+                        if let Some(file_id) = module.file_id() {
+                            document.set_module_name(*file_id, module.name().to_string());
+                        }
+                    }
+                }
 
                 let reports = loader.reporter_done(Some(module.name().to_string()))?;
 
                 if reports.total() > 0 {
                     Ok(ExitCode::FAILURE)
                 } else {
-                    println!("No issues found.");
+                    // machine-readable diagnostics, and reports, are the only output written to
+                    // stdout.
+                    if document.is_none()
+                        && !matches!(format, DiagnosticFormat::Sarif | DiagnosticFormat::Json)
+                    {
+                        println!("No issues found.");
+                    }
                     Ok(ExitCode::SUCCESS)
                 }
             }
@@ -143,3 +287,12 @@ impl From<DiagnosticLevel> for SeverityFilter {
         }
     }
 }
+
+impl From<DiagnosticReport> for ReportFormat {
+    fn from(value: DiagnosticReport) -> Self {
+        match value {
+            DiagnosticReport::Html => ReportFormat::Html,
+            DiagnosticReport::Markdown => ReportFormat::Markdown,
+        }
+    }
+}
@@ -46,6 +46,7 @@ use sdml_generate::Generator;
 ///   datatype Uuid <- sdml:string ;; ...
 ///
 ///   entity Example is
+///     identity example_id -> Uuid
 ///     version -> Uuid
 ///     name -> sdml:string ;; ...
 ///   end
@@ -66,6 +67,7 @@ use sdml_generate::Generator;
 ///   end
 ///
 ///   entity Example is
+///     identity example_id -> Uuid
 ///     version -> Uuid
 ///     name -> sdml:string is
 ///       @dc:description = "the name of this thing"@en
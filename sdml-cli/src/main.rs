@@ -3,6 +3,7 @@ use clap::{Parser, ValueEnum};
 use sdml_errors::diagnostics::UseColor;
 use sdml_errors::Error;
 use sdml_generate::color::set_colorize;
+use std::path::PathBuf;
 use std::process::ExitCode;
 use tracing::{error, info};
 use tracing_subscriber::filter::EnvFilter;
@@ -41,6 +42,14 @@ struct Cli {
     )]
     no_color: bool,
 
+    /// Directory in which to cache parsed modules between invocations
+    #[arg(long, env = "SDML_CACHE_DIR")]
+    cache_dir: Option<PathBuf>,
+
+    /// Locale for diagnostic messages, for example `de` or `fr-CA`
+    #[arg(long, env = "SDML_LOCALE")]
+    locale: Option<String>,
+
     #[command(subcommand)]
     command: commands::Commands,
 }
@@ -72,10 +81,16 @@ fn main() -> ExitCode {
 
     init_color(cli.no_color);
 
+    commands::set_cache_directory(cli.cache_dir);
+
     if let Err(e) = init_logging(cli.log_filter) {
         eprintln!("Could not initialize tracing; error: {e}");
         error!("init_logging failed, exiting. error: {e:?}");
         ExitCode::FAILURE
+    } else if let Err(e) = init_locale(cli.locale.as_deref()) {
+        eprintln!("Could not select locale; error: {e}");
+        error!("init_locale failed, exiting. error: {e:?}");
+        ExitCode::FAILURE
     } else {
         match cli.command.execute() {
             Ok(v) => v,
@@ -92,6 +107,14 @@ fn main() -> ExitCode {
 // Private Functions
 // ------------------------------------------------------------------------------------------------
 
+fn init_locale(locale: Option<&str>) -> Result<(), Error> {
+    if let Some(locale) = locale {
+        let selected = sdml_errors::i18n::set_locale(locale)?;
+        info!("Diagnostic locale set to `{selected}`");
+    }
+    Ok(())
+}
+
 fn init_logging(log_filter: LogFilter) -> Result<(), Error> {
     let log_level_filter = match log_filter {
         LogFilter::None => TracingLevelFilter::OFF,
@@ -0,0 +1,1029 @@
+/*!
+Provides a fluent builder API for constructing [`Module`]s programmatically.
+
+Each builder collects names as plain strings and values as model [`Value`]s; nothing is checked
+until [`ModuleBuilder::build`] is called, which validates every identifier and identifier
+reference, rejects duplicate definition, member, and variant names, and returns a complete
+[`Module`] that may be passed directly to the generators and the model checker.
+
+The following builders are provided, one for each kind of definition allowed in a non-library
+module:
+
+* [`DatatypeBuilder`] :: `datatype` definitions, including opaque datatypes.
+* [`EntityBuilder`] :: `entity` definitions with an identity member.
+* [`EnumBuilder`] :: `enum` definitions, with variants from [`VariantBuilder`].
+* [`EventBuilder`] :: `event` definitions with an event source.
+* [`MemberBuilder`] :: members, property definitions, and references to properties.
+* [`StructureBuilder`] :: `structure` definitions.
+* [`UnionBuilder`] :: `union` definitions, with variants from [`VariantBuilder`].
+
+Type names and annotation predicates may be either simple (`VIN`) or qualified (`xsd:string`)
+identifiers; the builtin simple types, such as `string`, are resolved to the `sdml` module as they
+are by the parser, and a type named `unknown` is the unknown type.
+
+# Example
+
+```rust
+use sdml_core::builder::{
+    DatatypeBuilder, EntityBuilder, EnumBuilder, MemberBuilder, ModuleBuilder,
+};
+use sdml_core::model::members::Cardinality;
+use sdml_core::model::{HasBody, HasName};
+
+let module = ModuleBuilder::new("rentals")
+    .with_base_uri("https://example.org/rentals/")
+    .with_version_info("1.0")
+    .with_import("skos")
+    .with_import("xsd")
+    .with_annotation("skos:prefLabel", "Rentals")
+    .with_datatype(DatatypeBuilder::new("VIN", "xsd:string").opaque())
+    .with_enum(EnumBuilder::new("Color").with_variant("Red").with_variant("Blue"))
+    .with_entity(
+        EntityBuilder::new("Vehicle")
+            .with_identity(MemberBuilder::new("vin", "VIN"))
+            .with_member(MemberBuilder::new("color", "Color"))
+            .with_member(
+                MemberBuilder::new("owners", "xsd:string")
+                    .with_cardinality(Cardinality::zero_or_more(None, None)),
+            ),
+    )
+    .build()
+    .unwrap();
+
+assert_eq!(module.name().as_ref(), "rentals");
+assert_eq!(module.body().definitions_len(), 3);
+
+let duplicate = ModuleBuilder::new("rentals")
+    .with_enum(EnumBuilder::new("Color"))
+    .with_enum(EnumBuilder::new("Color"))
+    .build();
+assert!(duplicate.is_err());
+```
+
+[`Module`]: crate::model::modules::Module
+[`Value`]: crate::model::values::Value
+*/
+
+use crate::error::Error;
+use crate::model::annotations::{
+    Annotation, AnnotationOnlyBody, AnnotationProperty, HasAnnotations,
+};
+use crate::model::definitions::{
+    DatatypeDef, Definition, EntityBody, EntityDef, EnumBody, EnumDef, EventDef, HasMembers,
+    HasVariants, PropertyDef, StructureBody, StructureDef, TypeVariant, UnionBody, UnionDef,
+    ValueVariant,
+};
+use crate::model::identifiers::{Identifier, IdentifierReference, QualifiedIdentifier};
+use crate::model::members::{
+    Cardinality, MappingType, Member, MemberDef, MemberKind, TypeReference,
+};
+use crate::model::modules::{Import, ImportStatement, Module, ModuleBody};
+use crate::model::values::Value;
+use crate::model::{HasName, HasOptionalBody};
+use crate::syntax::NAME_SDML;
+use std::collections::HashSet;
+use url::Url;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A builder for a complete [`Module`], see the [module documentation](self) for an example.
+///
+#[derive(Clone, Debug)]
+pub struct ModuleBuilder {
+    name: String,
+    base_uri: Option<String>,
+    version_info: Option<String>,
+    version_uri: Option<String>,
+    imports: Vec<String>,
+    annotations: Vec<(String, Value)>,
+    definitions: Vec<DefinitionBuilder>,
+}
+
+///
+/// A builder for a `datatype` definition.
+///
+#[derive(Clone, Debug)]
+pub struct DatatypeBuilder {
+    name: String,
+    base_type: String,
+    opaque: bool,
+    annotations: Vec<(String, Value)>,
+}
+
+///
+/// A builder for an `entity` definition; an entity with members or annotations must also have an
+/// identity member.
+///
+#[derive(Clone, Debug)]
+pub struct EntityBuilder {
+    name: String,
+    identity: Option<MemberBuilder>,
+    annotations: Vec<(String, Value)>,
+    members: Vec<MemberBuilder>,
+}
+
+///
+/// A builder for an `enum` definition.
+///
+#[derive(Clone, Debug)]
+pub struct EnumBuilder {
+    name: String,
+    annotations: Vec<(String, Value)>,
+    variants: Vec<VariantBuilder>,
+}
+
+///
+/// A builder for an `event` definition.
+///
+#[derive(Clone, Debug)]
+pub struct EventBuilder {
+    name: String,
+    event_source: String,
+    annotations: Vec<(String, Value)>,
+    members: Vec<MemberBuilder>,
+}
+
+///
+/// A builder for a `structure` definition.
+///
+#[derive(Clone, Debug)]
+pub struct StructureBuilder {
+    name: String,
+    annotations: Vec<(String, Value)>,
+    members: Vec<MemberBuilder>,
+}
+
+///
+/// A builder for a `union` definition.
+///
+#[derive(Clone, Debug)]
+pub struct UnionBuilder {
+    name: String,
+    annotations: Vec<(String, Value)>,
+    variants: Vec<VariantBuilder>,
+}
+
+///
+/// A builder for a member of an entity, event, or structure, or for the member definition of a
+/// `property` definition.
+///
+#[derive(Clone, Debug)]
+pub struct MemberBuilder {
+    kind: MemberBuilderKind,
+    cardinality: Option<Cardinality>,
+    annotations: Vec<(String, Value)>,
+}
+
+///
+/// A builder for either a value variant of an enum, or a type variant of a union. For a union the
+/// name is a reference to the variant's type and the variant may be renamed.
+///
+#[derive(Clone, Debug)]
+pub struct VariantBuilder {
+    name: String,
+    rename: Option<String>,
+    annotations: Vec<(String, Value)>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug)]
+enum DefinitionBuilder {
+    Datatype(DatatypeBuilder),
+    Entity(EntityBuilder),
+    Enum(EnumBuilder),
+    Event(EventBuilder),
+    Property(MemberBuilder),
+    Structure(StructureBuilder),
+    Union(UnionBuilder),
+    Existing(Definition),
+}
+
+#[derive(Clone, Debug)]
+enum MemberBuilderKind {
+    Definition {
+        name: String,
+        target_type: TargetType,
+    },
+    Reference(String),
+}
+
+#[derive(Clone, Debug)]
+enum TargetType {
+    Named(String),
+    Mapping(String, String),
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl ModuleBuilder {
+    pub fn new<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            base_uri: None,
+            version_info: None,
+            version_uri: None,
+            imports: Default::default(),
+            annotations: Default::default(),
+            definitions: Default::default(),
+        }
+    }
+
+    pub fn with_base_uri<S>(self, base_uri: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            base_uri: Some(base_uri.into()),
+            ..self
+        }
+    }
+
+    pub fn with_version_info<S>(self, version_info: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            version_info: Some(version_info.into()),
+            ..self
+        }
+    }
+
+    pub fn with_version_uri<S>(self, version_uri: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            version_uri: Some(version_uri.into()),
+            ..self
+        }
+    }
+
+    ///
+    /// Import either a module, `xsd`, or a single definition, `xsd:string`.
+    ///
+    pub fn with_import<S>(self, import: S) -> Self
+    where
+        S: Into<String>,
+    {
+        let mut self_mut = self;
+        self_mut.imports.push(import.into());
+        self_mut
+    }
+
+    pub fn with_annotation<S, V>(self, predicate: S, value: V) -> Self
+    where
+        S: Into<String>,
+        V: Into<Value>,
+    {
+        let mut self_mut = self;
+        self_mut.annotations.push((predicate.into(), value.into()));
+        self_mut
+    }
+
+    pub fn with_datatype(self, datatype: DatatypeBuilder) -> Self {
+        self.with_definition_builder(DefinitionBuilder::Datatype(datatype))
+    }
+
+    pub fn with_entity(self, entity: EntityBuilder) -> Self {
+        self.with_definition_builder(DefinitionBuilder::Entity(entity))
+    }
+
+    pub fn with_enum(self, an_enum: EnumBuilder) -> Self {
+        self.with_definition_builder(DefinitionBuilder::Enum(an_enum))
+    }
+
+    pub fn with_event(self, event: EventBuilder) -> Self {
+        self.with_definition_builder(DefinitionBuilder::Event(event))
+    }
+
+    ///
+    /// Add a `property` definition; the member must be a definition, not a property reference.
+    ///
+    pub fn with_property(self, property: MemberBuilder) -> Self {
+        self.with_definition_builder(DefinitionBuilder::Property(property))
+    }
+
+    pub fn with_structure(self, structure: StructureBuilder) -> Self {
+        self.with_definition_builder(DefinitionBuilder::Structure(structure))
+    }
+
+    pub fn with_union(self, union: UnionBuilder) -> Self {
+        self.with_definition_builder(DefinitionBuilder::Union(union))
+    }
+
+    ///
+    /// Add an existing definition; only its name is checked, for duplicates, when the module is
+    /// built.
+    ///
+    pub fn with_definition<D>(self, definition: D) -> Self
+    where
+        D: Into<Definition>,
+    {
+        self.with_definition_builder(DefinitionBuilder::Existing(definition.into()))
+    }
+
+    ///
+    /// Validate all names and construct the module.
+    ///
+    pub fn build(&self) -> Result<Module, Error> {
+        let name = identifier(&self.name, &self.name)?;
+
+        let mut body = ModuleBody::default();
+        body.set_library_status(&name);
+        if !self.imports.is_empty() {
+            let imports = self
+                .imports
+                .iter()
+                .map(|import| {
+                    reference(import, &self.name).map(|reference| match reference {
+                        IdentifierReference::Identifier(v) => Import::from(v),
+                        IdentifierReference::QualifiedIdentifier(v) => Import::from(v),
+                    })
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            body.add_to_imports(ImportStatement::new(imports));
+        }
+        for annotation in annotations(&self.annotations, &self.name)? {
+            body.add_to_annotations(annotation);
+        }
+
+        let mut names = HashSet::new();
+        for definition in &self.definitions {
+            let definition = definition.build(&self.name)?;
+            if !names.insert(definition.name().clone()) {
+                return Err(duplicate(&self.name, "definition", definition.name()));
+            }
+            body.add_to_definitions(definition)?;
+        }
+
+        let mut module = Module::new(name, body);
+        if let Some(base_uri) = &self.base_uri {
+            module = module.with_base_uri(Url::parse(base_uri)?);
+        }
+        if let Some(version_info) = &self.version_info {
+            module = module.with_version_info(version_info);
+        }
+        if let Some(version_uri) = &self.version_uri {
+            module = module.with_version_uri(Url::parse(version_uri)?);
+        }
+        Ok(module)
+    }
+
+    fn with_definition_builder(self, definition: DefinitionBuilder) -> Self {
+        let mut self_mut = self;
+        self_mut.definitions.push(definition);
+        self_mut
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl DefinitionBuilder {
+    fn build(&self, module: &str) -> Result<Definition, Error> {
+        Ok(match self {
+            Self::Datatype(v) => v.build(module)?.into(),
+            Self::Entity(v) => v.build(module)?.into(),
+            Self::Enum(v) => v.build(module)?.into(),
+            Self::Event(v) => v.build(module)?.into(),
+            Self::Property(v) => {
+                let context = format!("{module}:{}", v.name());
+                match v.build(&context)?.kind() {
+                    MemberKind::Definition(member) => PropertyDef::new(member.clone()).into(),
+                    MemberKind::Reference(_) => {
+                        return Err(builder_error(
+                            &context,
+                            "a property must define a member, not reference a property",
+                        ))
+                    }
+                }
+            }
+            Self::Structure(v) => v.build(module)?.into(),
+            Self::Union(v) => v.build(module)?.into(),
+            Self::Existing(v) => v.clone(),
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl DatatypeBuilder {
+    pub fn new<S1, S2>(name: S1, base_type: S2) -> Self
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            base_type: base_type.into(),
+            opaque: false,
+            annotations: Default::default(),
+        }
+    }
+
+    pub fn opaque(self) -> Self {
+        Self {
+            opaque: true,
+            ..self
+        }
+    }
+
+    pub fn with_annotation<S, V>(self, predicate: S, value: V) -> Self
+    where
+        S: Into<String>,
+        V: Into<Value>,
+    {
+        let mut self_mut = self;
+        self_mut.annotations.push((predicate.into(), value.into()));
+        self_mut
+    }
+
+    fn build(&self, module: &str) -> Result<DatatypeDef, Error> {
+        let context = format!("{module}:{}", self.name);
+        let name = identifier(&self.name, &context)?;
+        let base_type = type_reference(&self.base_type, &context)?;
+        let datatype = if self.opaque {
+            DatatypeDef::new_opaque(name, base_type)
+        } else {
+            DatatypeDef::new(name, base_type)
+        };
+        Ok(match annotation_body(&self.annotations, &context)? {
+            Some(body) => datatype.with_body(body),
+            None => datatype,
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl EntityBuilder {
+    pub fn new<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            identity: None,
+            annotations: Default::default(),
+            members: Default::default(),
+        }
+    }
+
+    pub fn with_identity(self, identity: MemberBuilder) -> Self {
+        Self {
+            identity: Some(identity),
+            ..self
+        }
+    }
+
+    pub fn with_annotation<S, V>(self, predicate: S, value: V) -> Self
+    where
+        S: Into<String>,
+        V: Into<Value>,
+    {
+        let mut self_mut = self;
+        self_mut.annotations.push((predicate.into(), value.into()));
+        self_mut
+    }
+
+    pub fn with_member(self, member: MemberBuilder) -> Self {
+        let mut self_mut = self;
+        self_mut.members.push(member);
+        self_mut
+    }
+
+    fn build(&self, module: &str) -> Result<EntityDef, Error> {
+        let context = format!("{module}:{}", self.name);
+        let mut entity = EntityDef::new(identifier(&self.name, &context)?);
+        match &self.identity {
+            Some(identity) => {
+                let identity = identity.build(&context)?;
+                let mut names = HashSet::from([identity.name().clone()]);
+                let mut body = EntityBody::new(identity);
+                for annotation in annotations(&self.annotations, &context)? {
+                    body.add_to_annotations(annotation);
+                }
+                body.extend_members(members(&self.members, &context, &mut names)?);
+                entity.set_body(body);
+            }
+            None if !self.annotations.is_empty() || !self.members.is_empty() => {
+                return Err(builder_error(
+                    &context,
+                    "an entity with annotations or members must have an identity member",
+                ));
+            }
+            None => {}
+        }
+        Ok(entity)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl EnumBuilder {
+    pub fn new<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            annotations: Default::default(),
+            variants: Default::default(),
+        }
+    }
+
+    pub fn with_annotation<S, V>(self, predicate: S, value: V) -> Self
+    where
+        S: Into<String>,
+        V: Into<Value>,
+    {
+        let mut self_mut = self;
+        self_mut.annotations.push((predicate.into(), value.into()));
+        self_mut
+    }
+
+    pub fn with_variant<V>(self, variant: V) -> Self
+    where
+        V: Into<VariantBuilder>,
+    {
+        let mut self_mut = self;
+        self_mut.variants.push(variant.into());
+        self_mut
+    }
+
+    fn build(&self, module: &str) -> Result<EnumDef, Error> {
+        let context = format!("{module}:{}", self.name);
+        let mut an_enum = EnumDef::new(identifier(&self.name, &context)?);
+        if !self.annotations.is_empty() || !self.variants.is_empty() {
+            let mut body = EnumBody::default();
+            for annotation in annotations(&self.annotations, &context)? {
+                body.add_to_annotations(annotation);
+            }
+            let mut names = HashSet::new();
+            for variant in &self.variants {
+                let variant = variant.build_value_variant(&context)?;
+                if !names.insert(variant.name().clone()) {
+                    return Err(duplicate(&context, "variant", variant.name()));
+                }
+                body.add_to_variants(variant);
+            }
+            an_enum.set_body(body);
+        }
+        Ok(an_enum)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl EventBuilder {
+    pub fn new<S1, S2>(name: S1, event_source: S2) -> Self
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            event_source: event_source.into(),
+            annotations: Default::default(),
+            members: Default::default(),
+        }
+    }
+
+    pub fn with_annotation<S, V>(self, predicate: S, value: V) -> Self
+    where
+        S: Into<String>,
+        V: Into<Value>,
+    {
+        let mut self_mut = self;
+        self_mut.annotations.push((predicate.into(), value.into()));
+        self_mut
+    }
+
+    pub fn with_member(self, member: MemberBuilder) -> Self {
+        let mut self_mut = self;
+        self_mut.members.push(member);
+        self_mut
+    }
+
+    fn build(&self, module: &str) -> Result<EventDef, Error> {
+        let context = format!("{module}:{}", self.name);
+        let mut event = EventDef::new(
+            identifier(&self.name, &context)?,
+            reference(&self.event_source, &context)?,
+        );
+        if let Some(body) = structure_body(&self.annotations, &self.members, &context)? {
+            event.set_body(body);
+        }
+        Ok(event)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl StructureBuilder {
+    pub fn new<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            annotations: Default::default(),
+            members: Default::default(),
+        }
+    }
+
+    pub fn with_annotation<S, V>(self, predicate: S, value: V) -> Self
+    where
+        S: Into<String>,
+        V: Into<Value>,
+    {
+        let mut self_mut = self;
+        self_mut.annotations.push((predicate.into(), value.into()));
+        self_mut
+    }
+
+    pub fn with_member(self, member: MemberBuilder) -> Self {
+        let mut self_mut = self;
+        self_mut.members.push(member);
+        self_mut
+    }
+
+    fn build(&self, module: &str) -> Result<StructureDef, Error> {
+        let context = format!("{module}:{}", self.name);
+        let mut structure = StructureDef::new(identifier(&self.name, &context)?);
+        if let Some(body) = structure_body(&self.annotations, &self.members, &context)? {
+            structure.set_body(body);
+        }
+        Ok(structure)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl UnionBuilder {
+    pub fn new<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            annotations: Default::default(),
+            variants: Default::default(),
+        }
+    }
+
+    pub fn with_annotation<S, V>(self, predicate: S, value: V) -> Self
+    where
+        S: Into<String>,
+        V: Into<Value>,
+    {
+        let mut self_mut = self;
+        self_mut.annotations.push((predicate.into(), value.into()));
+        self_mut
+    }
+
+    pub fn with_variant<V>(self, variant: V) -> Self
+    where
+        V: Into<VariantBuilder>,
+    {
+        let mut self_mut = self;
+        self_mut.variants.push(variant.into());
+        self_mut
+    }
+
+    fn build(&self, module: &str) -> Result<UnionDef, Error> {
+        let context = format!("{module}:{}", self.name);
+        let mut union = UnionDef::new(identifier(&self.name, &context)?);
+        if !self.annotations.is_empty() || !self.variants.is_empty() {
+            let mut body = UnionBody::default();
+            for annotation in annotations(&self.annotations, &context)? {
+                body.add_to_annotations(annotation);
+            }
+            let mut names = HashSet::new();
+            for variant in &self.variants {
+                let variant = variant.build_type_variant(&context)?;
+                let name = variant
+                    .rename()
+                    .unwrap_or_else(|| variant.name_reference().member());
+                if !names.insert(name.clone()) {
+                    return Err(duplicate(&context, "variant", name));
+                }
+                body.add_to_variants(variant);
+            }
+            union = union.with_body(body);
+        }
+        Ok(union)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl MemberBuilder {
+    ///
+    /// A member definition with the named target type, `xsd:string`, `Vehicle`, or `unknown`,
+    /// and the default cardinality of exactly one.
+    ///
+    pub fn new<S1, S2>(name: S1, target_type: S2) -> Self
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        Self {
+            kind: MemberBuilderKind::Definition {
+                name: name.into(),
+                target_type: TargetType::Named(target_type.into()),
+            },
+            cardinality: None,
+            annotations: Default::default(),
+        }
+    }
+
+    ///
+    /// A member definition with a mapping type, `(domain -> range)`.
+    ///
+    pub fn new_mapping<S1, S2, S3>(name: S1, domain: S2, range: S3) -> Self
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<String>,
+    {
+        Self {
+            kind: MemberBuilderKind::Definition {
+                name: name.into(),
+                target_type: TargetType::Mapping(domain.into(), range.into()),
+            },
+            cardinality: None,
+            annotations: Default::default(),
+        }
+    }
+
+    ///
+    /// A member that references a `property` definition by name.
+    ///
+    pub fn new_reference<S>(property: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            kind: MemberBuilderKind::Reference(property.into()),
+            cardinality: None,
+            annotations: Default::default(),
+        }
+    }
+
+    pub fn with_cardinality(self, cardinality: Cardinality) -> Self {
+        Self {
+            cardinality: Some(cardinality),
+            ..self
+        }
+    }
+
+    pub fn with_annotation<S, V>(self, predicate: S, value: V) -> Self
+    where
+        S: Into<String>,
+        V: Into<Value>,
+    {
+        let mut self_mut = self;
+        self_mut.annotations.push((predicate.into(), value.into()));
+        self_mut
+    }
+
+    fn name(&self) -> &str {
+        match &self.kind {
+            MemberBuilderKind::Definition { name, .. } => name,
+            MemberBuilderKind::Reference(name) => name,
+        }
+    }
+
+    fn build(&self, parent: &str) -> Result<Member, Error> {
+        let context = format!("{parent}.{}", self.name());
+        match &self.kind {
+            MemberBuilderKind::Definition { name, target_type } => {
+                let target_type = match target_type {
+                    TargetType::Named(name) if name == "unknown" => TypeReference::Unknown,
+                    TargetType::Named(name) => TypeReference::Type(type_reference(name, &context)?),
+                    TargetType::Mapping(domain, range) => {
+                        TypeReference::MappingType(MappingType::new(
+                            TypeReference::Type(type_reference(domain, &context)?),
+                            TypeReference::Type(type_reference(range, &context)?),
+                        ))
+                    }
+                };
+                let mut member = MemberDef::new(identifier(name, &context)?, target_type);
+                if let Some(cardinality) = &self.cardinality {
+                    member = member.with_target_cardinality(cardinality.clone());
+                }
+                if let Some(body) = annotation_body(&self.annotations, &context)? {
+                    member = member.with_body(body);
+                }
+                Ok(Member::new_definition(member))
+            }
+            MemberBuilderKind::Reference(property) => {
+                if self.cardinality.is_some() || !self.annotations.is_empty() {
+                    Err(builder_error(
+                        &context,
+                        "a property reference may not have a cardinality or annotations",
+                    ))
+                } else {
+                    Ok(Member::new_reference(reference(property, &context)?))
+                }
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl From<&str> for VariantBuilder {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for VariantBuilder {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl VariantBuilder {
+    pub fn new<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            rename: None,
+            annotations: Default::default(),
+        }
+    }
+
+    ///
+    /// Rename a union's type variant, this is an error for the variant of an enum.
+    ///
+    pub fn with_rename<S>(self, rename: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            rename: Some(rename.into()),
+            ..self
+        }
+    }
+
+    pub fn with_annotation<S, V>(self, predicate: S, value: V) -> Self
+    where
+        S: Into<String>,
+        V: Into<Value>,
+    {
+        let mut self_mut = self;
+        self_mut.annotations.push((predicate.into(), value.into()));
+        self_mut
+    }
+
+    fn build_value_variant(&self, parent: &str) -> Result<ValueVariant, Error> {
+        let context = format!("{parent}.{}", self.name);
+        if self.rename.is_some() {
+            return Err(builder_error(
+                &context,
+                "an enum variant may not be renamed",
+            ));
+        }
+        let mut variant = ValueVariant::new(identifier(&self.name, &context)?);
+        if let Some(body) = annotation_body(&self.annotations, &context)? {
+            variant.set_body(body);
+        }
+        Ok(variant)
+    }
+
+    fn build_type_variant(&self, parent: &str) -> Result<TypeVariant, Error> {
+        let context = format!("{parent}.{}", self.name);
+        let mut variant = match annotation_body(&self.annotations, &context)? {
+            Some(body) => TypeVariant::new_with(reference(&self.name, &context)?, body),
+            None => TypeVariant::new(reference(&self.name, &context)?),
+        };
+        if let Some(rename) = &self.rename {
+            variant = variant.with_rename(identifier(rename, &context)?);
+        }
+        Ok(variant)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn builder_error<S>(name: &str, message: S) -> Error
+where
+    S: Into<String>,
+{
+    Error::BuilderError {
+        name: name.to_string(),
+        message: message.into(),
+    }
+}
+
+fn duplicate(name: &str, kind: &str, duplicate: &Identifier) -> Error {
+    builder_error(name, format!("duplicate {kind} name `{duplicate}`"))
+}
+
+fn identifier(value: &str, context: &str) -> Result<Identifier, Error> {
+    if Identifier::is_valid(value) {
+        Ok(Identifier::new_unchecked(value))
+    } else {
+        Err(builder_error(
+            context,
+            format!("`{value}` is not a valid identifier"),
+        ))
+    }
+}
+
+fn reference(value: &str, context: &str) -> Result<IdentifierReference, Error> {
+    match value.split_once(':') {
+        Some((module, member)) => Ok(QualifiedIdentifier::new(
+            identifier(module, context)?,
+            identifier(member, context)?,
+        )
+        .into()),
+        None => Ok(identifier(value, context)?.into()),
+    }
+}
+
+fn type_reference(value: &str, context: &str) -> Result<IdentifierReference, Error> {
+    if Identifier::is_type_name(value) {
+        Ok(QualifiedIdentifier::new(
+            Identifier::new_unchecked(NAME_SDML),
+            Identifier::new_unchecked(value),
+        )
+        .into())
+    } else {
+        reference(value, context)
+    }
+}
+
+fn annotations(values: &[(String, Value)], context: &str) -> Result<Vec<Annotation>, Error> {
+    values
+        .iter()
+        .map(|(predicate, value)| {
+            Ok(AnnotationProperty::new(reference(predicate, context)?, value.clone()).into())
+        })
+        .collect()
+}
+
+fn annotation_body(
+    values: &[(String, Value)],
+    context: &str,
+) -> Result<Option<AnnotationOnlyBody>, Error> {
+    if values.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(AnnotationOnlyBody::from(annotations(
+            values, context,
+        )?)))
+    }
+}
+
+fn members(
+    builders: &[MemberBuilder],
+    context: &str,
+    names: &mut HashSet<Identifier>,
+) -> Result<Vec<Member>, Error> {
+    builders
+        .iter()
+        .map(|builder| {
+            let member = builder.build(context)?;
+            if names.insert(member.name().clone()) {
+                Ok(member)
+            } else {
+                Err(duplicate(context, "member", member.name()))
+            }
+        })
+        .collect()
+}
+
+fn structure_body(
+    annotation_values: &[(String, Value)],
+    member_builders: &[MemberBuilder],
+    context: &str,
+) -> Result<Option<StructureBody>, Error> {
+    if annotation_values.is_empty() && member_builders.is_empty() {
+        Ok(None)
+    } else {
+        let mut body = StructureBody::default();
+        for annotation in annotations(annotation_values, context)? {
+            body.add_to_annotations(annotation);
+        }
+        body.extend_members(members(member_builders, context, &mut HashSet::new())?);
+        Ok(Some(body))
+    }
+}
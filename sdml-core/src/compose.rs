@@ -0,0 +1,664 @@
+/*!
+Provides module composition, merging a set of modules into one and the inverse, splitting one
+module into several.
+
+A [`Merge`] combines the definitions of a set of modules into a single new module. References
+between the merged modules, qualified or not, become local references, imports of the merged
+modules are removed and all other imports are combined without duplicates, as are the
+annotations on the modules themselves. Where more than one module has a definition with the same
+name, the definitions are compared using [`ModuleDiff`]:
+
+* if they have the same *shape*, differing at most in their annotations, the first definition is
+  kept and the annotations of the others are added to it;
+* otherwise the first definition is kept and a [`MergeConflict`] records the definition that was
+  dropped; conflicts can be reported as diagnostics with [`MergeConflict::to_diagnostic`].
+
+A [`Split`] divides the definitions of a module into a number of new modules, either by the kind
+of each definition or by the value of an annotation on each definition, such as `dc:subject`.
+References between definitions that end up in different modules become qualified references and
+the modules that require them gain the corresponding imports. Each new module is named by adding
+a suffix to the original module's name, for example `rentals_entity` or `rentals_billing`;
+definitions without the annotation remain in a module with the original name.
+
+# Example
+
+```rust
+use sdml_core::compose::{Merge, Split};
+use sdml_core::model::definitions::{Definition, StructureDef};
+use sdml_core::model::identifiers::Identifier;
+use sdml_core::model::modules::{Module, ModuleBody};
+use sdml_core::model::{HasBody, HasName};
+
+fn module_with(name: &str, definition: &str) -> Module {
+    let mut body = ModuleBody::default();
+    body.add_to_definitions(Definition::Structure(StructureDef::new(
+        Identifier::new_unchecked(definition),
+    )))
+    .unwrap();
+    Module::new(Identifier::new_unchecked(name), body)
+}
+
+let addresses = module_with("addresses", "Address");
+let people = module_with("people", "Person");
+
+let merged = Merge::new(Identifier::new_unchecked("directory"))
+    .merge([&addresses, &people])
+    .unwrap();
+assert!(!merged.has_conflicts());
+assert_eq!(merged.module().body().definitions_len(), 2);
+
+let parts = Split::by_kind().split(merged.module()).unwrap();
+assert_eq!(parts.len(), 1);
+assert_eq!(parts[0].name().as_ref(), "directory_structure");
+```
+
+[`ModuleDiff`]: crate::diff::ModuleDiff
+*/
+
+use crate::builder::ModuleBuilder;
+use crate::diff::{Change, ModuleDiff};
+use crate::error::Error;
+use crate::model::annotations::{Annotation, HasAnnotations};
+use crate::model::check::usage::prune_module;
+use crate::model::definitions::Definition;
+use crate::model::identifiers::{Identifier, IdentifierReference, QualifiedIdentifier};
+use crate::model::modules::{Import, ImportStatement, Module, ModuleBody};
+use crate::model::values::{SimpleValue, Value};
+use crate::model::walk::passes::{ModulePass, QualifyReferences};
+use crate::model::walk::{walk_module_mut, MutModuleVisitor};
+use crate::model::{HasBody, HasName, HasNameReference, HasOptionalBody, HasSourceSpan};
+use sdml_errors::diagnostics::functions::conflicting_definition;
+use sdml_errors::diagnostics::Diagnostic;
+use sdml_errors::{FileId, Span};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use url::Url;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The options used to merge a set of modules into a new module.
+///
+#[derive(Clone, Debug)]
+pub struct Merge {
+    name: Identifier,
+    base_uri: Option<Url>,
+    version_info: Option<String>,
+    version_uri: Option<Url>,
+}
+
+///
+/// The result of a merge, the merged module and any conflicting definitions that were dropped.
+///
+#[derive(Clone, Debug)]
+pub struct MergedModule {
+    module: Module,
+    conflicts: Vec<MergeConflict>,
+}
+
+///
+/// A definition that was dropped from a merge as a definition with the same name, but a
+/// different shape, was merged from an earlier module.
+///
+#[derive(Clone, Debug)]
+pub struct MergeConflict {
+    definition: Identifier,
+    kept: DefinitionSource,
+    dropped: DefinitionSource,
+    differences: Vec<Change>,
+}
+
+///
+/// The criteria used to assign definitions to modules when splitting.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SplitBy {
+    /// One module for each kind of definition.
+    Kind,
+    /// One module for each value of the named annotation property.
+    Annotation(IdentifierReference),
+}
+
+///
+/// The options used to split a module into a number of new modules.
+///
+#[derive(Clone, Debug)]
+pub struct Split {
+    by: SplitBy,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug)]
+struct DefinitionSource {
+    module: Identifier,
+    file_id: Option<FileId>,
+    span: Option<Span>,
+}
+
+#[derive(Debug)]
+struct LocalizeReferences<'a> {
+    merged: &'a HashSet<Identifier>,
+}
+
+#[derive(Debug)]
+struct RelocateReferences<'a> {
+    module: &'a Identifier,
+    part: &'a Identifier,
+    parts: &'a HashMap<Identifier, Identifier>,
+    required: BTreeSet<Identifier>,
+}
+
+#[derive(Debug, Default)]
+struct ReferencedModules {
+    modules: BTreeSet<Identifier>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Merge {
+    pub fn new(name: Identifier) -> Self {
+        Self {
+            name,
+            base_uri: None,
+            version_info: None,
+            version_uri: None,
+        }
+    }
+
+    pub fn with_base_uri(self, base_uri: Url) -> Self {
+        Self {
+            base_uri: Some(base_uri),
+            ..self
+        }
+    }
+
+    pub fn with_version_info<S>(self, version_info: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            version_info: Some(version_info.into()),
+            ..self
+        }
+    }
+
+    pub fn with_version_uri(self, version_uri: Url) -> Self {
+        Self {
+            version_uri: Some(version_uri),
+            ..self
+        }
+    }
+
+    ///
+    /// Merge `modules`, in order, into a new module. Library modules may not be merged.
+    ///
+    pub fn merge<'a, I>(&self, modules: I) -> Result<MergedModule, Error>
+    where
+        I: IntoIterator<Item = &'a Module>,
+    {
+        let modules: Vec<&Module> = modules.into_iter().collect();
+        if modules.is_empty() {
+            return Err(self.error("there are no modules to merge"));
+        }
+        if let Some(library) = modules.iter().find(|module| module.is_library_module()) {
+            return Err(self.error(format!(
+                "the library module `{}` may not be merged",
+                library.name()
+            )));
+        }
+        let merged: HashSet<Identifier> =
+            modules.iter().map(|module| module.name().clone()).collect();
+
+        let mut imports: Vec<Import> = Default::default();
+        let mut annotations: Vec<Annotation> = Default::default();
+        let mut definitions: Vec<(Definition, DefinitionSource)> = Default::default();
+        let mut conflicts: Vec<MergeConflict> = Default::default();
+
+        for original in modules {
+            let mut module = original.clone();
+            QualifyReferences::default().apply(&mut module)?;
+            walk_module_mut(
+                &mut module,
+                &mut LocalizeReferences { merged: &merged },
+                true,
+                true,
+            )?;
+
+            for import in module.body().imports().flat_map(|s| s.imports()) {
+                if !imports.contains(import) {
+                    imports.push(import.clone());
+                }
+            }
+            for annotation in module.body().annotations() {
+                if !annotations.iter().any(|a| same_annotation(a, annotation)) {
+                    annotations.push(annotation.clone());
+                }
+            }
+
+            for definition in module.body().definitions() {
+                // spans are taken from the original, the clone's references have been changed.
+                let source = DefinitionSource {
+                    module: original.name().clone(),
+                    file_id: original.file_id().copied(),
+                    span: definition.source_span().map(|span| span.byte_range()),
+                };
+                match definitions
+                    .iter_mut()
+                    .find(|(kept, _)| kept.name() == definition.name())
+                {
+                    None => definitions.push((definition.clone(), source)),
+                    Some((kept, kept_source)) => {
+                        let differences = shape_differences(kept, definition)?;
+                        if differences.is_empty() {
+                            combine_annotations(kept, definition);
+                        } else {
+                            conflicts.push(MergeConflict {
+                                definition: definition.name().clone(),
+                                kept: kept_source.clone(),
+                                dropped: source,
+                                differences,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut body = ModuleBody::default();
+        body.set_library_status(&self.name);
+        for annotation in annotations {
+            body.add_to_annotations(annotation);
+        }
+        body.extend_definitions(definitions.into_iter().map(|(definition, _)| definition))?;
+
+        // Qualified references may require modules only imported for a member.
+        let mut referenced = ReferencedModules::default();
+        let mut module = Module::new(self.name.clone(), body);
+        walk_module_mut(&mut module, &mut referenced, true, true)?;
+        for required in referenced.modules {
+            if required != self.name
+                && !imports
+                    .iter()
+                    .any(|import| matches!(import, Import::Module(v) if v.name() == &required))
+            {
+                imports.push(Import::from(required));
+            }
+        }
+        if !imports.is_empty() {
+            module
+                .body_mut()
+                .add_to_imports(ImportStatement::new(imports));
+        }
+
+        if let Some(base_uri) = &self.base_uri {
+            module = module.with_base_uri(base_uri.clone());
+        }
+        if let Some(version_info) = &self.version_info {
+            module = module.with_version_info(version_info);
+        }
+        if let Some(version_uri) = &self.version_uri {
+            module = module.with_version_uri(version_uri.clone());
+        }
+
+        Ok(MergedModule { module, conflicts })
+    }
+
+    fn error<S>(&self, message: S) -> Error
+    where
+        S: Into<String>,
+    {
+        Error::RefactorError {
+            name: self.name.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl MergedModule {
+    pub fn module(&self) -> &Module {
+        &self.module
+    }
+
+    pub fn into_module(self) -> Module {
+        self.module
+    }
+
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+
+    pub fn conflicts(&self) -> impl Iterator<Item = &MergeConflict> {
+        self.conflicts.iter()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl MergeConflict {
+    pub fn definition(&self) -> &Identifier {
+        &self.definition
+    }
+
+    ///
+    /// The module containing the definition that was kept.
+    ///
+    pub fn kept_from(&self) -> &Identifier {
+        &self.kept.module
+    }
+
+    ///
+    /// The module containing the definition that was dropped.
+    ///
+    pub fn dropped_from(&self) -> &Identifier {
+        &self.dropped.module
+    }
+
+    ///
+    /// The changes needed to turn the kept definition into the dropped one.
+    ///
+    pub fn differences(&self) -> impl Iterator<Item = &Change> {
+        self.differences.iter()
+    }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        conflicting_definition(
+            self.dropped.location(),
+            self.kept.location(),
+            format!("{}:{}", self.dropped.module, self.definition),
+            &self
+                .differences
+                .iter()
+                .map(|change| change.to_string())
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl DefinitionSource {
+    // The file and span of the definition, if both are known.
+    fn location(&self) -> Option<(FileId, Span)> {
+        self.file_id.zip(self.span.clone())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Split {
+    pub fn new(by: SplitBy) -> Self {
+        Self { by }
+    }
+
+    pub fn by_kind() -> Self {
+        Self::new(SplitBy::Kind)
+    }
+
+    pub fn by_annotation(property: IdentifierReference) -> Self {
+        Self::new(SplitBy::Annotation(property))
+    }
+
+    ///
+    /// Split `module` into new modules, returned in name order. A module only has definitions,
+    /// the imports they use, and annotations copied from `module`; any version URI is dropped and a base URI
+    /// becomes the original base URI joined with the new module's name.
+    ///
+    pub fn split(&self, module: &Module) -> Result<Vec<Module>, Error> {
+        let module_name = module.name();
+        let mut qualified = module.clone();
+        QualifyReferences::default().apply(&mut qualified)?;
+
+        let mut parts: HashMap<Identifier, Identifier> = Default::default();
+        let mut definitions: BTreeMap<Identifier, Vec<Definition>> = Default::default();
+        for definition in qualified.body().definitions() {
+            let part = self.part_name(module_name, definition)?;
+            let _ = parts.insert(definition.name().clone(), part.clone());
+            definitions
+                .entry(part)
+                .or_default()
+                .push(definition.clone());
+        }
+
+        let mut result = Vec::default();
+        for (part, definitions) in definitions {
+            let mut body = ModuleBody::default();
+            body.set_library_status(&part);
+            for import in qualified.body().imports() {
+                body.add_to_imports(import.clone());
+            }
+            for annotation in qualified.body().annotations() {
+                body.add_to_annotations(annotation.clone());
+            }
+            body.extend_definitions(definitions)?;
+
+            let mut new_module = Module::new(part.clone(), body);
+            let mut relocate = RelocateReferences {
+                module: module_name,
+                part: &part,
+                parts: &parts,
+                required: Default::default(),
+            };
+            walk_module_mut(&mut new_module, &mut relocate, true, true)?;
+            new_module = prune_module(&new_module, None);
+            let imported: HashSet<Identifier> = new_module
+                .body()
+                .imported_modules()
+                .into_iter()
+                .cloned()
+                .collect();
+            for required in relocate.required {
+                if !imported.contains(&required) {
+                    new_module
+                        .body_mut()
+                        .add_to_imports(ImportStatement::new_module(required));
+                }
+            }
+
+            if let Some(base_uri) = module.base_uri() {
+                let base_uri = if &part == module_name {
+                    base_uri.value().clone()
+                } else {
+                    base_uri.value().join(&format!("{part}/"))?
+                };
+                new_module = new_module.with_base_uri(base_uri);
+            }
+            if let Some(version_info) = module.version_info() {
+                new_module = new_module.with_version_info(version_info.value());
+            }
+            result.push(new_module);
+        }
+        Ok(result)
+    }
+
+    fn part_name(&self, module: &Identifier, definition: &Definition) -> Result<Identifier, Error> {
+        let suffix = match &self.by {
+            SplitBy::Kind => Some(definition.kind().keyword().to_string()),
+            SplitBy::Annotation(property) => definition_annotations(definition)
+                .into_iter()
+                .filter_map(|annotation| annotation.as_annotation_property().cloned())
+                .find(|annotation| annotation.name_reference() == property)
+                .map(|annotation| tag_suffix(annotation.value()))
+                .filter(|suffix| !suffix.is_empty()),
+        };
+        match suffix {
+            None => Ok(module.clone()),
+            Some(suffix) => {
+                let name = format!("{module}_{suffix}");
+                if Identifier::is_valid(&name) {
+                    Ok(Identifier::new_unchecked(&name))
+                } else {
+                    Err(Error::RefactorError {
+                        name: module.to_string(),
+                        message: format!(
+                            "the definition `{}` would be split into the module `{name}` which is not a valid identifier",
+                            definition.name()
+                        ),
+                    })
+                }
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl MutModuleVisitor for LocalizeReferences<'_> {
+    fn import_statement(&mut self, thing: &mut ImportStatement) -> Result<bool, Error> {
+        let remaining: Vec<Import> = thing
+            .imports()
+            .filter(|import| !self.merged.contains(import.module()))
+            .cloned()
+            .collect();
+        if remaining.len() != thing.imports_len() {
+            *thing = ImportStatement::new(remaining);
+        }
+        Ok(thing.has_imports())
+    }
+
+    fn identifier_reference(&mut self, thing: &mut IdentifierReference) -> Result<(), Error> {
+        if let IdentifierReference::QualifiedIdentifier(v) = thing {
+            if self.merged.contains(v.module()) {
+                *thing = v.member().clone().into();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl MutModuleVisitor for RelocateReferences<'_> {
+    fn identifier_reference(&mut self, thing: &mut IdentifierReference) -> Result<(), Error> {
+        if let IdentifierReference::QualifiedIdentifier(v) = thing {
+            if v.module() == self.module {
+                if let Some(part) = self.parts.get(v.member()) {
+                    if part == self.part {
+                        *thing = v.member().clone().into();
+                    } else {
+                        let _ = self.required.insert(part.clone());
+                        *thing = QualifiedIdentifier::new(part.clone(), v.member().clone()).into();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl MutModuleVisitor for ReferencedModules {
+    fn identifier_reference(&mut self, thing: &mut IdentifierReference) -> Result<(), Error> {
+        if let IdentifierReference::QualifiedIdentifier(v) = thing {
+            let _ = self.modules.insert(v.module().clone());
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn same_annotation(lhs: &Annotation, rhs: &Annotation) -> bool {
+    match (lhs, rhs) {
+        (Annotation::Property(lhs), Annotation::Property(rhs)) => lhs == rhs,
+        (Annotation::Constraint(lhs), Annotation::Constraint(rhs)) => lhs.name() == rhs.name(),
+        _ => false,
+    }
+}
+
+fn single_definition_module(definition: &Definition) -> Result<Module, Error> {
+    ModuleBuilder::new("merged")
+        .with_definition(definition.clone())
+        .build()
+}
+
+fn shape_differences(kept: &Definition, other: &Definition) -> Result<Vec<Change>, Error> {
+    Ok(ModuleDiff::compare(
+        &single_definition_module(kept)?,
+        &single_definition_module(other)?,
+    )
+    .changes()
+    .filter(|change| {
+        !matches!(
+            change,
+            Change::AnnotationAdded { .. }
+                | Change::AnnotationRemoved { .. }
+                | Change::AnnotationChanged { .. }
+                | Change::FacetChanged { .. }
+        )
+    })
+    .cloned()
+    .collect())
+}
+
+fn definition_annotations(definition: &Definition) -> Vec<&Annotation> {
+    match definition {
+        Definition::Datatype(v) => v.body().map(|b| b.annotations().collect()),
+        Definition::Entity(v) => v.body().map(|b| b.annotations().collect()),
+        Definition::Enum(v) => v.body().map(|b| b.annotations().collect()),
+        Definition::Event(v) => v.body().map(|b| b.annotations().collect()),
+        Definition::Property(v) => v.member_def().body().map(|b| b.annotations().collect()),
+        Definition::Rdf(v) => Some(v.body().annotations().collect()),
+        Definition::Structure(v) => v.body().map(|b| b.annotations().collect()),
+        Definition::TypeClass(v) => v.body().map(|b| b.annotations().collect()),
+        Definition::Union(v) => v.body().map(|b| b.annotations().collect()),
+    }
+    .unwrap_or_default()
+}
+
+// Add the annotations of `other` missing from `kept`; if `kept` has no body to hold annotations,
+// and `other` has annotations, `other` is kept instead as the two have the same shape.
+fn combine_annotations(kept: &mut Definition, other: &Definition) {
+    let missing: Vec<Annotation> = definition_annotations(other)
+        .into_iter()
+        .filter(|annotation| {
+            !definition_annotations(kept)
+                .into_iter()
+                .any(|existing| same_annotation(existing, annotation))
+        })
+        .cloned()
+        .collect();
+    if missing.is_empty() {
+        return;
+    }
+    let added = match kept {
+        Definition::Datatype(v) => v.body_mut().map(|b| b.extend_annotations(missing)),
+        Definition::Entity(v) => v.body_mut().map(|b| b.extend_annotations(missing)),
+        Definition::Enum(v) => v.body_mut().map(|b| b.extend_annotations(missing)),
+        Definition::Event(v) => v.body_mut().map(|b| b.extend_annotations(missing)),
+        Definition::Property(v) => {
+            let mut member = v.member_def().clone();
+            let added = member.body_mut().map(|b| b.extend_annotations(missing));
+            v.set_member_def(member);
+            added
+        }
+        Definition::Rdf(v) => {
+            v.body_mut().extend_annotations(missing);
+            Some(())
+        }
+        Definition::Structure(v) => v.body_mut().map(|b| b.extend_annotations(missing)),
+        Definition::TypeClass(v) => v.body_mut().map(|b| b.extend_annotations(missing)),
+        Definition::Union(v) => v.body_mut().map(|b| b.extend_annotations(missing)),
+    };
+    if added.is_none() {
+        *kept = other.clone();
+    }
+}
+
+fn tag_suffix(value: &Value) -> String {
+    let text = match value {
+        Value::Simple(SimpleValue::String(v)) => v.value().to_string(),
+        _ => value.to_string(),
+    };
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
@@ -0,0 +1,912 @@
+/*!
+Provides a semantic comparison of two versions of a module.
+
+The comparison is structural, two modules that differ only in layout, comments, or the order of
+their definitions have no differences. Names, values, types, and cardinalities are compared with
+their `PartialEq` implementations which, unlike the `eq_with_span` methods, ignore source spans.
+
+# Example
+
+```rust
+use sdml_core::diff::{Change, ModuleDiff};
+use sdml_core::model::definitions::{DatatypeDef, Definition};
+use sdml_core::model::identifiers::{Identifier, QualifiedIdentifier};
+use sdml_core::model::modules::{Module, ModuleBody};
+
+let old = Module::empty(Identifier::new_unchecked("example"));
+
+let mut body = ModuleBody::default();
+body.add_to_definitions(Definition::Datatype(DatatypeDef::new(
+    Identifier::new_unchecked("Name"),
+    QualifiedIdentifier::new(
+        Identifier::new_unchecked("xsd"),
+        Identifier::new_unchecked("string"),
+    )
+    .into(),
+)))
+.unwrap();
+let new = Module::new(Identifier::new_unchecked("example"), body);
+
+let diff = ModuleDiff::compare(&old, &new);
+
+assert_eq!(
+    diff.changes().collect::<Vec<_>>(),
+    [&Change::DefinitionAdded {
+        definition: Identifier::new_unchecked("Name"),
+        kind: "datatype".to_string(),
+    }]
+);
+```
+
+Formal constraints are compared by name only; the text of informal constraints is compared.
+Type class definitions are not compared, other than their annotations, and each pair with the same
+name is reported as [`Change::DefinitionNotCompared`].
+*/
+
+use crate::model::annotations::{Annotation, HasAnnotations};
+use crate::model::constraints::ConstraintBody;
+use crate::model::definitions::{Definition, DefinitionKind, HasMembers, HasVariants};
+use crate::model::identifiers::Identifier;
+use crate::model::members::{Cardinality, Member, MemberKind};
+use crate::model::modules::Module;
+use crate::model::values::{SimpleValue, Value};
+use crate::model::{HasBody, HasName, HasNameReference, HasOptionalBody, HasSourceSpan};
+use crate::stdlib::xsd;
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The differences between an old and new version of a module.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ModuleDiff {
+    old_module: Identifier,
+    new_module: Identifier,
+    changes: Vec<Change>,
+}
+
+///
+/// A field of the module header.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum HeaderField {
+    Name,
+    BaseUri,
+    VersionInfo,
+    VersionUri,
+}
+
+///
+/// A single semantic difference between two versions of a module. Annotations are located by
+/// `owner`, which is `None` for the module itself, or the path of a definition, `Definition`, or
+/// of a member or variant, `Definition.member`.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "change", rename_all = "snake_case"))]
+pub enum Change {
+    HeaderChanged {
+        field: HeaderField,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    ImportAdded {
+        import: String,
+    },
+    ImportRemoved {
+        import: String,
+    },
+    AnnotationAdded {
+        owner: Option<String>,
+        name: String,
+        value: String,
+    },
+    AnnotationRemoved {
+        owner: Option<String>,
+        name: String,
+        value: String,
+    },
+    AnnotationChanged {
+        owner: Option<String>,
+        name: String,
+        old: String,
+        new: String,
+    },
+    FacetChanged {
+        owner: Option<String>,
+        name: String,
+        old: FacetValue,
+        new: FacetValue,
+    },
+    DefinitionAdded {
+        definition: Identifier,
+        kind: String,
+    },
+    DefinitionRemoved {
+        definition: Identifier,
+        kind: String,
+    },
+    DefinitionKindChanged {
+        definition: Identifier,
+        old: String,
+        new: String,
+    },
+    DefinitionNotCompared {
+        definition: Identifier,
+        kind: String,
+    },
+    BaseTypeChanged {
+        definition: Identifier,
+        old: String,
+        new: String,
+    },
+    MemberAdded {
+        definition: Identifier,
+        member: Identifier,
+        target_type: String,
+        cardinality: Option<Cardinality>,
+    },
+    MemberRemoved {
+        definition: Identifier,
+        member: Identifier,
+    },
+    MemberTypeChanged {
+        definition: Identifier,
+        member: Identifier,
+        old: String,
+        new: String,
+    },
+    MemberCardinalityChanged {
+        definition: Identifier,
+        member: Identifier,
+        old: Cardinality,
+        new: Cardinality,
+    },
+    VariantAdded {
+        definition: Identifier,
+        variant: Identifier,
+    },
+    VariantRemoved {
+        definition: Identifier,
+        variant: Identifier,
+    },
+}
+
+///
+/// The value of a single-valued constraining facet, such as `xsd:minLength`; numeric values are
+/// kept as numbers so that changes to them can be compared.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FacetValue {
+    Number(Decimal),
+    Other(String),
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// Annotation values, rendered as text, grouped by annotation name.
+type AnnotationValues = BTreeMap<String, BTreeSet<String>>;
+
+#[derive(Debug, Default)]
+struct Differ {
+    changes: Vec<Change>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl ModuleDiff {
+    ///
+    /// Compare the module `old` with the module `new`.
+    ///
+    pub fn compare(old: &Module, new: &Module) -> Self {
+        let mut differ = Differ::default();
+        differ.compare_modules(old, new);
+        Self {
+            old_module: old.name().without_span(),
+            new_module: new.name().without_span(),
+            changes: differ.changes,
+        }
+    }
+
+    pub fn old_module(&self) -> &Identifier {
+        &self.old_module
+    }
+
+    pub fn new_module(&self) -> &Identifier {
+        &self.new_module
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub fn changes(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for HeaderField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Name => "name",
+                Self::BaseUri => "base URI",
+                Self::VersionInfo => "version info",
+                Self::VersionUri => "version URI",
+            }
+        )
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for FacetValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(v) => write!(f, "{v}"),
+            Self::Other(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+impl From<&Value> for FacetValue {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Simple(SimpleValue::Decimal(v)) => Self::Number(*v),
+            Value::Simple(SimpleValue::Integer(v)) => Self::Number(Decimal::from(*v)),
+            Value::Simple(SimpleValue::Unsigned(v)) => Self::Number(Decimal::from(*v)),
+            Value::Simple(SimpleValue::Double(v)) => Decimal::try_from(v.into_inner())
+                .map(Self::Number)
+                .unwrap_or_else(|_| Self::Other(value.to_string())),
+            _ => Self::Other(value.to_string()),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.symbol(), self.description())
+    }
+}
+
+impl Change {
+    ///
+    /// A single character denoting additions, `+`, removals, `-`, and other changes, `~`.
+    ///
+    pub fn symbol(&self) -> char {
+        if self.is_addition() {
+            '+'
+        } else if self.is_removal() {
+            '-'
+        } else {
+            '~'
+        }
+    }
+
+    ///
+    /// A description of this change, without the leading symbol.
+    ///
+    pub fn description(&self) -> String {
+        fn owner_prefix(owner: &Option<String>) -> String {
+            owner
+                .as_ref()
+                .map(|owner| format!("{owner} "))
+                .unwrap_or_else(|| "module ".to_string())
+        }
+        fn optional(value: &Option<String>) -> &str {
+            value.as_deref().unwrap_or("(none)")
+        }
+        match self {
+            Self::HeaderChanged { field, old, new } => format!(
+                "module {field} changed from {} to {}",
+                optional(old),
+                optional(new)
+            ),
+            Self::ImportAdded { import } => format!("import {import}"),
+            Self::ImportRemoved { import } => format!("import {import}"),
+            Self::AnnotationAdded { owner, name, value } => {
+                format!("{}@{name} = {value}", owner_prefix(owner))
+            }
+            Self::AnnotationRemoved { owner, name, value } => {
+                format!("{}@{name} = {value}", owner_prefix(owner))
+            }
+            Self::AnnotationChanged {
+                owner,
+                name,
+                old,
+                new,
+            } => format!("{}@{name} changed from {old} to {new}", owner_prefix(owner)),
+            Self::FacetChanged {
+                owner,
+                name,
+                old,
+                new,
+            } => format!("{}@{name} changed from {old} to {new}", owner_prefix(owner)),
+            Self::DefinitionAdded { definition, kind } => format!("{kind} {definition}"),
+            Self::DefinitionRemoved { definition, kind } => format!("{kind} {definition}"),
+            Self::DefinitionKindChanged {
+                definition,
+                old,
+                new,
+            } => format!("{definition} changed from {old} to {new}"),
+            Self::DefinitionNotCompared { definition, kind } => {
+                format!("{kind} {definition} not compared, {kind} definitions are not supported")
+            }
+            Self::BaseTypeChanged {
+                definition,
+                old,
+                new,
+            } => format!("{definition} base type changed from {old} to {new}"),
+            Self::MemberAdded {
+                definition,
+                member,
+                target_type,
+                cardinality: Some(cardinality),
+            } => format!("member {definition}.{member} -> {cardinality} {target_type}"),
+            Self::MemberAdded {
+                definition,
+                member,
+                target_type,
+                cardinality: None,
+            } => format!("member {definition}.{member} -> {target_type}"),
+            Self::MemberRemoved { definition, member } => {
+                format!("member {definition}.{member}")
+            }
+            Self::MemberTypeChanged {
+                definition,
+                member,
+                old,
+                new,
+            } => format!("member {definition}.{member} type changed from {old} to {new}"),
+            Self::MemberCardinalityChanged {
+                definition,
+                member,
+                old,
+                new,
+            } => format!("member {definition}.{member} cardinality changed from {old} to {new}"),
+            Self::VariantAdded {
+                definition,
+                variant,
+            } => format!("variant {definition}.{variant}"),
+            Self::VariantRemoved {
+                definition,
+                variant,
+            } => format!("variant {definition}.{variant}"),
+        }
+    }
+
+    ///
+    /// The name of the definition this change is within, if any.
+    ///
+    pub fn definition(&self) -> Option<&Identifier> {
+        match self {
+            Self::DefinitionAdded { definition, .. }
+            | Self::DefinitionRemoved { definition, .. }
+            | Self::DefinitionKindChanged { definition, .. }
+            | Self::DefinitionNotCompared { definition, .. }
+            | Self::BaseTypeChanged { definition, .. }
+            | Self::MemberAdded { definition, .. }
+            | Self::MemberRemoved { definition, .. }
+            | Self::MemberTypeChanged { definition, .. }
+            | Self::MemberCardinalityChanged { definition, .. }
+            | Self::VariantAdded { definition, .. }
+            | Self::VariantRemoved { definition, .. } => Some(definition),
+            _ => None,
+        }
+    }
+
+    pub fn is_addition(&self) -> bool {
+        matches!(
+            self,
+            Self::ImportAdded { .. }
+                | Self::AnnotationAdded { .. }
+                | Self::DefinitionAdded { .. }
+                | Self::MemberAdded { .. }
+                | Self::VariantAdded { .. }
+        )
+    }
+
+    pub fn is_removal(&self) -> bool {
+        matches!(
+            self,
+            Self::ImportRemoved { .. }
+                | Self::AnnotationRemoved { .. }
+                | Self::DefinitionRemoved { .. }
+                | Self::MemberRemoved { .. }
+                | Self::VariantRemoved { .. }
+        )
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Differ {
+    fn compare_modules(&mut self, old: &Module, new: &Module) {
+        self.compare_header(HeaderField::Name, Some(old.name()), Some(new.name()));
+        self.compare_header(
+            HeaderField::BaseUri,
+            old.base_uri().map(|v| v.value()),
+            new.base_uri().map(|v| v.value()),
+        );
+        self.compare_header(
+            HeaderField::VersionInfo,
+            old.version_info().map(|v| v.value()),
+            new.version_info().map(|v| v.value()),
+        );
+        self.compare_header(
+            HeaderField::VersionUri,
+            old.version_uri().map(|v| v.value()),
+            new.version_uri().map(|v| v.value()),
+        );
+
+        let old_imports: BTreeSet<String> = old
+            .body()
+            .imports()
+            .flat_map(|statement| statement.imports())
+            .map(|import| import.to_string())
+            .collect();
+        let new_imports: BTreeSet<String> = new
+            .body()
+            .imports()
+            .flat_map(|statement| statement.imports())
+            .map(|import| import.to_string())
+            .collect();
+        for import in old_imports.difference(&new_imports) {
+            self.changes.push(Change::ImportRemoved {
+                import: import.clone(),
+            });
+        }
+        for import in new_imports.difference(&old_imports) {
+            self.changes.push(Change::ImportAdded {
+                import: import.clone(),
+            });
+        }
+
+        self.compare_annotations(None, Some(old.body()), Some(new.body()));
+
+        let old_definitions: BTreeMap<&Identifier, &Definition> = old
+            .body()
+            .definitions()
+            .map(|def| (def.name(), def))
+            .collect();
+        let new_definitions: BTreeMap<&Identifier, &Definition> = new
+            .body()
+            .definitions()
+            .map(|def| (def.name(), def))
+            .collect();
+        for (name, old_def) in &old_definitions {
+            match new_definitions.get(name) {
+                None => self.changes.push(Change::DefinitionRemoved {
+                    definition: name.without_span(),
+                    kind: old_def.kind().to_string(),
+                }),
+                Some(new_def) => self.compare_definitions(old_def, new_def),
+            }
+        }
+        for (name, new_def) in &new_definitions {
+            if !old_definitions.contains_key(name) {
+                self.changes.push(Change::DefinitionAdded {
+                    definition: name.without_span(),
+                    kind: new_def.kind().to_string(),
+                });
+            }
+        }
+    }
+
+    fn compare_header<T: Display + PartialEq>(
+        &mut self,
+        field: HeaderField,
+        old: Option<&T>,
+        new: Option<&T>,
+    ) {
+        if old != new {
+            self.changes.push(Change::HeaderChanged {
+                field,
+                old: old.map(|v| v.to_string()),
+                new: new.map(|v| v.to_string()),
+            });
+        }
+    }
+
+    fn compare_annotations<A: HasAnnotations>(
+        &mut self,
+        owner: Option<String>,
+        old: Option<&A>,
+        new: Option<&A>,
+    ) {
+        let old_facets = old.map(facet_values).unwrap_or_default();
+        let new_facets = new.map(facet_values).unwrap_or_default();
+        let old = old.map(annotation_values).unwrap_or_default();
+        let new = new.map(annotation_values).unwrap_or_default();
+        for (name, old_values) in &old {
+            match new.get(name) {
+                None => {
+                    for value in old_values {
+                        self.changes.push(Change::AnnotationRemoved {
+                            owner: owner.clone(),
+                            name: name.clone(),
+                            value: value.clone(),
+                        });
+                    }
+                }
+                Some(new_values) if new_values != old_values => {
+                    if let (Some(old_facet), Some(new_facet)) =
+                        (old_facets.get(name), new_facets.get(name))
+                    {
+                        self.changes.push(Change::FacetChanged {
+                            owner: owner.clone(),
+                            name: name.clone(),
+                            old: old_facet.clone(),
+                            new: new_facet.clone(),
+                        });
+                        continue;
+                    }
+                    self.changes.push(Change::AnnotationChanged {
+                        owner: owner.clone(),
+                        name: name.clone(),
+                        old: join_values(old_values),
+                        new: join_values(new_values),
+                    });
+                }
+                _ => {}
+            }
+        }
+        for (name, new_values) in &new {
+            if !old.contains_key(name) {
+                for value in new_values {
+                    self.changes.push(Change::AnnotationAdded {
+                        owner: owner.clone(),
+                        name: name.clone(),
+                        value: value.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    fn compare_definitions(&mut self, old: &Definition, new: &Definition) {
+        let name = old.name();
+        let owner = Some(name.to_string());
+        match (old, new) {
+            (Definition::Datatype(old), Definition::Datatype(new)) => {
+                if old.base_type() != new.base_type() || old.is_opaque() != new.is_opaque() {
+                    self.changes.push(Change::BaseTypeChanged {
+                        definition: name.without_span(),
+                        old: datatype_base(old.is_opaque(), old.base_type()),
+                        new: datatype_base(new.is_opaque(), new.base_type()),
+                    });
+                }
+                self.compare_annotations(owner, old.body(), new.body());
+            }
+            (Definition::Entity(old), Definition::Entity(new)) => {
+                self.compare_annotations(owner, old.body(), new.body());
+                let old_members: Vec<&Member> = old
+                    .body()
+                    .map(|b| std::iter::once(b.identity()).chain(b.members()).collect())
+                    .unwrap_or_default();
+                let new_members: Vec<&Member> = new
+                    .body()
+                    .map(|b| std::iter::once(b.identity()).chain(b.members()).collect())
+                    .unwrap_or_default();
+                self.compare_members(name, old_members, new_members);
+            }
+            (Definition::Enum(old), Definition::Enum(new)) => {
+                self.compare_annotations(owner, old.body(), new.body());
+                let old_variants: BTreeMap<&Identifier, _> = old
+                    .body()
+                    .map(|b| b.variants().map(|v| (v.name(), v)).collect())
+                    .unwrap_or_default();
+                let new_variants: BTreeMap<&Identifier, _> = new
+                    .body()
+                    .map(|b| b.variants().map(|v| (v.name(), v)).collect())
+                    .unwrap_or_default();
+                self.compare_variant_names(name, &old_variants, &new_variants);
+                for (variant, old_variant) in &old_variants {
+                    if let Some(new_variant) = new_variants.get(variant) {
+                        self.compare_annotations(
+                            Some(format!("{name}.{variant}")),
+                            old_variant.body(),
+                            new_variant.body(),
+                        );
+                    }
+                }
+            }
+            (Definition::Event(old), Definition::Event(new)) => {
+                if old.event_source() != new.event_source() {
+                    self.changes.push(Change::BaseTypeChanged {
+                        definition: name.without_span(),
+                        old: old.event_source().to_string(),
+                        new: new.event_source().to_string(),
+                    });
+                }
+                self.compare_annotations(owner, old.body(), new.body());
+                let old_members: Vec<&Member> = old
+                    .body()
+                    .map(|b| b.members().collect())
+                    .unwrap_or_default();
+                let new_members: Vec<&Member> = new
+                    .body()
+                    .map(|b| b.members().collect())
+                    .unwrap_or_default();
+                self.compare_members(name, old_members, new_members);
+            }
+            (Definition::Property(old), Definition::Property(new)) => {
+                let old = old.member_def();
+                let new = new.member_def();
+                self.compare_member_types(
+                    name,
+                    old.name(),
+                    (
+                        old.target_type().to_string(),
+                        Some(old.target_cardinality()),
+                    ),
+                    (
+                        new.target_type().to_string(),
+                        Some(new.target_cardinality()),
+                    ),
+                );
+                self.compare_annotations(owner, old.body(), new.body());
+            }
+            (Definition::Rdf(old), Definition::Rdf(new)) => {
+                self.compare_annotations(owner, Some(old.body()), Some(new.body()));
+            }
+            (Definition::Structure(old), Definition::Structure(new)) => {
+                self.compare_annotations(owner, old.body(), new.body());
+                let old_members: Vec<&Member> = old
+                    .body()
+                    .map(|b| b.members().collect())
+                    .unwrap_or_default();
+                let new_members: Vec<&Member> = new
+                    .body()
+                    .map(|b| b.members().collect())
+                    .unwrap_or_default();
+                self.compare_members(name, old_members, new_members);
+            }
+            (Definition::TypeClass(old), Definition::TypeClass(new)) => {
+                self.compare_annotations(owner, old.body(), new.body());
+                self.changes.push(Change::DefinitionNotCompared {
+                    definition: name.without_span(),
+                    kind: DefinitionKind::TypeClass.to_string(),
+                });
+            }
+            (Definition::Union(old), Definition::Union(new)) => {
+                self.compare_annotations(owner, old.body(), new.body());
+                let old_variants: BTreeMap<&Identifier, _> = old
+                    .body()
+                    .map(|b| b.variants().map(|v| (v.name(), v)).collect())
+                    .unwrap_or_default();
+                let new_variants: BTreeMap<&Identifier, _> = new
+                    .body()
+                    .map(|b| b.variants().map(|v| (v.name(), v)).collect())
+                    .unwrap_or_default();
+                self.compare_variant_names(name, &old_variants, &new_variants);
+                for (variant, old_variant) in &old_variants {
+                    if let Some(new_variant) = new_variants.get(variant) {
+                        if old_variant.name_reference() != new_variant.name_reference() {
+                            self.changes.push(Change::MemberTypeChanged {
+                                definition: name.without_span(),
+                                member: variant.without_span(),
+                                old: old_variant.name_reference().to_string(),
+                                new: new_variant.name_reference().to_string(),
+                            });
+                        }
+                        self.compare_annotations(
+                            Some(format!("{name}.{variant}")),
+                            old_variant.body(),
+                            new_variant.body(),
+                        );
+                    }
+                }
+            }
+            _ => self.changes.push(Change::DefinitionKindChanged {
+                definition: name.without_span(),
+                old: old.kind().to_string(),
+                new: new.kind().to_string(),
+            }),
+        }
+    }
+
+    fn compare_variant_names<V>(
+        &mut self,
+        definition: &Identifier,
+        old: &BTreeMap<&Identifier, V>,
+        new: &BTreeMap<&Identifier, V>,
+    ) {
+        for variant in old.keys().filter(|v| !new.contains_key(*v)) {
+            self.changes.push(Change::VariantRemoved {
+                definition: definition.without_span(),
+                variant: variant.without_span(),
+            });
+        }
+        for variant in new.keys().filter(|v| !old.contains_key(*v)) {
+            self.changes.push(Change::VariantAdded {
+                definition: definition.without_span(),
+                variant: variant.without_span(),
+            });
+        }
+    }
+
+    fn compare_members(&mut self, definition: &Identifier, old: Vec<&Member>, new: Vec<&Member>) {
+        let old: BTreeMap<&Identifier, &Member> = old.into_iter().map(|m| (m.name(), m)).collect();
+        let new: BTreeMap<&Identifier, &Member> = new.into_iter().map(|m| (m.name(), m)).collect();
+        for (name, old_member) in &old {
+            match new.get(name) {
+                None => self.changes.push(Change::MemberRemoved {
+                    definition: definition.without_span(),
+                    member: name.without_span(),
+                }),
+                Some(new_member) => {
+                    self.compare_member_types(
+                        definition,
+                        name,
+                        member_type(old_member),
+                        member_type(new_member),
+                    );
+                    if let (MemberKind::Definition(old), MemberKind::Definition(new)) =
+                        (old_member.kind(), new_member.kind())
+                    {
+                        self.compare_annotations(
+                            Some(format!("{definition}.{name}")),
+                            old.body(),
+                            new.body(),
+                        );
+                    }
+                }
+            }
+        }
+        for (name, new_member) in &new {
+            if !old.contains_key(name) {
+                let (target_type, cardinality) = member_type(new_member);
+                self.changes.push(Change::MemberAdded {
+                    definition: definition.without_span(),
+                    member: name.without_span(),
+                    target_type,
+                    cardinality: cardinality.map(cardinality_without_span),
+                });
+            }
+        }
+    }
+
+    fn compare_member_types(
+        &mut self,
+        definition: &Identifier,
+        member: &Identifier,
+        (old_type, old_cardinality): (String, Option<&Cardinality>),
+        (new_type, new_cardinality): (String, Option<&Cardinality>),
+    ) {
+        if old_type != new_type {
+            self.changes.push(Change::MemberTypeChanged {
+                definition: definition.without_span(),
+                member: member.without_span(),
+                old: old_type,
+                new: new_type,
+            });
+        }
+        if let (Some(old), Some(new)) = (old_cardinality, new_cardinality) {
+            if old != new {
+                self.changes.push(Change::MemberCardinalityChanged {
+                    definition: definition.without_span(),
+                    member: member.without_span(),
+                    old: cardinality_without_span(old),
+                    new: cardinality_without_span(new),
+                });
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn datatype_base(opaque: bool, base_type: &impl Display) -> String {
+    if opaque {
+        format!("opaque {base_type}")
+    } else {
+        base_type.to_string()
+    }
+}
+
+// The target type of a member, property references are shown as `ref property`, and the
+// cardinality if the member is a definition.
+fn member_type(member: &Member) -> (String, Option<&Cardinality>) {
+    match member.kind() {
+        MemberKind::Reference(property) => (format!("ref {property}"), None),
+        MemberKind::Definition(def) => (
+            def.target_type().to_string(),
+            Some(def.target_cardinality()),
+        ),
+    }
+}
+
+fn annotation_values<A: HasAnnotations>(thing: &A) -> AnnotationValues {
+    let mut values = AnnotationValues::default();
+    for annotation in thing.annotations() {
+        let (name, value) = match annotation {
+            Annotation::Property(property) => (
+                property.name_reference().to_string(),
+                property.value().to_string(),
+            ),
+            Annotation::Constraint(constraint) => (
+                format!("assert {}", constraint.name()),
+                match constraint.body() {
+                    ConstraintBody::Informal(v) => format!("{:?}", v.value()),
+                    ConstraintBody::Formal(_) => "(formal)".to_string(),
+                },
+            ),
+        };
+        let _ = values.entry(name).or_default().insert(value);
+    }
+    values
+}
+
+// The value of each constraining facet with exactly one value, by annotation name.
+fn facet_values<A: HasAnnotations>(thing: &A) -> BTreeMap<String, FacetValue> {
+    let mut values: BTreeMap<String, Vec<FacetValue>> = Default::default();
+    for property in thing.annotation_properties() {
+        let name = property.name_reference().to_string();
+        if is_facet(&name) {
+            values
+                .entry(name)
+                .or_default()
+                .push(FacetValue::from(property.value()));
+        }
+    }
+    values
+        .into_iter()
+        .filter(|(_, values)| values.len() == 1)
+        .map(|(name, mut values)| (name, values.remove(0)))
+        .collect()
+}
+
+fn is_facet(name: &str) -> bool {
+    name.split_once(':').is_some_and(|(module, member)| {
+        module == xsd::MODULE_NAME && xsd::is_constraining_facet_str(member)
+    })
+}
+
+fn join_values(values: &BTreeSet<String>) -> String {
+    values.iter().cloned().collect::<Vec<_>>().join(", ")
+}
+
+fn cardinality_without_span(cardinality: &Cardinality) -> Cardinality {
+    let mut cardinality = cardinality.clone();
+    cardinality.unset_source_span();
+    let mut range = cardinality.range().clone();
+    range.unset_source_span();
+    cardinality.set_range(range);
+    cardinality
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
+
+pub mod compatibility;
@@ -0,0 +1,299 @@
+/*!
+Provides a classification of the changes in a [`ModuleDiff`] by their effect on the consumers of
+a module, and a check that the module header version reflects that effect.
+
+A change is *breaking* if data, or models, valid against the old version of the module may be
+invalid against the new version; for example removing a definition, member, or variant, adding a
+required member, or narrowing a member's cardinality or a datatype's constraining facets. A change
+is *potentially breaking* if it may affect consumers depending on how they use the module; for
+example adding a variant, widening a cardinality, or adding a constraint. All other changes, such
+as adding a definition or an optional member, are *non-breaking*.
+
+Any change to a module requires a new `version_info`; a breaking change also requires a new
+`version_uri` so that modules importing the old version by URI are reported with the existing
+`module_version_mismatch` diagnostic rather than silently using the new one. A new version must be
+greater than the old one where both can be read as dotted numbers, `1.2.0`, compared with the
+final path segment of a version URI, `https://example.org/rentals/1.2/`.
+
+# Example
+
+```rust
+use sdml_core::diff::compatibility::Compatibility;
+use sdml_core::diff::{HeaderField, ModuleDiff};
+use sdml_core::model::definitions::{DatatypeDef, Definition};
+use sdml_core::model::identifiers::{Identifier, QualifiedIdentifier};
+use sdml_core::model::modules::{Module, ModuleBody};
+
+let mut body = ModuleBody::default();
+body.add_to_definitions(Definition::Datatype(DatatypeDef::new(
+    Identifier::new_unchecked("Name"),
+    QualifiedIdentifier::new(
+        Identifier::new_unchecked("xsd"),
+        Identifier::new_unchecked("string"),
+    )
+    .into(),
+)))
+.unwrap();
+let old = Module::new(Identifier::new_unchecked("example"), body);
+let new = Module::empty(Identifier::new_unchecked("example"));
+
+let diff = ModuleDiff::compare(&old, &new);
+
+assert_eq!(diff.compatibility(), Compatibility::Breaking);
+assert_eq!(
+    diff.missing_version_changes(),
+    [HeaderField::VersionInfo, HeaderField::VersionUri]
+);
+```
+
+[`ModuleDiff`]: crate::diff::ModuleDiff
+*/
+
+use crate::diff::{is_facet, Change, FacetValue, HeaderField, ModuleDiff};
+use crate::model::members::Cardinality;
+use crate::stdlib::xsd;
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The effect of a change on the consumers of a module, ordered from least to most severe.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Compatibility {
+    #[default]
+    NonBreaking,
+    PotentiallyBreaking,
+    Breaking,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Compatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::NonBreaking => "non-breaking",
+                Self::PotentiallyBreaking => "potentially breaking",
+                Self::Breaking => "breaking",
+            }
+        )
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl ModuleDiff {
+    ///
+    /// The most severe classification of all the changes, or `NonBreaking` if there are none.
+    ///
+    pub fn compatibility(&self) -> Compatibility {
+        self.changes()
+            .map(Change::compatibility)
+            .max()
+            .unwrap_or_default()
+    }
+
+    ///
+    /// The changes with the classification `compatibility`.
+    ///
+    pub fn changes_with(&self, compatibility: Compatibility) -> impl Iterator<Item = &Change> {
+        self.changes()
+            .filter(move |change| change.compatibility() == compatibility)
+    }
+
+    ///
+    /// The header fields that should have increased, given the changes to the module, but have
+    /// not. The version info must increase if there are any other changes, and the version URI
+    /// must also increase if any change is breaking. A version field that changed to a lower
+    /// version is always included.
+    ///
+    pub fn missing_version_changes(&self) -> Vec<HeaderField> {
+        let header_change = |field: HeaderField| {
+            self.changes().find_map(|change| match change {
+                Change::HeaderChanged {
+                    field: changed,
+                    old,
+                    new,
+                } if *changed == field => Some(is_version_increase(old, new)),
+                _ => None,
+            })
+        };
+        let has_other_changes = self.changes().any(|change| {
+            !matches!(
+                change,
+                Change::HeaderChanged {
+                    field: HeaderField::VersionInfo | HeaderField::VersionUri,
+                    ..
+                }
+            )
+        });
+
+        let mut missing = Vec::default();
+        for (field, required) in [
+            (HeaderField::VersionInfo, has_other_changes),
+            (
+                HeaderField::VersionUri,
+                self.compatibility() == Compatibility::Breaking,
+            ),
+        ] {
+            match header_change(field) {
+                Some(false) => missing.push(field),
+                None if required => missing.push(field),
+                _ => {}
+            }
+        }
+        missing
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Change {
+    ///
+    /// Classify this change by its effect on the consumers of the module.
+    ///
+    pub fn compatibility(&self) -> Compatibility {
+        match self {
+            Self::HeaderChanged { field, .. } => match field {
+                HeaderField::Name | HeaderField::BaseUri => Compatibility::Breaking,
+                HeaderField::VersionInfo | HeaderField::VersionUri => Compatibility::NonBreaking,
+            },
+            Self::ImportAdded { .. } | Self::ImportRemoved { .. } => Compatibility::NonBreaking,
+            Self::AnnotationAdded { name, .. } => {
+                if is_facet(name) {
+                    Compatibility::Breaking
+                } else if is_constraint(name) {
+                    Compatibility::PotentiallyBreaking
+                } else {
+                    Compatibility::NonBreaking
+                }
+            }
+            Self::AnnotationRemoved { .. } => Compatibility::NonBreaking,
+            Self::AnnotationChanged { name, .. } => {
+                // a facet with more than one value, these are not compared.
+                if is_facet(name) || is_constraint(name) {
+                    Compatibility::PotentiallyBreaking
+                } else {
+                    Compatibility::NonBreaking
+                }
+            }
+            Self::FacetChanged { name, old, new, .. } => facet_change(name, old, new),
+            Self::DefinitionAdded { .. } => Compatibility::NonBreaking,
+            // the definitions may differ in any way.
+            Self::DefinitionNotCompared { .. } => Compatibility::PotentiallyBreaking,
+            Self::DefinitionRemoved { .. }
+            | Self::DefinitionKindChanged { .. }
+            | Self::BaseTypeChanged { .. } => Compatibility::Breaking,
+            Self::MemberAdded { cardinality, .. } => match cardinality {
+                Some(cardinality) if cardinality.is_optional() => Compatibility::NonBreaking,
+                Some(_) => Compatibility::Breaking,
+                // the cardinality of a property reference is defined by the property.
+                None => Compatibility::PotentiallyBreaking,
+            },
+            Self::MemberRemoved { .. } | Self::MemberTypeChanged { .. } => Compatibility::Breaking,
+            Self::MemberCardinalityChanged { old, new, .. } => cardinality_change(old, new),
+            Self::VariantAdded { .. } => Compatibility::PotentiallyBreaking,
+            Self::VariantRemoved { .. } => Compatibility::Breaking,
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_breaking(&self) -> bool {
+        self.compatibility() == Compatibility::Breaking
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+// A change is an increase if the new version is greater, where both versions can be compared, or
+// if it added a version.
+fn is_version_increase(old: &Option<String>, new: &Option<String>) -> bool {
+    match (old, new) {
+        (_, None) => false,
+        (None, Some(_)) => true,
+        (Some(old), Some(new)) => match (version_numbers(old), version_numbers(new)) {
+            (Some(mut old), Some(mut new)) => {
+                let len = old.len().max(new.len());
+                old.resize(len, 0);
+                new.resize(len, 0);
+                new > old
+            }
+            _ => old != new,
+        },
+    }
+}
+
+// The dotted numbers of a version string, or of the final path segment of a version URI.
+fn version_numbers(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim_end_matches(['/', '#']);
+    let version = version.rsplit('/').next().unwrap_or(version);
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    version
+        .split('.')
+        .map(|number| number.parse::<u64>().ok())
+        .collect()
+}
+
+fn is_constraint(name: &str) -> bool {
+    name.starts_with("assert ")
+}
+
+fn facet_change(name: &str, old: &FacetValue, new: &FacetValue) -> Compatibility {
+    let facet = name.split_once(':').map(|(_, facet)| facet).unwrap_or(name);
+    if facet == xsd::LENGTH {
+        return Compatibility::Breaking;
+    }
+    let (FacetValue::Number(old), FacetValue::Number(new)) = (old, new) else {
+        return Compatibility::PotentiallyBreaking;
+    };
+    match facet {
+        xsd::MIN_EXCLUSIVE | xsd::MIN_INCLUSIVE | xsd::MIN_LENGTH => {
+            if new > old {
+                Compatibility::Breaking
+            } else {
+                Compatibility::NonBreaking
+            }
+        }
+        xsd::MAX_EXCLUSIVE
+        | xsd::MAX_INCLUSIVE
+        | xsd::MAX_LENGTH
+        | xsd::TOTAL_DIGITS
+        | xsd::FRACTION_DIGITS => {
+            if new < old {
+                Compatibility::Breaking
+            } else {
+                Compatibility::NonBreaking
+            }
+        }
+        _ => Compatibility::PotentiallyBreaking,
+    }
+}
+
+fn cardinality_change(old: &Cardinality, new: &Cardinality) -> Compatibility {
+    let narrowed_min = new.min_occurs() > old.min_occurs();
+    let narrowed_max = match (old.max_occurs(), new.max_occurs()) {
+        (Some(old), Some(new)) => new < old,
+        (None, Some(_)) => true,
+        (_, None) => false,
+    };
+    if narrowed_min || narrowed_max {
+        Compatibility::Breaking
+    } else {
+        // A wider range, or a change in ordering or uniqueness, may surprise consumers.
+        Compatibility::PotentiallyBreaking
+    }
+}
@@ -52,10 +52,20 @@ pub use sdml_errors::errors as error;
 
 pub mod store;
 
+pub mod builder;
+
+pub mod compose;
+
+pub mod diff;
+
 pub mod load;
 
 pub mod model;
 
+pub mod query;
+
+pub mod refactor;
+
 pub mod stdlib;
 
 pub mod syntax;
@@ -24,7 +24,10 @@ fn module_found(
 
 use crate::{model::identifiers::Identifier, store::ModuleStore};
 use sdml_errors::{
-    diagnostics::{reporter::ReportCounters, SeverityFilter},
+    diagnostics::{
+        reporter::ReportCounters, rules::LintRules, suggestions::SuggestedDiagnostic,
+        SeverityFilter,
+    },
     Diagnostic, FileId, Source,
 };
 use url::Url;
@@ -47,6 +50,20 @@ pub trait ModuleResolver: Default {
         name: &Identifier,
         from: Option<FileId>,
     ) -> Result<Url, sdml_errors::Error>;
+
+    ///
+    /// Return a URL given the module name `name` and the version URI `version_uri`, if any,
+    /// requested by an import. The default implementation ignores the version.
+    ///
+    fn name_and_version_to_resource(
+        &self,
+        name: &Identifier,
+        version_uri: Option<&Url>,
+        from: Option<FileId>,
+    ) -> Result<Url, sdml_errors::Error> {
+        let _ = version_uri;
+        self.name_to_resource(name, from)
+    }
 }
 
 ///
@@ -54,7 +71,7 @@ pub trait ModuleResolver: Default {
 /// memory. Note that the loader does not return the module instance itself but rather the module's
 /// name parsed from the resource, the module itself is inserted into the `cache`.
 ///
-pub trait ModuleLoader: Default {
+pub trait ModuleLoader {
     ///
     /// Resolve `name` into a resource identifier (URL) and parse into memory. The loader will check
     /// the `store` first to see if the module is already loaded, and will add the module into the
@@ -87,10 +104,27 @@ pub trait ModuleLoader: Default {
     fn get_source(&self, file_id: FileId) -> Option<Source>;
 
     fn report(&self, diagnostic: &Diagnostic) -> Result<(), sdml_errors::Error>;
+
+    ///
+    /// Report a diagnostic along with its suggested edits, by default the suggestions are
+    /// ignored.
+    ///
+    fn report_suggested(&self, diagnostic: &SuggestedDiagnostic) -> Result<(), sdml_errors::Error> {
+        self.report(diagnostic.diagnostic())
+    }
+
     fn reporter_done(
         &self,
         top_module_name: Option<String>,
     ) -> Result<ReportCounters, sdml_errors::Error>;
 
     fn set_severity_filter(&mut self, filter: SeverityFilter);
+
+    ///
+    /// Returns the project's lint rules applied to reported diagnostics, if any. Validation
+    /// uses these to determine the case styles expected of identifiers.
+    ///
+    fn lint_rules(&self) -> Option<&LintRules> {
+        None
+    }
 }
@@ -2,7 +2,7 @@
 Provide the Rust types that implement *annotation*-related components of the SDML Grammar.
 */
 use crate::load::ModuleLoader;
-use crate::model::values::{LanguageString, LanguageTag};
+use crate::model::values::{LanguageString, LanguageTag, SimpleValue};
 use crate::model::{
     check::Validate,
     constraints::Constraint,
@@ -11,11 +11,13 @@ use crate::model::{
     values::Value,
     HasNameReference, Span,
 };
-use crate::model::{HasName, References};
+use crate::model::{HasName, HasSourceSpan, References};
 use crate::stdlib;
 use crate::store::ModuleStore;
+use sdml_errors::diagnostics::functions::string_without_language;
+use sdml_errors::Error;
 use std::{collections::HashSet, fmt::Debug};
-use tracing::trace;
+use tracing::{error, trace};
 use url::Url;
 
 #[cfg(feature = "serde")]
@@ -94,6 +96,15 @@ pub trait HasAnnotations {
     }
 }
 
+///
+/// Annotated types that also allow annotations to be removed in place.
+///
+pub trait HasAnnotationsMut: HasAnnotations {
+    fn retain_annotations<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut Annotation) -> bool;
+}
+
 pub trait AnnotationBuilder {
     fn with_predicate<I, V>(self, predicate: I, value: V) -> Self
     where
@@ -409,13 +420,30 @@ impl Annotation {
 // Implementations ❱ Annotations ❱ Annotation Properties
 // ------------------------------------------------------------------------------------------------
 
+impl PartialEq for AnnotationProperty {
+    fn eq(&self, other: &Self) -> bool {
+        self.name_reference == other.name_reference && self.value == other.value
+    }
+}
+
+impl Eq for AnnotationProperty {}
+
 impl_has_source_span_for!(AnnotationProperty);
 
 impl_has_name_reference_for!(AnnotationProperty);
 
 impl Validate for AnnotationProperty {
-    fn validate(&self, _top: &Module, _cache: &impl ModuleStore, _: &impl ModuleLoader, _: bool) {
+    fn validate(
+        &self,
+        top: &Module,
+        _cache: &impl ModuleStore,
+        loader: &impl ModuleLoader,
+        _: bool,
+    ) {
         trace!("AnnotationProperty::is_valid -- missing type/value conformance");
+        if let Err(e) = self.validate_text_language(top, loader) {
+            error!("AnnotationProperty::validate could not report a diagnostic: {e}");
+        }
         // TODO: check value/type conformance
         // 1. Lookup property
         // 2. Get property range
@@ -446,6 +474,43 @@ impl AnnotationProperty {
     // AnnotationProperty :: Helpers
     // --------------------------------------------------------------------------------------------
 
+    ///
+    /// Returns `true` if this is a standard library property, such as `skos:prefLabel` or
+    /// `rdfs:comment`, whose value is natural language text and so should have a language tag.
+    ///
+    pub fn is_text_property(&self) -> bool {
+        if let IdentifierReference::QualifiedIdentifier(name) = self.name_reference() {
+            let member = name.member().as_ref();
+            match name.module().as_ref() {
+                stdlib::rdfs::MODULE_NAME => {
+                    [stdlib::rdfs::LABEL, stdlib::rdfs::COMMENT].contains(&member)
+                }
+                stdlib::skos::MODULE_NAME => [
+                    stdlib::skos::ALT_LABEL,
+                    stdlib::skos::CHANGE_NOTE,
+                    stdlib::skos::DEFINITION,
+                    stdlib::skos::EDITORIAL_NOTE,
+                    stdlib::skos::EXAMPLE,
+                    stdlib::skos::HIDDEN_LABEL,
+                    stdlib::skos::HISTORY_NOTE,
+                    stdlib::skos::NOTE,
+                    stdlib::skos::PREF_LABEL,
+                    stdlib::skos::SCOPE_NOTE,
+                ]
+                .contains(&member),
+                stdlib::dc::MODULE_NAME => {
+                    [stdlib::dc::TITLE, stdlib::dc::DESCRIPTION].contains(&member)
+                }
+                stdlib::dc::terms::MODULE_NAME => {
+                    [stdlib::dc::terms::TITLE, stdlib::dc::terms::DESCRIPTION].contains(&member)
+                }
+                _ => false,
+            }
+        } else {
+            false
+        }
+    }
+
     #[inline(always)]
     pub fn is_stdlib_property(&self) -> bool {
         if let IdentifierReference::QualifiedIdentifier(name) = self.name_reference() {
@@ -464,6 +529,26 @@ impl AnnotationProperty {
             false
         }
     }
+
+    // Text properties, such as `skos:prefLabel`, should have a language tag on string values.
+    fn validate_text_language(
+        &self,
+        top: &Module,
+        loader: &impl ModuleLoader,
+    ) -> Result<(), Error> {
+        if self.is_text_property() {
+            if let Value::Simple(SimpleValue::String(value)) = self.value() {
+                if !value.has_language() {
+                    loader.report(&string_without_language(
+                        top.file_id().copied().unwrap_or_default(),
+                        value.source_span().map(|span| span.byte_range()),
+                        value.value(),
+                    ))?;
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -40,6 +40,18 @@ pub fn find_definition<'a>(
     cache.resolve_or_in(name, current.name())
 }
 
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
+
+pub mod cycles;
+
+pub mod lint;
+
+pub mod suppressions;
+
+pub mod usage;
+
 // TODO: need a new version of this --v
 
 // pub fn validate_value(
@@ -80,6 +92,7 @@ pub mod terms {
     use crate::model::modules::*;
     use crate::model::values::*;
     use crate::model::*;
+    use crate::stdlib;
     use sdml_errors::diagnostics::functions::deprecated_term_used;
     use sdml_errors::Error;
     use serde::{Deserialize, Serialize};
@@ -88,6 +101,12 @@ pub mod terms {
     // Public Types
     // --------------------------------------------------------------------------------------------
 
+    ///
+    /// A named set of deprecated terms, each with alternatives to use instead.
+    ///
+    /// Term sets are usually loaded from JSON, but may also be declared as an SDML module, see
+    /// [`TermSet::from_module`].
+    ///
     #[derive(Clone, Debug, Deserialize, Serialize)]
     pub struct TermSet {
         name: String,
@@ -95,13 +114,19 @@ pub mod terms {
         version: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         description: Option<String>,
+        #[serde(default)]
         terms: HashMap<String, Term>,
     }
 
+    ///
+    /// A single deprecated term; if no `regex` is provided the term is matched as a whole word,
+    /// ignoring case.
+    ///
     #[derive(Clone, Debug, Deserialize, Serialize)]
     pub struct Term {
-        #[serde(skip_serializing_if = "Option::is_none", with = "serde_regex")]
+        #[serde(default, skip_serializing_if = "Option::is_none", with = "serde_regex")]
         regex: Option<regex::Regex>,
+        #[serde(default)]
         alternative_terms: Vec<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         reason: Option<String>,
@@ -149,6 +174,251 @@ pub mod terms {
     // Implementations
     // --------------------------------------------------------------------------------------------
 
+    impl TermSet {
+        pub fn new<S>(name: S) -> Self
+        where
+            S: Into<String>,
+        {
+            Self {
+                name: name.into(),
+                version: None,
+                description: None,
+                terms: Default::default(),
+            }
+        }
+
+        pub fn with_version<S>(self, version: S) -> Self
+        where
+            S: Into<String>,
+        {
+            Self {
+                version: Some(version.into()),
+                ..self
+            }
+        }
+
+        pub fn with_description<S>(self, description: S) -> Self
+        where
+            S: Into<String>,
+        {
+            Self {
+                description: Some(description.into()),
+                ..self
+            }
+        }
+
+        pub fn with_term<S>(mut self, term: S, info: Term) -> Self
+        where
+            S: Into<String>,
+        {
+            self.insert(term, info);
+            self
+        }
+
+        ///
+        /// Construct a term set from the module `module`; each definition with at least one
+        /// `skos:altLabel` annotation is a term:
+        ///
+        /// * the term is the definition's `skos:prefLabel`, if present, else its name,
+        /// * each `skos:altLabel` is an alternative term,
+        /// * an optional `skos:note` is the reason the term is deprecated, and
+        /// * an optional `skos:hiddenLabel` is the regular expression used to match the term.
+        ///
+        /// The set's name is the module's `skos:prefLabel`, if present, else the module's name;
+        /// its description is the module's `skos:definition` or `dc:description`, and its version
+        /// is the module's version information.
+        ///
+        /// ```sdml
+        /// module inclusive_terms is
+        ///
+        ///   import [ dc skos ]
+        ///
+        ///   @skos:prefLabel = "Inclusive Terms"@en
+        ///
+        ///   structure Blacklist is
+        ///     @skos:hiddenLabel = "(?i)\bblack[ _-]?list\b"
+        ///     @skos:altLabel = [ "Block list"@en "Deny list"@en ]
+        ///     @skos:note = "Applies a negative connotation to things that are black."@en
+        ///   end
+        ///
+        /// end
+        /// ```
+        ///
+        /// An error is returned if any `skos:hiddenLabel` is not a valid regular expression.
+        ///
+        pub fn from_module(module: &Module) -> Result<Self, Error> {
+            let annotations: Vec<&Annotation> = module.body().annotations().collect();
+            let mut term_set = Self::new(
+                first_string_value(
+                    &annotations,
+                    stdlib::skos::MODULE_NAME,
+                    stdlib::skos::PREF_LABEL,
+                )
+                .unwrap_or_else(|| module.name().to_string()),
+            );
+            term_set.description = first_string_value(
+                &annotations,
+                stdlib::skos::MODULE_NAME,
+                stdlib::skos::DEFINITION,
+            )
+            .or_else(|| {
+                first_string_value(
+                    &annotations,
+                    stdlib::dc::MODULE_NAME,
+                    stdlib::dc::DESCRIPTION,
+                )
+            });
+            term_set.version = module
+                .version_info()
+                .map(|version_info| version_info.as_ref().to_string());
+
+            for definition in module.body().definitions() {
+                let annotations = definition.body_annotations();
+                let alternative_terms = string_values(
+                    &annotations,
+                    stdlib::skos::MODULE_NAME,
+                    stdlib::skos::ALT_LABEL,
+                );
+                if alternative_terms.is_empty() {
+                    continue;
+                }
+                let regex = first_string_value(
+                    &annotations,
+                    stdlib::skos::MODULE_NAME,
+                    stdlib::skos::HIDDEN_LABEL,
+                )
+                .map(|pattern| regex::Regex::new(&pattern))
+                .transpose()
+                .map_err(|e| Error::TermSetError {
+                    name: term_set.name.clone(),
+                    message: format!("invalid `skos:hiddenLabel` on `{}`: {e}", definition.name()),
+                })?;
+                term_set.insert(
+                    first_string_value(
+                        &annotations,
+                        stdlib::skos::MODULE_NAME,
+                        stdlib::skos::PREF_LABEL,
+                    )
+                    .unwrap_or_else(|| definition.name().to_string()),
+                    Term {
+                        regex,
+                        alternative_terms,
+                        reason: first_string_value(
+                            &annotations,
+                            stdlib::skos::MODULE_NAME,
+                            stdlib::skos::NOTE,
+                        ),
+                    },
+                );
+            }
+            Ok(term_set)
+        }
+
+        ///
+        /// Merge all the term sets in `term_sets` into one, see [`TermSet::merge`]; returns
+        /// `None` if `term_sets` is empty.
+        ///
+        pub fn merged<I>(term_sets: I) -> Option<Self>
+        where
+            I: IntoIterator<Item = Self>,
+        {
+            term_sets.into_iter().reduce(|mut merged, term_set| {
+                merged.merge(term_set);
+                merged
+            })
+        }
+
+        // ----------------------------------------------------------------------------------------
+
+        pub fn name(&self) -> &String {
+            &self.name
+        }
+
+        pub fn version(&self) -> Option<&String> {
+            self.version.as_ref()
+        }
+
+        pub fn description(&self) -> Option<&String> {
+            self.description.as_ref()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.terms.is_empty()
+        }
+
+        pub fn len(&self) -> usize {
+            self.terms.len()
+        }
+
+        pub fn terms(&self) -> impl Iterator<Item = (&String, &Term)> {
+            self.terms.iter()
+        }
+
+        pub fn get(&self, term: &str) -> Option<&Term> {
+            self.terms.get(term)
+        }
+
+        pub fn insert<S>(&mut self, term: S, info: Term) -> Option<Term>
+        where
+            S: Into<String>,
+        {
+            self.terms.insert(term.into(), info)
+        }
+
+        ///
+        /// Add all the terms in `other` to this set, replacing any term with the same name. The
+        /// name, version, and description of this set are unchanged.
+        ///
+        pub fn merge(&mut self, other: TermSet) {
+            self.terms.extend(other.terms);
+        }
+    }
+
+    impl Term {
+        pub fn new<I, S>(alternative_terms: I) -> Self
+        where
+            I: IntoIterator<Item = S>,
+            S: Into<String>,
+        {
+            Self {
+                regex: None,
+                alternative_terms: alternative_terms.into_iter().map(Into::into).collect(),
+                reason: None,
+            }
+        }
+
+        pub fn with_regex(self, regex: regex::Regex) -> Self {
+            Self {
+                regex: Some(regex),
+                ..self
+            }
+        }
+
+        pub fn with_reason<S>(self, reason: S) -> Self
+        where
+            S: Into<String>,
+        {
+            Self {
+                reason: Some(reason.into()),
+                ..self
+            }
+        }
+
+        pub fn regex(&self) -> Option<&regex::Regex> {
+            self.regex.as_ref()
+        }
+
+        pub fn alternative_terms(&self) -> &Vec<String> {
+            &self.alternative_terms
+        }
+
+        pub fn reason(&self) -> Option<&String> {
+            self.reason.as_ref()
+        }
+    }
+
+    // --------------------------------------------------------------------------------------------
+
     impl<'a> From<&'a TermSet> for Validator<'a> {
         fn from(term_set: &'a TermSet) -> Self {
             let mut term_map: HashMap<String, TermInfo<'a>> = Default::default();
@@ -664,4 +934,49 @@ pub mod terms {
             }
         }
     }
+
+    // --------------------------------------------------------------------------------------------
+    // Private Functions
+    // --------------------------------------------------------------------------------------------
+
+    fn string_values(annotations: &[&Annotation], module: &str, member: &str) -> Vec<String> {
+        let as_string = |value: &SimpleValue| match value {
+            SimpleValue::String(value) => Some(value.value().to_string()),
+            _ => None,
+        };
+        annotations
+            .iter()
+            .filter_map(|annotation| annotation.as_annotation_property())
+            .filter(|property| {
+                property
+                    .name_reference()
+                    .as_qualified_identifier()
+                    .map(|name| {
+                        name.module().as_ref() == module && name.member().as_ref() == member
+                    })
+                    .unwrap_or_default()
+            })
+            .flat_map(|property| match property.value() {
+                Value::Simple(value) => as_string(value).into_iter().collect(),
+                Value::List(values) => values
+                    .iter()
+                    .filter_map(|value| match value {
+                        SequenceMember::Simple(value) => as_string(value),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::default(),
+            })
+            .collect()
+    }
+
+    fn first_string_value(
+        annotations: &[&Annotation],
+        module: &str,
+        member: &str,
+    ) -> Option<String> {
+        string_values(annotations, module, member)
+            .into_iter()
+            .next()
+    }
 }
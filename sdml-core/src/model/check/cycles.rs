@@ -0,0 +1,389 @@
+/*!
+Provides an analysis of the import graph between modules to find, and report, import cycles.
+
+The graph is built from [`Module::imported_modules`] for every module in a [`ModuleStore`], or
+every module reachable from a starting module, and cycles are found as the strongly connected
+components of that graph.
+
+# Example
+
+```
+use sdml_core::model::check::cycles::ImportGraph;
+use sdml_core::model::identifiers::Identifier;
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use std::str::FromStr;
+
+let store = InMemoryModuleCache::default().with_stdlib();
+let graph = ImportGraph::from_store(&store);
+
+// The standard library modules rdf and rdfs import each other.
+let rdf = Identifier::from_str("rdf").unwrap();
+assert!(graph.cycles().iter().any(|cycle| cycle.contains(&rdf)));
+```
+
+*/
+
+use crate::load::ModuleLoader;
+use crate::model::identifiers::Identifier;
+use crate::model::modules::Module;
+use crate::model::{HasBody, HasName, HasSourceSpan};
+use crate::stdlib::is_library_module;
+use crate::store::ModuleStore;
+use sdml_errors::diagnostics::functions::module_import_cycle;
+use sdml_errors::{Error, FileId, Span};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A directed graph of module names where each edge represents an import from one module of
+/// another.
+///
+#[derive(Clone, Debug, Default)]
+pub struct ImportGraph<'a> {
+    edges: BTreeMap<&'a Identifier, BTreeSet<&'a Identifier>>,
+}
+
+///
+/// A cycle in the import graph, this holds both the set of all modules in the strongly connected
+/// component and a single closed path through them.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportCycle<'a> {
+    modules: BTreeSet<&'a Identifier>,
+    path: Vec<&'a Identifier>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Report any import cycles reachable from `module`.
+///
+/// A cycle is ignored if all of its modules are either standard library modules, or are present
+/// in the `allowed` set.
+///
+pub fn validate_module_import_cycles(
+    module: &Module,
+    cache: &impl ModuleStore,
+    loader: &impl ModuleLoader,
+    allowed: &HashSet<Identifier>,
+) -> Result<(), Error> {
+    let graph = ImportGraph::from_module(module, cache);
+    for cycle in graph.cycles() {
+        if !cycle.is_allowed(allowed) {
+            let cycle = cycle.starting_from(module.name());
+            let mut links = cycle
+                .links()
+                .map(|(from, to)| (file_id_of(cache, from), import_span(cache, from, to)));
+            let (file_id, location) = links.next().unwrap_or_default();
+            let other_imports = links
+                .filter_map(|(file_id, span)| span.map(|span| (file_id, span)))
+                .collect();
+            loader.report(&module_import_cycle(
+                file_id,
+                location,
+                cycle.path(),
+                other_imports,
+            ))?;
+        }
+    }
+    Ok(())
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a> ImportGraph<'a> {
+    ///
+    /// Construct a graph from all the modules in `store`.
+    ///
+    pub fn from_store(store: &'a impl ModuleStore) -> Self {
+        let mut graph = Self::default();
+        for module in store.modules() {
+            graph.add_module(module);
+        }
+        graph
+    }
+
+    ///
+    /// Construct a graph of all the modules reachable from `module`; any imported module not
+    /// present in `store` will be a node with no imports of it's own.
+    ///
+    pub fn from_module(module: &'a Module, store: &'a impl ModuleStore) -> Self {
+        let mut graph = Self::default();
+        let mut visited: HashSet<&'a Identifier> = Default::default();
+        let mut queue = VecDeque::from([module]);
+        while let Some(module) = queue.pop_front() {
+            if visited.insert(module.name()) {
+                graph.add_module(module);
+                queue.extend(
+                    module
+                        .imported_modules()
+                        .into_iter()
+                        .filter(|name| !visited.contains(name))
+                        .filter_map(|name| store.get(name)),
+                );
+            }
+        }
+        graph
+    }
+
+    fn add_module(&mut self, module: &'a Module) {
+        let imported = module.imported_modules();
+        for name in &imported {
+            self.edges.entry(name).or_default();
+        }
+        self.edges
+            .entry(module.name())
+            .or_default()
+            .extend(imported);
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn contains(&self, name: &Identifier) -> bool {
+        self.edges.contains_key(name)
+    }
+
+    pub fn module_names(&self) -> impl Iterator<Item = &'a Identifier> + '_ {
+        self.edges.keys().copied()
+    }
+
+    pub fn imports_of(&self, name: &Identifier) -> impl Iterator<Item = &'a Identifier> + '_ {
+        self.edges.get(name).into_iter().flatten().copied()
+    }
+
+    pub fn imports(&self, from: &Identifier, to: &Identifier) -> bool {
+        self.edges
+            .get(from)
+            .map(|imports| imports.contains(to))
+            .unwrap_or_default()
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Return the strongly connected components of this graph, using Tarjan's algorithm. Every
+    /// module in the graph is in exactly one component, components of a single module are
+    /// included even if they do not import themselves.
+    ///
+    pub fn strongly_connected_components(&self) -> Vec<BTreeSet<&'a Identifier>> {
+        let mut state = TarjanState::default();
+        for name in self.edges.keys() {
+            if !state.indices.contains_key(name) {
+                self.strong_connect(name, &mut state);
+            }
+        }
+        state.components
+    }
+
+    // Tarjan's algorithm with an explicit stack of (module, imports, next import) frames rather
+    // than recursion, so that long chains of imports cannot overflow the stack.
+    fn strong_connect(&self, root: &'a Identifier, state: &mut TarjanState<'a>) {
+        let mut frames: Vec<(&'a Identifier, Vec<&'a Identifier>, usize)> = Default::default();
+        state.visit(root);
+        frames.push((root, self.imports_of(root).collect(), 0));
+
+        while let Some((name, imports, next)) = frames.last_mut() {
+            let name = *name;
+            if let Some(imported) = imports.get(*next).copied() {
+                *next += 1;
+                if !state.indices.contains_key(imported) {
+                    state.visit(imported);
+                    frames.push((imported, self.imports_of(imported).collect(), 0));
+                } else if state.on_stack.contains(imported) {
+                    let low_link = state.low_links[name].min(state.indices[imported]);
+                    state.low_links.insert(name, low_link);
+                }
+            } else {
+                frames.pop();
+                if let Some((parent, _, _)) = frames.last() {
+                    let low_link = state.low_links[parent].min(state.low_links[name]);
+                    state.low_links.insert(parent, low_link);
+                }
+                if state.low_links[name] == state.indices[name] {
+                    let mut component = BTreeSet::default();
+                    while let Some(member) = state.stack.pop() {
+                        state.on_stack.remove(member);
+                        component.insert(member);
+                        if member == name {
+                            break;
+                        }
+                    }
+                    state.components.push(component);
+                }
+            }
+        }
+    }
+
+    ///
+    /// Return all the import cycles in this graph, this is every strongly connected component
+    /// with more than one module, or a single module that imports itself.
+    ///
+    pub fn cycles(&self) -> Vec<ImportCycle<'a>> {
+        self.strongly_connected_components()
+            .into_iter()
+            .filter_map(|modules| {
+                let start = *modules.first()?;
+                if modules.len() > 1 || self.imports(start, start) {
+                    let path = self.cycle_path(start, &modules)?;
+                    Some(ImportCycle { modules, path })
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    // Find the shortest closed path from `start` back to itself within the component `modules`,
+    // there is always one for a component returned by `cycles`.
+    fn cycle_path(
+        &self,
+        start: &'a Identifier,
+        modules: &BTreeSet<&'a Identifier>,
+    ) -> Option<Vec<&'a Identifier>> {
+        let mut parents: HashMap<&'a Identifier, &'a Identifier> = Default::default();
+        let mut queue = VecDeque::from([start]);
+        while let Some(current) = queue.pop_front() {
+            for imported in self.imports_of(current) {
+                if imported == start {
+                    let mut path = vec![start, current];
+                    let mut node = current;
+                    while node != start {
+                        node = parents.get(node)?;
+                        path.push(node);
+                    }
+                    path.reverse();
+                    return Some(path);
+                } else if modules.contains(imported) && !parents.contains_key(imported) {
+                    parents.insert(imported, current);
+                    queue.push_back(imported);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[derive(Debug, Default)]
+struct TarjanState<'a> {
+    next_index: usize,
+    indices: HashMap<&'a Identifier, usize>,
+    low_links: HashMap<&'a Identifier, usize>,
+    stack: Vec<&'a Identifier>,
+    on_stack: HashSet<&'a Identifier>,
+    components: Vec<BTreeSet<&'a Identifier>>,
+}
+
+impl<'a> TarjanState<'a> {
+    fn visit(&mut self, name: &'a Identifier) {
+        self.indices.insert(name, self.next_index);
+        self.low_links.insert(name, self.next_index);
+        self.next_index += 1;
+        self.stack.push(name);
+        self.on_stack.insert(name);
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for ImportCycle<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.path
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+                .join(" -> ")
+        )
+    }
+}
+
+impl<'a> ImportCycle<'a> {
+    ///
+    /// All the modules in the strongly connected component containing this cycle.
+    ///
+    pub fn modules(&self) -> impl Iterator<Item = &'a Identifier> + '_ {
+        self.modules.iter().copied()
+    }
+
+    pub fn contains(&self, name: &Identifier) -> bool {
+        self.modules.contains(name)
+    }
+
+    ///
+    /// A closed path through the cycle, the first and last module names are the same.
+    ///
+    pub fn path(&self) -> &[&'a Identifier] {
+        &self.path
+    }
+
+    ///
+    /// Returns an iterator over each import `(from, to)` in the cycle's path.
+    ///
+    pub fn links(&self) -> impl Iterator<Item = (&'a Identifier, &'a Identifier)> + '_ {
+        self.path.windows(2).map(|pair| (pair[0], pair[1]))
+    }
+
+    ///
+    /// Returns `true` if every module in this cycle is a library module, or is in `allowed`.
+    ///
+    pub fn is_allowed(&self, allowed: &HashSet<Identifier>) -> bool {
+        self.modules
+            .iter()
+            .all(|name| is_library_module(name) || allowed.contains(*name))
+    }
+
+    ///
+    /// If `name` is on this cycle's path, return a copy with the path rotated to start, and end,
+    /// with `name`.
+    ///
+    pub fn starting_from(&self, name: &Identifier) -> Self {
+        let open_path = &self.path[..self.path.len() - 1];
+        if let Some(index) = open_path.iter().position(|n| *n == name) {
+            let mut path: Vec<&'a Identifier> = open_path[index..]
+                .iter()
+                .chain(open_path[..index].iter())
+                .copied()
+                .collect();
+            path.push(path[0]);
+            Self {
+                modules: self.modules.clone(),
+                path,
+            }
+        } else {
+            self.clone()
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn import_span(cache: &impl ModuleStore, from: &Identifier, to: &Identifier) -> Option<Span> {
+    cache.get(from).and_then(|module| {
+        module.body().imports().find_map(|statement| {
+            statement
+                .imports()
+                .find(|import| import.module() == to)
+                .and_then(|import| import.source_span().or(statement.source_span()))
+                .map(|span| span.byte_range())
+        })
+    })
+}
+
+fn file_id_of(cache: &impl ModuleStore, name: &Identifier) -> FileId {
+    cache
+        .get(name)
+        .and_then(|module| module.file_id().copied())
+        .unwrap_or_default()
+}
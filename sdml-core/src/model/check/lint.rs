@@ -0,0 +1,406 @@
+/*!
+Provides an extensible set of model-quality checks, *lint rules*, that go beyond the validation
+performed by [`Validate`](crate::model::check::Validate).
+
+Each [`LintRule`] checks a single module and returns any diagnostics it finds; rules are
+collected in a [`LintRegistry`] which runs them and reports their diagnostics through a
+[`ModuleLoader`]. Rules whose code has been disabled in the loader's
+[lint rules](sdml_errors::diagnostics::rules::LintRules) are not run.
+
+The [standard](LintRegistry::standard) registry contains the rules in [`rules`]:
+
+| Code    | Name                             | Reports                                                    |
+|---------|----------------------------------|------------------------------------------------------------|
+| `W0308` | `unused-import`                  | an import not referenced anywhere in the module            |
+| `W0309` | `unknown-member-type`            | a member whose type was left as `unknown`                  |
+| `I0507` | `entity-without-identity`        | an entity with no body, and so no identity member          |
+| `I0508` | `definition-without-description` | a definition without `skos:definition` or `dc:description` |
+| `I0509` | `unused-definition`              | a datatype or property not used within its module          |
+| `I0510` | `unbounded-identity-cardinality` | an identifying member with an unbounded cardinality        |
+| `I0511` | `single-variant-enum`            | an enum with only a single variant                         |
+
+# Example
+
+A team may add its own rules by implementing [`LintRule`] and adding them to a registry.
+
+A rule reports one of the existing [`ErrorCode`]s, so that it may be configured in the same way as
+the standard rules.
+
+```rust
+use sdml_core::model::check::lint::{LintContext, LintRegistry, LintRule};
+use sdml_core::model::HasBody;
+use sdml_errors::diagnostics::ErrorCode;
+use sdml_errors::Diagnostic;
+
+#[derive(Debug)]
+struct EmptyModule;
+
+impl LintRule for EmptyModule {
+    fn name(&self) -> &'static str {
+        "empty-module"
+    }
+
+    fn code(&self) -> ErrorCode {
+        ErrorCode::IncompleteModule
+    }
+
+    fn description(&self) -> &'static str {
+        "a module without any definitions"
+    }
+
+    fn check(&self, context: &LintContext<'_>) -> Vec<Diagnostic> {
+        let module = context.module();
+        if module.body().definitions_len() == 0 {
+            vec![Diagnostic::note()
+                .with_code(self.code().to_string())
+                .with_message(self.description())]
+        } else {
+            Vec::default()
+        }
+    }
+}
+
+let registry = LintRegistry::standard().with_rule(EmptyModule);
+
+assert!(registry.get("empty-module").is_some());
+assert!(registry.get("unused-import").is_some());
+```
+*/
+
+use crate::load::ModuleLoader;
+use crate::model::annotations::AnnotationProperty;
+use crate::model::definitions::{DatatypeDef, Definition, EventDef, TypeVariant};
+use crate::model::identifiers::{Identifier, IdentifierReference};
+use crate::model::members::{MemberDef, TypeReference};
+use crate::model::modules::Module;
+use crate::model::values::{SequenceMember, Value};
+use crate::model::walk::{walk_module_simple, SimpleModuleVisitor};
+use crate::model::{HasName, HasNameReference};
+use crate::store::ModuleStore;
+use sdml_errors::diagnostics::ErrorCode;
+use sdml_errors::{Diagnostic, Error, FileId};
+use std::collections::HashSet;
+use std::fmt::Debug;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A single model-quality check. Rules are identified by a unique, kebab-case, `name` and report
+/// diagnostics with a single `code`.
+///
+pub trait LintRule: Debug {
+    ///
+    /// The unique name of this rule, for example `"unused-import"`.
+    ///
+    fn name(&self) -> &'static str;
+
+    ///
+    /// The code of the diagnostics reported by this rule, for example
+    /// [`ErrorCode::UnusedImport`].
+    ///
+    fn code(&self) -> ErrorCode;
+
+    ///
+    /// A short description of what this rule checks for.
+    ///
+    fn description(&self) -> &'static str;
+
+    ///
+    /// Check the module in `context` and return any diagnostics.
+    ///
+    fn check(&self, context: &LintContext<'_>) -> Vec<Diagnostic>;
+}
+
+///
+/// The module being checked by a [`LintRule`], along with the means to resolve references to
+/// definitions in it and in the modules it imports.
+///
+pub struct LintContext<'a> {
+    module: &'a Module,
+    resolver: Resolver<'a>,
+    references: HashSet<IdentifierReference>,
+}
+
+///
+/// An ordered collection of [`LintRule`]s.
+///
+#[derive(Debug, Default)]
+pub struct LintRegistry {
+    rules: Vec<Box<dyn LintRule>>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+type Resolver<'a> = Box<dyn Fn(&IdentifierReference) -> Option<&'a Definition> + 'a>;
+
+#[derive(Debug, Default)]
+struct ReferenceCollector {
+    references: HashSet<IdentifierReference>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Debug for LintContext<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LintContext")
+            .field("module", self.module.name())
+            .field("references", &self.references)
+            .finish()
+    }
+}
+
+impl<'a> LintContext<'a> {
+    ///
+    /// Construct a context for `module`, references are resolved using the modules in `cache`.
+    ///
+    pub fn new(module: &'a Module, cache: &'a impl ModuleStore) -> Result<Self, Error> {
+        let mut collector = ReferenceCollector::default();
+        walk_module_simple(module, &mut collector, true, true)?;
+        Ok(Self {
+            module,
+            resolver: Box::new(move |name| cache.resolve_or_in(name, module.name())),
+            references: collector.references,
+        })
+    }
+
+    pub fn module(&self) -> &'a Module {
+        self.module
+    }
+
+    pub fn file_id(&self) -> FileId {
+        self.module.file_id().copied().unwrap_or_default()
+    }
+
+    ///
+    /// Resolve `name`, which may be unqualified, to a definition in this module or one it imports.
+    ///
+    pub fn resolve(&self, name: &IdentifierReference) -> Option<&'a Definition> {
+        (self.resolver)(name)
+    }
+
+    ///
+    /// Return every identifier reference within the module: datatype base types, event sources,
+    /// member types and property references, type variant names, and annotation property names
+    /// and values. References within formal constraints are not included.
+    ///
+    pub fn references(&self) -> impl Iterator<Item = &IdentifierReference> {
+        self.references.iter()
+    }
+
+    ///
+    /// Returns `true` if any reference in the module is to the definition `name` in this module,
+    /// either unqualified or qualified with this module's name.
+    ///
+    pub fn is_local_definition_referenced(&self, name: &Identifier) -> bool {
+        self.references.iter().any(|reference| match reference {
+            IdentifierReference::Identifier(id) => id == name,
+            IdentifierReference::QualifiedIdentifier(qid) => {
+                qid.module() == self.module.name() && qid.member() == name
+            }
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl LintRegistry {
+    ///
+    /// Construct a registry containing all the rules in [`rules`].
+    ///
+    pub fn standard() -> Self {
+        Self::default()
+            .with_rule(rules::UnusedImport)
+            .with_rule(rules::UnknownMemberType)
+            .with_rule(rules::EntityWithoutIdentity)
+            .with_rule(rules::DefinitionWithoutDescription)
+            .with_rule(rules::UnusedDefinition)
+            .with_rule(rules::UnboundedIdentityCardinality)
+            .with_rule(rules::SingleVariantEnum)
+    }
+
+    pub fn with_rule<R>(mut self, rule: R) -> Self
+    where
+        R: LintRule + 'static,
+    {
+        self.register(Box::new(rule));
+        self
+    }
+
+    ///
+    /// Add `rule` to this registry, replacing any existing rule with the same name.
+    ///
+    pub fn register(&mut self, rule: Box<dyn LintRule>) {
+        match self
+            .rules
+            .iter()
+            .position(|existing| existing.name() == rule.name())
+        {
+            Some(index) => self.rules[index] = rule,
+            None => self.rules.push(rule),
+        }
+    }
+
+    ///
+    /// Remove the rule named `name`, returning `true` if it was present.
+    ///
+    pub fn unregister(&mut self, name: &str) -> bool {
+        let before = self.rules.len();
+        self.rules.retain(|rule| rule.name() != name);
+        self.rules.len() != before
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = &dyn LintRule> {
+        self.rules.iter().map(|rule| rule.as_ref())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn LintRule> {
+        self.rules().find(|rule| rule.name() == name)
+    }
+
+    ///
+    /// Run all rules against `module` and return their diagnostics.
+    ///
+    pub fn check(
+        &self,
+        module: &Module,
+        cache: &impl ModuleStore,
+    ) -> Result<Vec<Diagnostic>, Error> {
+        let context = LintContext::new(module, cache)?;
+        Ok(self
+            .rules
+            .iter()
+            .flat_map(|rule| rule.check(&context))
+            .collect())
+    }
+
+    ///
+    /// Run all rules against `module` and report their diagnostics using `loader`. Rules whose
+    /// code has been disabled in the loader's lint rules are skipped.
+    ///
+    pub fn check_and_report(
+        &self,
+        module: &Module,
+        cache: &impl ModuleStore,
+        loader: &impl ModuleLoader,
+    ) -> Result<(), Error> {
+        let context = LintContext::new(module, cache)?;
+        for rule in self
+            .rules
+            .iter()
+            .filter(|rule| is_enabled(rule.as_ref(), loader))
+        {
+            for diagnostic in rule.check(&context) {
+                loader.report(&diagnostic)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl SimpleModuleVisitor for ReferenceCollector {
+    fn annotation_property(&mut self, thing: &AnnotationProperty) -> Result<(), Error> {
+        self.references.insert(thing.name_reference().clone());
+        self.value(thing.value());
+        Ok(())
+    }
+
+    fn datatype_start(&mut self, thing: &DatatypeDef) -> Result<bool, Error> {
+        self.references.insert(thing.base_type().clone());
+        Self::INCLUDE_NESTED
+    }
+
+    fn event_start(&mut self, thing: &EventDef) -> Result<bool, Error> {
+        self.references.insert(thing.event_source().clone());
+        Self::INCLUDE_NESTED
+    }
+
+    fn member_definition_start(&mut self, thing: &MemberDef) -> Result<bool, Error> {
+        self.type_reference(thing.target_type());
+        Self::INCLUDE_NESTED
+    }
+
+    fn property_reference_start(&mut self, thing: &IdentifierReference) -> Result<bool, Error> {
+        self.references.insert(thing.clone());
+        Self::INCLUDE_NESTED
+    }
+
+    fn type_variant_start(&mut self, thing: &TypeVariant) -> Result<bool, Error> {
+        self.references.insert(thing.name_reference().clone());
+        Self::INCLUDE_NESTED
+    }
+}
+
+impl ReferenceCollector {
+    fn type_reference(&mut self, thing: &TypeReference) {
+        match thing {
+            TypeReference::Unknown => {}
+            TypeReference::Type(name) => {
+                self.references.insert(name.clone());
+            }
+            TypeReference::MappingType(mapping) => {
+                self.type_reference(mapping.domain());
+                self.type_reference(mapping.range());
+            }
+        }
+    }
+
+    fn value(&mut self, thing: &Value) {
+        match thing {
+            Value::Simple(_) => {}
+            Value::ValueConstructor(value) => {
+                self.references.insert(value.type_name().clone());
+            }
+            Value::Mapping(value) => self.value(value.range()),
+            Value::Reference(name) => {
+                self.references.insert(name.clone());
+            }
+            Value::List(values) => {
+                for value in values.iter() {
+                    match value {
+                        SequenceMember::Simple(_) => {}
+                        SequenceMember::ValueConstructor(value) => {
+                            self.references.insert(value.type_name().clone());
+                        }
+                        SequenceMember::Reference(name) => {
+                            self.references.insert(name.clone());
+                        }
+                        SequenceMember::Mapping(value) => self.value(value.range()),
+                    }
+                }
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn is_enabled(rule: &dyn LintRule, loader: &impl ModuleLoader) -> bool {
+    loader
+        .lint_rules()
+        .map(|rules| rules.is_enabled(rule.code()))
+        .unwrap_or(true)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
+
+pub mod rules;
@@ -0,0 +1,398 @@
+/*!
+The standard [`LintRule`]s, each of which reports a single [`ErrorCode`].
+*/
+
+use crate::model::annotations::Annotation;
+use crate::model::check::lint::{LintContext, LintRule};
+use crate::model::check::usage::unused_imports;
+use crate::model::definitions::{Definition, HasMembers, HasVariants};
+use crate::model::identifiers::IdentifierReference;
+use crate::model::members::{Member, MemberDef, TypeReference};
+use crate::model::{HasBody, HasName, HasNameReference, HasOptionalBody, HasSourceSpan, Span};
+use crate::stdlib;
+use sdml_errors::diagnostics::functions::{
+    definition_without_description, entity_without_identity, member_type_unknown,
+    single_variant_enum, unbounded_identity_cardinality, unused_definition, unused_import,
+};
+use sdml_errors::diagnostics::ErrorCode;
+use sdml_errors::Diagnostic;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Reports a module or member import that is not referenced anywhere in the importing module, see
+/// [`unused_imports`].
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UnusedImport;
+
+///
+/// Reports a member, or property, whose type was left as `unknown`, including within a mapping
+/// type.
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UnknownMemberType;
+
+///
+/// Reports an entity declared without a body, and so without an identity member.
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EntityWithoutIdentity;
+
+///
+/// Reports a definition with no `skos:definition`, `dc:description`, or `dc_terms:description`
+/// annotation. RDF and type class definitions are not checked.
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefinitionWithoutDescription;
+
+///
+/// Reports a datatype or property definition that is not referenced anywhere in its module.
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UnusedDefinition;
+
+///
+/// Reports an entity identity member, or a member named `id` or `identifier`, with a cardinality
+/// that has no upper bound, such as `{0..}`.
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UnboundedIdentityCardinality;
+
+///
+/// Reports an enum with exactly one variant.
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SingleVariantEnum;
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl LintRule for UnusedImport {
+    fn name(&self) -> &'static str {
+        "unused-import"
+    }
+
+    fn code(&self) -> ErrorCode {
+        ErrorCode::UnusedImport
+    }
+
+    fn description(&self) -> &'static str {
+        "an import not referenced anywhere in the module"
+    }
+
+    fn check(&self, context: &LintContext<'_>) -> Vec<Diagnostic> {
+        unused_imports(context.module())
+            .into_iter()
+            .map(|import| {
+                unused_import(
+                    context.file_id(),
+                    import.source_span().map(|span| span.into()),
+                    import.to_string(),
+                )
+            })
+            .collect()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl LintRule for UnknownMemberType {
+    fn name(&self) -> &'static str {
+        "unknown-member-type"
+    }
+
+    fn code(&self) -> ErrorCode {
+        ErrorCode::UnknownMemberType
+    }
+
+    fn description(&self) -> &'static str {
+        "a member whose type was left as `unknown`"
+    }
+
+    fn check(&self, context: &LintContext<'_>) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::default();
+        let mut check_member_def = |member: &MemberDef, span: Option<&Span>| {
+            if is_unknown_type(member.target_type()) {
+                diagnostics.push(member_type_unknown(
+                    context.file_id(),
+                    member.name().source_span().or(span).map(|span| span.into()),
+                    member.name(),
+                ));
+            }
+        };
+        for definition in context.module().body().definitions() {
+            if let Definition::Property(def) = definition {
+                check_member_def(def.member_def(), definition.source_span());
+            } else {
+                for member in definition_members(definition) {
+                    if let Some(def) = member.as_definition() {
+                        check_member_def(def, member_span(member));
+                    }
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl LintRule for EntityWithoutIdentity {
+    fn name(&self) -> &'static str {
+        "entity-without-identity"
+    }
+
+    fn code(&self) -> ErrorCode {
+        ErrorCode::EntityWithoutIdentity
+    }
+
+    fn description(&self) -> &'static str {
+        "an entity with no body, and so no identity member"
+    }
+
+    fn check(&self, context: &LintContext<'_>) -> Vec<Diagnostic> {
+        context
+            .module()
+            .body()
+            .definitions()
+            .filter_map(|definition| match definition {
+                Definition::Entity(def) if !def.has_body() => Some(entity_without_identity(
+                    context.file_id(),
+                    name_span(definition),
+                    def.name(),
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl LintRule for DefinitionWithoutDescription {
+    fn name(&self) -> &'static str {
+        "definition-without-description"
+    }
+
+    fn code(&self) -> ErrorCode {
+        ErrorCode::DefinitionWithoutDescription
+    }
+
+    fn description(&self) -> &'static str {
+        "a definition without a `skos:definition` or `dc:description` annotation"
+    }
+
+    fn check(&self, context: &LintContext<'_>) -> Vec<Diagnostic> {
+        context
+            .module()
+            .body()
+            .definitions()
+            .filter(|definition| !definition.is_library_definition())
+            .filter(|definition| {
+                !definition
+                    .body_annotations()
+                    .into_iter()
+                    .filter_map(Annotation::as_annotation_property)
+                    .any(|property| is_description_property(property.name_reference()))
+            })
+            .map(|definition| {
+                definition_without_description(
+                    context.file_id(),
+                    name_span(definition),
+                    definition.name(),
+                )
+            })
+            .collect()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl LintRule for UnusedDefinition {
+    fn name(&self) -> &'static str {
+        "unused-definition"
+    }
+
+    fn code(&self) -> ErrorCode {
+        ErrorCode::UnusedDefinition
+    }
+
+    fn description(&self) -> &'static str {
+        "a datatype or property not used within its module"
+    }
+
+    fn check(&self, context: &LintContext<'_>) -> Vec<Diagnostic> {
+        context
+            .module()
+            .body()
+            .definitions()
+            .filter(|definition| {
+                matches!(
+                    definition,
+                    Definition::Datatype(_) | Definition::Property(_)
+                )
+            })
+            .filter(|definition| !context.is_local_definition_referenced(definition.name()))
+            .map(|definition| {
+                unused_definition(context.file_id(), name_span(definition), definition.name())
+            })
+            .collect()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl LintRule for UnboundedIdentityCardinality {
+    fn name(&self) -> &'static str {
+        "unbounded-identity-cardinality"
+    }
+
+    fn code(&self) -> ErrorCode {
+        ErrorCode::UnboundedIdentityCardinality
+    }
+
+    fn description(&self) -> &'static str {
+        "an identifying member with an unbounded cardinality"
+    }
+
+    fn check(&self, context: &LintContext<'_>) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::default();
+        for definition in context.module().body().definitions() {
+            let identity = match definition {
+                Definition::Entity(def) => def.body().map(|body| body.identity()),
+                _ => None,
+            };
+            for member in identity.into_iter().chain(
+                definition_members(definition)
+                    .into_iter()
+                    .filter(|member| is_identifier_like(member)),
+            ) {
+                let is_unbounded = match member.as_definition() {
+                    Some(def) => def.target_cardinality().max_occurs().is_none(),
+                    None => member
+                        .as_property_reference()
+                        .and_then(|name| context.resolve(name))
+                        .and_then(|definition| match definition {
+                            Definition::Property(def) => {
+                                Some(def.member_def().target_cardinality().max_occurs().is_none())
+                            }
+                            _ => None,
+                        })
+                        .unwrap_or_default(),
+                };
+                if is_unbounded {
+                    diagnostics.push(unbounded_identity_cardinality(
+                        context.file_id(),
+                        member_span(member).map(|span| span.into()),
+                        member.name(),
+                    ));
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl LintRule for SingleVariantEnum {
+    fn name(&self) -> &'static str {
+        "single-variant-enum"
+    }
+
+    fn code(&self) -> ErrorCode {
+        ErrorCode::SingleVariantEnum
+    }
+
+    fn description(&self) -> &'static str {
+        "an enum with only a single variant"
+    }
+
+    fn check(&self, context: &LintContext<'_>) -> Vec<Diagnostic> {
+        context
+            .module()
+            .body()
+            .definitions()
+            .filter_map(|definition| match definition {
+                Definition::Enum(def) if def.body().map(|body| body.variants_len()) == Some(1) => {
+                    Some(single_variant_enum(
+                        context.file_id(),
+                        name_span(definition),
+                        def.name(),
+                    ))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn name_span(definition: &Definition) -> Option<sdml_errors::Span> {
+    definition
+        .name()
+        .source_span()
+        .or(definition.source_span())
+        .map(|span| span.into())
+}
+
+fn member_span(member: &Member) -> Option<&Span> {
+    member
+        .source_span()
+        .or_else(|| member.as_definition().and_then(|def| def.source_span()))
+}
+
+fn is_unknown_type(type_reference: &TypeReference) -> bool {
+    match type_reference {
+        TypeReference::Unknown => true,
+        TypeReference::Type(_) => false,
+        TypeReference::MappingType(mapping) => {
+            is_unknown_type(mapping.domain()) || is_unknown_type(mapping.range())
+        }
+    }
+}
+
+fn is_identifier_like(member: &Member) -> bool {
+    matches!(member.name().as_ref(), "id" | "identifier")
+}
+
+fn is_description_property(name: &IdentifierReference) -> bool {
+    name.as_qualified_identifier()
+        .map(|name| {
+            matches!(
+                (name.module().as_ref(), name.member().as_ref()),
+                (stdlib::skos::MODULE_NAME, stdlib::skos::DEFINITION)
+                    | (stdlib::dc::MODULE_NAME, stdlib::dc::DESCRIPTION)
+                    | (
+                        stdlib::dc::terms::MODULE_NAME,
+                        stdlib::dc::terms::DESCRIPTION
+                    )
+            )
+        })
+        .unwrap_or_default()
+}
+
+fn definition_members(definition: &Definition) -> Vec<&Member> {
+    match definition {
+        Definition::Entity(def) => def
+            .body()
+            .map(|body| body.members().collect())
+            .unwrap_or_default(),
+        Definition::Event(def) => def
+            .body()
+            .map(|body| body.members().collect())
+            .unwrap_or_default(),
+        Definition::Structure(def) => def
+            .body()
+            .map(|body| body.members().collect())
+            .unwrap_or_default(),
+        _ => Vec::default(),
+    }
+}
@@ -0,0 +1,181 @@
+/*!
+Provides the collection of diagnostic [`Suppression`]s declared in a module.
+
+A suppression is declared with the `sdml:suppress` annotation property on a module, definition,
+member, or variant. The value is either a single code or a list of codes, and the scope of the
+suppression is the source span of the annotated element. A suppression on the module also
+applies to diagnostics, reported for the module, that have no location.
+
+```sdml
+module example is
+
+  import sdml
+
+  @sdml:suppress = "I0506"
+
+  entity legacy_record is
+    @sdml:suppress = [ "I0501" "I0504" ]
+
+    identity id -> string
+  end
+
+end
+```
+
+Modules that were not parsed from source have no spans and so no suppressions.
+*/
+
+use crate::model::annotations::{Annotation, HasAnnotations};
+use crate::model::definitions::{Definition, HasMembers, HasVariants};
+use crate::model::identifiers::IdentifierReference;
+use crate::model::members::Member;
+use crate::model::modules::Module;
+use crate::model::values::{SequenceMember, SimpleValue, Value};
+use crate::model::{HasBody, HasNameReference, HasOptionalBody, HasSourceSpan, Span};
+use crate::stdlib;
+use sdml_errors::diagnostics::suppressions::Suppression;
+use sdml_errors::FileId;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return all the suppressions declared in `module`.
+///
+pub fn module_suppressions(module: &Module) -> Vec<Suppression> {
+    let file_id = module.file_id().copied().unwrap_or_default();
+    let mut suppressions = Vec::default();
+    if let Some(suppression) = module
+        .source_span()
+        .and_then(|span| suppression(file_id, span, module.body().annotations().collect()))
+    {
+        suppressions.push(suppression.with_module_scope());
+    }
+
+    let mut add = |span: Option<&Span>, annotations: Vec<&Annotation>| {
+        if let Some(suppression) = span.and_then(|span| suppression(file_id, span, annotations)) {
+            suppressions.push(suppression);
+        }
+    };
+
+    for definition in module.body().definitions() {
+        let span = definition.source_span();
+        match definition {
+            Definition::Datatype(def) => add(span, optional_annotations(def.body())),
+            Definition::Entity(def) => {
+                if let Some(body) = def.body() {
+                    add(span, body.annotations().collect());
+                    for member in std::iter::once(body.identity()).chain(body.members()) {
+                        add(member_span(member), member_annotations(member));
+                    }
+                }
+            }
+            Definition::Enum(def) => {
+                if let Some(body) = def.body() {
+                    add(span, body.annotations().collect());
+                    for variant in body.variants() {
+                        add(variant.source_span(), optional_annotations(variant.body()));
+                    }
+                }
+            }
+            Definition::Event(def) => {
+                if let Some(body) = def.body() {
+                    add(span, body.annotations().collect());
+                    for member in body.members() {
+                        add(member_span(member), member_annotations(member));
+                    }
+                }
+            }
+            Definition::Property(def) => add(span, optional_annotations(def.member_def().body())),
+            Definition::Rdf(def) => add(span, def.body().annotations().collect()),
+            Definition::Structure(def) => {
+                if let Some(body) = def.body() {
+                    add(span, body.annotations().collect());
+                    for member in body.members() {
+                        add(member_span(member), member_annotations(member));
+                    }
+                }
+            }
+            Definition::TypeClass(def) => add(span, optional_annotations(def.body())),
+            Definition::Union(def) => {
+                if let Some(body) = def.body() {
+                    add(span, body.annotations().collect());
+                    for variant in body.variants() {
+                        add(variant.source_span(), optional_annotations(variant.body()));
+                    }
+                }
+            }
+        }
+    }
+
+    suppressions
+}
+
+///
+/// Returns `true` if `name` refers to the `sdml:suppress` annotation property.
+///
+pub fn is_suppress_property(name: &IdentifierReference) -> bool {
+    name.as_qualified_identifier()
+        .map(|name| {
+            name.module().as_ref() == stdlib::sdml::MODULE_NAME
+                && name.member().as_ref() == stdlib::sdml::SUPPRESS
+        })
+        .unwrap_or_default()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn optional_annotations<B: HasAnnotations>(body: Option<&B>) -> Vec<&Annotation> {
+    body.map(|body| body.annotations().collect())
+        .unwrap_or_default()
+}
+
+fn member_span(member: &Member) -> Option<&Span> {
+    member
+        .source_span()
+        .or_else(|| member.as_definition().and_then(|def| def.source_span()))
+}
+
+fn member_annotations(member: &Member) -> Vec<&Annotation> {
+    member
+        .as_definition()
+        .map(|def| optional_annotations(def.body()))
+        .unwrap_or_default()
+}
+
+fn suppression(
+    file_id: FileId,
+    scope: &Span,
+    annotations: Vec<&Annotation>,
+) -> Option<Suppression> {
+    let mut suppression = Suppression::new(file_id, scope.byte_range());
+    for property in annotations
+        .into_iter()
+        .filter_map(Annotation::as_annotation_property)
+        .filter(|property| is_suppress_property(property.name_reference()))
+    {
+        match property.value() {
+            Value::Simple(SimpleValue::String(code)) => {
+                suppression = suppression.with_code(
+                    code.value(),
+                    code.source_span().map(|span| span.byte_range()),
+                );
+            }
+            Value::List(codes) => {
+                for code in codes.iter() {
+                    if let SequenceMember::Simple(SimpleValue::String(code)) = code {
+                        suppression = suppression.with_code(
+                            code.value(),
+                            code.source_span().map(|span| span.byte_range()),
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    suppression.has_codes().then_some(suppression)
+}
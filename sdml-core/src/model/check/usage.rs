@@ -0,0 +1,343 @@
+/*!
+Provides an analysis of how the imports and definitions in a set of modules are used.
+
+The names referenced by a module are those returned by [`References::referenced_types`] and
+[`References::referenced_annotations`], along with the annotation properties on the module
+itself. From these:
+
+* [`unused_imports`] finds the imports of a module, both module imports and member imports, that
+  are never referenced;
+* [`Reachability`] finds every definition, across all the modules in a [`ModuleStore`], that can
+  be reached by following references from a set of root definitions; and
+* [`prune_module`] returns a copy of a module without its unreachable definitions and with only
+  the imports that the remaining definitions use.
+
+# Example
+
+```rust
+use sdml_core::model::check::usage::{prune_module, unused_imports, Reachability};
+use sdml_core::model::definitions::{Definition, StructureDef};
+use sdml_core::model::identifiers::{Identifier, IdentifierReference};
+use sdml_core::model::modules::{ImportStatement, Module, ModuleBody};
+use sdml_core::model::{HasBody, HasName};
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use std::str::FromStr;
+
+let mut body = ModuleBody::default();
+body.add_to_imports(ImportStatement::new_module(Identifier::from_str("xsd").unwrap()));
+body.add_to_definitions(Definition::Structure(StructureDef::new(
+    Identifier::from_str("Used").unwrap(),
+)))
+.unwrap();
+body.add_to_definitions(Definition::Structure(StructureDef::new(
+    Identifier::from_str("Unused").unwrap(),
+)))
+.unwrap();
+let module = Module::new(Identifier::from_str("example").unwrap(), body);
+
+assert_eq!(unused_imports(&module).len(), 1);
+
+let mut cache = InMemoryModuleCache::default().with_stdlib();
+cache.insert(module.clone());
+
+let reachability = Reachability::from_roots(
+    [IdentifierReference::from_str("Used").unwrap()],
+    module.name(),
+    &cache,
+);
+assert_eq!(reachability.unreachable_definitions(&module).len(), 1);
+
+let pruned = prune_module(&module, Some(&reachability));
+assert_eq!(pruned.body().imports().count(), 0);
+assert_eq!(pruned.body().definitions_len(), 1);
+```
+*/
+
+use crate::load::ModuleLoader;
+use crate::model::annotations::HasAnnotations;
+use crate::model::definitions::Definition;
+use crate::model::identifiers::{Identifier, IdentifierReference, QualifiedIdentifier};
+use crate::model::modules::{Import, ImportStatement, Module};
+use crate::model::{HasBody, HasName, HasNameReference, HasSourceSpan, References};
+use crate::stdlib::is_library_module;
+use crate::store::ModuleStore;
+use sdml_errors::diagnostics::functions::{unreachable_definition, unused_import};
+use sdml_errors::Error;
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The set of definitions reachable from a set of root definitions by following the types and
+/// annotation properties each definition references, see [`References`].
+///
+#[derive(Clone, Debug, Default)]
+pub struct Reachability {
+    roots: BTreeSet<QualifiedIdentifier>,
+    unresolved_roots: Vec<IdentifierReference>,
+    reached: BTreeSet<QualifiedIdentifier>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return all the names referenced by `module`, these are the types and annotation properties
+/// referenced by its definitions, and the annotation properties of the module itself.
+///
+pub fn referenced_names(module: &Module) -> HashSet<&IdentifierReference> {
+    let mut names = HashSet::default();
+    module.referenced_types(&mut names);
+    module.referenced_annotations(&mut names);
+    names.extend(
+        module
+            .body()
+            .annotation_properties()
+            .map(|property| property.name_reference()),
+    );
+    names
+}
+
+///
+/// Return the imports of `module` that are never referenced. A module import is used if any
+/// reference is qualified with the module's name; a member import is used if the member is
+/// referenced, either qualified or unqualified.
+///
+pub fn unused_imports(module: &Module) -> Vec<&Import> {
+    let names = referenced_names(module);
+    module
+        .body()
+        .imports()
+        .flat_map(|statement| statement.imports())
+        .filter(|import| !is_import_used(import, &names))
+        .collect()
+}
+
+///
+/// Return `module` followed by every non-library module in `cache` it imports, directly or
+/// transitively, in the order they are first imported.
+///
+pub fn module_set<'a>(module: &'a Module, cache: &'a impl ModuleStore) -> Vec<&'a Module> {
+    let mut seen: HashSet<&Identifier> = HashSet::from([module.name()]);
+    let mut modules = vec![module];
+    let mut index = 0;
+    while index < modules.len() {
+        let mut imported: Vec<&Identifier> = modules[index]
+            .body()
+            .imported_modules()
+            .into_iter()
+            .collect();
+        imported.sort();
+        for name in imported {
+            if !is_library_module(name) && seen.insert(name) {
+                if let Some(imported) = cache.get(name) {
+                    modules.push(imported);
+                }
+            }
+        }
+        index += 1;
+    }
+    modules
+}
+
+///
+/// Report the unused imports in `module` and, if `reachability` is provided, any of its
+/// definitions that are not reachable.
+///
+pub fn validate_module_usage(
+    module: &Module,
+    reachability: Option<&Reachability>,
+    loader: &impl ModuleLoader,
+) -> Result<(), Error> {
+    let file_id = module.file_id().copied().unwrap_or_default();
+    for import in unused_imports(module) {
+        loader.report(&unused_import(
+            file_id,
+            import.source_span().map(|span| span.into()),
+            import.to_string(),
+        ))?;
+    }
+    if let Some(reachability) = reachability {
+        for definition in reachability.unreachable_definitions(module) {
+            loader.report(&unreachable_definition(
+                file_id,
+                definition
+                    .name()
+                    .source_span()
+                    .or(definition.source_span())
+                    .map(|span| span.into()),
+                definition.name(),
+            ))?;
+        }
+    }
+    Ok(())
+}
+
+///
+/// Return a copy of `module` without any definitions that are not reachable, if `reachability`
+/// is provided, and without any imports that the remaining definitions do not use. Import
+/// statements left empty are removed.
+///
+pub fn prune_module(module: &Module, reachability: Option<&Reachability>) -> Module {
+    let mut pruned = module.clone();
+    if let Some(reachability) = reachability {
+        let module_name = module.name().clone();
+        pruned.body_mut().retain_definitions(|definition| {
+            reachability.is_reachable(&module_name.with_member(definition.name().clone()))
+        });
+    }
+    let unused: HashSet<String> = unused_imports(&pruned)
+        .into_iter()
+        .map(|import| import.to_string())
+        .collect();
+    if !unused.is_empty() {
+        pruned.body_mut().retain_imports(|statement| {
+            let imports: Vec<Import> = statement
+                .imports()
+                .filter(|import| !unused.contains(&import.to_string()))
+                .cloned()
+                .collect();
+            if imports.len() == statement.imports_len() {
+                true
+            } else {
+                *statement = ImportStatement::new(imports);
+                statement.has_imports()
+            }
+        });
+    }
+    pruned
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Reachability {
+    ///
+    /// Find all the definitions in `cache` reachable from `roots`; unqualified roots are
+    /// definitions in the module `in_module`. Roots that cannot be resolved are recorded, see
+    /// [`Reachability::unresolved_roots`].
+    ///
+    pub fn from_roots<I>(roots: I, in_module: &Identifier, cache: &impl ModuleStore) -> Self
+    where
+        I: IntoIterator<Item = IdentifierReference>,
+    {
+        let mut reachability = Self::default();
+        let mut queue: VecDeque<QualifiedIdentifier> = Default::default();
+        for root in roots {
+            let qualified = match &root {
+                IdentifierReference::Identifier(name) => in_module.with_member(name.clone()),
+                IdentifierReference::QualifiedIdentifier(name) => name.clone(),
+            };
+            if cache.resolve(&qualified).is_some() {
+                reachability.roots.insert(qualified.clone());
+                queue.push_back(qualified);
+            } else {
+                reachability.unresolved_roots.push(root);
+            }
+        }
+
+        while let Some(name) = queue.pop_front() {
+            if reachability.reached.contains(&name) {
+                continue;
+            }
+            let Some(module) = cache.get(name.module()) else {
+                continue;
+            };
+            let Some(definition) = module.resolve_local(name.member()) else {
+                continue;
+            };
+            for referenced in definition_references(definition) {
+                if let Some(referenced) = qualify_in(referenced, module) {
+                    if !reachability.reached.contains(&referenced) {
+                        queue.push_back(referenced);
+                    }
+                }
+            }
+            reachability.reached.insert(name);
+        }
+        reachability
+    }
+
+    pub fn roots(&self) -> impl Iterator<Item = &QualifiedIdentifier> {
+        self.roots.iter()
+    }
+
+    ///
+    /// Return the roots that could not be resolved to a definition.
+    ///
+    pub fn unresolved_roots(&self) -> impl Iterator<Item = &IdentifierReference> {
+        self.unresolved_roots.iter()
+    }
+
+    ///
+    /// Return every reachable definition's name, including the roots.
+    ///
+    pub fn reached(&self) -> impl Iterator<Item = &QualifiedIdentifier> {
+        self.reached.iter()
+    }
+
+    pub fn is_reachable(&self, name: &QualifiedIdentifier) -> bool {
+        self.reached.contains(name)
+    }
+
+    ///
+    /// Return the definitions in `module` that are not reachable.
+    ///
+    pub fn unreachable_definitions<'a>(&self, module: &'a Module) -> Vec<&'a Definition> {
+        module
+            .body()
+            .definitions()
+            .filter(|definition| {
+                !self.is_reachable(&module.name().with_member(definition.name().clone()))
+            })
+            .collect()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn is_import_used(import: &Import, names: &HashSet<&IdentifierReference>) -> bool {
+    match import {
+        Import::Module(import) => names
+            .iter()
+            .any(|name| name.module() == Some(import.name())),
+        Import::Member(import) => names.iter().any(|name| {
+            name.member() == import.member()
+                && name.module().is_none_or(|module| module == import.module())
+        }),
+    }
+}
+
+fn definition_references(definition: &Definition) -> HashSet<&IdentifierReference> {
+    let mut names = HashSet::default();
+    definition.referenced_types(&mut names);
+    definition.referenced_annotations(&mut names);
+    names
+}
+
+///
+/// An unqualified name refers to a definition in `module` or, failing that, to a member import.
+///
+fn qualify_in(name: &IdentifierReference, module: &Module) -> Option<QualifiedIdentifier> {
+    match name {
+        IdentifierReference::QualifiedIdentifier(name) => Some(name.clone()),
+        IdentifierReference::Identifier(name) => {
+            if module.resolve_local(name).is_some() {
+                Some(module.name().with_member(name.clone()))
+            } else {
+                module
+                    .body()
+                    .imported_types()
+                    .into_iter()
+                    .find(|imported| imported.member() == name)
+                    .cloned()
+            }
+        }
+    }
+}
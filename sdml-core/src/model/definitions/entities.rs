@@ -1,13 +1,13 @@
 use crate::load::ModuleLoader;
 use crate::model::References;
 use crate::model::{
-    annotations::Annotation,
+    annotations::{Annotation, HasAnnotations},
     check::Validate,
     definitions::HasMembers,
     identifiers::{Identifier, IdentifierReference},
     members::Member,
     modules::Module,
-    Span,
+    HasNameReference, Span,
 };
 use crate::store::ModuleStore;
 use sdml_errors::diagnostics::functions::IdentifierCaseConvention;
@@ -121,10 +121,13 @@ impl Validate for EntityBody {
 
 impl References for EntityBody {
     fn referenced_annotations<'a>(&'a self, names: &mut HashSet<&'a IdentifierReference>) {
+        names.extend(self.annotation_properties().map(|ann| ann.name_reference()));
+        self.identity.referenced_annotations(names);
         self.members().for_each(|m| m.referenced_annotations(names))
     }
 
     fn referenced_types<'a>(&'a self, names: &mut HashSet<&'a IdentifierReference>) {
+        self.identity.referenced_types(names);
         self.members().for_each(|m| m.referenced_types(names))
     }
 }
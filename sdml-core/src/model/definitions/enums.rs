@@ -6,7 +6,7 @@ use crate::{
         definitions::HasVariants,
         identifiers::{Identifier, IdentifierReference},
         modules::Module,
-        HasName, References, Span,
+        HasName, HasNameReference, References, Span,
     },
     store::ModuleStore,
 };
@@ -115,6 +115,7 @@ impl_has_variants_for!(EnumBody, ValueVariant);
 
 impl References for EnumBody {
     fn referenced_annotations<'a>(&'a self, names: &mut HashSet<&'a IdentifierReference>) {
+        names.extend(self.annotation_properties().map(|ann| ann.name_reference()));
         self.variants
             .iter()
             .for_each(|v| v.referenced_annotations(names));
@@ -2,10 +2,10 @@ use crate::model::{
     check::Validate,
     definitions::StructureBody,
     identifiers::{Identifier, IdentifierReference},
-    HasName, Span,
+    HasName, References, Span,
 };
 use sdml_errors::diagnostics::functions::IdentifierCaseConvention;
-use std::fmt::Debug;
+use std::{collections::HashSet, fmt::Debug};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -34,7 +34,22 @@ impl_has_name_for!(EventDef);
 
 impl_has_optional_body_for!(EventDef, StructureBody);
 
-impl_references_for!(EventDef => delegate optional body);
+impl References for EventDef {
+    fn referenced_annotations<'a>(&'a self, names: &mut HashSet<&'a IdentifierReference>) {
+        self.body
+            .as_ref()
+            .map(|b| b.referenced_annotations(names))
+            .unwrap_or_default()
+    }
+
+    fn referenced_types<'a>(&'a self, names: &mut HashSet<&'a IdentifierReference>) {
+        names.insert(&self.event_source);
+        self.body
+            .as_ref()
+            .map(|b| b.referenced_types(names))
+            .unwrap_or_default()
+    }
+}
 
 impl_has_source_span_for!(EventDef);
 
@@ -3,11 +3,16 @@ Provide the Rust types that implement *definition*-related components of the SDM
 */
 use crate::{
     load::ModuleLoader,
-    model::{check::MaybeIncomplete, members::Member, HasName, HasSourceSpan},
+    model::{
+        annotations::{Annotation, HasAnnotations},
+        check::MaybeIncomplete,
+        members::Member,
+        HasBody, HasName, HasOptionalBody, HasSourceSpan,
+    },
     store::ModuleStore,
 };
 use sdml_errors::diagnostics::functions::definition_is_incomplete;
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -32,6 +37,15 @@ pub trait HasMembers {
         I: IntoIterator<Item = Member>;
 }
 
+///
+/// Types with members that also allow members to be removed in place.
+///
+pub trait HasMembersMut: HasMembers {
+    fn retain_members<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut Member) -> bool;
+}
+
 pub trait HasVariants {
     type Variant;
 
@@ -50,6 +64,15 @@ pub trait HasVariants {
         I: IntoIterator<Item = Self::Variant>;
 }
 
+///
+/// Types with variants that also allow variants to be removed in place.
+///
+pub trait HasVariantsMut: HasVariants {
+    fn retain_variants<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut Self::Variant) -> bool;
+}
+
 // ------------------------------------------------------------------------------------------------
 // Public Types ❱ Type Definitions
 // ------------------------------------------------------------------------------------------------
@@ -69,6 +92,24 @@ pub enum Definition {
     Union(UnionDef),
 }
 
+///
+/// The kind of a [`Definition`], without its content.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DefinitionKind {
+    Datatype,
+    Entity,
+    Enum,
+    Event,
+    Property,
+    Rdf,
+    Structure,
+    TypeClass,
+    Union,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Implementations ❱ Type Definitions
 // ------------------------------------------------------------------------------------------------
@@ -161,6 +202,95 @@ impl Definition {
     pub fn is_library_definition(&self) -> bool {
         matches!(self, Self::Rdf(_) | Self::TypeClass(_))
     }
+
+    ///
+    /// Return the annotations in this definition's body, empty if it has no body.
+    ///
+    pub fn body_annotations(&self) -> Vec<&Annotation> {
+        fn optional<B: HasAnnotations>(body: Option<&B>) -> Vec<&Annotation> {
+            body.map(|body| body.annotations().collect())
+                .unwrap_or_default()
+        }
+        match self {
+            Self::Datatype(def) => optional(def.body()),
+            Self::Entity(def) => optional(def.body()),
+            Self::Enum(def) => optional(def.body()),
+            Self::Event(def) => optional(def.body()),
+            Self::Property(def) => optional(def.member_def().body()),
+            Self::Rdf(def) => def.body().annotations().collect(),
+            Self::Structure(def) => optional(def.body()),
+            Self::TypeClass(def) => optional(def.body()),
+            Self::Union(def) => optional(def.body()),
+        }
+    }
+
+    pub fn kind(&self) -> DefinitionKind {
+        match self {
+            Self::Datatype(_) => DefinitionKind::Datatype,
+            Self::Entity(_) => DefinitionKind::Entity,
+            Self::Enum(_) => DefinitionKind::Enum,
+            Self::Event(_) => DefinitionKind::Event,
+            Self::Property(_) => DefinitionKind::Property,
+            Self::Rdf(_) => DefinitionKind::Rdf,
+            Self::Structure(_) => DefinitionKind::Structure,
+            Self::TypeClass(_) => DefinitionKind::TypeClass,
+            Self::Union(_) => DefinitionKind::Union,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations ❱ Definition Kinds
+// ------------------------------------------------------------------------------------------------
+
+impl From<&Definition> for DefinitionKind {
+    fn from(value: &Definition) -> Self {
+        value.kind()
+    }
+}
+
+impl Display for DefinitionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.keyword())
+    }
+}
+
+impl DefinitionKind {
+    pub const ALL: [DefinitionKind; 9] = [
+        Self::Datatype,
+        Self::Entity,
+        Self::Enum,
+        Self::Event,
+        Self::Property,
+        Self::Rdf,
+        Self::Structure,
+        Self::TypeClass,
+        Self::Union,
+    ];
+
+    ///
+    /// The keyword that introduces definitions of this kind.
+    ///
+    pub const fn keyword(&self) -> &'static str {
+        match self {
+            Self::Datatype => "datatype",
+            Self::Entity => "entity",
+            Self::Enum => "enum",
+            Self::Event => "event",
+            Self::Property => "property",
+            Self::Rdf => "rdf",
+            Self::Structure => "structure",
+            Self::TypeClass => "class",
+            Self::Union => "union",
+        }
+    }
+
+    ///
+    /// The kind whose keyword is `keyword`, if any.
+    ///
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.keyword() == keyword)
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -4,7 +4,7 @@ use crate::model::{
     definitions::HasMembers,
     identifiers::{Identifier, IdentifierReference},
     members::Member,
-    References, Span,
+    HasNameReference, References, Span,
 };
 use std::{collections::HashSet, fmt::Debug};
 
@@ -103,6 +103,7 @@ impl References for StructureBody {
     }
 
     fn referenced_annotations<'a>(&'a self, names: &mut HashSet<&'a IdentifierReference>) {
+        names.extend(self.annotation_properties().map(|ann| ann.name_reference()));
         self.members().for_each(|m| m.referenced_annotations(names));
     }
 }
@@ -6,7 +6,7 @@ use crate::{
         definitions::HasVariants,
         identifiers::{Identifier, IdentifierReference},
         modules::Module,
-        References, Span,
+        HasNameReference, References, Span,
     },
     store::ModuleStore,
 };
@@ -91,6 +91,13 @@ impl References for UnionDef {
             .map(|b| b.referenced_annotations(names))
             .unwrap_or_default()
     }
+
+    fn referenced_types<'a>(&'a self, names: &mut HashSet<&'a IdentifierReference>) {
+        self.body
+            .as_ref()
+            .map(|b| b.referenced_types(names))
+            .unwrap_or_default()
+    }
 }
 
 impl UnionDef {
@@ -128,10 +135,15 @@ impl_annotation_builder!(UnionDef, optional body);
 
 impl References for UnionBody {
     fn referenced_annotations<'a>(&'a self, names: &mut HashSet<&'a IdentifierReference>) {
+        names.extend(self.annotation_properties().map(|ann| ann.name_reference()));
         self.variants
             .iter()
             .for_each(|v| v.referenced_annotations(names));
     }
+
+    fn referenced_types<'a>(&'a self, names: &mut HashSet<&'a IdentifierReference>) {
+        self.variants.iter().for_each(|v| v.referenced_types(names));
+    }
 }
 
 impl UnionBody {
@@ -175,6 +187,10 @@ impl References for TypeVariant {
             .map(|b| b.referenced_annotations(names))
             .unwrap_or_default()
     }
+
+    fn referenced_types<'a>(&'a self, names: &mut HashSet<&'a IdentifierReference>) {
+        names.insert(&self.name_reference);
+    }
 }
 
 impl TypeVariant {
@@ -8,7 +8,8 @@ use convert_case::{Case, Casing};
 use lazy_static::lazy_static;
 use regex::Regex;
 use sdml_errors::diagnostics::functions::{
-    identifier_not_preferred_case, invalid_identifier, IdentifierCaseConvention,
+    identifier_not_configured_case, identifier_not_preferred_case_with_rename, invalid_identifier,
+    IdentifierCaseConvention,
 };
 use std::{
     fmt::{Debug, Display},
@@ -182,6 +183,14 @@ impl Identifier {
         }
     }
 
+    ///
+    /// Return a copy of this identifier without its source span, for values that outlive, or
+    /// are compared across, a single module source.
+    ///
+    pub fn without_span(&self) -> Self {
+        Self::new_unchecked(&self.value)
+    }
+
     #[inline(always)]
     pub fn with_module(&self, module: Identifier) -> QualifiedIdentifier {
         QualifiedIdentifier::new(module, self.clone())
@@ -212,15 +221,30 @@ impl Identifier {
                 .unwrap();
         }
         if let Some(case) = as_case {
-            if !case.is_valid(self) {
-                loader
-                    .report(&identifier_not_preferred_case(
-                        top.file_id().copied().unwrap_or_default(),
-                        self.source_span().map(|span| span.byte_range()),
-                        self,
-                        case,
-                    ))
-                    .unwrap();
+            let file_id = top.file_id().copied().unwrap_or_default();
+            let location = self.source_span().map(|span| span.byte_range());
+            match loader
+                .lint_rules()
+                .and_then(|rules| rules.case_styles(case))
+            {
+                Some(styles) => {
+                    if !styles.iter().any(|style| style.is_valid(self)) {
+                        loader
+                            .report_suggested(&identifier_not_configured_case(
+                                file_id, location, self, styles,
+                            ))
+                            .unwrap();
+                    }
+                }
+                None => {
+                    if !case.is_valid(self) {
+                        loader
+                            .report_suggested(&identifier_not_preferred_case_with_rename(
+                                file_id, location, self, case,
+                            ))
+                            .unwrap();
+                    }
+                }
             }
         }
     }
@@ -341,6 +365,20 @@ impl PartialEq for QualifiedIdentifier {
 
 impl Eq for QualifiedIdentifier {}
 
+impl PartialOrd for QualifiedIdentifier {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QualifiedIdentifier {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.module
+            .cmp(&other.module)
+            .then_with(|| self.member.cmp(&other.member))
+    }
+}
+
 impl Hash for QualifiedIdentifier {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         // ignore: self.span.hash(state);
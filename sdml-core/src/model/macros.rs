@@ -507,6 +507,15 @@ macro_rules! impl_has_annotations_for {
                 self.$inner.extend(extension.into_iter())
             }
         }
+
+        impl $crate::model::annotations::HasAnnotationsMut for $type {
+            fn retain_annotations<F>(&mut self, f: F)
+            where
+                F: FnMut(&mut $crate::model::annotations::Annotation) -> bool,
+            {
+                self.$inner.retain_mut(f)
+            }
+        }
     };
 }
 
@@ -596,6 +605,15 @@ macro_rules! impl_has_members_for {
                 self.$inner.extend(extension.into_iter())
             }
         }
+
+        impl $crate::model::definitions::HasMembersMut for $type {
+            fn retain_members<F>(&mut self, f: F)
+            where
+                F: FnMut(&mut $crate::model::members::Member) -> bool,
+            {
+                self.$inner.retain_mut(f)
+            }
+        }
     };
 }
 
@@ -638,6 +656,15 @@ macro_rules! impl_has_variants_for {
                 self.$inner.extend(extension.into_iter())
             }
         }
+
+        impl $crate::model::definitions::HasVariantsMut for $type {
+            fn retain_variants<F>(&mut self, f: F)
+            where
+                F: FnMut(&mut Self::Variant) -> bool,
+            {
+                self.$inner.retain_mut(f)
+            }
+        }
     };
 }
 
@@ -24,7 +24,7 @@ pub trait HasCardinality {
 }
 
 /// Corresponds to the grammar rule `cardinality`.
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Cardinality {
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
@@ -47,7 +47,7 @@ pub const TYPE_ORDERED_SET_CARDINALITY: Cardinality =
     Cardinality::zero_or_more(Some(Ordering::Ordered), Some(Uniqueness::Unique));
 pub const TYPE_MAYBE_CARDINALITY: Cardinality = Cardinality::zero_or_one();
 
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct CardinalityRange {
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
@@ -120,6 +120,16 @@ impl From<CardinalityRange> for Cardinality {
     }
 }
 
+impl PartialEq for Cardinality {
+    fn eq(&self, other: &Self) -> bool {
+        self.ordering == other.ordering
+            && self.uniqueness == other.uniqueness
+            && self.range == other.range
+    }
+}
+
+impl Eq for Cardinality {}
+
 impl_has_source_span_for!(Cardinality);
 
 impl Validate for Cardinality {
@@ -301,6 +311,13 @@ impl Cardinality {
     // Cardinality :: Helpers
     // --------------------------------------------------------------------------------------------
 
+    pub fn eq_with_span(&self, other: &Self) -> bool {
+        self.span == other.span
+            && self.ordering == other.ordering
+            && self.uniqueness == other.uniqueness
+            && self.range.eq_with_span(&other.range)
+    }
+
     #[inline(always)]
     pub fn is_optional(&self) -> bool {
         self.range.is_optional()
@@ -361,6 +378,14 @@ impl From<u32> for CardinalityRange {
     }
 }
 
+impl PartialEq for CardinalityRange {
+    fn eq(&self, other: &Self) -> bool {
+        self.min == other.min && self.max == other.max
+    }
+}
+
+impl Eq for CardinalityRange {}
+
 impl_has_source_span_for!(CardinalityRange);
 
 impl Validate for CardinalityRange {
@@ -475,6 +500,10 @@ impl CardinalityRange {
     // Cardinality :: Helpers
     // --------------------------------------------------------------------------------------------
 
+    pub fn eq_with_span(&self, other: &Self) -> bool {
+        self.span == other.span && self.min == other.min && self.max == other.max
+    }
+
     #[inline(always)]
     pub const fn is_optional(&self) -> bool {
         self.min_occurs() == 0
@@ -187,6 +187,10 @@ impl Member {
         &self.kind
     }
 
+    pub fn kind_mut(&mut self) -> &mut MemberKind {
+        &mut self.kind
+    }
+
     delegate!(pub const is_definition, bool, kind);
     delegate!(pub const as_definition, Option<&MemberDef>, kind);
 
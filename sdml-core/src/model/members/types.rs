@@ -87,6 +87,19 @@ impl IdentifierReference {
     }
 }
 
+impl PartialEq for TypeReference {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Unknown, Self::Unknown) => true,
+            (Self::Type(l0), Self::Type(r0)) => l0 == r0,
+            (Self::MappingType(l0), Self::MappingType(r0)) => l0 == r0,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for TypeReference {}
+
 impl References for TypeReference {
     fn referenced_types<'a>(&'a self, names: &mut HashSet<&'a IdentifierReference>) {
         match self {
@@ -197,6 +210,14 @@ impl Display for MappingType {
     }
 }
 
+impl PartialEq for MappingType {
+    fn eq(&self, other: &Self) -> bool {
+        self.domain == other.domain && self.range == other.range
+    }
+}
+
+impl Eq for MappingType {}
+
 impl_has_source_span_for!(MappingType);
 
 impl References for MappingType {
@@ -13,7 +13,7 @@ use crate::model::{
     identifiers::{Identifier, IdentifierReference, QualifiedIdentifier},
     HasBody, HasName, HasSourceSpan, Span,
 };
-use crate::store::{InMemoryModuleCache, ModuleStore};
+use crate::store::ModuleStore;
 use sdml_errors::diagnostics::functions::{
     definition_not_found, imported_module_not_found, library_definition_not_allowed,
     module_is_incomplete, module_version_info_empty, module_version_mismatch,
@@ -239,7 +239,7 @@ impl Module {
     // Module :: Pseudo-Validate
     // --------------------------------------------------------------------------------------------
 
-    pub fn is_incomplete(&self, cache: &InMemoryModuleCache) -> bool {
+    pub fn is_incomplete(&self, cache: &impl ModuleStore) -> bool {
         if !self.is_library_module() {
             self.body.is_incomplete(self, cache)
         } else {
@@ -258,7 +258,7 @@ impl Module {
     ///
     pub fn validate(
         &self,
-        cache: &InMemoryModuleCache,
+        cache: &impl ModuleStore,
         loader: &impl ModuleLoader,
         check_constraints: bool,
     ) {
@@ -487,6 +487,26 @@ impl ModuleBody {
         Ok(())
     }
 
+    ///
+    /// Retain only the definitions for which `f` returns `true`.
+    ///
+    pub fn retain_definitions<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut Definition) -> bool,
+    {
+        self.definitions.retain_mut(f)
+    }
+
+    ///
+    /// Retain only the import statements for which `f` returns `true`.
+    ///
+    pub fn retain_imports<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut ImportStatement) -> bool,
+    {
+        self.imports.retain_mut(f)
+    }
+
     // --------------------------------------------------------------------------------------------
     // ModuleBody :: Helpers
     // --------------------------------------------------------------------------------------------
@@ -629,7 +649,7 @@ impl Validate for ImportStatement {
     ///
     /// - For each [`Import`]:
     ///   - If module import:
-    ///     1. Ensure it is in the cache
+    ///     1. Ensure it is in the cache, selecting the requested version if there is one
     ///     1. If the import has a version URI ensure the imported module has a matching one
     ///     1.
     ///     1.
@@ -650,7 +670,13 @@ impl Validate for ImportStatement {
                     module_ref
                         .name()
                         .validate(top, loader, Some(IdentifierCaseConvention::Module));
-                    if let Some(actual_module) = cache.get(module_ref.name()) {
+                    let actual_module = module_ref
+                        .version_uri()
+                        .and_then(|version_uri| {
+                            cache.get_version(module_ref.name(), version_uri.value())
+                        })
+                        .or_else(|| cache.get(module_ref.name()));
+                    if let Some(actual_module) = actual_module {
                         match (module_ref.version_uri(), actual_module.version_uri()) {
                             (None, _) => {}
                             (Some(expected), Some(actual)) => {
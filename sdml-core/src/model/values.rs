@@ -57,7 +57,7 @@ pub enum SimpleValue {
 }
 
 /// Corresponds to the grammar rule `binary`.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Binary(Vec<u8>);
 
@@ -147,6 +147,18 @@ impl From<LanguageString> for Value {
     }
 }
 
+impl From<&str> for Value {
+    fn from(v: &str) -> Self {
+        Self::from(LanguageString::from(v))
+    }
+}
+
+impl From<String> for Value {
+    fn from(v: String) -> Self {
+        Self::from(LanguageString::from(v))
+    }
+}
+
 impl From<f64> for Value {
     fn from(v: f64) -> Self {
         Self::Simple(SimpleValue::Double(v.into()))
@@ -233,6 +245,21 @@ impl From<SequenceOfValues> for Value {
 
 enum_display_impl!(Value => Simple, ValueConstructor, Reference, Mapping, List);
 
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Simple(l0), Self::Simple(r0)) => l0 == r0,
+            (Self::ValueConstructor(l0), Self::ValueConstructor(r0)) => l0 == r0,
+            (Self::Reference(l0), Self::Reference(r0)) => l0 == r0,
+            (Self::Mapping(l0), Self::Mapping(r0)) => l0 == r0,
+            (Self::List(l0), Self::List(r0)) => l0 == r0,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Value {}
+
 impl Value {
     is_as_variant!(Simple (SimpleValue) => is_simple, as_simple);
     is_as_variant!(ValueConstructor (ValueConstructor) => is_value_constructor, as_value_constructor);
@@ -347,6 +374,24 @@ impl_from_for_variant!(SimpleValue, IriReference, Url);
 
 impl_from_for_variant!(SimpleValue, Binary, Binary);
 
+impl PartialEq for SimpleValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Boolean(l0), Self::Boolean(r0)) => l0 == r0,
+            (Self::Double(l0), Self::Double(r0)) => l0 == r0,
+            (Self::Decimal(l0), Self::Decimal(r0)) => l0 == r0,
+            (Self::Integer(l0), Self::Integer(r0)) => l0 == r0,
+            (Self::Unsigned(l0), Self::Unsigned(r0)) => l0 == r0,
+            (Self::String(l0), Self::String(r0)) => l0 == r0,
+            (Self::IriReference(l0), Self::IriReference(r0)) => l0 == r0,
+            (Self::Binary(l0), Self::Binary(r0)) => l0 == r0,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for SimpleValue {}
+
 impl Display for SimpleValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -636,6 +681,14 @@ impl Display for MappingValue {
     }
 }
 
+impl PartialEq for MappingValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.domain == other.domain && self.range == other.range
+    }
+}
+
+impl Eq for MappingValue {}
+
 impl_has_source_span_for!(MappingValue);
 
 impl MappingValue {
@@ -693,6 +746,16 @@ impl FromIterator<SequenceMember> for SequenceOfValues {
     }
 }
 
+impl PartialEq for SequenceOfValues {
+    fn eq(&self, other: &Self) -> bool {
+        self.ordering == other.ordering
+            && self.uniqueness == other.uniqueness
+            && self.values == other.values
+    }
+}
+
+impl Eq for SequenceOfValues {}
+
 impl_has_source_span_for!(SequenceOfValues);
 
 impl_as_sequence!(pub SequenceOfValues => SequenceMember);
@@ -731,6 +794,20 @@ impl_from_for_variant!(SequenceMember, Reference, IdentifierReference);
 
 enum_display_impl!(SequenceMember => Simple, ValueConstructor, Reference, Mapping);
 
+impl PartialEq for SequenceMember {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Simple(l0), Self::Simple(r0)) => l0 == r0,
+            (Self::ValueConstructor(l0), Self::ValueConstructor(r0)) => l0 == r0,
+            (Self::Reference(l0), Self::Reference(r0)) => l0 == r0,
+            (Self::Mapping(l0), Self::Mapping(r0)) => l0 == r0,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for SequenceMember {}
+
 // ------------------------------------------------------------------------------------------------
 
 impl Display for ValueConstructor {
@@ -739,6 +816,14 @@ impl Display for ValueConstructor {
     }
 }
 
+impl PartialEq for ValueConstructor {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_name == other.type_name && self.value == other.value
+    }
+}
+
+impl Eq for ValueConstructor {}
+
 impl_has_source_span_for!(ValueConstructor);
 
 impl ValueConstructor {
@@ -24,20 +24,28 @@ walk_module_simple(
 );
 ```
 
+To change a module as it is walked implement `MutModuleVisitor` instead, and use the function
+`walk_module_mut`. Its callbacks receive mutable references and may change, replace, or remove
+the visited definitions, members, annotations, and variants. The [`passes`] module provides
+transformations built on this that may be chained together.
+
 */
 
 use crate::error::Error;
-use crate::model::annotations::{Annotation, AnnotationProperty, HasAnnotations};
+use crate::model::annotations::{
+    Annotation, AnnotationProperty, HasAnnotations, HasAnnotationsMut,
+};
 use crate::model::constraints::{ConstraintBody, ControlledLanguageString, FormalConstraint};
 use crate::model::definitions::{
-    DatatypeDef, Definition, EntityDef, EnumDef, EventDef, HasMembers, HasVariants, PropertyDef,
-    RdfDef, StructureDef, TypeVariant, UnionDef, ValueVariant,
+    DatatypeDef, Definition, EntityDef, EnumDef, EventDef, HasMembers, HasMembersMut, HasVariants,
+    HasVariantsMut, PropertyDef, RdfDef, StructureDef, TypeVariant, UnionDef, ValueVariant,
 };
 use crate::model::identifiers::{IdentifierReference, QualifiedIdentifier};
-use crate::model::members::{Member, MemberDef, MemberKind};
+use crate::model::members::{Member, MemberDef, MemberKind, TypeReference};
 use crate::model::modules::{Import, ModuleImport};
 use crate::model::modules::{ImportStatement, Module};
-use crate::model::{HasBody, HasOptionalBody};
+use crate::model::values::{MappingValue, SequenceMember, Value, ValueConstructor};
+use crate::model::{HasBody, HasNameReference, HasOptionalBody};
 use tracing::info;
 
 // ------------------------------------------------------------------------------------------------
@@ -601,6 +609,171 @@ pub trait SimpleModuleVisitor {
     }
 }
 
+///
+/// The result of those [`MutModuleVisitor`] callbacks that may remove the visited element.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MutVisitAction {
+    /// Keep the element and walk any nested structure.
+    #[default]
+    IncludeNested,
+    /// Keep the element but do not walk any nested structure, this also removes the
+    /// corresponding `_end` call.
+    NoNested,
+    /// Remove the element from its parent, no nested structure is walked.
+    Remove,
+}
+
+///
+/// The trait that captures the callbacks that [`walk_module_mut`] uses as it traverses, and
+/// possibly changes, the module.
+///
+/// Each callback receives a mutable reference to the visited element, it may change the element
+/// in place or replace it entirely by assigning to it; any nested structure walked is that of the
+/// changed, or replacement, element. Callbacks returning [`MutVisitAction`] may also remove the
+/// element.
+///
+/// The callback `identifier_reference` is called for each identifier reference in the walked
+/// elements: datatype base types, event sources, member types and property references, type
+/// variant names, and annotation property names and values. References within formal constraints
+/// are not visited.
+///
+pub trait MutModuleVisitor {
+    const INCLUDE_NESTED: Result<MutVisitAction, Error> = Ok(MutVisitAction::IncludeNested);
+    const NO_NESTED: Result<MutVisitAction, Error> = Ok(MutVisitAction::NoNested);
+    const REMOVE: Result<MutVisitAction, Error> = Ok(MutVisitAction::Remove);
+
+    // --------------------------------------------------------------------------------------------
+    // Module-level
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Called to denote the start of a `Module` instance, if this returns `false` nothing within
+    /// the module is walked.
+    ///
+    fn module_start(&mut self, _thing: &mut Module) -> Result<bool, Error> {
+        Ok(true)
+    }
+
+    ///
+    /// Called to denote the end of the `Module` instance.
+    ///
+    fn module_end(&mut self, _thing: &mut Module) -> Result<(), Error> {
+        Ok(())
+    }
+
+    ///
+    /// Called for each `ImportStatement` in the module, return `false` to remove the statement.
+    ///
+    fn import_statement(&mut self, _thing: &mut ImportStatement) -> Result<bool, Error> {
+        Ok(true)
+    }
+
+    // --------------------------------------------------------------------------------------------
+    // Annotations
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Called for each `Annotation`, property or constraint, on any element.
+    ///
+    fn annotation(&mut self, _thing: &mut Annotation) -> Result<MutVisitAction, Error> {
+        Self::INCLUDE_NESTED
+    }
+
+    // --------------------------------------------------------------------------------------------
+    // Definitions
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Called to denote the start of a `Definition` instance.
+    ///
+    fn definition_start(&mut self, _thing: &mut Definition) -> Result<MutVisitAction, Error> {
+        Self::INCLUDE_NESTED
+    }
+
+    ///
+    /// Called to denote the end of the `Definition` instance.
+    ///
+    fn definition_end(&mut self, _thing: &mut Definition) -> Result<(), Error> {
+        Ok(())
+    }
+
+    // --------------------------------------------------------------------------------------------
+    // Members
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Called to denote the start of a `Member` of an entity, event, or structure.
+    ///
+    fn member_start(&mut self, _thing: &mut Member) -> Result<MutVisitAction, Error> {
+        Self::INCLUDE_NESTED
+    }
+
+    ///
+    /// Called to denote the end of the `Member` instance.
+    ///
+    fn member_end(&mut self, _thing: &mut Member) -> Result<(), Error> {
+        Ok(())
+    }
+
+    ///
+    /// Called to denote the start of the identity `Member` of an entity, this cannot be removed.
+    ///
+    fn identity_member_start(&mut self, _thing: &mut Member) -> Result<bool, Error> {
+        Ok(true)
+    }
+
+    ///
+    /// Called to denote the end of the identity `Member` instance.
+    ///
+    fn identity_member_end(&mut self, _thing: &mut Member) -> Result<(), Error> {
+        Ok(())
+    }
+
+    // --------------------------------------------------------------------------------------------
+    // Variants
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Called to denote the start of a `ValueVariant` of an enumeration.
+    ///
+    fn value_variant_start(&mut self, _thing: &mut ValueVariant) -> Result<MutVisitAction, Error> {
+        Self::INCLUDE_NESTED
+    }
+
+    ///
+    /// Called to denote the end of the `ValueVariant` instance.
+    ///
+    fn value_variant_end(&mut self, _thing: &mut ValueVariant) -> Result<(), Error> {
+        Ok(())
+    }
+
+    ///
+    /// Called to denote the start of a `TypeVariant` of a union.
+    ///
+    fn type_variant_start(&mut self, _thing: &mut TypeVariant) -> Result<MutVisitAction, Error> {
+        Self::INCLUDE_NESTED
+    }
+
+    ///
+    /// Called to denote the end of the `TypeVariant` instance.
+    ///
+    fn type_variant_end(&mut self, _thing: &mut TypeVariant) -> Result<(), Error> {
+        Ok(())
+    }
+
+    // --------------------------------------------------------------------------------------------
+    // References
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Called for each `IdentifierReference` within the walked elements.
+    ///
+    fn identifier_reference(&mut self, _thing: &mut IdentifierReference) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Private Macros
 // ------------------------------------------------------------------------------------------------
@@ -680,7 +853,7 @@ pub fn walk_module_simple(
                         visit_annotations,
                         visit_members_and_variants,
                     )?,
-                    Definition::TypeClass(_) => todo!(),
+                    Definition::TypeClass(_) => {}
                     Definition::Union(def) => {
                         walk_union_def(def, walker, visit_annotations, visit_members_and_variants)?
                     }
@@ -694,6 +867,42 @@ pub fn walk_module_simple(
     Ok(())
 }
 
+///
+/// Walk the module `module` calling the relevant methods on `walker`, which may change the module.
+///
+pub fn walk_module_mut(
+    module: &mut Module,
+    walker: &mut impl MutModuleVisitor,
+    visit_annotations: bool,
+    visit_members_and_variants: bool,
+) -> Result<(), Error> {
+    if walker.module_start(module)? {
+        let body = module.body_mut();
+
+        retain_visited(
+            |f| body.retain_imports(f),
+            |import| walker.import_statement(import),
+        )?;
+
+        walk_annotations_mut(body, walker, visit_annotations)?;
+
+        retain_visited(
+            |f| body.retain_definitions(f),
+            |definition| {
+                walk_definition_mut(
+                    definition,
+                    walker,
+                    visit_annotations,
+                    visit_members_and_variants,
+                )
+            },
+        )?;
+
+        walker.module_end(module)?;
+    }
+    Ok(())
+}
+
 // ------------------------------------------------------------------------------------------------
 // Private Functions
 // ------------------------------------------------------------------------------------------------
@@ -924,3 +1133,291 @@ fn walk_type_variant(
 
     walker.type_variant_end(thing)
 }
+
+// ------------------------------------------------------------------------------------------------
+
+// Call `retain` with a predicate that visits each element, the first error stops any further
+// visits and is returned once `retain` completes.
+fn retain_visited<T, R, V>(retain: R, mut visit: V) -> Result<(), Error>
+where
+    R: FnOnce(&mut dyn FnMut(&mut T) -> bool),
+    V: FnMut(&mut T) -> Result<bool, Error>,
+{
+    let mut result = Ok(());
+    retain(&mut |thing| {
+        if result.is_err() {
+            return true;
+        }
+        match visit(thing) {
+            Ok(keep) => keep,
+            Err(e) => {
+                result = Err(e);
+                true
+            }
+        }
+    });
+    result
+}
+
+fn walk_annotations_mut(
+    thing: &mut impl HasAnnotationsMut,
+    walker: &mut impl MutModuleVisitor,
+    visit_annotations: bool,
+) -> Result<(), Error> {
+    if visit_annotations {
+        retain_visited(
+            |f| thing.retain_annotations(f),
+            |annotation| match walker.annotation(annotation)? {
+                MutVisitAction::IncludeNested => {
+                    if let Annotation::Property(property) = annotation {
+                        let mut name = property.name_reference().clone();
+                        walker.identifier_reference(&mut name)?;
+                        property.set_name_reference(name);
+                        let mut value = property.value().clone();
+                        walk_value_mut(&mut value, walker)?;
+                        property.set_value(value);
+                    }
+                    Ok(true)
+                }
+                MutVisitAction::NoNested => Ok(true),
+                MutVisitAction::Remove => Ok(false),
+            },
+        )?;
+    }
+    Ok(())
+}
+
+fn walk_optional_annotations_mut<B: HasAnnotationsMut>(
+    thing: &mut impl HasOptionalBody<Body = B>,
+    walker: &mut impl MutModuleVisitor,
+    visit_annotations: bool,
+) -> Result<(), Error> {
+    if let Some(body) = thing.body_mut() {
+        walk_annotations_mut(body, walker, visit_annotations)?;
+    }
+    Ok(())
+}
+
+fn walk_value_mut(thing: &mut Value, walker: &mut impl MutModuleVisitor) -> Result<(), Error> {
+    match thing {
+        Value::Simple(_) => {}
+        Value::ValueConstructor(v) => walk_value_constructor_mut(v, walker)?,
+        Value::Mapping(v) => walk_mapping_value_mut(v, walker)?,
+        Value::Reference(v) => walker.identifier_reference(v)?,
+        Value::List(vs) => {
+            for v in vs.iter_mut() {
+                match v {
+                    SequenceMember::Simple(_) => {}
+                    SequenceMember::ValueConstructor(v) => walk_value_constructor_mut(v, walker)?,
+                    SequenceMember::Reference(v) => walker.identifier_reference(v)?,
+                    SequenceMember::Mapping(v) => walk_mapping_value_mut(v, walker)?,
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn walk_value_constructor_mut(
+    thing: &mut ValueConstructor,
+    walker: &mut impl MutModuleVisitor,
+) -> Result<(), Error> {
+    let mut type_name = thing.type_name().clone();
+    walker.identifier_reference(&mut type_name)?;
+    thing.set_type_name(type_name);
+    Ok(())
+}
+
+fn walk_mapping_value_mut(
+    thing: &mut MappingValue,
+    walker: &mut impl MutModuleVisitor,
+) -> Result<(), Error> {
+    let mut range = thing.range().clone();
+    walk_value_mut(&mut range, walker)?;
+    thing.set_range(range);
+    Ok(())
+}
+
+fn walk_type_reference_mut(
+    thing: &mut TypeReference,
+    walker: &mut impl MutModuleVisitor,
+) -> Result<(), Error> {
+    match thing {
+        TypeReference::Unknown => {}
+        TypeReference::Type(v) => walker.identifier_reference(v)?,
+        TypeReference::MappingType(v) => {
+            let mut domain = v.domain().clone();
+            walk_type_reference_mut(&mut domain, walker)?;
+            v.set_domain(domain);
+            let mut range = v.range().clone();
+            walk_type_reference_mut(&mut range, walker)?;
+            v.set_range(range);
+        }
+    }
+    Ok(())
+}
+
+fn walk_definition_mut(
+    thing: &mut Definition,
+    walker: &mut impl MutModuleVisitor,
+    visit_annotations: bool,
+    visit_members_and_variants: bool,
+) -> Result<bool, Error> {
+    match walker.definition_start(thing)? {
+        MutVisitAction::Remove => return Ok(false),
+        MutVisitAction::NoNested => return Ok(true),
+        MutVisitAction::IncludeNested => {}
+    }
+    match thing {
+        Definition::Datatype(def) => {
+            let mut base_type = def.base_type().clone();
+            walker.identifier_reference(&mut base_type)?;
+            def.set_base_type(base_type);
+            walk_optional_annotations_mut(def, walker, visit_annotations)?;
+        }
+        Definition::Entity(def) => {
+            if let Some(body) = def.body_mut() {
+                let mut identity = body.identity().clone();
+                if walker.identity_member_start(&mut identity)? {
+                    walk_member_common_mut(&mut identity, walker, visit_annotations)?;
+                    walker.identity_member_end(&mut identity)?;
+                }
+                body.set_identity(identity);
+                walk_annotations_mut(body, walker, visit_annotations)?;
+                walk_members_mut(body, walker, visit_annotations, visit_members_and_variants)?;
+            }
+        }
+        Definition::Enum(def) => {
+            if let Some(body) = def.body_mut() {
+                walk_annotations_mut(body, walker, visit_annotations)?;
+                if visit_members_and_variants {
+                    retain_visited(
+                        |f| body.retain_variants(f),
+                        |variant| walk_value_variant_mut(variant, walker, visit_annotations),
+                    )?;
+                }
+            }
+        }
+        Definition::Event(def) => {
+            let mut event_source = def.event_source().clone();
+            walker.identifier_reference(&mut event_source)?;
+            def.set_event_source(event_source);
+            if let Some(body) = def.body_mut() {
+                walk_annotations_mut(body, walker, visit_annotations)?;
+                walk_members_mut(body, walker, visit_annotations, visit_members_and_variants)?;
+            }
+        }
+        Definition::Property(def) => {
+            let mut member_def = def.member_def().clone();
+            walk_member_def_mut(&mut member_def, walker, visit_annotations)?;
+            def.set_member_def(member_def);
+        }
+        Definition::Rdf(def) => walk_annotations_mut(def.body_mut(), walker, visit_annotations)?,
+        Definition::Structure(def) => {
+            if let Some(body) = def.body_mut() {
+                walk_annotations_mut(body, walker, visit_annotations)?;
+                walk_members_mut(body, walker, visit_annotations, visit_members_and_variants)?;
+            }
+        }
+        Definition::TypeClass(_) => {}
+        Definition::Union(def) => {
+            if let Some(body) = def.body_mut() {
+                walk_annotations_mut(body, walker, visit_annotations)?;
+                if visit_members_and_variants {
+                    retain_visited(
+                        |f| body.retain_variants(f),
+                        |variant| walk_type_variant_mut(variant, walker, visit_annotations),
+                    )?;
+                }
+            }
+        }
+    }
+    walker.definition_end(thing)?;
+    Ok(true)
+}
+
+fn walk_members_mut(
+    thing: &mut impl HasMembersMut,
+    walker: &mut impl MutModuleVisitor,
+    visit_annotations: bool,
+    visit_members_and_variants: bool,
+) -> Result<(), Error> {
+    if visit_members_and_variants {
+        retain_visited(
+            |f| thing.retain_members(f),
+            |member| match walker.member_start(member)? {
+                MutVisitAction::IncludeNested => {
+                    walk_member_common_mut(member, walker, visit_annotations)?;
+                    walker.member_end(member)?;
+                    Ok(true)
+                }
+                MutVisitAction::NoNested => Ok(true),
+                MutVisitAction::Remove => Ok(false),
+            },
+        )?;
+    }
+    Ok(())
+}
+
+fn walk_member_common_mut(
+    thing: &mut Member,
+    walker: &mut impl MutModuleVisitor,
+    visit_annotations: bool,
+) -> Result<(), Error> {
+    match thing.kind_mut() {
+        MemberKind::Reference(v) => walker.identifier_reference(v),
+        MemberKind::Definition(v) => walk_member_def_mut(v, walker, visit_annotations),
+    }
+}
+
+fn walk_member_def_mut(
+    thing: &mut MemberDef,
+    walker: &mut impl MutModuleVisitor,
+    visit_annotations: bool,
+) -> Result<(), Error> {
+    let mut target_type = thing.target_type().clone();
+    walk_type_reference_mut(&mut target_type, walker)?;
+    thing.set_target_type(target_type);
+    walk_optional_annotations_mut(thing, walker, visit_annotations)
+}
+
+fn walk_value_variant_mut(
+    thing: &mut ValueVariant,
+    walker: &mut impl MutModuleVisitor,
+    visit_annotations: bool,
+) -> Result<bool, Error> {
+    match walker.value_variant_start(thing)? {
+        MutVisitAction::IncludeNested => {
+            walk_optional_annotations_mut(thing, walker, visit_annotations)?;
+            walker.value_variant_end(thing)?;
+            Ok(true)
+        }
+        MutVisitAction::NoNested => Ok(true),
+        MutVisitAction::Remove => Ok(false),
+    }
+}
+
+fn walk_type_variant_mut(
+    thing: &mut TypeVariant,
+    walker: &mut impl MutModuleVisitor,
+    visit_annotations: bool,
+) -> Result<bool, Error> {
+    match walker.type_variant_start(thing)? {
+        MutVisitAction::IncludeNested => {
+            let mut name_reference = thing.name_reference().clone();
+            walker.identifier_reference(&mut name_reference)?;
+            thing.set_name_reference(name_reference);
+            walk_optional_annotations_mut(thing, walker, visit_annotations)?;
+            walker.type_variant_end(thing)?;
+            Ok(true)
+        }
+        MutVisitAction::NoNested => Ok(true),
+        MutVisitAction::Remove => Ok(false),
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
+
+pub mod passes;
@@ -0,0 +1,404 @@
+/*!
+Provides transformation *passes*, each of which changes a module in place using a
+[`MutModuleVisitor`], and a [`PassChain`] to run a sequence of passes.
+
+# Example
+
+```rust
+use sdml_core::model::identifiers::Identifier;
+use sdml_core::model::modules::Module;
+use sdml_core::model::walk::passes::{
+    ModulePass, PassChain, QualifyReferences, StripAnnotations,
+};
+
+let mut module = Module::empty(Identifier::new_unchecked("example"));
+
+let mut passes = PassChain::default()
+    .with_pass(StripAnnotations::default())
+    .with_pass(QualifyReferences::default());
+
+passes.apply(&mut module).unwrap();
+```
+*/
+
+use crate::error::Error;
+use crate::model::annotations::{Annotation, AnnotationOnlyBody, HasAnnotations};
+use crate::model::definitions::{Definition, TypeVariant, ValueVariant};
+use crate::model::identifiers::{Identifier, IdentifierReference, QualifiedIdentifier};
+use crate::model::members::{Member, MemberKind};
+use crate::model::modules::{Import, ImportStatement, Module};
+use crate::model::walk::{walk_module_mut, MutModuleVisitor, MutVisitAction};
+use crate::model::{HasBody, HasName, HasOptionalBody};
+use crate::store::ModuleStore;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Debug;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A transformation that changes a module in place.
+///
+pub trait ModulePass: Debug {
+    ///
+    /// A short name for this pass, used in messages.
+    ///
+    fn name(&self) -> &'static str;
+
+    ///
+    /// Apply this pass to `module`.
+    ///
+    fn apply(&mut self, module: &mut Module) -> Result<(), Error>;
+}
+
+///
+/// A sequence of passes, applied in the order they were added. The chain is itself a pass so that
+/// chains may be nested.
+///
+#[derive(Debug, Default)]
+pub struct PassChain<'a> {
+    passes: Vec<Box<dyn ModulePass + 'a>>,
+}
+
+///
+/// A pass that removes annotations from the module and all of its definitions, members, and
+/// variants. By default both annotation properties and constraints are removed.
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StripAnnotations {
+    keep_constraints: bool,
+}
+
+///
+/// A pass that replaces every unqualified identifier reference with a qualified one where the
+/// identifier names a definition in the module itself, or a member imported into it.
+///
+#[derive(Clone, Debug, Default)]
+pub struct QualifyReferences {
+    module_name: Option<Identifier>,
+    local_names: HashSet<Identifier>,
+    member_imports: HashMap<Identifier, Identifier>,
+}
+
+///
+/// A pass that copies definitions referenced from, but defined in, other non-library modules in
+/// `cache` into the module. References to copied definitions become unqualified, member imports
+/// of copied definitions are removed, and module imports are added for any modules referenced by
+/// the copied definitions.
+///
+/// Only definitions referenced directly are copied, definitions they in turn reference remain
+/// qualified references to their original module. A definition is not copied if the module
+/// already has a definition with the same name.
+///
+#[derive(Debug)]
+pub struct InlineImports<'a, S: ModuleStore> {
+    cache: &'a S,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct CollectReferences {
+    references: BTreeSet<(Identifier, Identifier)>,
+}
+
+#[derive(Debug)]
+struct UnqualifyReferences<'a> {
+    inlined: &'a BTreeSet<(Identifier, Identifier)>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl ModulePass for PassChain<'_> {
+    fn name(&self) -> &'static str {
+        "chain"
+    }
+
+    fn apply(&mut self, module: &mut Module) -> Result<(), Error> {
+        for pass in self.passes.iter_mut() {
+            pass.apply(module)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> PassChain<'a> {
+    ///
+    /// Add `pass` to the end of this chain.
+    ///
+    pub fn with_pass<P: ModulePass + 'a>(self, pass: P) -> Self {
+        let mut self_mut = self;
+        self_mut.push(pass);
+        self_mut
+    }
+
+    ///
+    /// Add `pass` to the end of this chain.
+    ///
+    pub fn push<P: ModulePass + 'a>(&mut self, pass: P) {
+        self.passes.push(Box::new(pass))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.passes.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.passes.len()
+    }
+
+    pub fn pass_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.passes.iter().map(|pass| pass.name())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl ModulePass for StripAnnotations {
+    fn name(&self) -> &'static str {
+        "strip-annotations"
+    }
+
+    fn apply(&mut self, module: &mut Module) -> Result<(), Error> {
+        walk_module_mut(module, self, true, true)
+    }
+}
+
+impl MutModuleVisitor for StripAnnotations {
+    fn annotation(&mut self, thing: &mut Annotation) -> Result<MutVisitAction, Error> {
+        if self.keep_constraints && thing.is_constraint() {
+            Self::NO_NESTED
+        } else {
+            Self::REMOVE
+        }
+    }
+
+    fn definition_end(&mut self, thing: &mut Definition) -> Result<(), Error> {
+        if let Definition::Datatype(thing) = thing {
+            unset_empty_body(thing);
+        }
+        Ok(())
+    }
+
+    fn member_end(&mut self, thing: &mut Member) -> Result<(), Error> {
+        if let MemberKind::Definition(thing) = thing.kind_mut() {
+            unset_empty_body(thing);
+        }
+        Ok(())
+    }
+
+    fn value_variant_end(&mut self, thing: &mut ValueVariant) -> Result<(), Error> {
+        unset_empty_body(thing);
+        Ok(())
+    }
+
+    fn type_variant_end(&mut self, thing: &mut TypeVariant) -> Result<(), Error> {
+        unset_empty_body(thing);
+        Ok(())
+    }
+}
+
+impl StripAnnotations {
+    ///
+    /// If `keep_constraints` is `true` only annotation properties are removed.
+    ///
+    pub fn with_keep_constraints(self, keep_constraints: bool) -> Self {
+        Self { keep_constraints }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl ModulePass for QualifyReferences {
+    fn name(&self) -> &'static str {
+        "qualify-references"
+    }
+
+    fn apply(&mut self, module: &mut Module) -> Result<(), Error> {
+        walk_module_mut(module, self, true, true)
+    }
+}
+
+impl MutModuleVisitor for QualifyReferences {
+    fn module_start(&mut self, thing: &mut Module) -> Result<bool, Error> {
+        self.module_name = Some(thing.name().clone());
+        self.local_names = thing.defined_names().into_iter().cloned().collect();
+        self.member_imports = thing
+            .body()
+            .imports()
+            .flat_map(|statement| statement.imports())
+            .filter_map(|import| match import {
+                Import::Member(v) => Some((v.member().clone(), v.module().clone())),
+                Import::Module(_) => None,
+            })
+            .collect();
+        Ok(true)
+    }
+
+    fn identifier_reference(&mut self, thing: &mut IdentifierReference) -> Result<(), Error> {
+        if let IdentifierReference::Identifier(name) = thing {
+            let module = if self.local_names.contains(name) {
+                self.module_name.as_ref()
+            } else {
+                self.member_imports.get(name)
+            };
+            if let Some(module) = module {
+                *thing = QualifiedIdentifier::new(module.clone(), name.clone()).into();
+            }
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<S: ModuleStore + Debug> ModulePass for InlineImports<'_, S> {
+    fn name(&self) -> &'static str {
+        "inline-imports"
+    }
+
+    fn apply(&mut self, module: &mut Module) -> Result<(), Error> {
+        let module_name = module.name().clone();
+        let local_names: HashSet<Identifier> =
+            module.defined_names().into_iter().cloned().collect();
+
+        let mut collector = CollectReferences::default();
+        walk_module_mut(module, &mut collector, true, true)?;
+        for import in module.body().imports().flat_map(|s| s.imports()) {
+            if let Import::Member(v) = import {
+                let _ = collector
+                    .references
+                    .insert((v.module().clone(), v.member().clone()));
+            }
+        }
+
+        // Select the definitions to copy, grouped by their source module.
+        let mut inlined: BTreeSet<(Identifier, Identifier)> = Default::default();
+        let mut copied_names: HashSet<Identifier> = Default::default();
+        for (from_module, member) in collector.references {
+            if from_module != module_name
+                && !Identifier::is_library_module_name(from_module.as_ref())
+                && !local_names.contains(&member)
+                && !copied_names.contains(&member)
+                && self
+                    .cache
+                    .get(&from_module)
+                    .is_some_and(|m| m.resolve_local(&member).is_some())
+            {
+                let _ = copied_names.insert(member.clone());
+                let _ = inlined.insert((from_module, member));
+            }
+        }
+        if inlined.is_empty() {
+            return Ok(());
+        }
+
+        // Definitions are qualified against their own module before they are copied.
+        let source_modules: BTreeSet<&Identifier> = inlined.iter().map(|(m, _)| m).collect();
+        for from_module in source_modules {
+            let mut source = self.cache.get(from_module).unwrap().clone();
+            QualifyReferences::default().apply(&mut source)?;
+            let definitions: Vec<Definition> = source
+                .body()
+                .definitions()
+                .filter(|def| inlined.contains(&(from_module.clone(), def.name().clone())))
+                .cloned()
+                .collect();
+            module.body_mut().extend_definitions(definitions)?;
+        }
+
+        walk_module_mut(
+            module,
+            &mut UnqualifyReferences { inlined: &inlined },
+            true,
+            true,
+        )?;
+
+        // Import any module now referenced but not imported.
+        let mut collector = CollectReferences::default();
+        walk_module_mut(module, &mut collector, true, true)?;
+        let body = module.body_mut();
+        let imported: HashSet<Identifier> = body.imported_modules().into_iter().cloned().collect();
+        let required: BTreeSet<Identifier> = collector
+            .references
+            .into_iter()
+            .map(|(m, _)| m)
+            .filter(|m| m != &module_name && !imported.contains(m))
+            .collect();
+        for required in required {
+            body.add_to_imports(ImportStatement::new_module(required));
+        }
+        Ok(())
+    }
+}
+
+impl<'a, S: ModuleStore> InlineImports<'a, S> {
+    ///
+    /// Construct a new pass that copies definitions from modules in `cache`.
+    ///
+    pub fn new(cache: &'a S) -> Self {
+        Self { cache }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl MutModuleVisitor for CollectReferences {
+    fn identifier_reference(&mut self, thing: &mut IdentifierReference) -> Result<(), Error> {
+        if let IdentifierReference::QualifiedIdentifier(v) = thing {
+            let _ = self
+                .references
+                .insert((v.module().clone(), v.member().clone()));
+        }
+        Ok(())
+    }
+}
+
+impl MutModuleVisitor for UnqualifyReferences<'_> {
+    fn import_statement(&mut self, thing: &mut ImportStatement) -> Result<bool, Error> {
+        let remaining: Vec<Import> = thing
+            .imports()
+            .filter(|import| match import {
+                Import::Member(v) => !self
+                    .inlined
+                    .contains(&(v.module().clone(), v.member().clone())),
+                Import::Module(_) => true,
+            })
+            .cloned()
+            .collect();
+        if remaining.len() != thing.imports_len() {
+            *thing = ImportStatement::new(remaining);
+        }
+        Ok(thing.has_imports())
+    }
+
+    fn identifier_reference(&mut self, thing: &mut IdentifierReference) -> Result<(), Error> {
+        if let IdentifierReference::QualifiedIdentifier(v) = thing {
+            if self
+                .inlined
+                .contains(&(v.module().clone(), v.member().clone()))
+            {
+                *thing = v.member().clone().into();
+            }
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn unset_empty_body<T>(thing: &mut T)
+where
+    T: HasOptionalBody<Body = AnnotationOnlyBody>,
+{
+    if thing.body().is_some_and(|body| !body.has_annotations()) {
+        thing.unset_body();
+    }
+}
@@ -0,0 +1,827 @@
+/*!
+Provides a small path and filter query language over the modules in a [`ModuleStore`].
+
+A query is a sequence of *steps*, separated by `/`, each of which selects a kind of model element
+and may be followed by any number of filters in square brackets. The first step selects from all
+elements of the loaded modules, each following step selects from the children of the elements
+selected by the step before.
+
+```text
+query     ::= step ( "/" step )*
+step      ::= selector filter*
+selector  ::= "*" | "module" | "definition" | "datatype" | "entity" | "enum" | "event"
+            | "property" | "rdf" | "structure" | "class" | "union"
+            | "member" | "variant" | "annotation" | "constraint"
+filter    ::= "[" "!"? condition "]"
+condition ::= "@" name ( operator value )?
+            | field operator value
+field     ::= "name" | "module" | "kind" | "type" | "cardinality" | "value"
+operator  ::= "=" | "!=" | "~"
+```
+
+The condition `@name` is true if the element has an annotation property `name`, and optionally
+with a matching value. The operator `~` tests whether a field contains the value. Values are
+either a single word or a double-quoted string, and a quoted model value matches without its
+quotes. For example:
+
+* `entity[!@skos:definition]` :: entities with no `skos:definition` annotation.
+* `structure/member[type = xsd:date]` :: members of structures with the type `xsd:date`.
+* `definition[name ~ Customer]` :: definitions with `Customer` in their name.
+* `annotation[name = skos:prefLabel][value ~ Rental]` :: `skos:prefLabel` annotations on any
+  element whose value contains `Rental`.
+
+# Example
+
+```rust
+use sdml_core::model::identifiers::Identifier;
+use sdml_core::model::modules::Module;
+use sdml_core::query::Query;
+use sdml_core::store::InMemoryModuleCache;
+use std::str::FromStr;
+
+let query = Query::from_str("entity[!@skos:definition]").unwrap();
+
+let store = InMemoryModuleCache::default();
+let module = Module::empty(Identifier::new_unchecked("example"));
+let rows = query.execute([&module], &store).unwrap();
+
+assert!(rows.is_empty());
+assert!(Query::from_str("entity[name").is_err());
+```
+
+[`ModuleStore`]: crate::store::ModuleStore
+*/
+
+use crate::error::Error;
+use crate::model::annotations::{Annotation, AnnotationProperty};
+use crate::model::constraints::{ControlledLanguageString, FormalConstraint};
+use crate::model::definitions::{Definition, DefinitionKind, TypeVariant, ValueVariant};
+use crate::model::identifiers::Identifier;
+use crate::model::members::{Member, MemberKind};
+use crate::model::modules::Module;
+use crate::model::walk::{walk_module_simple, SimpleModuleVisitor};
+use crate::model::{HasName, HasNameReference};
+use crate::store::ModuleStore;
+use std::fmt::Display;
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A parsed query, see the module documentation for the syntax.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Query {
+    source: String,
+    steps: Vec<Step>,
+}
+
+///
+/// The kinds of model element a query step may select.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Selector {
+    Any,
+    Module,
+    Definition(Option<DefinitionKind>),
+    Member,
+    Variant,
+    Annotation,
+    Constraint,
+}
+
+///
+/// The fields of a model element that a filter may test.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Field {
+    Name,
+    Module,
+    Kind,
+    Type,
+    Cardinality,
+    Value,
+}
+
+///
+/// A single model element selected by a query. The `definition` and `member` are the enclosing
+/// definition and member, or variant, if any; annotations and constraints have the enclosing
+/// element's names and their own `name`.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct QueryRow {
+    kind: String,
+    module: Identifier,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    definition: Option<Identifier>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    member: Option<Identifier>,
+    name: String,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    target_type: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    cardinality: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    value: Option<String>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Step {
+    selector: Selector,
+    filters: Vec<Filter>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Filter {
+    negated: bool,
+    condition: Condition,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Condition {
+    HasAnnotation {
+        name: String,
+        test: Option<(Operator, String)>,
+    },
+    Field {
+        field: Field,
+        operator: Operator,
+        value: String,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operator {
+    Equals,
+    NotEquals,
+    Contains,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum NodeKind {
+    Module,
+    Definition(DefinitionKind),
+    Member,
+    Variant,
+    Annotation,
+    Constraint,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    kind: NodeKind,
+    row: QueryRow,
+    children: Vec<usize>,
+}
+
+// Builds a tree of nodes, in document order, from one or more modules.
+#[derive(Debug)]
+struct NodeCollector<'a, S: ModuleStore> {
+    store: &'a S,
+    nodes: Vec<Node>,
+    stack: Vec<usize>,
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    chars: Peekable<Chars<'a>>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Query {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl FromStr for Query {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Parser::new(s).query()
+    }
+}
+
+impl Query {
+    ///
+    /// Execute this query over `modules`; `store` is used to resolve the types of members that
+    /// reference a property definition.
+    ///
+    pub fn execute<'a, I, S>(&self, modules: I, store: &S) -> Result<Vec<QueryRow>, Error>
+    where
+        I: IntoIterator<Item = &'a Module>,
+        S: ModuleStore,
+    {
+        let mut collector = NodeCollector::new(store);
+        for module in modules {
+            walk_module_simple(module, &mut collector, true, true)?;
+        }
+        let nodes = collector.nodes;
+
+        let mut steps = self.steps.iter();
+        let mut selected: Vec<usize> = match steps.next() {
+            Some(step) => (0..nodes.len())
+                .filter(|i| step.matches(&nodes, *i))
+                .collect(),
+            None => Vec::default(),
+        };
+        for step in steps {
+            selected = selected
+                .into_iter()
+                .flat_map(|i| nodes[i].children.iter().copied())
+                .filter(|i| step.matches(&nodes, *i))
+                .collect();
+        }
+
+        Ok(selected.into_iter().map(|i| nodes[i].row.clone()).collect())
+    }
+
+    pub fn selectors(&self) -> impl Iterator<Item = Selector> + '_ {
+        self.steps.iter().map(|step| step.selector)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Selector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Any => "*",
+                Self::Module => "module",
+                Self::Definition(None) => "definition",
+                Self::Definition(Some(kind)) => kind.keyword(),
+                Self::Member => "member",
+                Self::Variant => "variant",
+                Self::Annotation => "annotation",
+                Self::Constraint => "constraint",
+            }
+        )
+    }
+}
+
+impl FromStr for Selector {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "*" => Ok(Self::Any),
+            "module" => Ok(Self::Module),
+            "definition" => Ok(Self::Definition(None)),
+            "member" => Ok(Self::Member),
+            "variant" => Ok(Self::Variant),
+            "annotation" => Ok(Self::Annotation),
+            "constraint" => Ok(Self::Constraint),
+            _ => DefinitionKind::from_keyword(s)
+                .map(|kind| Self::Definition(Some(kind)))
+                .ok_or(()),
+        }
+    }
+}
+
+impl Selector {
+    fn matches(&self, kind: NodeKind) -> bool {
+        match (self, kind) {
+            (Self::Any, _)
+            | (Self::Module, NodeKind::Module)
+            | (Self::Definition(None), NodeKind::Definition(_))
+            | (Self::Member, NodeKind::Member)
+            | (Self::Variant, NodeKind::Variant)
+            | (Self::Annotation, NodeKind::Annotation)
+            | (Self::Constraint, NodeKind::Constraint) => true,
+            (Self::Definition(Some(expected)), NodeKind::Definition(actual)) => *expected == actual,
+            _ => false,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Name => "name",
+                Self::Module => "module",
+                Self::Kind => "kind",
+                Self::Type => "type",
+                Self::Cardinality => "cardinality",
+                Self::Value => "value",
+            }
+        )
+    }
+}
+
+impl FromStr for Field {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "name" => Ok(Self::Name),
+            "module" => Ok(Self::Module),
+            "kind" => Ok(Self::Kind),
+            "type" => Ok(Self::Type),
+            "cardinality" => Ok(Self::Cardinality),
+            "value" => Ok(Self::Value),
+            _ => Err(()),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl QueryRow {
+    fn new(kind: &str, module: Identifier, name: String) -> Self {
+        Self {
+            kind: kind.to_string(),
+            module,
+            definition: None,
+            member: None,
+            name,
+            target_type: None,
+            cardinality: None,
+            value: None,
+        }
+    }
+
+    ///
+    /// The kind of element, either `module`, a definition keyword, `member`, `variant`,
+    /// `annotation`, or `constraint`.
+    ///
+    pub fn kind(&self) -> &String {
+        &self.kind
+    }
+
+    pub fn module(&self) -> &Identifier {
+        &self.module
+    }
+
+    pub fn definition(&self) -> Option<&Identifier> {
+        self.definition.as_ref()
+    }
+
+    pub fn member(&self) -> Option<&Identifier> {
+        self.member.as_ref()
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn target_type(&self) -> Option<&String> {
+        self.target_type.as_ref()
+    }
+
+    pub fn cardinality(&self) -> Option<&String> {
+        self.cardinality.as_ref()
+    }
+
+    pub fn value(&self) -> Option<&String> {
+        self.value.as_ref()
+    }
+
+    ///
+    /// The path to this element, `module:Definition.member`, with `@name` for an annotation or
+    /// constraint.
+    ///
+    pub fn path(&self) -> String {
+        let mut path = self.module.to_string();
+        if let Some(definition) = &self.definition {
+            path.push(':');
+            path.push_str(definition.as_ref());
+        }
+        if let Some(member) = &self.member {
+            path.push('.');
+            path.push_str(member.as_ref());
+        }
+        if self.kind == "annotation" || self.kind == "constraint" {
+            path.push('@');
+            path.push_str(&self.name);
+        }
+        path
+    }
+
+    fn field(&self, field: Field) -> Option<&str> {
+        match field {
+            Field::Name => Some(self.name.as_str()),
+            Field::Module => Some(self.module.as_ref()),
+            Field::Kind => Some(self.kind.as_str()),
+            Field::Type => self.target_type.as_deref(),
+            Field::Cardinality => self.cardinality.as_deref(),
+            Field::Value => self.value.as_deref(),
+        }
+    }
+
+    fn child(&self, kind: &str, name: String) -> Self {
+        Self {
+            kind: kind.to_string(),
+            module: self.module.clone(),
+            definition: self.definition.clone(),
+            member: self.member.clone(),
+            name,
+            target_type: None,
+            cardinality: None,
+            value: None,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Step {
+    fn matches(&self, nodes: &[Node], index: usize) -> bool {
+        let node = &nodes[index];
+        self.selector.matches(node.kind)
+            && self
+                .filters
+                .iter()
+                .all(|filter| filter.matches(nodes, node) != filter.negated)
+    }
+}
+
+impl Filter {
+    fn matches(&self, nodes: &[Node], node: &Node) -> bool {
+        match &self.condition {
+            Condition::HasAnnotation { name, test } => node
+                .children
+                .iter()
+                .map(|i| &nodes[*i])
+                .filter(|child| child.kind == NodeKind::Annotation && &child.row.name == name)
+                .any(|child| match test {
+                    None => true,
+                    Some((operator, value)) => operator.matches(child.row.value.as_deref(), value),
+                }),
+            Condition::Field {
+                field,
+                operator,
+                value,
+            } => operator.matches(node.row.field(*field), value),
+        }
+    }
+}
+
+impl Operator {
+    fn matches(&self, actual: Option<&str>, expected: &str) -> bool {
+        match (self, actual) {
+            (Self::Equals, Some(actual)) => actual == expected || unquoted(actual) == expected,
+            (Self::NotEquals, Some(actual)) => actual != expected && unquoted(actual) != expected,
+            (Self::Contains, Some(actual)) => actual.contains(expected),
+            (Self::NotEquals, None) => true,
+            (_, None) => false,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, S: ModuleStore> NodeCollector<'a, S> {
+    fn new(store: &'a S) -> Self {
+        Self {
+            store,
+            nodes: Default::default(),
+            stack: Default::default(),
+        }
+    }
+
+    fn current(&self) -> &QueryRow {
+        &self.nodes[*self.stack.last().expect("no current node")].row
+    }
+
+    fn add(&mut self, kind: NodeKind, row: QueryRow) -> usize {
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            kind,
+            row,
+            children: Default::default(),
+        });
+        if let Some(parent) = self.stack.last() {
+            self.nodes[*parent].children.push(index);
+        }
+        index
+    }
+
+    fn push(&mut self, kind: NodeKind, row: QueryRow) {
+        let index = self.add(kind, row);
+        self.stack.push(index);
+    }
+
+    fn pop(&mut self) -> Result<(), Error> {
+        let _ = self.stack.pop();
+        Ok(())
+    }
+
+    fn add_member(&mut self, thing: &Member) -> Result<bool, Error> {
+        let mut row = self.current().child("member", thing.name().to_string());
+        row.member = Some(thing.name().without_span());
+        match thing.kind() {
+            MemberKind::Definition(def) => {
+                row.target_type = Some(def.target_type().to_string());
+                row.cardinality = Some(def.target_cardinality().to_string());
+            }
+            MemberKind::Reference(_) => {
+                if let Some(module) = self.store.get(&row.module) {
+                    row.target_type = thing
+                        .resolve_target_type(module, self.store)
+                        .map(|t| t.to_string());
+                    row.cardinality = thing
+                        .resolve_target_cardinality(module, self.store)
+                        .map(|c| c.to_string());
+                }
+            }
+        }
+        self.push(NodeKind::Member, row);
+        Ok(true)
+    }
+
+    fn add_constraint(&mut self, value: Option<String>) -> Result<(), Error> {
+        if let Some(index) = self.stack.last() {
+            if let Some(last) = self.nodes[*index].children.last().copied() {
+                if self.nodes[last].kind == NodeKind::Constraint {
+                    self.nodes[last].row.value = value;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<S: ModuleStore> SimpleModuleVisitor for NodeCollector<'_, S> {
+    fn module_start(&mut self, thing: &Module) -> Result<bool, Error> {
+        let mut row = QueryRow::new(
+            "module",
+            thing.name().without_span(),
+            thing.name().to_string(),
+        );
+        row.value = thing.base_uri().map(|uri| uri.value().to_string());
+        self.push(NodeKind::Module, row);
+        Ok(true)
+    }
+
+    fn module_end(&mut self, _: &Module) -> Result<(), Error> {
+        self.pop()
+    }
+
+    fn annotation_start(&mut self, thing: &Annotation) -> Result<bool, Error> {
+        if let Annotation::Constraint(constraint) = thing {
+            let row = self
+                .current()
+                .child("constraint", constraint.name().to_string());
+            let _ = self.add(NodeKind::Constraint, row);
+        }
+        Ok(true)
+    }
+
+    fn annotation_property(&mut self, thing: &AnnotationProperty) -> Result<(), Error> {
+        let mut row = self
+            .current()
+            .child("annotation", thing.name_reference().to_string());
+        row.value = Some(thing.value().to_string());
+        let _ = self.add(NodeKind::Annotation, row);
+        Ok(())
+    }
+
+    fn informal_constraint(&mut self, thing: &ControlledLanguageString) -> Result<(), Error> {
+        self.add_constraint(Some(format!(
+            "\"{}\"",
+            thing.value().trim().trim_matches('"')
+        )))
+    }
+
+    fn formal_constraint(&mut self, _: &FormalConstraint) -> Result<(), Error> {
+        self.add_constraint(None)
+    }
+
+    fn definition_start(&mut self, thing: &Definition) -> Result<bool, Error> {
+        let kind = thing.kind();
+        let mut row = self
+            .current()
+            .child(kind.keyword(), thing.name().to_string());
+        row.definition = Some(thing.name().without_span());
+        match thing {
+            Definition::Datatype(def) => row.target_type = Some(def.base_type().to_string()),
+            Definition::Event(def) => row.target_type = Some(def.event_source().to_string()),
+            Definition::Property(def) => {
+                row.target_type = Some(def.member_def().target_type().to_string());
+                row.cardinality = Some(def.member_def().target_cardinality().to_string());
+            }
+            _ => {}
+        }
+        if let Definition::TypeClass(_) = thing {
+            // Type classes are not walked, so only the definition itself is included.
+            let _ = self.add(NodeKind::Definition(kind), row);
+            Ok(false)
+        } else {
+            self.push(NodeKind::Definition(kind), row);
+            Ok(true)
+        }
+    }
+
+    fn definition_end(&mut self, _: &Definition) -> Result<(), Error> {
+        self.pop()
+    }
+
+    fn member_start(&mut self, thing: &Member) -> Result<bool, Error> {
+        self.add_member(thing)
+    }
+
+    fn member_end(&mut self, _: &Member) -> Result<(), Error> {
+        self.pop()
+    }
+
+    fn identity_member_start(&mut self, thing: &Member) -> Result<bool, Error> {
+        self.add_member(thing)
+    }
+
+    fn identity_member_end(&mut self, _: &Member) -> Result<(), Error> {
+        self.pop()
+    }
+
+    fn value_variant_start(&mut self, thing: &ValueVariant) -> Result<bool, Error> {
+        let mut row = self.current().child("variant", thing.name().to_string());
+        row.member = Some(thing.name().without_span());
+        self.push(NodeKind::Variant, row);
+        Ok(true)
+    }
+
+    fn value_variant_end(&mut self, _: &ValueVariant) -> Result<(), Error> {
+        self.pop()
+    }
+
+    fn type_variant_start(&mut self, thing: &TypeVariant) -> Result<bool, Error> {
+        let mut row = self.current().child("variant", thing.name().to_string());
+        row.member = Some(thing.name().without_span());
+        row.target_type = Some(thing.name_reference().to_string());
+        self.push(NodeKind::Variant, row);
+        Ok(true)
+    }
+
+    fn type_variant_end(&mut self, _: &TypeVariant) -> Result<(), Error> {
+        self.pop()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            chars: source.chars().peekable(),
+        }
+    }
+
+    fn query(mut self) -> Result<Query, Error> {
+        let mut steps = vec![self.step()?];
+        loop {
+            self.skip_whitespace();
+            match self.chars.next() {
+                None => break,
+                Some('/') => steps.push(self.step()?),
+                Some(c) => return Err(self.error(format!("expecting `/`, not `{c}`"))),
+            }
+        }
+        Ok(Query {
+            source: self.source.to_string(),
+            steps,
+        })
+    }
+
+    fn step(&mut self) -> Result<Step, Error> {
+        let word = self.word()?;
+        let selector = Selector::from_str(&word)
+            .map_err(|_| self.error(format!("unknown selector `{word}`")))?;
+        let mut filters = Vec::default();
+        loop {
+            self.skip_whitespace();
+            if self.chars.peek() == Some(&'[') {
+                let _ = self.chars.next();
+                filters.push(self.filter()?);
+            } else {
+                break;
+            }
+        }
+        Ok(Step { selector, filters })
+    }
+
+    fn filter(&mut self) -> Result<Filter, Error> {
+        self.skip_whitespace();
+        let negated = self.chars.next_if_eq(&'!').is_some();
+        self.skip_whitespace();
+        let condition = if self.chars.next_if_eq(&'@').is_some() {
+            let name = self.word()?;
+            self.skip_whitespace();
+            let test = if self.chars.peek() == Some(&']') {
+                None
+            } else {
+                Some((self.operator()?, self.value()?))
+            };
+            Condition::HasAnnotation { name, test }
+        } else {
+            let word = self.word()?;
+            let field = Field::from_str(&word)
+                .map_err(|_| self.error(format!("unknown field `{word}`")))?;
+            Condition::Field {
+                field,
+                operator: self.operator()?,
+                value: self.value()?,
+            }
+        };
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some(']') => Ok(Filter { negated, condition }),
+            _ => Err(self.error("expecting `]` to end the filter")),
+        }
+    }
+
+    fn operator(&mut self) -> Result<Operator, Error> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some('=') => Ok(Operator::Equals),
+            Some('~') => Ok(Operator::Contains),
+            Some('!') if self.chars.next_if_eq(&'=').is_some() => Ok(Operator::NotEquals),
+            _ => Err(self.error("expecting one of the operators `=`, `!=`, or `~`")),
+        }
+    }
+
+    fn value(&mut self) -> Result<String, Error> {
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&'"').is_some() {
+            let mut value = String::new();
+            loop {
+                match self.chars.next() {
+                    Some('"') => return Ok(value),
+                    Some('\\') => match self.chars.next() {
+                        Some(c) => value.push(c),
+                        None => break,
+                    },
+                    Some(c) => value.push(c),
+                    None => break,
+                }
+            }
+            Err(self.error("expecting `\"` to end the string"))
+        } else {
+            self.word()
+        }
+    }
+
+    fn word(&mut self) -> Result<String, Error> {
+        self.skip_whitespace();
+        let mut word = String::new();
+        while let Some(c) = self.chars.next_if(|c| !is_delimiter(*c)) {
+            word.push(c);
+        }
+        if word.is_empty() {
+            match self.chars.peek().copied() {
+                Some(c) => Err(self.error(format!("unexpected `{c}`"))),
+                None => Err(self.error("unexpected end of query")),
+            }
+        } else {
+            Ok(word)
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn error<S: Into<String>>(&self, message: S) -> Error {
+        Error::QueryError {
+            query: self.source.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '/' | '[' | ']' | '=' | '!' | '~' | '"' | '@')
+}
+
+// Model string values are displayed with quotes, and possibly a language tag.
+fn unquoted(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.rfind('"').map(|end| &value[..end]))
+        .unwrap_or(value)
+}
@@ -0,0 +1,141 @@
+/*!
+Provides refactoring operations that change one or more modules in a [`ModuleStore`].
+
+Rather than re-generating a module from the in-memory model, which would lose comments and
+layout, a refactoring produces a set of [`TextEdit`]s for each affected module. Each edit
+replaces the text at the source span of some part of the module, so applying the edits to the
+original source changes only the text that must change.
+
+# Example
+
+```rust
+use sdml_core::model::{SpanPosition, Span};
+use sdml_core::refactor::{apply_edits, TextEdit};
+
+let source = "module example is datatype Old <- string end";
+let edit = TextEdit::new(
+    Span::new(SpanPosition::new(27, 1, 28), SpanPosition::new(30, 1, 31)),
+    "New",
+);
+
+assert_eq!(
+    apply_edits(source, &[edit]),
+    "module example is datatype New <- string end"
+);
+```
+
+[`ModuleStore`]: crate::store::ModuleStore
+*/
+
+use crate::model::{Span, SpanPosition};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A single change to module source, the text within `span` is replaced by `replacement`. An
+/// empty span denotes an insertion.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextEdit {
+    span: Span,
+    replacement: String,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Apply `edits` to `source` returning the changed text. Edits may be in any order; where more
+/// than one edit has the same span only the last is applied.
+///
+pub fn apply_edits(source: &str, edits: &[TextEdit]) -> String {
+    let edits: BTreeMap<(usize, usize), &str> = edits
+        .iter()
+        .map(|edit| {
+            let range = edit.span.byte_range();
+            ((range.start, range.end), edit.replacement.as_str())
+        })
+        .collect();
+
+    let mut result = String::with_capacity(source.len());
+    let mut last = 0;
+    for ((start, end), replacement) in edits {
+        if start < last || end > source.len() {
+            continue;
+        }
+        result.push_str(&source[last..start]);
+        result.push_str(replacement);
+        last = end;
+    }
+    result.push_str(&source[last..]);
+    result
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for TextEdit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let start = self.span.start();
+        write!(
+            f,
+            "{}:{}: {:?}",
+            start.line(),
+            start.column(),
+            self.replacement
+        )
+    }
+}
+
+impl TextEdit {
+    // --------------------------------------------------------------------------------------------
+    // Constructors
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Replace the text within `span` with `replacement`.
+    ///
+    pub fn new<S: Into<String>>(span: Span, replacement: S) -> Self {
+        Self {
+            span,
+            replacement: replacement.into(),
+        }
+    }
+
+    ///
+    /// Insert `text` at the position `at`.
+    ///
+    pub fn insert<S: Into<String>>(at: SpanPosition, text: S) -> Self {
+        Self::new(Span::new(at, at), text)
+    }
+
+    // --------------------------------------------------------------------------------------------
+    // Fields
+    // --------------------------------------------------------------------------------------------
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    pub fn replacement(&self) -> &String {
+        &self.replacement
+    }
+
+    pub fn is_insertion(&self) -> bool {
+        self.span.start().byte() == self.span.end().byte()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
+
+pub mod fix;
+
+pub mod rename;
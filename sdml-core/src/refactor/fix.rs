@@ -0,0 +1,522 @@
+/*!
+Provides automatic fixes for some of the diagnostics reported while loading and validating
+modules.
+
+A [`Fix`] takes the diagnostics reported for a set of modules and produces a [`FixPlan`], the
+[`TextEdit`]s for each module that address them. The kinds of fix, [`FixKind`], are:
+
+* **preferred-case**, an identifier not in the preferred case (`I0506`) is renamed to its
+  preferred form using [`Rename`] so that all references are also changed; module names and
+  imported members are not renamed.
+* **duplicate-import**, a duplicate module or member import (`W0301`, `W0302`) is removed.
+* **missing-import**, a qualified reference to a module that is not imported, but is either
+  loaded or can be found by the loader's resolver, has an import added.
+* **language-tag**, a string without a language tag (`I0503`) has one added.
+
+# Example
+
+```rust
+use sdml_core::refactor::fix::{Fix, FixKind};
+use sdml_core::model::values::LanguageTag;
+use std::str::FromStr;
+
+let fix = Fix::default()
+    .only([FixKind::DuplicateImport, FixKind::LanguageTag])
+    .with_language(LanguageTag::from_str("en-GB").unwrap());
+
+assert!(fix.includes(FixKind::LanguageTag));
+assert!(!fix.includes(FixKind::PreferredCase));
+```
+*/
+
+use crate::error::Error;
+use crate::load::{ModuleLoader, ModuleResolver};
+use crate::model::definitions::{Definition, HasMembers, HasVariants};
+use crate::model::identifiers::{Identifier, IdentifierReference, QualifiedIdentifier};
+use crate::model::members::Member;
+use crate::model::modules::Module;
+use crate::model::values::LanguageTag;
+use crate::model::{HasBody, HasName, HasOptionalBody, HasSourceSpan, References, SpanPosition};
+use crate::refactor::rename::{Rename, RenameTarget};
+use crate::refactor::{apply_edits, TextEdit};
+use crate::store::ModuleStore;
+use sdml_errors::diagnostics::suggestions::{SuggestedDiagnostic, Suggestion};
+use sdml_errors::diagnostics::ErrorCode;
+use sdml_errors::FileId;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Display;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The kinds of automatic fix.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FixKind {
+    PreferredCase,
+    DuplicateImport,
+    MissingImport,
+    LanguageTag,
+}
+
+///
+/// The set of fixes to apply, and the language tag to add to strings without one.
+///
+#[derive(Clone, Debug)]
+pub struct Fix {
+    kinds: BTreeSet<FixKind>,
+    language: LanguageTag,
+}
+
+///
+/// The edits, grouped by module name, that perform a [`Fix`], along with a description of any
+/// diagnostic that could not be fixed automatically.
+///
+#[derive(Clone, Debug, Default)]
+pub struct FixPlan {
+    edits: BTreeMap<Identifier, Vec<(FixKind, TextEdit)>>,
+    skipped: Vec<String>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct FixModule<'a> {
+    module: &'a Module,
+    source: String,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for FixKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::PreferredCase => "preferred-case",
+                Self::DuplicateImport => "duplicate-import",
+                Self::MissingImport => "missing-import",
+                Self::LanguageTag => "language-tag",
+            }
+        )
+    }
+}
+
+impl FromStr for FixKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "preferred-case" => Ok(Self::PreferredCase),
+            "duplicate-import" => Ok(Self::DuplicateImport),
+            "missing-import" => Ok(Self::MissingImport),
+            "language-tag" => Ok(Self::LanguageTag),
+            _ => Err(Error::RefactorError {
+                name: s.to_string(),
+                message: "not a known kind of fix".to_string(),
+            }),
+        }
+    }
+}
+
+impl FixKind {
+    pub fn all() -> impl Iterator<Item = Self> {
+        [
+            Self::PreferredCase,
+            Self::DuplicateImport,
+            Self::MissingImport,
+            Self::LanguageTag,
+        ]
+        .into_iter()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Default for Fix {
+    fn default() -> Self {
+        Self {
+            kinds: FixKind::all().collect(),
+            language: LanguageTag::new_unchecked("en"),
+        }
+    }
+}
+
+impl Fix {
+    // --------------------------------------------------------------------------------------------
+    // Constructors
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Only apply the fixes in `kinds`.
+    ///
+    pub fn only<I>(self, kinds: I) -> Self
+    where
+        I: IntoIterator<Item = FixKind>,
+    {
+        Self {
+            kinds: kinds.into_iter().collect(),
+            ..self
+        }
+    }
+
+    ///
+    /// Use `language` as the tag added to strings without one, the default is `en`.
+    ///
+    pub fn with_language(self, language: LanguageTag) -> Self {
+        Self { language, ..self }
+    }
+
+    // --------------------------------------------------------------------------------------------
+    // Fields
+    // --------------------------------------------------------------------------------------------
+
+    pub fn includes(&self, kind: FixKind) -> bool {
+        self.kinds.contains(&kind)
+    }
+
+    pub fn language(&self) -> &LanguageTag {
+        &self.language
+    }
+
+    // --------------------------------------------------------------------------------------------
+    // Planning
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Compute the edits that fix `diagnostics` in the modules named in `modules`, all of which
+    /// must be in `cache` and have their source available from `loader`. Diagnostics for any
+    /// other file are ignored, as are the suggestions of any diagnostic without a fix.
+    ///
+    pub fn plan(
+        &self,
+        modules: &[Identifier],
+        diagnostics: &[SuggestedDiagnostic],
+        cache: &impl ModuleStore,
+        loader: &impl ModuleLoader,
+    ) -> Result<FixPlan, Error> {
+        let mut files: HashMap<FileId, FixModule<'_>> = Default::default();
+        for name in modules {
+            let module = cache.get(name).ok_or_else(|| Error::RefactorError {
+                name: name.to_string(),
+                message: "the module was not found".to_string(),
+            })?;
+            if let Some(file_id) = module.file_id() {
+                if let Some(source) = loader.get_source(*file_id) {
+                    let source = AsRef::<str>::as_ref(&source).to_string();
+                    let _ = files.insert(*file_id, FixModule { module, source });
+                }
+            }
+        }
+
+        let mut plan = FixPlan::default();
+        for suggested in diagnostics {
+            let diagnostic = suggested.diagnostic();
+            let Some(code) = diagnostic.code.as_deref().and_then(ErrorCode::from_code) else {
+                continue;
+            };
+            match code {
+                ErrorCode::IdentifierNotPreferredCase if self.includes(FixKind::PreferredCase) => {
+                    for suggestion in suggested.suggestions() {
+                        if let Some(file) = files.get(&suggestion.file_id()) {
+                            self.plan_preferred_case(file, suggestion, modules, cache, &mut plan);
+                        }
+                    }
+                }
+                ErrorCode::DuplicateModuleImport | ErrorCode::DuplicateDefinitionImport
+                    if self.includes(FixKind::DuplicateImport) =>
+                {
+                    for suggestion in suggested.suggestions() {
+                        if let Some(file) = files.get(&suggestion.file_id()) {
+                            let span = suggestion.span();
+                            plan.add(
+                                file.module.name(),
+                                FixKind::DuplicateImport,
+                                TextEdit::new(
+                                    crate::model::Span::new(
+                                        file.position(span.start),
+                                        file.position(span.end),
+                                    ),
+                                    suggestion.replacement(),
+                                ),
+                            );
+                        }
+                    }
+                }
+                ErrorCode::StringWithoutLanguage if self.includes(FixKind::LanguageTag) => {
+                    for label in &diagnostic.labels {
+                        if label.style == codespan_reporting::diagnostic::LabelStyle::Primary {
+                            if let Some(file) = files.get(&label.file_id) {
+                                plan.add(
+                                    file.module.name(),
+                                    FixKind::LanguageTag,
+                                    TextEdit::insert(
+                                        file.position(label.range.end),
+                                        self.language.to_string(),
+                                    ),
+                                );
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if self.includes(FixKind::MissingImport) {
+            let mut files: Vec<&FixModule<'_>> = files.values().collect();
+            files.sort_by_key(|file| file.module.name());
+            for file in files {
+                self.plan_missing_imports(file, cache, loader, &mut plan);
+            }
+        }
+
+        Ok(plan)
+    }
+
+    fn plan_preferred_case(
+        &self,
+        file: &FixModule<'_>,
+        suggestion: &Suggestion,
+        modules: &[Identifier],
+        cache: &impl ModuleStore,
+        plan: &mut FixPlan,
+    ) {
+        let span = suggestion.span();
+        let Some(target) = rename_target(file.module, span.start, span.end) else {
+            plan.skipped.push(format!(
+                "{}: `{}` is not a definition, member, or variant name",
+                file.module.name(),
+                &file.source[span.clone()]
+            ));
+            return;
+        };
+        let new_name = match Identifier::from_str(suggestion.replacement()) {
+            Ok(new_name) => new_name,
+            Err(e) => {
+                plan.skipped.push(format!("{target}: {e}"));
+                return;
+            }
+        };
+        match Rename::new(target.clone(), new_name).plan(cache) {
+            Ok(rename) => {
+                if let Some(other) = rename.module_names().find(|name| !modules.contains(name)) {
+                    plan.skipped.push(format!(
+                        "{target}: the module `{other}` also refers to this name and is not being fixed"
+                    ));
+                } else {
+                    for name in rename.module_names() {
+                        for edit in rename.edits(name) {
+                            plan.add(name, FixKind::PreferredCase, edit.clone());
+                        }
+                    }
+                }
+            }
+            Err(Error::RefactorError { message, .. }) => {
+                plan.skipped.push(format!("{target}: {message}"));
+            }
+            Err(e) => plan.skipped.push(format!("{target}: {e}")),
+        }
+    }
+
+    fn plan_missing_imports(
+        &self,
+        file: &FixModule<'_>,
+        cache: &impl ModuleStore,
+        loader: &impl ModuleLoader,
+        plan: &mut FixPlan,
+    ) {
+        let module = file.module;
+        let mut names: HashSet<&IdentifierReference> = Default::default();
+        module.referenced_types(&mut names);
+        module.referenced_annotations(&mut names);
+        let imported = module.body().imported_modules();
+
+        let missing: BTreeSet<&Identifier> = names
+            .iter()
+            .filter_map(|name| name.module())
+            .filter(|name| *name != module.name() && !imported.contains(name))
+            .filter(|name| {
+                cache.contains(name)
+                    || loader
+                        .resolver()
+                        .name_to_resource(name, module.file_id().copied())
+                        .is_ok()
+            })
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
+
+        let last_import = module
+            .body()
+            .imports()
+            .filter_map(|import| import.source_span())
+            .map(|span| span.end().byte())
+            .max();
+        let (at, prefix, suffix) = if let Some(end) = last_import {
+            let line_start = file.source[..end].rfind('\n').map(|i| i + 1).unwrap_or(0);
+            let indent: String = file.source[line_start..]
+                .chars()
+                .take_while(|c| *c == ' ' || *c == '\t')
+                .collect();
+            (end, format!("\n{indent}"), String::new())
+        } else {
+            let Some(start) = module
+                .body()
+                .source_span()
+                .map(|span| span.start().byte())
+                .filter(|start| file.source[*start..].starts_with("is"))
+            else {
+                plan.skipped
+                    .push(format!("{}: no place to add imports", module.name()));
+                return;
+            };
+            (start + 2, "\n  ".to_string(), "\n".to_string())
+        };
+
+        let text: String = missing
+            .iter()
+            .map(|name| format!("{prefix}import {name}"))
+            .collect::<String>()
+            + &suffix;
+        plan.add(
+            module.name(),
+            FixKind::MissingImport,
+            TextEdit::insert(file.position(at), text),
+        );
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl FixPlan {
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    ///
+    /// The total number of edits across all modules.
+    ///
+    pub fn len(&self) -> usize {
+        self.edits.values().map(Vec::len).sum()
+    }
+
+    ///
+    /// The names of all modules that are changed by this plan.
+    ///
+    pub fn module_names(&self) -> impl Iterator<Item = &Identifier> {
+        self.edits.keys()
+    }
+
+    ///
+    /// The edits for the module `module_name`, each with the kind of fix it performs.
+    ///
+    pub fn fixes(&self, module_name: &Identifier) -> &[(FixKind, TextEdit)] {
+        self.edits
+            .get(module_name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn edits(&self, module_name: &Identifier) -> Vec<TextEdit> {
+        self.fixes(module_name)
+            .iter()
+            .map(|(_, edit)| edit.clone())
+            .collect()
+    }
+
+    ///
+    /// Descriptions of the diagnostics that could not be fixed.
+    ///
+    pub fn skipped(&self) -> &[String] {
+        &self.skipped
+    }
+
+    ///
+    /// Apply the edits for the module `module_name` to `source`, which must be the source the
+    /// module was parsed from.
+    ///
+    pub fn apply(&self, module_name: &Identifier, source: &str) -> String {
+        apply_edits(source, &self.edits(module_name))
+    }
+
+    fn add(&mut self, module_name: &Identifier, kind: FixKind, edit: TextEdit) {
+        let edits = self.edits.entry(module_name.clone()).or_default();
+        if !edits.iter().any(|(_, existing)| existing == &edit) {
+            edits.push((kind, edit));
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl FixModule<'_> {
+    fn position(&self, byte: usize) -> SpanPosition {
+        let before = &self.source[..byte.min(self.source.len())];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        SpanPosition::new(
+            byte,
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+        )
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn rename_target(module: &Module, start: usize, end: usize) -> Option<RenameTarget> {
+    let is_at = |name: &Identifier| {
+        name.source_span()
+            .map(|span| span.byte_range() == (start..end))
+            .unwrap_or_default()
+    };
+    for definition in module.body().definitions() {
+        let qualified = QualifiedIdentifier::new(module.name().clone(), definition.name().clone());
+        if is_at(definition.name()) {
+            return Some(RenameTarget::new(qualified));
+        }
+        let names: Vec<&Identifier> = match definition {
+            Definition::Entity(def) => def
+                .body()
+                .map(|body| {
+                    std::iter::once(body.identity())
+                        .chain(body.members())
+                        .map(Member::name)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            Definition::Event(def) => def
+                .body()
+                .map(|body| body.members().map(Member::name).collect())
+                .unwrap_or_default(),
+            Definition::Structure(def) => def
+                .body()
+                .map(|body| body.members().map(Member::name).collect())
+                .unwrap_or_default(),
+            Definition::Enum(def) => def
+                .body()
+                .map(|body| body.variants().map(|v| v.name()).collect())
+                .unwrap_or_default(),
+            Definition::Union(def) => def
+                .body()
+                .map(|body| body.variants().filter_map(|v| v.rename()).collect())
+                .unwrap_or_default(),
+            _ => Default::default(),
+        };
+        if let Some(name) = names.into_iter().find(|name| is_at(name)) {
+            return Some(RenameTarget::new_member(qualified, name.clone()));
+        }
+    }
+    None
+}
@@ -0,0 +1,551 @@
+/*!
+Provides the *rename* refactoring for definitions, members, and variants.
+
+When a definition is renamed every reference to it, in all the modules of a [`ModuleStore`], is
+changed. This includes unqualified references within its own module, qualified references from
+other modules, member imports, unqualified references in modules that import it as a member, and
+the names of annotation properties. Members and variants are only named within their enclosing
+definition, so only the name itself is changed.
+
+# Example
+
+```rust
+use sdml_core::model::identifiers::Identifier;
+use sdml_core::refactor::rename::{Rename, RenameTarget};
+use sdml_core::store::InMemoryModuleCache;
+use std::str::FromStr;
+
+let rename = Rename::new(
+    RenameTarget::from_str("rentals:Customer").unwrap(),
+    Identifier::from_str("Client").unwrap(),
+);
+
+// With an empty store there is no module `rentals` to rename within.
+assert!(rename.plan(&InMemoryModuleCache::default()).is_err());
+```
+*/
+
+use crate::error::Error;
+use crate::model::definitions::{Definition, HasMembers, HasVariants, TypeVariant, ValueVariant};
+use crate::model::identifiers::{Identifier, IdentifierReference, QualifiedIdentifier};
+use crate::model::members::{Member, MemberKind};
+use crate::model::modules::{Import, ImportStatement, Module};
+use crate::model::walk::{walk_module_mut, MutModuleVisitor, MutVisitAction};
+use crate::model::{HasBody, HasName, HasOptionalBody, HasSourceSpan};
+use crate::refactor::{apply_edits, TextEdit};
+use crate::store::ModuleStore;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The thing to rename, either a definition `module:Definition`, or a member or variant of a
+/// definition `module:Definition.member`.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RenameTarget {
+    definition: QualifiedIdentifier,
+    member: Option<Identifier>,
+}
+
+///
+/// A rename of `target` to `new_name`.
+///
+#[derive(Clone, Debug)]
+pub struct Rename {
+    target: RenameTarget,
+    new_name: Identifier,
+}
+
+///
+/// The edits, grouped by module name, that perform a [`Rename`].
+///
+#[derive(Clone, Debug, Default)]
+pub struct RenamePlan {
+    edits: BTreeMap<Identifier, Vec<TextEdit>>,
+    missing_spans: usize,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct RenameVisitor<'a> {
+    target: &'a RenameTarget,
+    new_name: &'a Identifier,
+    module_name: Option<Identifier>,
+    unqualified_refers: bool,
+    current_definition: Option<Identifier>,
+    edits: Vec<TextEdit>,
+    missing_spans: usize,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for RenameTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(member) = &self.member {
+            write!(f, "{}.{member}", self.definition)
+        } else {
+            write!(f, "{}", self.definition)
+        }
+    }
+}
+
+impl FromStr for RenameTarget {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((definition, member)) = s.split_once(Self::MEMBER_SEPARATOR) {
+            Ok(Self::new_member(
+                QualifiedIdentifier::from_str(definition)?,
+                Identifier::from_str(member)?,
+            ))
+        } else {
+            Ok(Self::new(QualifiedIdentifier::from_str(s)?))
+        }
+    }
+}
+
+impl From<QualifiedIdentifier> for RenameTarget {
+    fn from(value: QualifiedIdentifier) -> Self {
+        Self::new(value)
+    }
+}
+
+impl RenameTarget {
+    pub const MEMBER_SEPARATOR: char = '.';
+
+    // --------------------------------------------------------------------------------------------
+    // Constructors
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Target the definition `definition`.
+    ///
+    pub fn new(definition: QualifiedIdentifier) -> Self {
+        Self {
+            definition,
+            member: None,
+        }
+    }
+
+    ///
+    /// Target the member, or variant, `member` of the definition `definition`.
+    ///
+    pub fn new_member(definition: QualifiedIdentifier, member: Identifier) -> Self {
+        Self {
+            definition,
+            member: Some(member),
+        }
+    }
+
+    // --------------------------------------------------------------------------------------------
+    // Fields
+    // --------------------------------------------------------------------------------------------
+
+    pub fn module(&self) -> &Identifier {
+        self.definition.module()
+    }
+
+    pub fn definition(&self) -> &QualifiedIdentifier {
+        &self.definition
+    }
+
+    pub fn member(&self) -> Option<&Identifier> {
+        self.member.as_ref()
+    }
+
+    pub fn is_member(&self) -> bool {
+        self.member.is_some()
+    }
+
+    fn name(&self) -> &Identifier {
+        self.member
+            .as_ref()
+            .unwrap_or_else(|| self.definition.member())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Rename {
+    // --------------------------------------------------------------------------------------------
+    // Constructors
+    // --------------------------------------------------------------------------------------------
+
+    pub fn new(target: RenameTarget, new_name: Identifier) -> Self {
+        Self { target, new_name }
+    }
+
+    // --------------------------------------------------------------------------------------------
+    // Fields
+    // --------------------------------------------------------------------------------------------
+
+    pub fn target(&self) -> &RenameTarget {
+        &self.target
+    }
+
+    pub fn new_name(&self) -> &Identifier {
+        &self.new_name
+    }
+
+    // --------------------------------------------------------------------------------------------
+    // Planning
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Compute the edits required to perform this rename across all the non-library modules in
+    /// `cache`. An error is returned if the target does not exist, or if the new name would
+    /// conflict with an existing name.
+    ///
+    pub fn plan(&self, cache: &impl ModuleStore) -> Result<RenamePlan, Error> {
+        let module_name = self.target.module();
+        if Identifier::is_library_module_name(module_name.as_ref()) {
+            return Err(self.error(format!(
+                "the library module `{module_name}` cannot be changed"
+            )));
+        }
+        if self.target.name() == &self.new_name {
+            return Err(self.error("the new name is the same as the current name"));
+        }
+        let module = cache
+            .get(module_name)
+            .ok_or_else(|| self.error(format!("the module `{module_name}` was not found")))?;
+        let definition = module
+            .resolve_local(self.target.definition.member())
+            .ok_or_else(|| self.error("the definition was not found"))?;
+
+        if let Some(member) = &self.target.member {
+            self.check_member(definition, member)?;
+        } else {
+            self.check_definition(module, cache)?;
+        }
+
+        let mut plan = RenamePlan::default();
+        let names: Vec<Identifier> = cache
+            .module_names()
+            .filter(|name| !Identifier::is_library_module_name(name.as_ref()))
+            .cloned()
+            .collect();
+        for name in names {
+            if self.target.is_member() && &name != module_name {
+                continue;
+            }
+            let Some(module) = cache.get(&name) else {
+                continue;
+            };
+            let mut visitor = RenameVisitor::new(&self.target, &self.new_name);
+            let mut module = module.clone();
+            walk_module_mut(&mut module, &mut visitor, true, true)?;
+            plan.missing_spans += visitor.missing_spans;
+            if !visitor.edits.is_empty() {
+                let _ = plan.edits.insert(name, visitor.edits);
+            }
+        }
+        Ok(plan)
+    }
+
+    fn check_definition(&self, module: &Module, cache: &impl ModuleStore) -> Result<(), Error> {
+        if module.resolve_local(&self.new_name).is_some() {
+            return Err(self.error(format!(
+                "the module `{}` already has a definition named `{}`",
+                module.name(),
+                self.new_name
+            )));
+        }
+        let imported = QualifiedIdentifier::new(
+            self.target.module().clone(),
+            self.target.definition.member().clone(),
+        );
+        for other in cache.modules() {
+            if other.name() == module.name()
+                || other.is_library_module()
+                || !other.body().imported_types().contains(&imported)
+            {
+                continue;
+            }
+            let clashes = other.resolve_local(&self.new_name).is_some()
+                || other
+                    .body()
+                    .imported_types()
+                    .iter()
+                    .any(|import| import.member() == &self.new_name);
+            if clashes {
+                return Err(self.error(format!(
+                    "the module `{}` imports `{imported}` but already has the name `{}`",
+                    other.name(),
+                    self.new_name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_member(&self, definition: &Definition, member: &Identifier) -> Result<(), Error> {
+        let members: Vec<&Member> = match definition {
+            Definition::Entity(def) => def
+                .body()
+                .map(|body| {
+                    std::iter::once(body.identity())
+                        .chain(body.members())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            Definition::Event(def) => def
+                .body()
+                .map(|body| body.members().collect())
+                .unwrap_or_default(),
+            Definition::Structure(def) => def
+                .body()
+                .map(|body| body.members().collect())
+                .unwrap_or_default(),
+            _ => Default::default(),
+        };
+        if members
+            .iter()
+            .any(|m| m.name() == member && m.is_property_reference())
+        {
+            return Err(self.error(format!(
+                "the member `{member}` is a reference to a property, rename the property instead"
+            )));
+        }
+        let names: Vec<&Identifier> = match definition {
+            Definition::Entity(_) | Definition::Event(_) | Definition::Structure(_) => {
+                members.iter().map(|m| m.name()).collect()
+            }
+            Definition::Enum(def) => def
+                .body()
+                .map(|body| body.variants().map(|v| v.name()).collect())
+                .unwrap_or_default(),
+            Definition::Union(def) => def
+                .body()
+                .map(|body| body.variants().map(|v| v.name()).collect())
+                .unwrap_or_default(),
+            _ => {
+                return Err(self.error(format!(
+                    "the definition `{}` has no members or variants",
+                    definition.name()
+                )))
+            }
+        };
+        if !names.contains(&member) {
+            Err(self.error(format!(
+                "the definition `{}` has no member or variant `{member}`",
+                definition.name()
+            )))
+        } else if names.contains(&&self.new_name) {
+            Err(self.error(format!(
+                "the definition `{}` already has a member or variant `{}`",
+                definition.name(),
+                self.new_name
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn error<S: Display>(&self, message: S) -> Error {
+        Error::RefactorError {
+            name: self.target.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl RenamePlan {
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    ///
+    /// The total number of edits across all modules.
+    ///
+    pub fn len(&self) -> usize {
+        self.edits.values().map(Vec::len).sum()
+    }
+
+    ///
+    /// The number of references that could not be changed as they have no source span, this
+    /// is only expected for modules that were not parsed from source.
+    ///
+    pub fn missing_spans(&self) -> usize {
+        self.missing_spans
+    }
+
+    ///
+    /// The names of all modules that are changed by this plan.
+    ///
+    pub fn module_names(&self) -> impl Iterator<Item = &Identifier> {
+        self.edits.keys()
+    }
+
+    pub fn edits(&self, module_name: &Identifier) -> &[TextEdit] {
+        self.edits
+            .get(module_name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Apply the edits for the module `module_name` to `source`, which must be the source the
+    /// module was parsed from.
+    ///
+    pub fn apply(&self, module_name: &Identifier, source: &str) -> String {
+        apply_edits(source, self.edits(module_name))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a> RenameVisitor<'a> {
+    fn new(target: &'a RenameTarget, new_name: &'a Identifier) -> Self {
+        Self {
+            target,
+            new_name,
+            module_name: None,
+            unqualified_refers: false,
+            current_definition: None,
+            edits: Default::default(),
+            missing_spans: 0,
+        }
+    }
+
+    fn in_target_module(&self) -> bool {
+        self.module_name.as_ref() == Some(self.target.module())
+    }
+
+    fn in_target_definition(&self) -> bool {
+        self.in_target_module()
+            && self.current_definition.as_ref() == Some(self.target.definition.member())
+    }
+
+    fn is_target_definition(&self, name: &QualifiedIdentifier) -> bool {
+        name.module() == self.target.module() && name.member() == self.target.definition.member()
+    }
+
+    fn rename(&mut self, name: &Identifier) {
+        if let Some(span) = name.source_span() {
+            self.edits
+                .push(TextEdit::new(*span, self.new_name.to_string()));
+        } else {
+            self.missing_spans += 1;
+        }
+    }
+
+    fn rename_member(&mut self, thing: &Member) {
+        if self.in_target_definition() && Some(thing.name()) == self.target.member() {
+            if let MemberKind::Definition(def) = thing.kind() {
+                self.rename(def.name());
+            }
+        }
+    }
+}
+
+impl MutModuleVisitor for RenameVisitor<'_> {
+    fn module_start(&mut self, thing: &mut Module) -> Result<bool, Error> {
+        let name = self.target.definition.member();
+        self.module_name = Some(thing.name().clone());
+        self.unqualified_refers = self.in_target_module()
+            || (thing.resolve_local(name).is_none()
+                && thing
+                    .body()
+                    .imported_types()
+                    .iter()
+                    .any(|import| self.is_target_definition(import)));
+        Ok(true)
+    }
+
+    fn import_statement(&mut self, thing: &mut ImportStatement) -> Result<bool, Error> {
+        if !self.target.is_member() {
+            let members: Vec<Identifier> = thing
+                .imports()
+                .filter_map(|import| match import {
+                    Import::Member(v) if self.is_target_definition(v) => Some(v.member().clone()),
+                    _ => None,
+                })
+                .collect();
+            for member in members {
+                self.rename(&member);
+            }
+        }
+        Ok(true)
+    }
+
+    fn definition_start(&mut self, thing: &mut Definition) -> Result<MutVisitAction, Error> {
+        self.current_definition = Some(thing.name().clone());
+        if !self.target.is_member() && self.in_target_definition() {
+            self.rename(thing.name());
+        }
+        Self::INCLUDE_NESTED
+    }
+
+    fn definition_end(&mut self, _thing: &mut Definition) -> Result<(), Error> {
+        self.current_definition = None;
+        Ok(())
+    }
+
+    fn member_start(&mut self, thing: &mut Member) -> Result<MutVisitAction, Error> {
+        self.rename_member(thing);
+        Self::INCLUDE_NESTED
+    }
+
+    fn identity_member_start(&mut self, thing: &mut Member) -> Result<bool, Error> {
+        self.rename_member(thing);
+        Ok(true)
+    }
+
+    fn value_variant_start(&mut self, thing: &mut ValueVariant) -> Result<MutVisitAction, Error> {
+        if self.in_target_definition() && Some(thing.name()) == self.target.member() {
+            self.rename(thing.name());
+        }
+        Self::INCLUDE_NESTED
+    }
+
+    fn type_variant_start(&mut self, thing: &mut TypeVariant) -> Result<MutVisitAction, Error> {
+        if self.in_target_definition() && Some(thing.name()) == self.target.member() {
+            if let Some(rename) = thing.rename() {
+                self.rename(&rename.clone());
+            } else if let Some(span) = thing.name_reference().source_span() {
+                // A variant named by its type is given a new name rather than a new type.
+                self.edits.push(TextEdit::insert(
+                    span.end(),
+                    format!(" as {}", self.new_name),
+                ));
+            } else {
+                self.missing_spans += 1;
+            }
+        }
+        Self::INCLUDE_NESTED
+    }
+
+    fn identifier_reference(&mut self, thing: &mut IdentifierReference) -> Result<(), Error> {
+        if self.target.is_member() {
+            return Ok(());
+        }
+        match thing {
+            IdentifierReference::Identifier(name) => {
+                if self.unqualified_refers && name == self.target.definition.member() {
+                    let name = name.clone();
+                    self.rename(&name);
+                }
+            }
+            IdentifierReference::QualifiedIdentifier(name) => {
+                if self.is_target_definition(name) {
+                    let member = name.member().clone();
+                    self.rename(&member);
+                }
+            }
+        }
+        Ok(())
+    }
+}
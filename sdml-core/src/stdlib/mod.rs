@@ -17,19 +17,7 @@ use crate::model::{identifiers::Identifier, modules::Module};
 // ------------------------------------------------------------------------------------------------
 
 pub fn is_library_module(name: &Identifier) -> bool {
-    [
-        dc::MODULE_NAME,
-        dc::terms::MODULE_NAME,
-        iso_3166::MODULE_NAME,
-        iso_4217::MODULE_NAME,
-        owl::MODULE_NAME,
-        rdf::MODULE_NAME,
-        rdfs::MODULE_NAME,
-        sdml::MODULE_NAME,
-        skos::MODULE_NAME,
-        xsd::MODULE_NAME,
-    ]
-    .contains(&name.as_ref())
+    library_module_entry(name).is_some()
 }
 
 pub fn is_builtin_type_name(name: &Identifier) -> bool {
@@ -48,19 +36,88 @@ pub fn is_builtin_type_name(name: &Identifier) -> bool {
 }
 
 pub fn library_module(name: &Identifier) -> Option<Module> {
-    match name.as_ref() {
-        dc::MODULE_NAME => Some(dc::module()),
-        dc::terms::MODULE_NAME => Some(dc::terms::module()),
-        iso_3166::MODULE_NAME => Some(iso_3166::module()),
-        iso_4217::MODULE_NAME => Some(iso_4217::module()),
-        owl::MODULE_NAME => Some(owl::module()),
-        rdf::MODULE_NAME => Some(rdf::module()),
-        rdfs::MODULE_NAME => Some(rdfs::module()),
-        sdml::MODULE_NAME => Some(sdml::module()),
-        skos::MODULE_NAME => Some(skos::module()),
-        xsd::MODULE_NAME => Some(xsd::module()),
-        _ => None,
-    }
+    library_module_entry(name).map(|entry| (entry.constructor)())
+}
+
+///
+/// Return the name and base URL of each standard library module, without constructing any of them.
+///
+pub fn library_modules() -> impl Iterator<Item = (&'static str, &'static str)> {
+    LIBRARY_MODULES.iter().map(|entry| (entry.name, entry.url))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+struct LibraryEntry {
+    name: &'static str,
+    url: &'static str,
+    constructor: fn() -> Module,
+}
+
+// NYI dc::am, dc::types
+const LIBRARY_MODULES: &[LibraryEntry] = &[
+    LibraryEntry {
+        name: dc::MODULE_NAME,
+        url: dc::MODULE_URL,
+        constructor: dc::module,
+    },
+    LibraryEntry {
+        name: dc::terms::MODULE_NAME,
+        url: dc::terms::MODULE_URL,
+        constructor: dc::terms::module,
+    },
+    LibraryEntry {
+        name: iso_3166::MODULE_NAME,
+        url: iso_3166::MODULE_URL,
+        constructor: iso_3166::module,
+    },
+    LibraryEntry {
+        name: iso_4217::MODULE_NAME,
+        url: iso_4217::MODULE_URL,
+        constructor: iso_4217::module,
+    },
+    LibraryEntry {
+        name: owl::MODULE_NAME,
+        url: owl::MODULE_URL,
+        constructor: owl::module,
+    },
+    LibraryEntry {
+        name: rdf::MODULE_NAME,
+        url: rdf::MODULE_URL,
+        constructor: rdf::module,
+    },
+    LibraryEntry {
+        name: rdfs::MODULE_NAME,
+        url: rdfs::MODULE_URL,
+        constructor: rdfs::module,
+    },
+    LibraryEntry {
+        name: sdml::MODULE_NAME,
+        url: sdml::MODULE_URL,
+        constructor: sdml::module,
+    },
+    LibraryEntry {
+        name: skos::MODULE_NAME,
+        url: skos::MODULE_URL,
+        constructor: skos::module,
+    },
+    LibraryEntry {
+        name: xsd::MODULE_NAME,
+        url: xsd::MODULE_URL,
+        constructor: xsd::module,
+    },
+];
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn library_module_entry(name: &Identifier) -> Option<&'static LibraryEntry> {
+    LIBRARY_MODULES
+        .iter()
+        .find(|entry| entry.name == name.as_ref())
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -85,6 +85,7 @@ pub const MAX_OCCURS: &str = "maxOccurs";
 pub const MIN_OCCURS: &str = "minOccurs";
 pub const ORDERING: &str = "ordering";
 pub const SRC_LABEL: &str = "srcLabel";
+pub const SUPPRESS: &str = "suppress";
 pub const UNIQUENESS: &str = "uniqueness";
 
 pub const ORDERED: &str = "Ordered";
@@ -183,6 +184,7 @@ pub fn module() -> Module {
             rdf!(property MIN_OCCURS, MODULE_IRI).into(),
             rdf!(property ORDERING, MODULE_IRI).into(),
             rdf!(property SRC_LABEL, MODULE_IRI).into(),
+            rdf!(property SUPPRESS, MODULE_IRI).into(),
             rdf!(property UNIQUENESS, MODULE_IRI).into(),
             // Individuals
             rdf!(thing ORDERED, MODULE_IRI, ORDERING_CONSTRAINT).into(),
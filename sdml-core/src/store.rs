@@ -1,6 +1,10 @@
 /*!
 This module provides a trait for module *stores*, and an implementation for in-memory caches.
 
+A store may hold more than one version of a module, distinguished by the module's version URI.
+The plain name-based methods such as [`ModuleStore::get`] return the first version inserted, the
+`_version` methods such as [`ModuleStore::get_version`] select a specific version.
+
 # Example
 
 ```
@@ -22,6 +26,7 @@ use crate::model::identifiers::{Identifier, IdentifierReference, QualifiedIdenti
 use crate::model::modules::Module;
 use crate::model::HasName;
 use crate::stdlib;
+use crate::store::index::ModuleIndex;
 use std::collections::HashMap;
 use url::Url;
 
@@ -74,6 +79,39 @@ pub trait ModuleStore {
     ///
     fn get_mut(&mut self, name: &Identifier) -> Option<&mut Module>;
 
+    ///
+    /// Returns `true` if the store contains the version of module `name` with the version URI
+    /// `version_uri`, else `false`.
+    ///
+    fn contains_version(&self, name: &Identifier, version_uri: &Url) -> bool {
+        self.get_version(name, version_uri).is_some()
+    }
+
+    ///
+    /// Returns a reference to the version of the `Module` identified by `name` with the version URI
+    /// `version_uri` if the store contains it; else `None`.
+    ///
+    fn get_version(&self, name: &Identifier, version_uri: &Url) -> Option<&Module> {
+        self.get(name)
+            .filter(|module| module.version_uri().map(|v| v.value()) == Some(version_uri))
+    }
+
+    ///
+    /// Returns a mutable reference to the version of the `Module` identified by `name` with the
+    /// version URI `version_uri` if the store contains it; else `None`.
+    ///
+    fn get_version_mut(&mut self, name: &Identifier, version_uri: &Url) -> Option<&mut Module> {
+        self.get_mut(name)
+            .filter(|module| module.version_uri().map(|v| v.value()) == Some(version_uri))
+    }
+
+    ///
+    /// Return an iterator over all the versions of the module identified by `name`.
+    ///
+    fn module_versions(&self, name: &Identifier) -> impl Iterator<Item = &Module> {
+        self.get(name).into_iter()
+    }
+
     ///
     /// Returns a reference to the `Module` identified by `uri` if the store contains it;
     /// else `None`.
@@ -138,6 +176,23 @@ pub trait ModuleStore {
     ///
     fn module_name_to_uri(&self, name: &Identifier) -> Option<&Url>;
 
+    ///
+    /// Return a copy of the module previously parsed from `source`, if this store persists parsed
+    /// modules and holds one; else `None`. Loaders call this before parsing `source`.
+    ///
+    fn get_parsed(&self, source: &str) -> Option<Module> {
+        let _ = source;
+        None
+    }
+
+    ///
+    /// Record `module` as the result of parsing `source`, if this store persists parsed modules.
+    /// This does not insert `module` into the store.
+    ///
+    fn cache_parsed(&mut self, source: &str, module: &Module) {
+        let _ = (source, module);
+    }
+
     ///
     /// Given a qualified identifier, find the named module or return `None`, then find the named
     /// member in the found module or return `None`.
@@ -192,16 +247,31 @@ pub trait ModuleStore {
             IdentifierReference::QualifiedIdentifier(v) => self.resolve(v),
         }
     }
+
+    ///
+    /// Build an index of the definitions in all the modules in this store, see
+    /// [`index::ModuleIndex`].
+    ///
+    fn index(&self) -> ModuleIndex
+    where
+        Self: Sized,
+    {
+        ModuleIndex::from_store(self)
+    }
 }
 
 ///
 /// An implementation of [`ModuleStore`] that has no persistence it simply acts as an in-process
 /// cache.
 ///
+/// When a module is inserted with a version URI that differs from the version already cached
+/// under the same name it is kept alongside, rather than replacing, the existing module.
+///
 #[derive(Clone, Debug, Default)]
 pub struct InMemoryModuleCache {
     uri_map: HashMap<Url, Identifier>,
     modules: HashMap<Identifier, Module>,
+    other_versions: HashMap<Identifier, HashMap<Url, Module>>,
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -229,6 +299,39 @@ impl ModuleStore for InMemoryModuleCache {
         self.modules.get_mut(name)
     }
 
+    fn get_version(&self, name: &Identifier, version_uri: &Url) -> Option<&Module> {
+        match self.modules.get(name) {
+            Some(module) if module.version_uri().map(|v| v.value()) == Some(version_uri) => {
+                Some(module)
+            }
+            _ => self
+                .other_versions
+                .get(name)
+                .and_then(|versions| versions.get(version_uri)),
+        }
+    }
+
+    fn get_version_mut(&mut self, name: &Identifier, version_uri: &Url) -> Option<&mut Module> {
+        match self.modules.get_mut(name) {
+            Some(module) if module.version_uri().map(|v| v.value()) == Some(version_uri) => {
+                Some(module)
+            }
+            _ => self
+                .other_versions
+                .get_mut(name)
+                .and_then(|versions| versions.get_mut(version_uri)),
+        }
+    }
+
+    fn module_versions(&self, name: &Identifier) -> impl Iterator<Item = &Module> {
+        self.modules.get(name).into_iter().chain(
+            self.other_versions
+                .get(name)
+                .into_iter()
+                .flat_map(|versions| versions.values()),
+        )
+    }
+
     fn get_by_uri(&self, uri: &Url) -> Option<&Module> {
         match self.uri_map.get(uri) {
             Some(name) => self.get(name),
@@ -257,12 +360,25 @@ impl ModuleStore for InMemoryModuleCache {
             self.uri_map
                 .insert(base_uri.value().clone(), module.name().clone());
         }
-        self.modules.insert(module.name().clone(), module);
+        let other_version = match (self.modules.get(module.name()), module.version_uri()) {
+            (Some(existing), Some(version_uri)) => existing.version_uri() != Some(version_uri),
+            _ => false,
+        };
+        if other_version {
+            let version_uri = module.version_uri().unwrap().value().clone();
+            self.other_versions
+                .entry(module.name().clone())
+                .or_default()
+                .insert(version_uri, module);
+        } else {
+            self.modules.insert(module.name().clone(), module);
+        }
     }
 
     fn remove(&mut self, name: &Identifier) -> bool {
         if self.modules.remove(name).is_some() {
-            self.uri_map.retain(|_, v| v == name);
+            self.other_versions.remove(name);
+            self.uri_map.retain(|_, v| v != name);
             true
         } else {
             false
@@ -272,6 +388,7 @@ impl ModuleStore for InMemoryModuleCache {
     fn remove_by_uri(&mut self, uri: &Url) -> bool {
         if let Some(name) = self.uri_map.remove(uri) {
             self.modules.remove(&name);
+            self.other_versions.remove(&name);
             true
         } else {
             false
@@ -295,18 +412,9 @@ impl InMemoryModuleCache {
     /// Construct a cache with all of the standard library modules pre-inserted.
     ///
     pub fn with_stdlib(self) -> Self {
-        self.with(stdlib::dc::module())
-            // NYI .with(stdlib::dc::am::module())
-            .with(stdlib::dc::terms::module())
-            // NYI .with(stdlib::dc::types::module())
-            .with(stdlib::iso_3166::module())
-            .with(stdlib::iso_4217::module())
-            .with(stdlib::owl::module())
-            .with(stdlib::rdf::module())
-            .with(stdlib::rdfs::module())
-            .with(stdlib::sdml::module())
-            .with(stdlib::skos::module())
-            .with(stdlib::xsd::module())
+        stdlib::library_modules()
+            .filter_map(|(name, _)| stdlib::library_module(&Identifier::new_unchecked(name)))
+            .fold(self, Self::with)
     }
 
     ///
@@ -318,3 +426,12 @@ impl InMemoryModuleCache {
         self_mut
     }
 }
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "disk-cache")]
+pub mod disk;
+
+pub mod index;
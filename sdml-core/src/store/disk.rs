@@ -0,0 +1,424 @@
+/*!
+Provides a [`ModuleStore`] implementation that persists parsed modules in a local cache directory.
+
+Each parsed module is serialized, as JSON, to a file in the cache directory named for a hash of
+the module's source text and the version of the tool that parsed it. A loader asks the store for
+a parsed copy, with [`ModuleStore::get_parsed`], before parsing any source; if the source file has
+changed since it was cached its hash differs and it is simply parsed again. Entries are only read
+from disk when requested and the standard library modules are only constructed when first
+retrieved from the store.
+
+# Example
+
+```rust,no_run
+use sdml_core::model::identifiers::Identifier;
+use sdml_core::store::disk::DiskModuleCache;
+use sdml_core::store::ModuleStore;
+use std::str::FromStr;
+
+let store = DiskModuleCache::open(".sdml-cache").unwrap().with_stdlib();
+
+let xml_schema_module = Identifier::from_str("xsd").unwrap();
+
+assert_eq!(true, store.contains(&xml_schema_module));
+```
+*/
+
+use crate::model::identifiers::Identifier;
+use crate::model::modules::Module;
+use crate::model::HasName;
+use crate::stdlib;
+use crate::store::{InMemoryModuleCache, ModuleStore};
+use sdml_errors::Error;
+use sha2::{Digest, Sha256};
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, trace, warn};
+use url::Url;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// An implementation of [`ModuleStore`] that holds loaded modules in memory, as
+/// [`InMemoryModuleCache`] does, and persists parsed modules to a cache directory so that later
+/// processes need not parse unchanged source again.
+///
+/// If constructed with `default()` there is no cache directory and no modules are persisted.
+///
+#[derive(Debug)]
+pub struct DiskModuleCache {
+    directory: Option<PathBuf>,
+    tool_version: String,
+    modules: InMemoryModuleCache,
+    library: HashMap<Identifier, LibraryModule>,
+}
+
+///
+/// The file extension used for module entries in the cache directory.
+///
+pub const CACHE_ENTRY_FILE_EXTENSION: &str = "json";
+
+///
+/// The default tool version used in cache keys, the name and version of this crate.
+///
+pub const DEFAULT_TOOL_VERSION: &str =
+    concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct LibraryModule {
+    name: Identifier,
+    uri: Url,
+    module: OnceCell<Module>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Default for DiskModuleCache {
+    fn default() -> Self {
+        Self {
+            directory: None,
+            tool_version: DEFAULT_TOOL_VERSION.to_string(),
+            modules: Default::default(),
+            library: Default::default(),
+        }
+    }
+}
+
+impl ModuleStore for DiskModuleCache {
+    fn len(&self) -> usize {
+        self.modules.len() + self.library_names().count()
+    }
+
+    fn contains(&self, name: &Identifier) -> bool {
+        self.modules.contains(name) || self.library.contains_key(name)
+    }
+
+    fn contains_by_uri(&self, uri: &Url) -> bool {
+        self.uri_to_module_name(uri).is_some()
+    }
+
+    fn get(&self, name: &Identifier) -> Option<&Module> {
+        self.modules
+            .get(name)
+            .or_else(|| self.library.get(name).map(LibraryModule::get))
+    }
+
+    fn get_mut(&mut self, name: &Identifier) -> Option<&mut Module> {
+        if self.modules.contains(name) {
+            self.modules.get_mut(name)
+        } else {
+            self.library.get_mut(name).map(LibraryModule::get_mut)
+        }
+    }
+
+    fn get_version(&self, name: &Identifier, version_uri: &Url) -> Option<&Module> {
+        self.modules.get_version(name, version_uri).or_else(|| {
+            self.library
+                .get(name)
+                .map(LibraryModule::get)
+                .filter(|module| module.version_uri().map(|v| v.value()) == Some(version_uri))
+        })
+    }
+
+    fn get_version_mut(&mut self, name: &Identifier, version_uri: &Url) -> Option<&mut Module> {
+        if self.modules.contains_version(name, version_uri) {
+            self.modules.get_version_mut(name, version_uri)
+        } else {
+            self.library
+                .get_mut(name)
+                .map(LibraryModule::get_mut)
+                .filter(|module| module.version_uri().map(|v| v.value()) == Some(version_uri))
+        }
+    }
+
+    fn module_versions(&self, name: &Identifier) -> impl Iterator<Item = &Module> {
+        let library = if self.modules.contains(name) {
+            None
+        } else {
+            self.library.get(name).map(LibraryModule::get)
+        };
+        self.modules.module_versions(name).chain(library)
+    }
+
+    fn modules(&self) -> impl Iterator<Item = &Module> {
+        self.modules.modules().chain(
+            self.library
+                .iter()
+                .filter(|(name, _)| !self.modules.contains(name))
+                .map(|(_, library)| library.get()),
+        )
+    }
+
+    fn module_names(&self) -> impl Iterator<Item = &Identifier> {
+        self.modules.module_names().chain(self.library_names())
+    }
+
+    fn insert(&mut self, module: Module) {
+        self.modules.insert(module)
+    }
+
+    fn remove(&mut self, name: &Identifier) -> bool {
+        let removed = self.modules.remove(name);
+        self.library.remove(name).is_some() || removed
+    }
+
+    fn uri_to_module_name(&self, url: &Url) -> Option<&Identifier> {
+        self.modules.uri_to_module_name(url).or_else(|| {
+            self.library
+                .iter()
+                .find(|(_, library)| &library.uri == url)
+                .map(|(name, _)| name)
+        })
+    }
+
+    fn module_name_to_uri(&self, name: &Identifier) -> Option<&Url> {
+        self.modules
+            .module_name_to_uri(name)
+            .or_else(|| self.library.get(name).map(|library| &library.uri))
+    }
+
+    fn get_parsed(&self, source: &str) -> Option<Module> {
+        let path = self.entry_path(source)?;
+        trace!("DiskModuleCache::get_parsed(...) => {path:?}");
+        let entry = fs::read_to_string(&path).ok()?;
+        match serde_json::from_str::<Module>(&entry) {
+            Ok(module) => {
+                debug!("read parsed module {} from cache {path:?}", module.name());
+                Some(module)
+            }
+            Err(e) => {
+                warn!("ignoring unreadable cache entry {path:?}, error: {e}");
+                let _ = fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    fn cache_parsed(&mut self, source: &str, module: &Module) {
+        if let Some(path) = self.entry_path(source) {
+            trace!(
+                "DiskModuleCache::cache_parsed(..., {}) => {path:?}",
+                module.name()
+            );
+            if let Err(e) = write_entry(&path, module) {
+                warn!("could not write cache entry {path:?}, error: {e}");
+            }
+        }
+    }
+}
+
+impl DiskModuleCache {
+    ///
+    /// Construct a store that persists parsed modules in `directory`, which is created if it
+    /// does not exist.
+    ///
+    pub fn open<P: Into<PathBuf>>(directory: P) -> Result<Self, Error> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+        Ok(Self {
+            directory: Some(directory),
+            ..Default::default()
+        })
+    }
+
+    ///
+    /// Construct a store with all of the standard library modules available, each module is only
+    /// constructed when first retrieved.
+    ///
+    pub fn with_stdlib(self) -> Self {
+        let mut self_mut = self;
+        for (name, uri) in stdlib::library_modules() {
+            let name = Identifier::new_unchecked(name);
+            let _ = self_mut.library.insert(
+                name.clone(),
+                LibraryModule {
+                    name,
+                    uri: Url::parse(uri).unwrap(),
+                    module: OnceCell::new(),
+                },
+            );
+        }
+        self_mut
+    }
+
+    ///
+    /// Set the tool version included in the key of each cache entry, this should change whenever
+    /// the parser, or the model, changes in a way that makes previously cached modules invalid.
+    ///
+    pub fn with_tool_version<S: Into<String>>(self, tool_version: S) -> Self {
+        Self {
+            tool_version: tool_version.into(),
+            ..self
+        }
+    }
+
+    ///
+    /// Builder-like function to add a module to a newly constructed store.
+    ///
+    pub fn with(self, module: Module) -> Self {
+        let mut self_mut = self;
+        self_mut.insert(module);
+        self_mut
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn directory(&self) -> Option<&PathBuf> {
+        self.directory.as_ref()
+    }
+
+    pub fn tool_version(&self) -> &String {
+        &self.tool_version
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Return the key of the cache entry for `source`, a hex-encoded SHA-256 hash of the tool
+    /// version and the source text.
+    ///
+    pub fn entry_key(&self, source: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.tool_version.as_bytes());
+        hasher.update([0]);
+        hasher.update(source.as_bytes());
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    ///
+    /// Remove all entries from the cache directory, returning the number of entries removed.
+    ///
+    pub fn clear(&self) -> Result<usize, Error> {
+        let mut count = 0;
+        if let Some(directory) = &self.directory {
+            for entry in fs::read_dir(directory)? {
+                let path = entry?.path();
+                if path
+                    .extension()
+                    .is_some_and(|ext| ext == CACHE_ENTRY_FILE_EXTENSION)
+                {
+                    fs::remove_file(path)?;
+                    count += 1;
+                }
+            }
+        }
+        Ok(count)
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    fn library_names(&self) -> impl Iterator<Item = &Identifier> {
+        self.library
+            .keys()
+            .filter(|name| !self.modules.contains(name))
+    }
+
+    fn entry_path(&self, source: &str) -> Option<PathBuf> {
+        self.directory.as_ref().map(|directory| {
+            directory.join(format!(
+                "{}.{CACHE_ENTRY_FILE_EXTENSION}",
+                self.entry_key(source)
+            ))
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl LibraryModule {
+    fn get(&self) -> &Module {
+        self.module.get_or_init(|| self.construct())
+    }
+
+    fn get_mut(&mut self) -> &mut Module {
+        let _ = self.get();
+        self.module.get_mut().unwrap()
+    }
+
+    fn construct(&self) -> Module {
+        stdlib::library_module(&self.name)
+            .expect("a library module name must name a library module")
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+// Write to a temporary file first so that concurrent readers never see a partial entry.
+fn write_entry(path: &Path, module: &Module) -> Result<(), Error> {
+    let entry = serde_json::to_string(module).map_err(std::io::Error::other)?;
+    let temporary = path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&temporary, entry)?;
+    fs::rename(&temporary, path)?;
+    Ok(())
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::HasBody;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_stdlib_is_lazy() {
+        let store = DiskModuleCache::default().with_stdlib();
+        let xsd = Identifier::from_str("xsd").unwrap();
+        assert!(store.library.get(&xsd).unwrap().module.get().is_none());
+        assert!(store.contains(&xsd));
+        assert_eq!(
+            store.uri_to_module_name(&Url::parse(stdlib::xsd::MODULE_URL).unwrap()),
+            Some(&xsd)
+        );
+        assert!(store.library.get(&xsd).unwrap().module.get().is_none());
+        assert!(store.get(&xsd).is_some());
+        assert!(store.library.get(&xsd).unwrap().module.get().is_some());
+        assert_eq!(store.len(), 10);
+    }
+
+    #[test]
+    fn test_parsed_module_round_trip() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut store = DiskModuleCache::open(directory.path()).unwrap();
+        let source = "module example is end";
+        assert!(store.get_parsed(source).is_none());
+
+        let module = stdlib::rdfs::module();
+        store.cache_parsed(source, &module);
+        let cached = store.get_parsed(source).unwrap();
+        assert_eq!(cached.name(), module.name());
+        assert_eq!(
+            cached.base_uri().map(|v| v.value()),
+            module.base_uri().map(|v| v.value())
+        );
+        assert_eq!(
+            cached.body().definitions().count(),
+            module.body().definitions().count()
+        );
+
+        // A different source, or tool version, has a different key.
+        assert!(store.get_parsed("module example is end\n").is_none());
+        let store = store.with_tool_version("other");
+        assert!(store.get_parsed(source).is_none());
+
+        assert_eq!(store.clear().unwrap(), 1);
+    }
+}
@@ -0,0 +1,490 @@
+/*!
+Provides a cross-module index of the definitions in a [`ModuleStore`] to answer questions that
+would otherwise require walking every module.
+
+The index is built once, from all of the modules in a store or from a selection of them, and holds
+only names so it does not borrow the store. All names are qualified; unqualified references within
+a module are qualified with the name of the module that defines them, either the module itself or
+the module a member import names. The following questions are answered by the index.
+
+1. Every definition of a given kind, [`ModuleIndex::definitions_of_kind`].
+2. Every member whose type is a given definition, [`ModuleIndex::members_of_type`].
+3. Every definition that references a given definition, [`ModuleIndex::references_to`], this is
+   the reverse of the [`References`] trait.
+4. Every definition with a given annotation property, and optionally value,
+   [`ModuleIndex::annotated_with`].
+5. The sub-types, [`ModuleIndex::subtypes_of`], and instances, [`ModuleIndex::instances_of`], of a
+   type or type class.
+
+# Example
+
+```rust
+use sdml_core::model::definitions::DefinitionKind;
+use sdml_core::model::identifiers::QualifiedIdentifier;
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use std::str::FromStr;
+
+let store = InMemoryModuleCache::default().with_stdlib();
+let index = store.index();
+
+let integer = QualifiedIdentifier::from_str("xsd:integer").unwrap();
+let long = QualifiedIdentifier::from_str("xsd:long").unwrap();
+
+assert!(index
+    .definitions_of_kind(DefinitionKind::Rdf)
+    .any(|name| name == &integer));
+assert!(index.subtypes_of(&integer).any(|name| name == &long));
+```
+
+[`References`]: crate::model::References
+*/
+
+use crate::model::annotations::{Annotation, HasAnnotations};
+use crate::model::definitions::{Definition, DefinitionKind, HasMembers};
+use crate::model::identifiers::{Identifier, IdentifierReference, QualifiedIdentifier};
+use crate::model::members::{Member, MemberKind, TypeReference};
+use crate::model::modules::{Import, Module};
+use crate::model::values::Value;
+use crate::model::{HasBody, HasName, HasNameReference, HasOptionalBody, References};
+use crate::stdlib;
+use crate::store::ModuleStore;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Display;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// An index of the definitions in a set of modules.
+///
+#[derive(Clone, Debug, Default)]
+pub struct ModuleIndex {
+    definitions: BTreeMap<QualifiedIdentifier, DefinitionKind>,
+    by_kind: BTreeMap<DefinitionKind, BTreeSet<QualifiedIdentifier>>,
+    members_by_type: BTreeMap<QualifiedIdentifier, BTreeSet<QualifiedMember>>,
+    references: BTreeMap<QualifiedIdentifier, BTreeSet<QualifiedIdentifier>>,
+    annotations: BTreeMap<QualifiedIdentifier, Vec<(QualifiedIdentifier, Value)>>,
+    subtypes: BTreeMap<QualifiedIdentifier, BTreeSet<QualifiedIdentifier>>,
+    instances: BTreeMap<QualifiedIdentifier, BTreeSet<QualifiedIdentifier>>,
+}
+
+///
+/// The name of a member within a definition, displayed as `module:Definition.member`.
+///
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QualifiedMember {
+    definition: QualifiedIdentifier,
+    member: Identifier,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// Qualifies unqualified references within a single module.
+#[derive(Debug)]
+struct Qualifier<'a> {
+    module: &'a Identifier,
+    local_names: HashSet<&'a Identifier>,
+    member_imports: HashMap<&'a Identifier, &'a Identifier>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for QualifiedMember {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.definition, self.member)
+    }
+}
+
+impl QualifiedMember {
+    pub const fn new(definition: QualifiedIdentifier, member: Identifier) -> Self {
+        Self { definition, member }
+    }
+
+    pub const fn definition(&self) -> &QualifiedIdentifier {
+        &self.definition
+    }
+
+    pub const fn member(&self) -> &Identifier {
+        &self.member
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl ModuleIndex {
+    // --------------------------------------------------------------------------------------------
+    // Constructors
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Index all of the modules in `store`.
+    ///
+    pub fn from_store(store: &impl ModuleStore) -> Self {
+        Self::from_modules(store.modules(), store)
+    }
+
+    ///
+    /// Index only the modules in `modules`; `store` is used to resolve the types of members that
+    /// reference a property definition.
+    ///
+    pub fn from_modules<'a, I>(modules: I, store: &impl ModuleStore) -> Self
+    where
+        I: IntoIterator<Item = &'a Module>,
+    {
+        let mut index = Self::default();
+        for module in modules {
+            index.add_module(module, store);
+        }
+        index
+    }
+
+    // --------------------------------------------------------------------------------------------
+    // Queries
+    // --------------------------------------------------------------------------------------------
+
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.definitions.len()
+    }
+
+    pub fn contains(&self, name: &QualifiedIdentifier) -> bool {
+        self.definitions.contains_key(name)
+    }
+
+    ///
+    /// The kind of the definition `name`, if it is in the index.
+    ///
+    pub fn kind_of(&self, name: &QualifiedIdentifier) -> Option<DefinitionKind> {
+        self.definitions.get(name).copied()
+    }
+
+    ///
+    /// All the definitions in the index, ordered by name.
+    ///
+    pub fn definitions(&self) -> impl Iterator<Item = (&QualifiedIdentifier, DefinitionKind)> {
+        self.definitions.iter().map(|(name, kind)| (name, *kind))
+    }
+
+    ///
+    /// Every definition of the given `kind`.
+    ///
+    pub fn definitions_of_kind(
+        &self,
+        kind: DefinitionKind,
+    ) -> impl Iterator<Item = &QualifiedIdentifier> {
+        self.by_kind.get(&kind).into_iter().flatten()
+    }
+
+    ///
+    /// Every member, of an entity, event, or structure, whose type is `type_name`. For a mapping
+    /// type both the domain and range are considered.
+    ///
+    pub fn members_of_type(
+        &self,
+        type_name: &QualifiedIdentifier,
+    ) -> impl Iterator<Item = &QualifiedMember> {
+        self.members_by_type.get(type_name).into_iter().flatten()
+    }
+
+    ///
+    /// Every definition that references `name`, as a type or as an annotation property.
+    ///
+    pub fn references_to(
+        &self,
+        name: &QualifiedIdentifier,
+    ) -> impl Iterator<Item = &QualifiedIdentifier> {
+        self.references.get(name).into_iter().flatten()
+    }
+
+    ///
+    /// Every definition with an annotation property named `property`, and if `value` is
+    /// provided, with that value.
+    ///
+    pub fn annotated_with<'a>(
+        &'a self,
+        property: &QualifiedIdentifier,
+        value: Option<&'a Value>,
+    ) -> impl Iterator<Item = &'a QualifiedIdentifier> {
+        let mut names: Vec<&QualifiedIdentifier> = self
+            .annotations
+            .get(property)
+            .into_iter()
+            .flatten()
+            .filter(move |(_, v)| value.map(|value| value == v).unwrap_or(true))
+            .map(|(name, _)| name)
+            .collect();
+        names.sort();
+        names.dedup();
+        names.into_iter()
+    }
+
+    ///
+    /// The annotation property values for `property` on the definition `name`.
+    ///
+    pub fn annotation_values<'a>(
+        &'a self,
+        name: &'a QualifiedIdentifier,
+        property: &QualifiedIdentifier,
+    ) -> impl Iterator<Item = &'a Value> {
+        self.annotations
+            .get(property)
+            .into_iter()
+            .flatten()
+            .filter(move |(n, _)| n == name)
+            .map(|(_, value)| value)
+    }
+
+    ///
+    /// The direct sub-types of `name`. These are datatypes with `name` as their base type, type
+    /// classes with a variable restricted by the class `name`, and RDF definitions with the
+    /// annotation `rdfs:subClassOf = name`.
+    ///
+    pub fn subtypes_of(
+        &self,
+        name: &QualifiedIdentifier,
+    ) -> impl Iterator<Item = &QualifiedIdentifier> {
+        self.subtypes.get(name).into_iter().flatten()
+    }
+
+    ///
+    /// All sub-types of `name`, direct and indirect.
+    ///
+    pub fn all_subtypes_of(&self, name: &QualifiedIdentifier) -> BTreeSet<&QualifiedIdentifier> {
+        let mut found: BTreeSet<&QualifiedIdentifier> = Default::default();
+        let mut pending: Vec<&QualifiedIdentifier> = self.subtypes_of(name).collect();
+        while let Some(subtype) = pending.pop() {
+            if found.insert(subtype) {
+                pending.extend(self.subtypes_of(subtype));
+            }
+        }
+        found
+    }
+
+    ///
+    /// The instances of `name`, these are RDF definitions with the annotation `rdf:type = name`.
+    ///
+    pub fn instances_of(
+        &self,
+        name: &QualifiedIdentifier,
+    ) -> impl Iterator<Item = &QualifiedIdentifier> {
+        self.instances.get(name).into_iter().flatten()
+    }
+
+    // --------------------------------------------------------------------------------------------
+    // Private
+    // --------------------------------------------------------------------------------------------
+
+    fn add_module(&mut self, module: &Module, store: &impl ModuleStore) {
+        let qualifier = Qualifier::new(module);
+        let rdf_type = qualified(stdlib::rdf::MODULE_NAME, stdlib::rdf::TYPE);
+        let rdfs_sub_class_of = qualified(stdlib::rdfs::MODULE_NAME, stdlib::rdfs::SUB_CLASS_OF);
+
+        for definition in module.body().definitions() {
+            let name = QualifiedIdentifier::new(
+                module.name().without_span(),
+                definition.name().without_span(),
+            );
+            let kind = definition.kind();
+            let _ = self.definitions.insert(name.clone(), kind);
+            let _ = self.by_kind.entry(kind).or_default().insert(name.clone());
+
+            // Reverse references.
+            let mut referenced = HashSet::default();
+            definition.referenced_types(&mut referenced);
+            definition.referenced_annotations(&mut referenced);
+            for reference in referenced.into_iter().filter_map(|r| qualifier.qualify(r)) {
+                if reference != name {
+                    let _ = self
+                        .references
+                        .entry(reference)
+                        .or_default()
+                        .insert(name.clone());
+                }
+            }
+
+            // Members by type.
+            for member in definition_members(definition) {
+                let target_type = match member.kind() {
+                    MemberKind::Definition(def) => Some(def.target_type().clone()),
+                    MemberKind::Reference(_) => member.resolve_target_type(module, store),
+                };
+                let mut types = Vec::default();
+                if let Some(target_type) = &target_type {
+                    type_names(target_type, &mut types);
+                }
+                for type_name in types.into_iter().filter_map(|t| qualifier.qualify(t)) {
+                    let _ = self.members_by_type.entry(type_name).or_default().insert(
+                        QualifiedMember::new(name.clone(), member.name().without_span()),
+                    );
+                }
+            }
+
+            // Annotations, including the sub-type and instance relationships they declare.
+            for annotation in definition_annotations(definition) {
+                if let Annotation::Property(property) = annotation {
+                    let Some(property_name) = qualifier.qualify(property.name_reference()) else {
+                        continue;
+                    };
+                    let _ = self
+                        .references
+                        .entry(property_name.clone())
+                        .or_default()
+                        .insert(name.clone());
+                    if let Some(value) = property
+                        .value()
+                        .as_reference()
+                        .and_then(|value| qualifier.qualify(value))
+                    {
+                        if property_name == rdf_type {
+                            let _ = self
+                                .instances
+                                .entry(value)
+                                .or_default()
+                                .insert(name.clone());
+                        } else if property_name == rdfs_sub_class_of {
+                            let _ = self.subtypes.entry(value).or_default().insert(name.clone());
+                        }
+                    }
+                    self.annotations
+                        .entry(property_name)
+                        .or_default()
+                        .push((name.clone(), property.value().clone()));
+                }
+            }
+
+            // Other sub-type relationships.
+            let supertypes: Vec<&IdentifierReference> = match definition {
+                Definition::Datatype(def) => vec![def.base_type()],
+                Definition::TypeClass(def) => def
+                    .variables()
+                    .flat_map(|variable| variable.restrictions())
+                    .map(|restriction| restriction.name())
+                    .collect(),
+                _ => Vec::default(),
+            };
+            for supertype in supertypes.into_iter().filter_map(|t| qualifier.qualify(t)) {
+                if supertype != name {
+                    let _ = self
+                        .subtypes
+                        .entry(supertype)
+                        .or_default()
+                        .insert(name.clone());
+                }
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a> Qualifier<'a> {
+    fn new(module: &'a Module) -> Self {
+        Self {
+            module: module.name(),
+            local_names: module.defined_names(),
+            member_imports: module
+                .body()
+                .imports()
+                .flat_map(|statement| statement.imports())
+                .filter_map(|import| match import {
+                    Import::Member(v) => Some((v.member(), v.module())),
+                    Import::Module(_) => None,
+                })
+                .collect(),
+        }
+    }
+
+    // Unqualified references that are neither defined locally, imported by member, nor a
+    // builtin type are left unresolved.
+    fn qualify(&self, reference: &IdentifierReference) -> Option<QualifiedIdentifier> {
+        match reference {
+            IdentifierReference::Identifier(name) => {
+                let module = if self.local_names.contains(name) {
+                    self.module.without_span()
+                } else if let Some(module) = self.member_imports.get(name) {
+                    module.without_span()
+                } else if stdlib::is_builtin_type_name(name) {
+                    Identifier::new_unchecked(stdlib::sdml::MODULE_NAME)
+                } else {
+                    return None;
+                };
+                Some(QualifiedIdentifier::new(module, name.without_span()))
+            }
+            IdentifierReference::QualifiedIdentifier(name) => Some(QualifiedIdentifier::new(
+                name.module().without_span(),
+                name.member().without_span(),
+            )),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn definition_members(definition: &Definition) -> Vec<&Member> {
+    match definition {
+        Definition::Entity(def) => def
+            .body()
+            .map(|body| {
+                std::iter::once(body.identity())
+                    .chain(body.members())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Definition::Event(def) => def
+            .body()
+            .map(|body| body.members().collect())
+            .unwrap_or_default(),
+        Definition::Structure(def) => def
+            .body()
+            .map(|body| body.members().collect())
+            .unwrap_or_default(),
+        _ => Vec::default(),
+    }
+}
+
+fn definition_annotations(definition: &Definition) -> Vec<&Annotation> {
+    match definition {
+        Definition::Datatype(def) => optional_annotations(def.body()),
+        Definition::Entity(def) => optional_annotations(def.body()),
+        Definition::Enum(def) => optional_annotations(def.body()),
+        Definition::Event(def) => optional_annotations(def.body()),
+        Definition::Property(def) => optional_annotations(def.member_def().body()),
+        Definition::Rdf(def) => def.body().annotations().collect(),
+        Definition::Structure(def) => optional_annotations(def.body()),
+        Definition::TypeClass(def) => optional_annotations(def.body()),
+        Definition::Union(def) => optional_annotations(def.body()),
+    }
+}
+
+fn optional_annotations<A: HasAnnotations>(body: Option<&A>) -> Vec<&Annotation> {
+    body.map(|body| body.annotations().collect())
+        .unwrap_or_default()
+}
+
+fn type_names<'a>(target_type: &'a TypeReference, names: &mut Vec<&'a IdentifierReference>) {
+    match target_type {
+        TypeReference::Unknown => {}
+        TypeReference::Type(name) => names.push(name),
+        TypeReference::MappingType(mapping) => {
+            type_names(mapping.domain(), names);
+            type_names(mapping.range(), names);
+        }
+    }
+}
+
+fn qualified(module: &str, member: &str) -> QualifiedIdentifier {
+    QualifiedIdentifier::new(
+        Identifier::new_unchecked(module),
+        Identifier::new_unchecked(member),
+    )
+}
@@ -0,0 +1,133 @@
+count_of_bugs = {$count} Fehler in der Implementierung
+count_of_errors = {$count} Fehler
+count_of_informational = {$count} Hinweise
+count_of_warnings = {$count} Warnungen
+counts_generated_summary = erzeugt: {$counts}
+
+help_alternative_terms = Hilfe: erwägen Sie einen der folgenden Begriffe: {$terms}
+help_datatype_invalid_base_type = Hilfe: eine Typreferenz an dieser Stelle muss auf eine Datentypdefinition verweisen
+help_deprecated_term_reason = Hilfe: {$reason}
+help_error_node = Hilfe: im Syntaxbaum wurde ein tree-sitter-ERROR-Knoten gefunden
+help_definition_description = Hilfe: fügen Sie eine Annotation `skos:definition` oder `dc:description` hinzu, um diese Definition zu beschreiben
+help_entity_identity = Hilfe: fügen Sie ein Identitätsmitglied hinzu, zum Beispiel `identity id -> string`
+help_feature_set_not_a_union = Hilfe: eine Typreferenz an dieser Stelle muss auf eine Union-Definition verweisen
+help_identity_cardinality = Hilfe: ein identifizierendes Mitglied sollte genau einen Wert haben, zum Beispiel `{"{"}1{"}"}`
+help_more_details_url = Hilfe: weitere Einzelheiten finden Sie unter <{$url}>
+help_property_reference_not_property = Hilfe: eine Typreferenz an dieser Stelle muss auf eine Eigenschaftsdefinition verweisen
+help_single_variant_enum = Hilfe: prüfen Sie, ob ein Datentyp besser geeignet ist oder ob Varianten fehlen
+help_type_definition_not_found = Hilfe: haben Sie vergessen, diesen Typ zu importieren oder seinen Namen zu qualifizieren?
+help_unreachable_definition = Hilfe: referenzieren Sie diese Definition von einer Wurzel aus, oder entfernen Sie sie mit `sdml unused --prune`
+
+lbl_actual_node_kind = tatsächliche Knotenart: {$kind}
+lbl_actual_this_version_uri = das Modul enthielt diese Versions-URI
+lbl_actual_version_uri = tatsächliche Versions-URI: `<{$url}>`
+lbl_definition_name = Name der Definition: `{$name}`
+lbl_expected_this_version_uri = diese Versions-URI wurde erwartet
+lbl_expected_version_uri = erwartete Versions-URI: `<{$url}>`
+lbl_expecting_node_kind = erwartete Knotenart: {$kind}
+lbl_expecting_one_of_node_kind = erwartet eine der Knotenarten: {$kind}
+lbl_here = hier
+lbl_identifier = Bezeichner: `{$name}`
+lbl_in_this = in: `{$val}`
+lbl_import_cycle = Importzyklus: {$path}
+lbl_import_name = importierter Name: `{$name}`
+lbl_in_grammar_rule = in Grammatikregel: `{$name}`
+lbl_missing_node_kind = fehlender Knoten der Art: `{$kind}`
+lbl_missing_node_kind_in_variable = fehlender Knoten der Art: `{$kind}`, im Feld: `{$field_name}`
+lbl_member_name = Name des Mitglieds: `{$name}`
+lbl_module_name_short = Modul `{$name}`
+lbl_module_name = Name des Moduls: `{$name}`
+lbl_not_valid_for_type = für diesen Typ nicht gültig
+lbl_parser = Parser
+lbl_previously_defined_here = wurde bereits hier definiert
+lbl_previously_imported_here = wurde bereits hier importiert
+lbl_continues_import_cycle = setzt den Importzyklus fort
+lbl_conflicts_with_definition = steht im Konflikt mit dieser Definition
+lbl_definition_difference = Unterschied: {$change}
+lbl_property_name = Name der Eigenschaft: `{$name}`
+lbl_rdf_name = RDF-Name: `{$name}`
+lbl_term_name = gefundener Begriff: `{$name}`
+lbl_this_definition = diese Definition
+lbl_this_definition_name = dieser Definitionsname
+lbl_this_identifier = dieser Bezeichner
+lbl_this_import = dieser Import
+lbl_this_language_tag = dieses Sprach-Tag
+lbl_this_member = dieses Mitglied
+lbl_this_member_name = dieser Mitgliedsname
+lbl_this_module = dieses Modul
+lbl_this_reference = diese Referenz
+lbl_this_suppression = diese Unterdrückung
+lbl_suppressed_code = unterdrückter Code: `{$code}`
+lbl_this_type = dieser Typ
+lbl_this_usage = diese Verwendung
+lbl_this_value = dieser Wert
+lbl_this_variant = diese Variante
+lbl_this_variant_name = dieser Variantenname
+lbl_type_name = Name des Typs: `{$name}`
+lbl_typeclass_name = Name der Typklasse: `{$name}`
+lbl_value = Wert: `{$val}`
+lbl_expected_case = erwartet: {$case}
+lbl_specific_error = konkreter Fehler: {$err}
+
+lbl_case_module = Snake Case (snake_case)
+lbl_case_member = Snake Case (snake_case) oder Lower Camel Case (lowerCamelCase)
+lbl_case_imported_member = Snake Case (snake_case), Lower Camel Case (lowerCamelCase) oder Upper Camel Case (UpperCamelCase)
+lbl_case_datatype = Snake Case (snake_case), Lower Camel Case (lowerCamelCase) oder Upper Camel Case (UpperCamelCase)
+lbl_case_property = Snake Case (snake_case) oder Lower Camel Case (lowerCamelCase)
+lbl_case_rdf = Snake Case (snake_case), Lower Camel Case (lowerCamelCase) oder Upper Camel Case (UpperCamelCase)
+lbl_case_type_defn = Upper Camel Case (UpperCamelCase)
+lbl_case_value_variant = Upper Camel Case (UpperCamelCase) oder Shouty Snake Case (SHOUTY_SNAKE_CASE)
+
+msg_conflicting_definition = gleichnamige Definitionen in zusammengeführten Modulen unterscheiden sich
+msg_datatype_invalid_base = ungültiger Basistyp für einen Datentyp, kein Datentyp
+msg_definition_not_found = Definition im Modul nicht gefunden
+msg_deprecated_term_used = veralteter Begriff gefunden, erwägen Sie eine Alternative
+msg_definition_without_description = diese Definition hat keine Beschreibung
+msg_double_underscored_identifier = Bezeichner sollten doppelte Unterstriche vermeiden
+msg_duplicate_definition_import = doppelter Import einer Definition
+msg_duplicate_definition_name = eine Definition mit diesem Namen existiert bereits in diesem Modul
+msg_duplicate_member_name = ein Mitglied mit diesem Namen existiert bereits in dieser Definition
+msg_duplicate_module_import = doppelter Import eines Moduls
+msg_duplicate_variant_name = eine Variante mit diesem Namen existiert bereits in dieser Definition
+msg_entity_without_identity = diese Entität hat kein Identitätsmitglied
+msg_featureset_not_union = ungültiger Typ für ein Feature-Set, keine Union
+msg_incomplete_definition = diese Definition ist unvollständig
+msg_incomplete_member = dieses Mitglied ist unvollständig
+msg_incomplete_module = dieses Modul ist unvollständig
+msg_invalid_identifier = ungültiger Wert für einen Bezeichner
+msg_invalid_language_tag = ungültiger Wert für ein Sprach-Tag
+msg_invalid_module_base_url = die Basis-URL des Moduls ist ungültig oder nicht absolut
+msg_invalid_module_version_url = die Versions-URL des Moduls ist ungültig oder nicht absolut
+msg_invalid_value_for_type = ungültiger Literalwert für seinen Typ
+msg_imported_module_not_found = das in der Importanweisung genannte Modul wurde nicht gefunden
+msg_module_import_cycle = das Modul ist Teil eines Importzyklus
+msg_module_not_found = Modul nicht gefunden
+msg_module_version_info_empty = die Versionsinformation des Moduls ist leer
+msg_module_version_mismatch = die tatsächliche Modul-URI entspricht nicht der Importanforderung
+msg_module_version_not_found = das importierte Modul hat keine Versions-URI
+msg_property_incompatible = eine Eigenschaftsdefinition ist an dieser Stelle nicht zulässig
+msg_property_reference_not_property = das Mitglied referenziert etwas als Eigenschaft, das keine Eigenschaft ist
+msg_library_definition_not_allowed = Definitionsart in einem Modul, das keine Bibliothek ist, nicht erlaubt
+msg_rdf_definition_incompatible = eine RDF-Definition ist an dieser Stelle nicht zulässig
+msg_string_without_language = dieser Zeichenkettenwert hat kein Sprach-Tag
+msg_treesitter_error_node = tree-sitter-Parserfehler aufgetreten
+msg_treesitter_missing_node = ein erwarteter tree-sitter-Knoten fehlt
+msg_treesitter_unexpected_node = unerwarteter tree-sitter-Knoten gefunden
+msg_single_variant_enum = diese Aufzählung hat nur eine einzige Variante
+msg_type_definition_not_found = Typdefinition im Modul nicht gefunden
+msg_typeclass_incompatible = eine Typklassendefinition ist an dieser Stelle nicht zulässig
+msg_unbounded_identity_cardinality = dieses identifizierende Mitglied hat eine unbeschränkte Kardinalität
+msg_unconstrained_datatype = dieser Datentyp wird ohne jede Einschränkung verwendet
+msg_unknown_member_type = der Typ dieses Mitglieds ist `unknown`
+msg_unreachable_definition = diese Definition ist von keiner Wurzeldefinition aus erreichbar
+msg_unused_definition = diese Definition wird in ihrem Modul nicht verwendet
+msg_unused_import = dieser Import wird im Modul nicht verwendet
+msg_unused_suppression = der unterdrückte Diagnosecode wurde in diesem Bereich nicht gemeldet
+msg_validation_incomplete = die Validierung ist für diesen Typ möglicherweise unvollständig
+msg_not_preferred_case = Bezeichner verwendet nicht die bevorzugte Schreibweise
+
+word_bug = Implementierungsfehler
+word_error = Fehler
+word_help = Hilfe
+word_note = Hinweis
+word_warning = Warnung
@@ -8,10 +8,15 @@ help_alternative_terms = help: consider one of: {$terms}
 help_datatype_invalid_base_type = help: A type reference in this position must refer to a datatype definition
 help_deprecated_term_reason = help: {$reason}
 help_error_node = help: encountered a tree-sitter ERROR node in the parse tree
+help_definition_description = help: add a `skos:definition` or `dc:description` annotation to describe this definition
+help_entity_identity = help: add an identity member, for example `identity id -> string`
 help_feature_set_not_a_union = help: A type reference in this position must refer to a union definition
+help_identity_cardinality = help: an identifying member should have exactly one value, for example `{"{"}1{"}"}`
 help_more_details_url = help: for more details, see <{$url}>
 help_property_reference_not_property = help: A type reference in this position must refer to a property definition
+help_single_variant_enum = help: consider whether this is better modeled as a datatype, or whether variants are missing
 help_type_definition_not_found = help: did you forget to add an import for this type, or qualify it's name
+help_unreachable_definition = help: reference this definition from a root, or remove it with `sdml unused --prune`
 
 lbl_actual_node_kind = actual node kind: {$kind}
 lbl_actual_this_version_uri = module contained this version URI
@@ -24,6 +29,8 @@ lbl_expecting_one_of_node_kind = expecting one of node kinds: {$kind}
 lbl_here = here
 lbl_identifier = identifier: `{$name}`
 lbl_in_this = in_this: `{$val}`
+lbl_import_cycle = import cycle: {$path}
+lbl_import_name = imported name: `{$name}`
 lbl_in_grammar_rule = in grammar rule: `{$name}`
 lbl_missing_node_kind = missing node of kind: `{$kind}`
 lbl_missing_node_kind_in_variable = missing node of kind: `{$kind}`, in field: `{$field_name}`
@@ -34,6 +41,9 @@ lbl_not_valid_for_type = not valid for this type
 lbl_parser = parser
 lbl_previously_defined_here = was previously defined here
 lbl_previously_imported_here = was previously imported here
+lbl_continues_import_cycle = continues the import cycle
+lbl_conflicts_with_definition = conflicts with this definition
+lbl_definition_difference = difference: {$change}
 lbl_property_name = property name: `{$name}`
 lbl_rdf_name = RDF name: `{$name}`
 lbl_term_name = found term: `{$name}`
@@ -46,6 +56,8 @@ lbl_this_member = this member
 lbl_this_member_name = this member name
 lbl_this_module = this module
 lbl_this_reference = this reference
+lbl_this_suppression = this suppression
+lbl_suppressed_code = suppressed code: `{$code}`
 lbl_this_type = this type
 lbl_this_usage = this usage
 lbl_this_value = this value
@@ -66,15 +78,18 @@ lbl_case_rdf = snake case (snake_case), lower camel case (lowerCamelCase), or up
 lbl_case_type_defn = upper camel case (UpperCamelCase)
 lbl_case_value_variant= upper camel (UpperCamelCase) or shouty snake case (SHOUTY_SNAKE_CASE)
 
+msg_conflicting_definition = definitions with the same name in merged modules are different
 msg_datatype_invalid_base = invalid type for datatype base, not a datatype
 msg_definition_not_found = definition not found in module
 msg_deprecated_term_used = found a deprecated term, consider an alternative
+msg_definition_without_description = this definition has no description
 msg_double_underscored_identifier = identifiers should avoid using double underscores
 msg_duplicate_definition_import = duplicate import of definition
 msg_duplicate_definition_name = a definition with this name already exists in this module
 msg_duplicate_member_name = a member with this name already exists in this definition
 msg_duplicate_module_import = duplicate import of module
 msg_duplicate_variant_name = a variant with this name already exists in this definition
+msg_entity_without_identity = this entity has no identity member
 msg_featureset_not_union = invalid type for feature set, not a union
 msg_incomplete_definition = this definition is incomplete
 msg_incomplete_member = this member is incomplete
@@ -85,6 +100,7 @@ msg_invalid_module_base_url = module base URL is invalid or not absolute
 msg_invalid_module_version_url = module base URL is invalid or not absolute
 msg_invalid_value_for_type = invalid literal value for it's type
 msg_imported_module_not_found = module named in import statement not found
+msg_module_import_cycle = module is part of an import cycle
 msg_module_not_found = module not found
 msg_module_version_info_empty = module's version info string is empty
 msg_module_version_mismatch = actual module URI does not match import requirement
@@ -97,9 +113,16 @@ msg_string_without_language = this string value has no language tag
 msg_treesitter_error_node = tree-sitter parse error encountered
 msg_treesitter_missing_node = missing an expected tree-sitter node
 msg_treesitter_unexpected_node = encountered an unexpected tree-sitter node
+msg_single_variant_enum = this enum has only a single variant
 msg_type_definition_not_found = type definition not found in module
 msg_typeclass_incompatible = a type-class definition is not compatible in this location
+msg_unbounded_identity_cardinality = this identifying member has an unbounded cardinality
 msg_unconstrained_datatype = this datatype is used without any constraint
+msg_unknown_member_type = this member's type is `unknown`
+msg_unreachable_definition = this definition is not reachable from any root definition
+msg_unused_definition = this definition is not used within its module
+msg_unused_import = this import is not used within the module
+msg_unused_suppression = suppressed diagnostic code was not reported in this scope
 msg_validation_incomplete = validation may not be complete for this type
 msg_not_preferred_case = identifier not using preferred casing
 
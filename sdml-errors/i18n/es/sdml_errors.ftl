@@ -0,0 +1,133 @@
+count_of_bugs = {$count} fallos internos
+count_of_errors = {$count} errores
+count_of_informational = {$count} informativos
+count_of_warnings = {$count} advertencias
+counts_generated_summary = generados {$counts}
+
+help_alternative_terms = ayuda: considere uno de los siguientes términos: {$terms}
+help_datatype_invalid_base_type = ayuda: una referencia de tipo en esta posición debe referirse a una definición de tipo de datos
+help_deprecated_term_reason = ayuda: {$reason}
+help_error_node = ayuda: se encontró un nodo ERROR de tree-sitter en el árbol sintáctico
+help_definition_description = ayuda: añada una anotación `skos:definition` o `dc:description` para describir esta definición
+help_entity_identity = ayuda: añada un miembro de identidad, por ejemplo `identity id -> string`
+help_feature_set_not_a_union = ayuda: una referencia de tipo en esta posición debe referirse a una definición de unión
+help_identity_cardinality = ayuda: un miembro identificador debería tener exactamente un valor, por ejemplo `{"{"}1{"}"}`
+help_more_details_url = ayuda: para más detalles, consulte <{$url}>
+help_property_reference_not_property = ayuda: una referencia de tipo en esta posición debe referirse a una definición de propiedad
+help_single_variant_enum = ayuda: considere si un tipo de datos sería más adecuado, o si faltan variantes
+help_type_definition_not_found = ayuda: ¿olvidó importar este tipo o calificar su nombre?
+help_unreachable_definition = ayuda: referencie esta definición desde una raíz, o elimínela con `sdml unused --prune`
+
+lbl_actual_node_kind = tipo de nodo real: {$kind}
+lbl_actual_this_version_uri = el módulo contenía esta URI de versión
+lbl_actual_version_uri = URI de versión real: `<{$url}>`
+lbl_definition_name = nombre de la definición: `{$name}`
+lbl_expected_this_version_uri = se esperaba esta URI de versión
+lbl_expected_version_uri = URI de versión esperada: `<{$url}>`
+lbl_expecting_node_kind = tipo de nodo esperado: {$kind}
+lbl_expecting_one_of_node_kind = se esperaba uno de los tipos de nodo: {$kind}
+lbl_here = aquí
+lbl_identifier = identificador: `{$name}`
+lbl_in_this = en: `{$val}`
+lbl_import_cycle = ciclo de importación: {$path}
+lbl_import_name = nombre importado: `{$name}`
+lbl_in_grammar_rule = en la regla gramatical: `{$name}`
+lbl_missing_node_kind = falta un nodo de tipo: `{$kind}`
+lbl_missing_node_kind_in_variable = falta un nodo de tipo: `{$kind}`, en el campo: `{$field_name}`
+lbl_member_name = nombre del miembro: `{$name}`
+lbl_module_name_short = módulo `{$name}`
+lbl_module_name = nombre del módulo: `{$name}`
+lbl_not_valid_for_type = no es válido para este tipo
+lbl_parser = analizador
+lbl_previously_defined_here = se definió previamente aquí
+lbl_previously_imported_here = se importó previamente aquí
+lbl_continues_import_cycle = continúa el ciclo de importación
+lbl_conflicts_with_definition = entra en conflicto con esta definición
+lbl_definition_difference = diferencia: {$change}
+lbl_property_name = nombre de la propiedad: `{$name}`
+lbl_rdf_name = nombre RDF: `{$name}`
+lbl_term_name = término encontrado: `{$name}`
+lbl_this_definition = esta definición
+lbl_this_definition_name = este nombre de definición
+lbl_this_identifier = este identificador
+lbl_this_import = esta importación
+lbl_this_language_tag = esta etiqueta de idioma
+lbl_this_member = este miembro
+lbl_this_member_name = este nombre de miembro
+lbl_this_module = este módulo
+lbl_this_reference = esta referencia
+lbl_this_suppression = esta supresión
+lbl_suppressed_code = código suprimido: `{$code}`
+lbl_this_type = este tipo
+lbl_this_usage = este uso
+lbl_this_value = este valor
+lbl_this_variant = esta variante
+lbl_this_variant_name = este nombre de variante
+lbl_type_name = nombre del tipo: `{$name}`
+lbl_typeclass_name = nombre de la clase de tipos: `{$name}`
+lbl_value = valor: `{$val}`
+lbl_expected_case = se esperaba {$case}
+lbl_specific_error = error concreto: {$err}
+
+lbl_case_module = snake case (snake_case)
+lbl_case_member = snake case (snake_case) o lower camel case (lowerCamelCase)
+lbl_case_imported_member = snake case (snake_case), lower camel case (lowerCamelCase) o upper camel case (UpperCamelCase)
+lbl_case_datatype = snake case (snake_case), lower camel case (lowerCamelCase) o upper camel case (UpperCamelCase)
+lbl_case_property = snake case (snake_case) o lower camel case (lowerCamelCase)
+lbl_case_rdf = snake case (snake_case), lower camel case (lowerCamelCase) o upper camel case (UpperCamelCase)
+lbl_case_type_defn = upper camel case (UpperCamelCase)
+lbl_case_value_variant = upper camel case (UpperCamelCase) o shouty snake case (SHOUTY_SNAKE_CASE)
+
+msg_conflicting_definition = las definiciones con el mismo nombre en los módulos combinados son diferentes
+msg_datatype_invalid_base = tipo no válido para la base de un tipo de datos, no es un tipo de datos
+msg_definition_not_found = definición no encontrada en el módulo
+msg_deprecated_term_used = se encontró un término obsoleto, considere una alternativa
+msg_definition_without_description = esta definición no tiene descripción
+msg_double_underscored_identifier = los identificadores deberían evitar los guiones bajos dobles
+msg_duplicate_definition_import = importación duplicada de una definición
+msg_duplicate_definition_name = ya existe una definición con este nombre en este módulo
+msg_duplicate_member_name = ya existe un miembro con este nombre en esta definición
+msg_duplicate_module_import = importación duplicada de un módulo
+msg_duplicate_variant_name = ya existe una variante con este nombre en esta definición
+msg_entity_without_identity = esta entidad no tiene un miembro de identidad
+msg_featureset_not_union = tipo no válido para un conjunto de características, no es una unión
+msg_incomplete_definition = esta definición está incompleta
+msg_incomplete_member = este miembro está incompleto
+msg_incomplete_module = este módulo está incompleto
+msg_invalid_identifier = valor no válido para un identificador
+msg_invalid_language_tag = valor no válido para una etiqueta de idioma
+msg_invalid_module_base_url = la URL base del módulo no es válida o no es absoluta
+msg_invalid_module_version_url = la URL de versión del módulo no es válida o no es absoluta
+msg_invalid_value_for_type = valor literal no válido para su tipo
+msg_imported_module_not_found = no se encontró el módulo indicado en la instrucción de importación
+msg_module_import_cycle = el módulo forma parte de un ciclo de importación
+msg_module_not_found = módulo no encontrado
+msg_module_version_info_empty = la cadena de información de versión del módulo está vacía
+msg_module_version_mismatch = la URI real del módulo no coincide con el requisito de importación
+msg_module_version_not_found = el módulo importado no tiene URI de versión
+msg_property_incompatible = una definición de propiedad no es compatible en esta ubicación
+msg_property_reference_not_property = el miembro hace referencia como propiedad a algo que no es una propiedad
+msg_library_definition_not_allowed = tipo de definición no permitido en un módulo que no es de biblioteca
+msg_rdf_definition_incompatible = una definición RDF no es compatible en esta ubicación
+msg_string_without_language = este valor de cadena no tiene etiqueta de idioma
+msg_treesitter_error_node = se encontró un error de análisis de tree-sitter
+msg_treesitter_missing_node = falta un nodo de tree-sitter esperado
+msg_treesitter_unexpected_node = se encontró un nodo de tree-sitter inesperado
+msg_single_variant_enum = esta enumeración tiene una sola variante
+msg_type_definition_not_found = definición de tipo no encontrada en el módulo
+msg_typeclass_incompatible = una definición de clase de tipos no es compatible en esta ubicación
+msg_unbounded_identity_cardinality = este miembro identificador tiene una cardinalidad no acotada
+msg_unconstrained_datatype = este tipo de datos se usa sin ninguna restricción
+msg_unknown_member_type = el tipo de este miembro es `unknown`
+msg_unreachable_definition = esta definición no es alcanzable desde ninguna definición raíz
+msg_unused_definition = esta definición no se usa en su módulo
+msg_unused_import = esta importación no se usa en el módulo
+msg_unused_suppression = el código de diagnóstico suprimido no se informó en este ámbito
+msg_validation_incomplete = la validación puede no estar completa para este tipo
+msg_not_preferred_case = el identificador no usa el estilo de mayúsculas preferido
+
+word_bug = fallo interno
+word_error = error
+word_help = ayuda
+word_note = nota
+word_warning = advertencia
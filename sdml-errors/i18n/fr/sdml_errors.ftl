@@ -0,0 +1,133 @@
+count_of_bugs = {$count} bogues
+count_of_errors = {$count} erreurs
+count_of_informational = {$count} informations
+count_of_warnings = {$count} avertissements
+counts_generated_summary = généré {$counts}
+
+help_alternative_terms = aide : envisagez l'un des termes suivants : {$terms}
+help_datatype_invalid_base_type = aide : une référence de type à cette position doit désigner une définition de type de données
+help_deprecated_term_reason = aide : {$reason}
+help_error_node = aide : un nœud ERROR de tree-sitter a été rencontré dans l'arbre syntaxique
+help_definition_description = aide : ajoutez une annotation `skos:definition` ou `dc:description` pour décrire cette définition
+help_entity_identity = aide : ajoutez un membre d'identité, par exemple `identity id -> string`
+help_feature_set_not_a_union = aide : une référence de type à cette position doit désigner une définition d'union
+help_identity_cardinality = aide : un membre identifiant doit avoir exactement une valeur, par exemple `{"{"}1{"}"}`
+help_more_details_url = aide : pour plus de détails, consultez <{$url}>
+help_property_reference_not_property = aide : une référence de type à cette position doit désigner une définition de propriété
+help_single_variant_enum = aide : demandez-vous si un type de données conviendrait mieux, ou s'il manque des variantes
+help_type_definition_not_found = aide : avez-vous oublié d'importer ce type, ou de qualifier son nom ?
+help_unreachable_definition = aide : référencez cette définition depuis une racine, ou supprimez-la avec `sdml unused --prune`
+
+lbl_actual_node_kind = type de nœud réel : {$kind}
+lbl_actual_this_version_uri = le module contenait cette URI de version
+lbl_actual_version_uri = URI de version réelle : `<{$url}>`
+lbl_definition_name = nom de la définition : `{$name}`
+lbl_expected_this_version_uri = cette URI de version était attendue
+lbl_expected_version_uri = URI de version attendue : `<{$url}>`
+lbl_expecting_node_kind = type de nœud attendu : {$kind}
+lbl_expecting_one_of_node_kind = l'un des types de nœud attendus : {$kind}
+lbl_here = ici
+lbl_identifier = identifiant : `{$name}`
+lbl_in_this = dans : `{$val}`
+lbl_import_cycle = cycle d'importation : {$path}
+lbl_import_name = nom importé : `{$name}`
+lbl_in_grammar_rule = dans la règle de grammaire : `{$name}`
+lbl_missing_node_kind = nœud manquant de type : `{$kind}`
+lbl_missing_node_kind_in_variable = nœud manquant de type : `{$kind}`, dans le champ : `{$field_name}`
+lbl_member_name = nom du membre : `{$name}`
+lbl_module_name_short = module `{$name}`
+lbl_module_name = nom du module : `{$name}`
+lbl_not_valid_for_type = non valide pour ce type
+lbl_parser = analyseur
+lbl_previously_defined_here = a été défini précédemment ici
+lbl_previously_imported_here = a été importé précédemment ici
+lbl_continues_import_cycle = poursuit le cycle d'importation
+lbl_conflicts_with_definition = est en conflit avec cette définition
+lbl_definition_difference = différence : {$change}
+lbl_property_name = nom de la propriété : `{$name}`
+lbl_rdf_name = nom RDF : `{$name}`
+lbl_term_name = terme trouvé : `{$name}`
+lbl_this_definition = cette définition
+lbl_this_definition_name = ce nom de définition
+lbl_this_identifier = cet identifiant
+lbl_this_import = cette importation
+lbl_this_language_tag = cette étiquette de langue
+lbl_this_member = ce membre
+lbl_this_member_name = ce nom de membre
+lbl_this_module = ce module
+lbl_this_reference = cette référence
+lbl_this_suppression = cette suppression
+lbl_suppressed_code = code supprimé : `{$code}`
+lbl_this_type = ce type
+lbl_this_usage = cette utilisation
+lbl_this_value = cette valeur
+lbl_this_variant = cette variante
+lbl_this_variant_name = ce nom de variante
+lbl_type_name = nom du type : `{$name}`
+lbl_typeclass_name = nom de la classe de types : `{$name}`
+lbl_value = valeur : `{$val}`
+lbl_expected_case = attendu : {$case}
+lbl_specific_error = erreur précise : {$err}
+
+lbl_case_module = snake case (snake_case)
+lbl_case_member = snake case (snake_case) ou lower camel case (lowerCamelCase)
+lbl_case_imported_member = snake case (snake_case), lower camel case (lowerCamelCase) ou upper camel case (UpperCamelCase)
+lbl_case_datatype = snake case (snake_case), lower camel case (lowerCamelCase) ou upper camel case (UpperCamelCase)
+lbl_case_property = snake case (snake_case) ou lower camel case (lowerCamelCase)
+lbl_case_rdf = snake case (snake_case), lower camel case (lowerCamelCase) ou upper camel case (UpperCamelCase)
+lbl_case_type_defn = upper camel case (UpperCamelCase)
+lbl_case_value_variant = upper camel case (UpperCamelCase) ou shouty snake case (SHOUTY_SNAKE_CASE)
+
+msg_conflicting_definition = des définitions portant le même nom dans les modules fusionnés sont différentes
+msg_datatype_invalid_base = type invalide pour la base d'un type de données, ce n'est pas un type de données
+msg_definition_not_found = définition introuvable dans le module
+msg_deprecated_term_used = terme obsolète trouvé, envisagez une alternative
+msg_definition_without_description = cette définition n'a pas de description
+msg_double_underscored_identifier = les identifiants devraient éviter les doubles tirets bas
+msg_duplicate_definition_import = importation en double d'une définition
+msg_duplicate_definition_name = une définition portant ce nom existe déjà dans ce module
+msg_duplicate_member_name = un membre portant ce nom existe déjà dans cette définition
+msg_duplicate_module_import = importation en double d'un module
+msg_duplicate_variant_name = une variante portant ce nom existe déjà dans cette définition
+msg_entity_without_identity = cette entité n'a pas de membre d'identité
+msg_featureset_not_union = type invalide pour un ensemble de fonctionnalités, ce n'est pas une union
+msg_incomplete_definition = cette définition est incomplète
+msg_incomplete_member = ce membre est incomplet
+msg_incomplete_module = ce module est incomplet
+msg_invalid_identifier = valeur invalide pour un identifiant
+msg_invalid_language_tag = valeur invalide pour une étiquette de langue
+msg_invalid_module_base_url = l'URL de base du module est invalide ou n'est pas absolue
+msg_invalid_module_version_url = l'URL de version du module est invalide ou n'est pas absolue
+msg_invalid_value_for_type = valeur littérale invalide pour son type
+msg_imported_module_not_found = le module nommé dans l'instruction d'importation est introuvable
+msg_module_import_cycle = le module fait partie d'un cycle d'importation
+msg_module_not_found = module introuvable
+msg_module_version_info_empty = la chaîne d'information de version du module est vide
+msg_module_version_mismatch = l'URI réelle du module ne correspond pas à celle exigée par l'importation
+msg_module_version_not_found = le module importé n'a pas d'URI de version
+msg_property_incompatible = une définition de propriété n'est pas compatible à cet endroit
+msg_property_reference_not_property = le membre référence comme propriété quelque chose qui n'en est pas une
+msg_library_definition_not_allowed = type de définition non autorisé dans un module qui n'est pas une bibliothèque
+msg_rdf_definition_incompatible = une définition RDF n'est pas compatible à cet endroit
+msg_string_without_language = cette chaîne n'a pas d'étiquette de langue
+msg_treesitter_error_node = erreur d'analyse tree-sitter rencontrée
+msg_treesitter_missing_node = un nœud tree-sitter attendu est manquant
+msg_treesitter_unexpected_node = un nœud tree-sitter inattendu a été rencontré
+msg_single_variant_enum = cette énumération n'a qu'une seule variante
+msg_type_definition_not_found = définition de type introuvable dans le module
+msg_typeclass_incompatible = une définition de classe de types n'est pas compatible à cet endroit
+msg_unbounded_identity_cardinality = ce membre identifiant a une cardinalité non bornée
+msg_unconstrained_datatype = ce type de données est utilisé sans aucune contrainte
+msg_unknown_member_type = le type de ce membre est `unknown`
+msg_unreachable_definition = cette définition n'est atteignable depuis aucune définition racine
+msg_unused_definition = cette définition n'est pas utilisée dans son module
+msg_unused_import = cette importation n'est pas utilisée dans le module
+msg_unused_suppression = le code de diagnostic supprimé n'a pas été signalé dans cette portée
+msg_validation_incomplete = la validation peut être incomplète pour ce type
+msg_not_preferred_case = l'identifiant n'utilise pas la casse préférée
+
+word_bug = bogue
+word_error = erreur
+word_help = aide
+word_note = note
+word_warning = avertissement
@@ -5,64 +5,128 @@ The [`ErrorCode`] type represents the set of conditions reported by the Diagnost
 use codespan_reporting::diagnostic::Severity;
 use std::fmt::Display;
 
+// ------------------------------------------------------------------------------------------------
+// Private Macros
+// ------------------------------------------------------------------------------------------------
+
+// Each code is declared once, within the section for its severity, with its number and the
+// identifier of its message in the translation files.
+macro_rules! error_codes {
+    ($($severity:ident {
+        $($variant:ident = $number:literal => $message_id:literal,)+
+    })+) => {
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[repr(u32)]
+        pub enum ErrorCode {
+            $($($variant = $number,)+)+
+        }
+
+        impl ErrorCode {
+            /// All codes, in numeric order.
+            pub const ALL: &'static [ErrorCode] = &[$($(Self::$variant,)+)+];
+
+            /// Return the code with the numeric value `number`, if one exists.
+            pub fn from_number(number: u32) -> Option<Self> {
+                match number {
+                    $($($number => Some(Self::$variant),)+)+
+                    _ => None,
+                }
+            }
+
+            /// Return the severity of this code.
+            #[inline(always)]
+            pub fn severity(&self) -> Severity {
+                match self {
+                    $($(Self::$variant)|+ => Severity::$severity,)+
+                }
+            }
+
+            /// Return the identifier of the descriptive message for this code in the translation
+            /// files.
+            pub fn message_id(&self) -> &'static str {
+                match self {
+                    $($(Self::$variant => $message_id,)+)+
+                }
+            }
+        }
+    };
+}
+
 // ------------------------------------------------------------------------------------------------
 // Public Types
 // ------------------------------------------------------------------------------------------------
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
-#[repr(u32)]
-pub enum ErrorCode {
+error_codes! {
     // --------------------------------------------------------------------------------------------
     // Bugs
     // --------------------------------------------------------------------------------------------
-    TreeSitterErrorNode = 1,
-    TreeSitterUnexpectedNode = 2,
-    TreeSitterMissingNode = 3,
+    Bug {
+        TreeSitterErrorNode = 1 => "msg_treesitter_error_node",
+        TreeSitterUnexpectedNode = 2 => "msg_treesitter_unexpected_node",
+        TreeSitterMissingNode = 3 => "msg_treesitter_missing_node",
+    }
 
     // --------------------------------------------------------------------------------------------
     // Errors
     // --------------------------------------------------------------------------------------------
-    ModuleNotFound = 100,
-    ImportedModuleNotFound = 101,
-    ModuleVersionNotFound = 102,
-    ModuleVersionMismatch = 103,
-    DuplicateDefinitionName = 104,
-    DuplicateMemberName = 105,
-    DuplicateVariantName = 106,
-    InvalidIdentifier = 107,
-    InvalidLanguageTag = 108,
-    InvalidValueForType = 109,
-    InvalidModuleBaseUrl = 110,
-    InvalidModuleVersionUrl = 112,
-    DefinitionNotFound = 113,
-    TypeDefinitionNotFound = 114,
-    DatatypeInvalidBase = 115,
-    TypeClassIncompatible = 116,
-    PropertyIncompatible = 117,
-    RdfDefinitionIncompatible = 118,
-    FeatureSetNotUnion = 119, // <<< deprecated
-    PropertyReferenceNotProperty = 120,
-    LibraryDefinitionNotAllowed = 121,
+    Error {
+        ModuleNotFound = 100 => "msg_module_not_found",
+        ImportedModuleNotFound = 101 => "msg_imported_module_not_found",
+        ModuleVersionNotFound = 102 => "msg_module_version_not_found",
+        ModuleVersionMismatch = 103 => "msg_module_version_mismatch",
+        DuplicateDefinitionName = 104 => "msg_duplicate_definition_name",
+        DuplicateMemberName = 105 => "msg_duplicate_member_name",
+        DuplicateVariantName = 106 => "msg_duplicate_variant_name",
+        InvalidIdentifier = 107 => "msg_invalid_identifier",
+        InvalidLanguageTag = 108 => "msg_invalid_language_tag",
+        InvalidValueForType = 109 => "msg_invalid_value_for_type",
+        InvalidModuleBaseUrl = 110 => "msg_invalid_module_base_url",
+        InvalidModuleVersionUrl = 112 => "msg_invalid_module_version_url",
+        DefinitionNotFound = 113 => "msg_definition_not_found",
+        TypeDefinitionNotFound = 114 => "msg_type_definition_not_found",
+        DatatypeInvalidBase = 115 => "msg_datatype_invalid_base",
+        TypeClassIncompatible = 116 => "msg_typeclass_incompatible",
+        PropertyIncompatible = 117 => "msg_property_incompatible",
+        RdfDefinitionIncompatible = 118 => "msg_rdf_definition_incompatible",
+        FeatureSetNotUnion = 119 => "msg_featureset_not_union", // <<< deprecated
+        PropertyReferenceNotProperty = 120 => "msg_property_reference_not_property",
+        LibraryDefinitionNotAllowed = 121 => "msg_library_definition_not_allowed",
+        ConflictingDefinition = 122 => "msg_conflicting_definition",
+    }
 
     // --------------------------------------------------------------------------------------------
     // Warnings
     // --------------------------------------------------------------------------------------------
-    DuplicateModuleImport = 301,
-    DuplicateDefinitionImport = 302,
-    ValidationIncomplete = 303,
-    ModuleVersionInfoEmpty = 304,
-    DeprecatedTermUsed = 305,
+    Warning {
+        DuplicateModuleImport = 301 => "msg_duplicate_module_import",
+        DuplicateDefinitionImport = 302 => "msg_duplicate_definition_import",
+        ValidationIncomplete = 303 => "msg_validation_incomplete",
+        ModuleVersionInfoEmpty = 304 => "msg_module_version_info_empty",
+        DeprecatedTermUsed = 305 => "msg_deprecated_term_used",
+        ModuleImportCycle = 306 => "msg_module_import_cycle",
+        UnusedSuppression = 307 => "msg_unused_suppression",
+        UnusedImport = 308 => "msg_unused_import",
+        UnknownMemberType = 309 => "msg_unknown_member_type",
+    }
 
     // --------------------------------------------------------------------------------------------
     // Informational
     // --------------------------------------------------------------------------------------------
-    IncompleteModule = 500,
-    IncompleteDefinition = 501,
-    IncompleteMember = 502,
-    StringWithoutLanguage = 503,
-    UnconstrainedDatatype = 504,
-    DoubleUnderscoredIdentifier = 505,
-    IdentifierNotPreferredCase = 506,
+    Note {
+        IncompleteModule = 500 => "msg_incomplete_module",
+        IncompleteDefinition = 501 => "msg_incomplete_definition",
+        IncompleteMember = 502 => "msg_incomplete_member",
+        StringWithoutLanguage = 503 => "msg_string_without_language",
+        UnconstrainedDatatype = 504 => "msg_unconstrained_datatype",
+        DoubleUnderscoredIdentifier = 505 => "msg_double_underscored_identifier",
+        IdentifierNotPreferredCase = 506 => "msg_not_preferred_case",
+        EntityWithoutIdentity = 507 => "msg_entity_without_identity",
+        DefinitionWithoutDescription = 508 => "msg_definition_without_description",
+        UnusedDefinition = 509 => "msg_unused_definition",
+        UnboundedIdentityCardinality = 510 => "msg_unbounded_identity_cardinality",
+        SingleVariantEnum = 511 => "msg_single_variant_enum",
+        UnreachableDefinition = 512 => "msg_unreachable_definition",
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -93,103 +157,17 @@ impl ErrorCode {
         *self as u32
     }
 
-    /// Return the severity of this code.
-    #[inline(always)]
-    pub fn severity(&self) -> Severity {
-        match self {
-            Self::TreeSitterErrorNode
-            | Self::TreeSitterUnexpectedNode
-            | Self::TreeSitterMissingNode => Severity::Bug,
-            Self::ModuleNotFound
-            | Self::ImportedModuleNotFound
-            | Self::ModuleVersionNotFound
-            | Self::ModuleVersionMismatch
-            | Self::DuplicateDefinitionName
-            | Self::DuplicateMemberName
-            | Self::DuplicateVariantName
-            | Self::InvalidIdentifier
-            | Self::InvalidLanguageTag
-            | Self::InvalidValueForType
-            | Self::InvalidModuleBaseUrl
-            | Self::InvalidModuleVersionUrl
-            | Self::DefinitionNotFound
-            | Self::TypeDefinitionNotFound
-            | Self::DatatypeInvalidBase
-            | Self::TypeClassIncompatible
-            | Self::PropertyIncompatible
-            | Self::RdfDefinitionIncompatible
-            | Self::FeatureSetNotUnion
-            | Self::PropertyReferenceNotProperty
-            | Self::LibraryDefinitionNotAllowed => Severity::Error,
-            Self::DuplicateModuleImport
-            | Self::DuplicateDefinitionImport
-            | Self::ValidationIncomplete
-            | Self::ModuleVersionInfoEmpty
-            | Self::DeprecatedTermUsed => Severity::Warning,
-            Self::IncompleteModule
-            | Self::IncompleteDefinition
-            | Self::IncompleteMember
-            | Self::StringWithoutLanguage
-            | Self::UnconstrainedDatatype
-            | Self::DoubleUnderscoredIdentifier
-            | Self::IdentifierNotPreferredCase => Severity::Note,
-        }
+    /// Return the code with the string form `code`, for example `"E0122"`, if one exists.
+    pub fn from_code(code: &str) -> Option<Self> {
+        code.get(1..)
+            .and_then(|number| number.parse::<u32>().ok())
+            .and_then(Self::from_number)
+            .filter(|error_code| error_code.to_string() == code)
     }
 
-    /// Return the descriptive message for this code.
+    /// Return the descriptive message for this code, in the current locale.
     pub fn message(&self) -> String {
-        match self {
-            Self::TreeSitterErrorNode => i18n!("msg_treesitter_error_node"),
-            Self::TreeSitterUnexpectedNode => i18n!("msg_treesitter_unexpected_node"),
-            Self::TreeSitterMissingNode => i18n!("msg_treesitter_missing_node"),
-            Self::ModuleNotFound => i18n!("msg_module_not_found"),
-            Self::ImportedModuleNotFound => i18n!("msg_imported_module_not_found"),
-            Self::ModuleVersionNotFound => i18n!("msg_module_version_not_found"),
-            Self::ModuleVersionMismatch => {
-                i18n!("msg_module_version_mismatch")
-            }
-            Self::DuplicateDefinitionName => {
-                i18n!("msg_duplicate_definition_name")
-            }
-            Self::DuplicateMemberName => {
-                i18n!("msg_duplicate_member_name")
-            }
-            Self::DuplicateVariantName => {
-                i18n!("msg_duplicate_variant_name")
-            }
-            Self::InvalidIdentifier => i18n!("msg_invalid_identifier"),
-            Self::InvalidLanguageTag => i18n!("msg_invalid_language_tag"),
-            Self::InvalidValueForType => i18n!("msg_invalid_value_for_type"),
-            Self::InvalidModuleBaseUrl => i18n!("msg_invalid_module_base_url"),
-            Self::InvalidModuleVersionUrl => i18n!("msg_invalid_module_version_url"),
-            Self::DefinitionNotFound => i18n!("msg_definition_not_found"),
-            Self::TypeDefinitionNotFound => i18n!("msg_type_definition_not_found"),
-            Self::DatatypeInvalidBase => i18n!("msg_datatype_invalid_base"),
-            Self::TypeClassIncompatible => {
-                i18n!("msg_typeclass_incompatible")
-            }
-            Self::PropertyIncompatible => {
-                i18n!("msg_property_incompatible")
-            }
-            Self::RdfDefinitionIncompatible => {
-                i18n!("msg_rdf_definition_incompatible")
-            }
-            Self::FeatureSetNotUnion => i18n!("msg_featureset_not_union"),
-            Self::PropertyReferenceNotProperty => i18n!("msg_property_reference_not_property"),
-            Self::LibraryDefinitionNotAllowed => i18n!("msg_library_definition_not_allowed"),
-            Self::DeprecatedTermUsed => i18n!("msg_deprecated_term_used"),
-            Self::DuplicateModuleImport => i18n!("msg_duplicate_module_import"),
-            Self::DuplicateDefinitionImport => i18n!("msg_duplicate_definition_import"),
-            Self::ValidationIncomplete => i18n!("msg_validation_incomplete"),
-            Self::ModuleVersionInfoEmpty => i18n!("msg_module_version_info_empty"),
-            Self::IncompleteModule => i18n!("msg_incomplete_module"),
-            Self::IncompleteDefinition => i18n!("msg_incomplete_definition"),
-            Self::IncompleteMember => i18n!("msg_incomplete_member"),
-            Self::StringWithoutLanguage => i18n!("msg_string_without_language"),
-            Self::UnconstrainedDatatype => i18n!("msg_unconstrained_datatype"),
-            Self::DoubleUnderscoredIdentifier => i18n!("msg_double_underscored_identifier"),
-            Self::IdentifierNotPreferredCase => i18n!("msg_not_preferred_case"),
-        }
+        crate::i18n::message(self.message_id())
     }
 
     /// Return a URL (as String) for the associated help documentation.
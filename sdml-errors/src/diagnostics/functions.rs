@@ -9,11 +9,13 @@ End of file during parsingSymbol’s value as variable is void: rustEnd of file
 
  */
 
+use crate::diagnostics::suggestions::{SuggestedDiagnostic, Suggestion};
 use crate::diagnostics::{Diagnostic, ErrorCode};
 use crate::{FileId, Span};
 use codespan_reporting::diagnostic::Label;
 use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 use std::error::Error;
+use std::fmt::Display;
 
 // ------------------------------------------------------------------------------------------------
 // Public Types
@@ -31,6 +33,18 @@ pub enum IdentifierCaseConvention {
     ValueVariant,
 }
 
+///
+/// A case style that an identifier may be written in; each [`IdentifierCaseConvention`] allows
+/// one or more styles, the first of which is preferred.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CaseStyle {
+    SnakeCase,
+    LowerCamelCase,
+    UpperCamelCase,
+    ShoutySnakeCase,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Private Macros
 // ------------------------------------------------------------------------------------------------
@@ -561,12 +575,56 @@ where
     })
 }
 
+///
+/// Report a definition that has the same name as, but a different shape from, a definition in
+/// another module being merged. The `location` is the file and span of the definition that was
+/// dropped, `other_location` that of the definition that was kept, and `differences` describe how
+/// they differ. A definition without a location is not labeled.
+///
+#[inline]
+#[allow(clippy::redundant_closure_call)]
+pub fn conflicting_definition<S>(
+    location: Option<(FileId, Span)>,
+    other_location: Option<(FileId, Span)>,
+    name: S,
+    differences: &[String],
+) -> Diagnostic
+where
+    S: Into<String>,
+{
+    new_diagnostic!(ConflictingDefinition, |diagnostic: Diagnostic| {
+        let mut labels = Vec::default();
+        if let Some((file_id, location)) = location {
+            labels
+                .push(Label::primary(file_id, location).with_message(i18n!("lbl_this_definition")));
+        }
+        if let Some((other_file_id, other_location)) = other_location {
+            labels.push(
+                Label::secondary(other_file_id, other_location)
+                    .with_message(i18n!("lbl_conflicts_with_definition")),
+            );
+        }
+        let diagnostic = if labels.is_empty() {
+            diagnostic.with_notes(vec![i18n!("lbl_definition_name", name = name.into())])
+        } else {
+            diagnostic.with_labels(labels)
+        };
+        diagnostic.with_notes(
+            differences
+                .iter()
+                .map(|change| i18n!("lbl_definition_difference", change = change.as_str()))
+                .collect(),
+        )
+    })
+}
+
 // ------------------------------------------------------------------------------------------------
 // Public Functions  Warnings
 // ------------------------------------------------------------------------------------------------
 
 #[inline]
 #[allow(clippy::redundant_closure_call)]
+#[deprecated(note = "use `duplicate_module_import_with_removal` to suggest removing the import")]
 pub fn duplicate_module_import(file_id: FileId, first: Span, second: Span) -> Diagnostic {
     new_diagnostic!(DuplicateModuleImport, |diagnostic: Diagnostic| diagnostic
         .with_labels(vec![
@@ -575,8 +633,26 @@ pub fn duplicate_module_import(file_id: FileId, first: Span, second: Span) -> Di
         ]))
 }
 
+///
+/// Report a module imported more than once, where `removal` is the source to remove to fix it.
+///
+#[inline]
+pub fn duplicate_module_import_with_removal(
+    file_id: FileId,
+    first: Span,
+    second: Span,
+    removal: Span,
+) -> SuggestedDiagnostic {
+    #[allow(deprecated)]
+    SuggestedDiagnostic::new(duplicate_module_import(file_id, first, second))
+        .with_suggestion(Suggestion::remove(file_id, removal))
+}
+
 #[inline]
 #[allow(clippy::redundant_closure_call)]
+#[deprecated(
+    note = "use `duplicate_definition_import_with_removal` to suggest removing the import"
+)]
 pub fn duplicate_definition_import(file_id: FileId, first: Span, second: Span) -> Diagnostic {
     new_diagnostic!(DuplicateDefinitionImport, |diagnostic: Diagnostic| {
         diagnostic.with_labels(vec![
@@ -586,6 +662,22 @@ pub fn duplicate_definition_import(file_id: FileId, first: Span, second: Span) -
     })
 }
 
+///
+/// Report a definition imported more than once, where `removal` is the source to remove to fix
+/// it.
+///
+#[inline]
+pub fn duplicate_definition_import_with_removal(
+    file_id: FileId,
+    first: Span,
+    second: Span,
+    removal: Span,
+) -> SuggestedDiagnostic {
+    #[allow(deprecated)]
+    SuggestedDiagnostic::new(duplicate_definition_import(file_id, first, second))
+        .with_suggestion(Suggestion::remove(file_id, removal))
+}
+
 #[inline]
 #[allow(clippy::redundant_closure_call)]
 pub fn type_validation_incomplete<S>(
@@ -662,6 +754,114 @@ where
     })
 }
 
+///
+/// Report an import cycle; the `cycle` is the closed path of module names, so the first and last
+/// names are the same. The `location` is the import, in `file_id`, that starts the cycle and
+/// `other_imports` are the imports in other modules that continue the cycle.
+///
+#[inline]
+#[allow(clippy::redundant_closure_call)]
+pub fn module_import_cycle<S>(
+    file_id: FileId,
+    location: Option<Span>,
+    cycle: &[S],
+    other_imports: Vec<(FileId, Span)>,
+) -> Diagnostic
+where
+    S: AsRef<str>,
+{
+    let path = cycle
+        .iter()
+        .map(|name| name.as_ref())
+        .collect::<Vec<_>>()
+        .join(" -> ");
+    new_diagnostic!(ModuleImportCycle, |diagnostic: Diagnostic| {
+        let diagnostic = if let Some(location) = location {
+            diagnostic.with_labels(vec![
+                Label::primary(file_id, location).with_message(i18n!("lbl_this_import"))
+            ])
+        } else {
+            diagnostic
+        };
+        diagnostic
+            .with_labels(
+                other_imports
+                    .into_iter()
+                    .map(|(file_id, location)| {
+                        Label::secondary(file_id, location)
+                            .with_message(i18n!("lbl_continues_import_cycle"))
+                    })
+                    .collect(),
+            )
+            .with_notes(vec![i18n!("lbl_import_cycle", path = path)])
+    })
+}
+
+///
+/// Report a code, from an `sdml:suppress` annotation, that did not suppress any diagnostic
+/// within the annotated scope; the `location` is the code's value in the annotation.
+///
+#[inline]
+#[allow(clippy::redundant_closure_call)]
+pub fn unused_suppression<S>(file_id: FileId, location: Option<Span>, code: S) -> Diagnostic
+where
+    S: Into<String>,
+{
+    new_diagnostic!(UnusedSuppression, |diagnostic: Diagnostic| {
+        let code = code.into();
+        if let Some(location) = location {
+            diagnostic.with_labels(vec![
+                Label::primary(file_id, location).with_message(i18n!("lbl_this_suppression"))
+            ])
+        } else {
+            diagnostic
+        }
+        .with_notes(vec![i18n!("lbl_suppressed_code", code = code)])
+    })
+}
+
+///
+/// An imported module or member that is not referenced anywhere in the importing module.
+///
+#[inline]
+#[allow(clippy::redundant_closure_call)]
+pub fn unused_import<S>(file_id: FileId, location: Option<Span>, name: S) -> Diagnostic
+where
+    S: Into<String>,
+{
+    new_diagnostic!(
+        UnusedImport,
+        |diagnostic: Diagnostic| if let Some(location) = location {
+            diagnostic.with_labels(vec![
+                Label::primary(file_id, location).with_message(i18n!("lbl_this_import"))
+            ])
+        } else {
+            diagnostic.with_notes(vec![i18n!("lbl_import_name", name = name.into())])
+        }
+    )
+}
+
+///
+/// A member whose type was left as `unknown`.
+///
+#[inline]
+#[allow(clippy::redundant_closure_call)]
+pub fn member_type_unknown<S>(file_id: FileId, location: Option<Span>, name: S) -> Diagnostic
+where
+    S: Into<String>,
+{
+    new_diagnostic!(
+        UnknownMemberType,
+        |diagnostic: Diagnostic| if let Some(location) = location {
+            diagnostic.with_labels(vec![
+                Label::primary(file_id, location).with_message(i18n!("lbl_this_member"))
+            ])
+        } else {
+            diagnostic.with_notes(vec![i18n!("lbl_member_name", name = name.into())])
+        }
+    )
+}
+
 // ------------------------------------------------------------------------------------------------
 // Public Functions  Informational
 // ------------------------------------------------------------------------------------------------
@@ -817,57 +1017,351 @@ where
     })
 }
 
+///
+/// As [`identifier_not_preferred_case`], suggesting that the identifier at `location` is
+/// renamed to its preferred case.
+///
+#[inline]
+pub fn identifier_not_preferred_case_with_rename<S>(
+    file_id: FileId,
+    location: Option<Span>,
+    name: S,
+    case: IdentifierCaseConvention,
+) -> SuggestedDiagnostic
+where
+    S: Into<String>,
+{
+    let name = name.into();
+    let suggestion = location
+        .clone()
+        .map(|location| Suggestion::replace(file_id, location, case.to_preferred(&name)));
+    let diagnostic =
+        SuggestedDiagnostic::new(identifier_not_preferred_case(file_id, location, name, case));
+    match suggestion {
+        Some(suggestion) => diagnostic.with_suggestion(suggestion),
+        None => diagnostic,
+    }
+}
+
+///
+/// As [`identifier_not_preferred_case`] but where the allowed case `styles` have been configured
+/// rather than taken from the identifier's [`IdentifierCaseConvention`].
+///
+#[inline]
+#[allow(clippy::redundant_closure_call)]
+pub fn identifier_not_configured_case<S>(
+    file_id: FileId,
+    location: Option<Span>,
+    name: S,
+    styles: &[CaseStyle],
+) -> SuggestedDiagnostic
+where
+    S: Into<String>,
+{
+    let name = name.into();
+    let suggestion = match (&location, styles.first()) {
+        (Some(location), Some(preferred)) => Some(Suggestion::replace(
+            file_id,
+            location.clone(),
+            preferred.convert(&name),
+        )),
+        _ => None,
+    };
+    let diagnostic = SuggestedDiagnostic::new(new_diagnostic!(
+        IdentifierNotPreferredCase,
+        |diagnostic: Diagnostic| if let Some(location) = location {
+            diagnostic.with_labels(vec![
+                Label::primary(file_id, location).with_message(i18n!("lbl_this_identifier"))
+            ])
+        } else {
+            diagnostic.with_notes(vec![i18n!("lbl_identifier", name = name)])
+        }
+        .with_notes(vec![i18n!(
+            "lbl_expected_case",
+            case = styles
+                .iter()
+                .map(|style| style.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )])
+    ));
+    match suggestion {
+        Some(suggestion) => diagnostic.with_suggestion(suggestion),
+        None => diagnostic,
+    }
+}
+
+///
+/// An entity declared without a body, and so without an identity member.
+///
+#[inline]
+#[allow(clippy::redundant_closure_call)]
+pub fn entity_without_identity<S>(file_id: FileId, location: Option<Span>, name: S) -> Diagnostic
+where
+    S: Into<String>,
+{
+    new_diagnostic!(EntityWithoutIdentity, |diagnostic: Diagnostic| {
+        if let Some(location) = location {
+            diagnostic.with_labels(vec![
+                Label::primary(file_id, location).with_message(i18n!("lbl_this_definition"))
+            ])
+        } else {
+            diagnostic.with_notes(vec![i18n!("lbl_definition_name", name = name.into())])
+        }
+        .with_notes(vec![i18n!("help_entity_identity")])
+    })
+}
+
+///
+/// A definition without a `skos:definition` or `dc:description` annotation.
+///
+#[inline]
+#[allow(clippy::redundant_closure_call)]
+pub fn definition_without_description<S>(
+    file_id: FileId,
+    location: Option<Span>,
+    name: S,
+) -> Diagnostic
+where
+    S: Into<String>,
+{
+    new_diagnostic!(DefinitionWithoutDescription, |diagnostic: Diagnostic| {
+        if let Some(location) = location {
+            diagnostic.with_labels(vec![
+                Label::primary(file_id, location).with_message(i18n!("lbl_this_definition"))
+            ])
+        } else {
+            diagnostic.with_notes(vec![i18n!("lbl_definition_name", name = name.into())])
+        }
+        .with_notes(vec![i18n!("help_definition_description")])
+    })
+}
+
+///
+/// A datatype or property definition that is not referenced by any other definition in the
+/// same module.
+///
+#[inline]
+#[allow(clippy::redundant_closure_call)]
+pub fn unused_definition<S>(file_id: FileId, location: Option<Span>, name: S) -> Diagnostic
+where
+    S: Into<String>,
+{
+    new_diagnostic!(
+        UnusedDefinition,
+        |diagnostic: Diagnostic| if let Some(location) = location {
+            diagnostic.with_labels(vec![
+                Label::primary(file_id, location).with_message(i18n!("lbl_this_definition"))
+            ])
+        } else {
+            diagnostic.with_notes(vec![i18n!("lbl_definition_name", name = name.into())])
+        }
+    )
+}
+
+///
+/// An entity's identity, or an identifier-like member, whose cardinality has no upper bound.
+///
+#[inline]
+#[allow(clippy::redundant_closure_call)]
+pub fn unbounded_identity_cardinality<S>(
+    file_id: FileId,
+    location: Option<Span>,
+    name: S,
+) -> Diagnostic
+where
+    S: Into<String>,
+{
+    new_diagnostic!(UnboundedIdentityCardinality, |diagnostic: Diagnostic| {
+        if let Some(location) = location {
+            diagnostic.with_labels(vec![
+                Label::primary(file_id, location).with_message(i18n!("lbl_this_member"))
+            ])
+        } else {
+            diagnostic.with_notes(vec![i18n!("lbl_member_name", name = name.into())])
+        }
+        .with_notes(vec![i18n!("help_identity_cardinality")])
+    })
+}
+
+///
+/// An enum with exactly one variant.
+///
+#[inline]
+#[allow(clippy::redundant_closure_call)]
+pub fn single_variant_enum<S>(file_id: FileId, location: Option<Span>, name: S) -> Diagnostic
+where
+    S: Into<String>,
+{
+    new_diagnostic!(SingleVariantEnum, |diagnostic: Diagnostic| {
+        if let Some(location) = location {
+            diagnostic.with_labels(vec![
+                Label::primary(file_id, location).with_message(i18n!("lbl_this_definition"))
+            ])
+        } else {
+            diagnostic.with_notes(vec![i18n!("lbl_definition_name", name = name.into())])
+        }
+        .with_notes(vec![i18n!("help_single_variant_enum")])
+    })
+}
+
+///
+/// A definition that cannot be reached, through references, from any of a set of root
+/// definitions.
+///
+#[inline]
+#[allow(clippy::redundant_closure_call)]
+pub fn unreachable_definition<S>(file_id: FileId, location: Option<Span>, name: S) -> Diagnostic
+where
+    S: Into<String>,
+{
+    new_diagnostic!(UnreachableDefinition, |diagnostic: Diagnostic| {
+        if let Some(location) = location {
+            diagnostic.with_labels(vec![
+                Label::primary(file_id, location).with_message(i18n!("lbl_this_definition"))
+            ])
+        } else {
+            diagnostic.with_notes(vec![i18n!("lbl_definition_name", name = name.into())])
+        }
+        .with_notes(vec![i18n!("help_unreachable_definition")])
+    })
+}
+
 // ------------------------------------------------------------------------------------------------
 // Implementations
 // ------------------------------------------------------------------------------------------------
 
-impl IdentifierCaseConvention {
+impl Display for CaseStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::SnakeCase => "snake_case",
+                Self::LowerCamelCase => "lowerCamelCase",
+                Self::UpperCamelCase => "UpperCamelCase",
+                Self::ShoutySnakeCase => "SHOUTY_SNAKE_CASE",
+            }
+        )
+    }
+}
+
+impl CaseStyle {
+    ///
+    /// Return the style named `name`, the names are those used by [`Display`], for example
+    /// `"lowerCamelCase"`.
+    ///
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            Self::SnakeCase,
+            Self::LowerCamelCase,
+            Self::UpperCamelCase,
+            Self::ShoutySnakeCase,
+        ]
+        .into_iter()
+        .find(|style| style.to_string() == name)
+    }
+
     pub fn is_valid<S>(&self, id: S) -> bool
     where
-        S: Into<String>,
+        S: AsRef<str>,
     {
-        let id = id.into();
+        let id = id.as_ref();
+        id == self.convert(id)
+    }
+
+    ///
+    /// Return the form of `id` in this style.
+    ///
+    pub fn convert<S>(&self, id: S) -> String
+    where
+        S: AsRef<str>,
+    {
+        let id = id.as_ref();
         match self {
-            Self::Module => id == Self::to_snake_case(&id),
-            Self::Member => id == Self::to_snake_case(&id) || id == Self::to_lower_camel_case(&id),
-            Self::ImportedMember => {
-                id == Self::to_snake_case(&id)
-                    || id == Self::to_lower_camel_case(&id)
-                    || id == Self::to_upper_camel_case(&id)
-            }
-            Self::DatatypeDefinition => {
-                id == Self::to_snake_case(&id)
-                    || id == Self::to_lower_camel_case(&id)
-                    || id == Self::to_upper_camel_case(&id)
-            }
-            Self::PropertyDefinition => {
-                id == Self::to_snake_case(&id) || id == Self::to_lower_camel_case(&id)
-            }
-            Self::RdfDefinition => {
-                id == Self::to_snake_case(&id)
-                    || id == Self::to_lower_camel_case(&id)
-                    || id == Self::to_upper_camel_case(&id)
-            }
-            Self::TypeDefinition => id == Self::to_upper_camel_case(&id),
-            Self::ValueVariant => {
-                id == Self::to_upper_camel_case(&id) || id == Self::to_shouty_snake_case(&id)
-            }
+            Self::SnakeCase => id.to_snake_case(),
+            Self::LowerCamelCase => id.to_lower_camel_case(),
+            Self::UpperCamelCase => id.to_upper_camel_case(),
+            Self::ShoutySnakeCase => id.to_shouty_snake_case(),
         }
     }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for IdentifierCaseConvention {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Module => "module",
+                Self::Member => "member",
+                Self::ImportedMember => "imported_member",
+                Self::DatatypeDefinition => "datatype",
+                Self::PropertyDefinition => "property",
+                Self::RdfDefinition => "rdf",
+                Self::TypeDefinition => "type",
+                Self::ValueVariant => "variant",
+            }
+        )
+    }
+}
 
-    fn to_snake_case(id: &str) -> String {
-        id.to_snake_case()
+impl IdentifierCaseConvention {
+    ///
+    /// Return the convention named `name`, the names are those used by [`Display`], for example
+    /// `"imported_member"`.
+    ///
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            Self::Module,
+            Self::Member,
+            Self::ImportedMember,
+            Self::DatatypeDefinition,
+            Self::PropertyDefinition,
+            Self::RdfDefinition,
+            Self::TypeDefinition,
+            Self::ValueVariant,
+        ]
+        .into_iter()
+        .find(|convention| convention.to_string() == name)
     }
 
-    fn to_upper_camel_case(id: &str) -> String {
-        id.to_upper_camel_case()
+    ///
+    /// Return the case styles allowed for this usage, the first is the preferred style.
+    ///
+    pub fn styles(&self) -> &'static [CaseStyle] {
+        match self {
+            Self::Module => &[CaseStyle::SnakeCase],
+            Self::Member | Self::PropertyDefinition => {
+                &[CaseStyle::SnakeCase, CaseStyle::LowerCamelCase]
+            }
+            Self::ImportedMember | Self::DatatypeDefinition | Self::RdfDefinition => &[
+                CaseStyle::SnakeCase,
+                CaseStyle::LowerCamelCase,
+                CaseStyle::UpperCamelCase,
+            ],
+            Self::TypeDefinition => &[CaseStyle::UpperCamelCase],
+            Self::ValueVariant => &[CaseStyle::UpperCamelCase, CaseStyle::ShoutySnakeCase],
+        }
     }
 
-    fn to_lower_camel_case(id: &str) -> String {
-        id.to_lower_camel_case()
+    pub fn is_valid<S>(&self, id: S) -> bool
+    where
+        S: Into<String>,
+    {
+        let id = id.into();
+        self.styles().iter().any(|style| style.is_valid(&id))
     }
 
-    fn to_shouty_snake_case(id: &str) -> String {
-        id.to_shouty_snake_case()
+    ///
+    /// Return the form of `id` in the first, preferred, case style for this usage.
+    ///
+    pub fn to_preferred<S>(&self, id: S) -> String
+    where
+        S: AsRef<str>,
+    {
+        self.styles()[0].convert(id)
     }
 }
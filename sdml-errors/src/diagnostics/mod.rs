@@ -86,3 +86,9 @@ pub mod functions;
 
 pub mod reporter;
 pub use reporter::{Reporter, StandardStreamReporter};
+
+pub mod rules;
+
+pub mod suggestions;
+
+pub mod suppressions;
@@ -1,11 +1,20 @@
 /*!
 This module contains the trait [`Reporter`] and common implementations.
+
+With the feature `sarif` enabled the [`SarifReporter`] collects diagnostics and writes them as a
+[SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) log, as consumed
+by code-scanning tools. With the feature `json` enabled the [`JsonLinesReporter`] writes each
+diagnostic as a single line of JSON, including any [suggested edits](crate::diagnostics::suggestions).
  */
 
 use crate::diagnostics::color::UseColor;
+use crate::diagnostics::functions::unused_suppression;
+use crate::diagnostics::rules::LintRules;
+use crate::diagnostics::suggestions::{SuggestedDiagnostic, Suggestion};
+use crate::diagnostics::suppressions::Suppression;
 use crate::diagnostics::{Diagnostic, ErrorCode, SeverityFilter};
 use crate::errors::Error;
-use crate::SourceFiles;
+use crate::{FileId, SourceFiles};
 use codespan_reporting::{
     diagnostic::Severity,
     term::{
@@ -14,11 +23,20 @@ use codespan_reporting::{
         Chars, Config,
     },
 };
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::io::Write;
 use std::ops::{Add, AddAssign};
+use std::rc::Rc;
 use tracing::{error, info, warn};
+#[cfg(any(feature = "json", feature = "sarif"))]
+use {
+    codespan_reporting::diagnostic::{Label, LabelStyle},
+    serde_json::{json, Value},
+};
+#[cfg(feature = "sarif")]
+use {std::path::Path, url::Url};
 
 // ------------------------------------------------------------------------------------------------
 // Public Types
@@ -33,6 +51,18 @@ pub trait Reporter: Debug {
     ///
     fn emit(&self, diagnostic: &Diagnostic, sources: &SourceFiles) -> Result<(), Error>;
 
+    ///
+    /// Emit a diagnostic along with its suggested edits, by default the suggestions are
+    /// ignored.
+    ///
+    fn emit_suggested(
+        &self,
+        diagnostic: &SuggestedDiagnostic,
+        sources: &SourceFiles,
+    ) -> Result<(), Error> {
+        self.emit(diagnostic.diagnostic(), sources)
+    }
+
     fn emit_without_source(&self, diagnostic: &Diagnostic) -> Result<(), Error> {
         self.emit(diagnostic, &SourceFiles::new())
     }
@@ -100,6 +130,61 @@ pub struct CompactStreamReporter {
     counters: RefCell<ReportCounters>,
 }
 
+///
+/// A reporter that collects all emitted diagnostics and writes them as a single SARIF log when
+/// [`Reporter::done`] is called. Each code reported is described by a rule, using the number,
+/// severity, message, and help URL of the corresponding [`ErrorCode`].
+///
+#[cfg(feature = "sarif")]
+pub struct SarifReporter {
+    writer: RefCell<Box<dyn Write>>,
+    filter: SeverityFilter,
+    counters: RefCell<ReportCounters>,
+    tool_name: String,
+    tool_version: String,
+    rules: RefCell<Vec<String>>,
+    results: RefCell<Vec<Value>>,
+}
+
+///
+/// A reporter that writes each emitted diagnostic as a single line of JSON, a JSON object with
+/// the diagnostic's code, severity, message, labels, notes, help URL, and suggested edits. Label
+/// and suggestion locations include both the byte range and the line and column range.
+///
+#[cfg(feature = "json")]
+pub struct JsonLinesReporter {
+    writer: RefCell<Box<dyn Write>>,
+    filter: SeverityFilter,
+    counters: RefCell<ReportCounters>,
+}
+
+///
+/// A reporter that keeps every emitted diagnostic so that they may be processed after loading
+/// and validation, for example to apply their suggestions. Clones share the same diagnostics, so
+/// a clone may be given to a loader and the original used to retrieve them. By default all
+/// diagnostics are kept.
+///
+#[derive(Clone, Debug)]
+pub struct CollectingReporter {
+    filter: SeverityFilter,
+    counters: Rc<RefCell<ReportCounters>>,
+    diagnostics: Rc<RefCell<Vec<SuggestedDiagnostic>>>,
+}
+
+///
+/// A reporter that applies a project's [`LintRules`], and then any [`Suppression`]s, to
+/// diagnostics before passing those that remain to another reporter. The reporter remembers
+/// which suppressed codes were used so that unused suppressions may themselves be reported.
+///
+#[derive(Debug)]
+pub struct SuppressingReporter {
+    inner: Box<dyn Reporter>,
+    rules: LintRules,
+    suppressions: Vec<Suppression>,
+    used: RefCell<HashSet<(usize, usize)>>,
+    current_file: Cell<Option<FileId>>,
+}
+
 #[derive(Debug, Default)]
 pub struct BailoutReporter {
     filter: SeverityFilter,
@@ -158,8 +243,11 @@ impl AddAssign for ReportCounters {
 }
 
 impl ReportCounters {
+    ///
+    /// Count a diagnostic with the given `severity`; notes and help are both counted as `info`.
+    ///
     #[inline(always)]
-    fn report(&mut self, severity: Severity) {
+    pub fn report(&mut self, severity: Severity) {
         match severity {
             Severity::Bug => self.bugs += 1,
             Severity::Error => self.errors += 1,
@@ -210,19 +298,15 @@ impl Default for StandardStreamReporter {
 
 impl Reporter for StandardStreamReporter {
     fn emit(&self, diagnostic: &Diagnostic, sources: &SourceFiles) -> Result<(), Error> {
-        if self.is_enabled(diagnostic.severity) {
-            self.log(diagnostic);
-            let mut counters = self.counters.borrow_mut();
-            counters.report(diagnostic.severity);
-            Ok(emit(
-                &mut self.stream.lock(),
-                &self.config,
-                sources,
-                diagnostic,
-            )?)
-        } else {
-            Ok(())
-        }
+        self.emit_with_suggestions(diagnostic, &[], sources)
+    }
+
+    fn emit_suggested(
+        &self,
+        diagnostic: &SuggestedDiagnostic,
+        sources: &SourceFiles,
+    ) -> Result<(), Error> {
+        self.emit_with_suggestions(diagnostic.diagnostic(), diagnostic.suggestions(), sources)
     }
 
     fn counters(&self) -> ReportCounters {
@@ -339,6 +423,32 @@ impl StandardStreamReporter {
         }
         Ok(())
     }
+
+    fn emit_with_suggestions(
+        &self,
+        diagnostic: &Diagnostic,
+        suggestions: &[Suggestion],
+        sources: &SourceFiles,
+    ) -> Result<(), Error> {
+        if self.is_enabled(diagnostic.severity) {
+            self.log(diagnostic);
+            let mut counters = self.counters.borrow_mut();
+            counters.report(diagnostic.severity);
+            // suggestions are shown as labels.
+            let mut diagnostic = diagnostic.clone();
+            diagnostic
+                .labels
+                .extend(suggestions.iter().map(Suggestion::to_label));
+            Ok(emit(
+                &mut self.stream.lock(),
+                &self.config,
+                sources,
+                &diagnostic,
+            )?)
+        } else {
+            Ok(())
+        }
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -423,6 +533,228 @@ impl CompactStreamReporter {
 
 // ------------------------------------------------------------------------------------------------
 
+impl Default for CollectingReporter {
+    fn default() -> Self {
+        Self {
+            filter: SeverityFilter::Help,
+            counters: Default::default(),
+            diagnostics: Default::default(),
+        }
+    }
+}
+
+impl Reporter for CollectingReporter {
+    fn emit(&self, diagnostic: &Diagnostic, sources: &SourceFiles) -> Result<(), Error> {
+        self.emit_suggested(&SuggestedDiagnostic::new(diagnostic.clone()), sources)
+    }
+
+    fn emit_suggested(
+        &self,
+        diagnostic: &SuggestedDiagnostic,
+        _: &SourceFiles,
+    ) -> Result<(), Error> {
+        let severity = diagnostic.diagnostic().severity;
+        if self.is_enabled(severity) {
+            self.log(diagnostic.diagnostic());
+            self.counters.borrow_mut().report(severity);
+            self.diagnostics.borrow_mut().push(diagnostic.clone());
+        }
+        Ok(())
+    }
+
+    fn counters(&self) -> ReportCounters {
+        *self.counters.borrow()
+    }
+
+    fn done(&self, _: Option<String>) -> Result<ReportCounters, Error> {
+        let old_counters = self.counters.replace(ReportCounters::default());
+        Ok(old_counters)
+    }
+
+    fn severity_filter(&self) -> SeverityFilter {
+        self.filter
+    }
+
+    fn set_severity_filter(&mut self, filter: SeverityFilter) {
+        self.filter = filter;
+    }
+}
+
+impl CollectingReporter {
+    pub fn with_severity_filter(self, filter: SeverityFilter) -> Self {
+        Self { filter, ..self }
+    }
+
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.diagnostics
+            .borrow()
+            .iter()
+            .map(|diagnostic| diagnostic.diagnostic().clone())
+            .collect()
+    }
+
+    ///
+    /// Remove, and return, all the diagnostics kept so far.
+    ///
+    pub fn take_diagnostics(&self) -> Vec<Diagnostic> {
+        self.take_suggested_diagnostics()
+            .into_iter()
+            .map(Diagnostic::from)
+            .collect()
+    }
+
+    ///
+    /// Remove, and return, all the diagnostics kept so far along with their suggestions.
+    ///
+    pub fn take_suggested_diagnostics(&self) -> Vec<SuggestedDiagnostic> {
+        self.diagnostics.replace(Default::default())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Default for SuppressingReporter {
+    fn default() -> Self {
+        Self::new(Box::<StandardStreamReporter>::default())
+    }
+}
+
+impl Reporter for SuppressingReporter {
+    fn emit(&self, diagnostic: &Diagnostic, sources: &SourceFiles) -> Result<(), Error> {
+        match self.rules.apply(diagnostic) {
+            Some(diagnostic) if !self.is_suppressed(&diagnostic) => {
+                self.inner.emit(&diagnostic, sources)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn emit_suggested(
+        &self,
+        diagnostic: &SuggestedDiagnostic,
+        sources: &SourceFiles,
+    ) -> Result<(), Error> {
+        match self.rules.apply(diagnostic.diagnostic()) {
+            Some(applied) if !self.is_suppressed(&applied) => {
+                let suggested = SuggestedDiagnostic::new(applied.into_owned())
+                    .with_suggestions(diagnostic.suggestions().iter().cloned());
+                self.inner.emit_suggested(&suggested, sources)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn counters(&self) -> ReportCounters {
+        self.inner.counters()
+    }
+
+    fn done(&self, module_name: Option<String>) -> Result<ReportCounters, Error> {
+        self.inner.done(module_name)
+    }
+
+    fn severity_filter(&self) -> SeverityFilter {
+        self.inner.severity_filter()
+    }
+
+    fn set_severity_filter(&mut self, filter: SeverityFilter) {
+        self.inner.set_severity_filter(filter);
+    }
+}
+
+impl SuppressingReporter {
+    pub fn new(inner: Box<dyn Reporter>) -> Self {
+        Self {
+            inner,
+            rules: Default::default(),
+            suppressions: Default::default(),
+            used: Default::default(),
+            current_file: Default::default(),
+        }
+    }
+
+    pub fn with_rules(self, rules: LintRules) -> Self {
+        Self { rules, ..self }
+    }
+
+    pub fn rules(&self) -> &LintRules {
+        &self.rules
+    }
+
+    pub fn set_rules(&mut self, rules: LintRules) {
+        self.rules = rules;
+    }
+
+    pub fn add_suppression(&mut self, suppression: Suppression) {
+        if suppression.has_codes() {
+            self.suppressions.push(suppression);
+        }
+    }
+
+    pub fn suppressions(&self) -> impl Iterator<Item = &Suppression> {
+        self.suppressions.iter()
+    }
+
+    ///
+    /// Set the source file that diagnostics without a primary label are reported for, these may
+    /// then be suppressed by that file's module-scope suppressions. Returns the previous file.
+    ///
+    pub fn set_current_file(&self, file_id: Option<FileId>) -> Option<FileId> {
+        self.current_file.replace(file_id)
+    }
+
+    pub fn current_file(&self) -> Option<FileId> {
+        self.current_file.get()
+    }
+
+    ///
+    /// Returns `true` if `diagnostic` is suppressed, where more than one suppression applies the
+    /// one with the narrowest scope is marked as used.
+    ///
+    pub fn is_suppressed(&self, diagnostic: &Diagnostic) -> bool {
+        let found = self
+            .suppressions
+            .iter()
+            .enumerate()
+            .filter_map(|(i, suppression)| {
+                suppression
+                    .suppresses_in_file(diagnostic, self.current_file.get())
+                    .map(|code| (i, code, suppression.scope().len()))
+            })
+            .min_by_key(|(_, _, len)| *len);
+        if let Some((i, code, _)) = found {
+            self.used.borrow_mut().insert((i, code));
+            true
+        } else {
+            false
+        }
+    }
+
+    ///
+    /// Return a diagnostic for each suppressed code, in the source file `file_id`, that has not
+    /// suppressed any diagnostic.
+    ///
+    pub fn unused_suppressions(&self, file_id: FileId) -> Vec<Diagnostic> {
+        let used = self.used.borrow();
+        let used = &*used;
+        self.suppressions
+            .iter()
+            .enumerate()
+            .filter(|(_, suppression)| suppression.file_id() == file_id)
+            .flat_map(|(i, suppression)| {
+                suppression
+                    .codes()
+                    .enumerate()
+                    .filter(move |(code, _)| !used.contains(&(i, *code)))
+                    .map(move |(_, code)| {
+                        unused_suppression(file_id, code.span().cloned(), code.code())
+                    })
+            })
+            .collect()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
 impl Reporter for BailoutReporter {
     fn emit(&self, diagnostic: &Diagnostic, _: &SourceFiles) -> Result<(), Error> {
         if self.is_enabled(diagnostic.severity) {
@@ -452,3 +784,458 @@ impl Reporter for BailoutReporter {
         self.filter = filter;
     }
 }
+
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "sarif")]
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+#[cfg(feature = "sarif")]
+const SARIF_VERSION: &str = "2.1.0";
+
+#[cfg(feature = "sarif")]
+const SDML_INFORMATION_URI: &str = "https://sdml.io/";
+
+#[cfg(feature = "sarif")]
+impl Debug for SarifReporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SarifReporter")
+            .field("filter", &self.filter)
+            .field("counters", &self.counters)
+            .field("tool_name", &self.tool_name)
+            .field("tool_version", &self.tool_version)
+            .field("rules", &self.rules)
+            .field("results", &self.results)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "sarif")]
+impl Default for SarifReporter {
+    fn default() -> Self {
+        Self::new(std::io::stdout())
+    }
+}
+
+#[cfg(feature = "sarif")]
+impl Reporter for SarifReporter {
+    fn emit(&self, diagnostic: &Diagnostic, sources: &SourceFiles) -> Result<(), Error> {
+        self.emit_with_suggestions(diagnostic, &[], sources)
+    }
+
+    fn emit_suggested(
+        &self,
+        diagnostic: &SuggestedDiagnostic,
+        sources: &SourceFiles,
+    ) -> Result<(), Error> {
+        self.emit_with_suggestions(diagnostic.diagnostic(), diagnostic.suggestions(), sources)
+    }
+
+    fn counters(&self) -> ReportCounters {
+        *self.counters.borrow()
+    }
+
+    fn done(&self, _: Option<String>) -> Result<ReportCounters, Error> {
+        let rules: Vec<Value> = self
+            .rules
+            .replace(Default::default())
+            .iter()
+            .map(|rule_id| sarif_rule(rule_id))
+            .collect();
+        let results = self.results.replace(Default::default());
+        let log = json!({
+            "$schema": SARIF_SCHEMA,
+            "version": SARIF_VERSION,
+            "runs": [
+                {
+                    "tool": {
+                        "driver": {
+                            "name": self.tool_name,
+                            "version": self.tool_version,
+                            "informationUri": SDML_INFORMATION_URI,
+                            "rules": rules,
+                        }
+                    },
+                    "columnKind": "unicodeCodePoints",
+                    "results": results,
+                }
+            ]
+        });
+
+        let mut writer = self.writer.borrow_mut();
+        serde_json::to_writer_pretty(&mut *writer, &log).map_err(std::io::Error::from)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+
+        let old_counters = self.counters.replace(ReportCounters::default());
+        Ok(old_counters)
+    }
+
+    fn severity_filter(&self) -> SeverityFilter {
+        self.filter
+    }
+
+    fn set_severity_filter(&mut self, filter: SeverityFilter) {
+        self.filter = filter;
+    }
+}
+
+#[cfg(feature = "sarif")]
+impl SarifReporter {
+    ///
+    /// Create a new reporter that writes the SARIF log to `writer`. The tool is described as this
+    /// crate, use [`SarifReporter::with_tool`] to describe the actual tool.
+    ///
+    pub fn new<W>(writer: W) -> Self
+    where
+        W: Write + 'static,
+    {
+        Self {
+            writer: RefCell::new(Box::new(writer)),
+            filter: Default::default(),
+            counters: Default::default(),
+            tool_name: env!("CARGO_PKG_NAME").to_string(),
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            rules: Default::default(),
+            results: Default::default(),
+        }
+    }
+
+    pub fn with_tool<S1, S2>(self, name: S1, version: S2) -> Self
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        Self {
+            tool_name: name.into(),
+            tool_version: version.into(),
+            ..self
+        }
+    }
+
+    pub fn with_severity_filter(self, filter: SeverityFilter) -> Self {
+        Self { filter, ..self }
+    }
+
+    fn emit_with_suggestions(
+        &self,
+        diagnostic: &Diagnostic,
+        suggestions: &[Suggestion],
+        sources: &SourceFiles,
+    ) -> Result<(), Error> {
+        if self.is_enabled(diagnostic.severity) {
+            self.log(diagnostic);
+            let mut counters = self.counters.borrow_mut();
+            counters.report(diagnostic.severity);
+
+            let rule_id = diagnostic.code.clone().unwrap_or_default();
+            let rule_index = {
+                let mut rules = self.rules.borrow_mut();
+                match rules.iter().position(|rule| rule == &rule_id) {
+                    Some(index) => index,
+                    None => {
+                        rules.push(rule_id.clone());
+                        rules.len() - 1
+                    }
+                }
+            };
+
+            let mut result = json!({
+                "ruleId": rule_id,
+                "ruleIndex": rule_index,
+                "level": sarif_level(diagnostic.severity),
+                "message": { "text": diagnostic.message },
+            });
+            let labels = &diagnostic.labels;
+            let locations: Vec<Value> = labels
+                .iter()
+                .filter(|label| label.style == LabelStyle::Primary)
+                .filter_map(|label| sarif_location(label, sources))
+                .collect();
+            if !locations.is_empty() {
+                result["locations"] = Value::from(locations);
+            }
+            let related: Vec<Value> = labels
+                .iter()
+                .filter(|label| label.style == LabelStyle::Secondary)
+                .filter_map(|label| sarif_location(label, sources))
+                .enumerate()
+                .map(|(id, mut location)| {
+                    location["id"] = Value::from(id);
+                    location
+                })
+                .collect();
+            if !related.is_empty() {
+                result["relatedLocations"] = Value::from(related);
+            }
+            let fixes: Vec<Value> = suggestions
+                .iter()
+                .filter_map(|suggestion| sarif_fix(suggestion, sources))
+                .collect();
+            if !fixes.is_empty() {
+                result["fixes"] = Value::from(fixes);
+            }
+            if !diagnostic.notes.is_empty() {
+                result["properties"] = json!({ "notes": diagnostic.notes });
+            }
+
+            self.results.borrow_mut().push(result);
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "json")]
+impl Debug for JsonLinesReporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonLinesReporter")
+            .field("filter", &self.filter)
+            .field("counters", &self.counters)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "json")]
+impl Default for JsonLinesReporter {
+    fn default() -> Self {
+        Self::new(std::io::stdout())
+    }
+}
+
+#[cfg(feature = "json")]
+impl Reporter for JsonLinesReporter {
+    fn emit(&self, diagnostic: &Diagnostic, sources: &SourceFiles) -> Result<(), Error> {
+        self.emit_with_suggestions(diagnostic, &[], sources)
+    }
+
+    fn emit_suggested(
+        &self,
+        diagnostic: &SuggestedDiagnostic,
+        sources: &SourceFiles,
+    ) -> Result<(), Error> {
+        self.emit_with_suggestions(diagnostic.diagnostic(), diagnostic.suggestions(), sources)
+    }
+
+    fn counters(&self) -> ReportCounters {
+        *self.counters.borrow()
+    }
+
+    fn done(&self, _: Option<String>) -> Result<ReportCounters, Error> {
+        self.writer.borrow_mut().flush()?;
+        let old_counters = self.counters.replace(ReportCounters::default());
+        Ok(old_counters)
+    }
+
+    fn severity_filter(&self) -> SeverityFilter {
+        self.filter
+    }
+
+    fn set_severity_filter(&mut self, filter: SeverityFilter) {
+        self.filter = filter;
+    }
+}
+
+#[cfg(feature = "json")]
+impl JsonLinesReporter {
+    pub fn new<W>(writer: W) -> Self
+    where
+        W: Write + 'static,
+    {
+        Self {
+            writer: RefCell::new(Box::new(writer)),
+            filter: Default::default(),
+            counters: Default::default(),
+        }
+    }
+
+    pub fn with_severity_filter(self, filter: SeverityFilter) -> Self {
+        Self { filter, ..self }
+    }
+
+    fn emit_with_suggestions(
+        &self,
+        diagnostic: &Diagnostic,
+        suggestions: &[Suggestion],
+        sources: &SourceFiles,
+    ) -> Result<(), Error> {
+        if self.is_enabled(diagnostic.severity) {
+            self.log(diagnostic);
+            let mut counters = self.counters.borrow_mut();
+            counters.report(diagnostic.severity);
+
+            let code = diagnostic
+                .code
+                .as_ref()
+                .and_then(|code| ErrorCode::from_code(code));
+            let help_note = code
+                .map(|code| i18n!("help_more_details_url", url = code.url_string()).to_string());
+            let record = json!({
+                "code": diagnostic.code,
+                "severity": json_severity(diagnostic.severity),
+                "message": diagnostic.message,
+                "labels": diagnostic
+                    .labels
+                    .iter()
+                    .map(|label| json_label(label, sources))
+                    .collect::<Vec<_>>(),
+                "notes": diagnostic
+                    .notes
+                    .iter()
+                    .filter(|note| Some(*note) != help_note.as_ref())
+                    .collect::<Vec<_>>(),
+                "help": code.map(|code| code.url_string()),
+                "suggestions": suggestions
+                    .iter()
+                    .map(|suggestion| json_suggestion(suggestion, sources))
+                    .collect::<Vec<_>>(),
+            });
+
+            let mut writer = self.writer.borrow_mut();
+            serde_json::to_writer(&mut *writer, &record).map_err(std::io::Error::from)?;
+            writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "sarif")]
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug | Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note | Severity::Help => "note",
+    }
+}
+
+#[cfg(feature = "sarif")]
+fn sarif_rule(rule_id: &str) -> Value {
+    match ErrorCode::from_code(rule_id) {
+        Some(code) => json!({
+            "id": rule_id,
+            "name": format!("{code:?}"),
+            "shortDescription": { "text": code.message() },
+            "helpUri": code.url_string(),
+            "defaultConfiguration": { "level": sarif_level(code.severity()) },
+            "properties": { "number": code.number() },
+        }),
+        None => json!({ "id": rule_id }),
+    }
+}
+
+#[cfg(feature = "sarif")]
+fn sarif_location(label: &Label<FileId>, sources: &SourceFiles) -> Option<Value> {
+    let mut location = json!({
+        "physicalLocation": sarif_physical_location(label.file_id, &label.range, sources)?
+    });
+    if !label.message.is_empty() {
+        location["message"] = json!({ "text": label.message });
+    }
+    Some(location)
+}
+
+#[cfg(feature = "sarif")]
+fn sarif_fix(suggestion: &Suggestion, sources: &SourceFiles) -> Option<Value> {
+    let location = sarif_physical_location(suggestion.file_id(), suggestion.span(), sources)?;
+    Some(json!({
+        "artifactChanges": [
+            {
+                "artifactLocation": location["artifactLocation"],
+                "replacements": [
+                    {
+                        "deletedRegion": location["region"],
+                        "insertedContent": { "text": suggestion.replacement() },
+                    }
+                ]
+            }
+        ]
+    }))
+}
+
+#[cfg(feature = "sarif")]
+fn sarif_physical_location(
+    file_id: FileId,
+    range: &crate::Span,
+    sources: &SourceFiles,
+) -> Option<Value> {
+    use codespan_reporting::files::Files;
+    let file_name = sources.name(file_id).ok()?;
+    let start = sources.location(file_id, range.start).ok()?;
+    let end = sources.location(file_id, range.end).ok()?;
+    Some(json!({
+        "artifactLocation": { "uri": sarif_uri(&file_name) },
+        "region": {
+            "startLine": start.line_number,
+            "startColumn": start.column_number,
+            "endLine": end.line_number,
+            "endColumn": end.column_number,
+            "byteOffset": range.start,
+            "byteLength": range.end - range.start,
+        }
+    }))
+}
+
+#[cfg(feature = "sarif")]
+fn sarif_uri(file_name: &str) -> String {
+    let path = Path::new(file_name);
+    if path.is_absolute() {
+        if let Ok(url) = Url::from_file_path(path) {
+            return url.to_string();
+        }
+    }
+    file_name.replace('\\', "/")
+}
+
+#[cfg(feature = "json")]
+fn json_severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug => "bug",
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+        Severity::Help => "help",
+    }
+}
+
+#[cfg(feature = "json")]
+fn json_label(label: &Label<FileId>, sources: &SourceFiles) -> Value {
+    let mut value = json_location(label.file_id, &label.range, sources);
+    value["style"] = Value::from(match label.style {
+        LabelStyle::Primary => "primary",
+        LabelStyle::Secondary => "secondary",
+    });
+    value["message"] = Value::from(label.message.as_str());
+    value
+}
+
+#[cfg(feature = "json")]
+fn json_suggestion(suggestion: &Suggestion, sources: &SourceFiles) -> Value {
+    let mut value = json_location(suggestion.file_id(), suggestion.span(), sources);
+    value["replacement"] = Value::from(suggestion.replacement());
+    value
+}
+
+// The file name, and start and end line and column, are omitted if not in `sources`.
+#[cfg(feature = "json")]
+fn json_location(file_id: FileId, range: &crate::Span, sources: &SourceFiles) -> Value {
+    use codespan_reporting::files::Files;
+    let mut value = json!({
+        "span": { "start": range.start, "end": range.end },
+    });
+    if let Ok(file_name) = sources.name(file_id) {
+        value["file"] = Value::from(file_name);
+    }
+    if let (Ok(start), Ok(end)) = (
+        sources.location(file_id, range.start),
+        sources.location(file_id, range.end),
+    ) {
+        value["start"] = json!({ "line": start.line_number, "column": start.column_number });
+        value["end"] = json!({ "line": end.line_number, "column": end.column_number });
+    }
+    value
+}
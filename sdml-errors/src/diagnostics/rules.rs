@@ -0,0 +1,157 @@
+/*!
+Provides [`LintRules`], a project's configuration of which diagnostics are reported, at what
+severity, and which case styles identifiers are expected to use.
+
+Rules only ever relax or strengthen warnings and informational diagnostics; errors, and bugs,
+are always reported and may not be disabled or lowered to a non-error severity. The
+[`SuppressingReporter`](crate::diagnostics::reporter::SuppressingReporter) applies rules to the
+diagnostics it is given.
+
+# Example
+
+```rust
+use codespan_reporting::diagnostic::Severity;
+use sdml_errors::diagnostics::functions::{
+    double_underscored_identifier, string_without_language, CaseStyle, IdentifierCaseConvention,
+};
+use sdml_errors::diagnostics::rules::LintRules;
+use sdml_errors::diagnostics::ErrorCode;
+
+let rules = LintRules::default()
+    .with_disabled(ErrorCode::StringWithoutLanguage)
+    .with_severity(ErrorCode::DoubleUnderscoredIdentifier, Severity::Error)
+    .with_case_styles(IdentifierCaseConvention::Member, [CaseStyle::LowerCamelCase]);
+
+assert!(rules.apply(&string_without_language(0, None, "Name")).is_none());
+assert_eq!(
+    rules.apply(&double_underscored_identifier(0, None, "__name")).unwrap().severity,
+    Severity::Error
+);
+assert_eq!(
+    rules.case_styles(IdentifierCaseConvention::Member),
+    Some([CaseStyle::LowerCamelCase].as_slice())
+);
+```
+ */
+
+use crate::diagnostics::functions::{CaseStyle, IdentifierCaseConvention};
+use crate::diagnostics::{Diagnostic, ErrorCode};
+use codespan_reporting::diagnostic::Severity;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The set of disabled codes, severity overrides, and case style overrides for a project. The
+/// default rules change nothing.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LintRules {
+    disabled: HashSet<ErrorCode>,
+    severities: HashMap<ErrorCode, Severity>,
+    case_styles: HashMap<IdentifierCaseConvention, Vec<CaseStyle>>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl LintRules {
+    pub fn with_disabled(mut self, code: ErrorCode) -> Self {
+        self.disable(code);
+        self
+    }
+
+    pub fn with_severity(mut self, code: ErrorCode, severity: Severity) -> Self {
+        self.set_severity(code, severity);
+        self
+    }
+
+    pub fn with_case_styles<I>(mut self, convention: IdentifierCaseConvention, styles: I) -> Self
+    where
+        I: IntoIterator<Item = CaseStyle>,
+    {
+        self.set_case_styles(convention, styles);
+        self
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn is_empty(&self) -> bool {
+        self.disabled.is_empty() && self.severities.is_empty() && self.case_styles.is_empty()
+    }
+
+    pub fn disable(&mut self, code: ErrorCode) {
+        self.disabled.insert(code);
+    }
+
+    ///
+    /// Re-enable `code`, if disabled; this does not remove any severity override.
+    ///
+    pub fn enable(&mut self, code: ErrorCode) {
+        self.disabled.remove(&code);
+    }
+
+    ///
+    /// Returns `true` unless `code` has been disabled, errors are always enabled.
+    ///
+    pub fn is_enabled(&self, code: ErrorCode) -> bool {
+        code.severity() >= Severity::Error || !self.disabled.contains(&code)
+    }
+
+    pub fn set_severity(&mut self, code: ErrorCode, severity: Severity) {
+        self.severities.insert(code, severity);
+    }
+
+    ///
+    /// Return the severity that `code` is reported with; errors and bugs are never reported with
+    /// a severity lower than their default.
+    ///
+    pub fn severity(&self, code: ErrorCode) -> Severity {
+        let default = code.severity();
+        match self.severities.get(&code) {
+            Some(severity) if default < Severity::Error || *severity >= default => *severity,
+            _ => default,
+        }
+    }
+
+    pub fn set_case_styles<I>(&mut self, convention: IdentifierCaseConvention, styles: I)
+    where
+        I: IntoIterator<Item = CaseStyle>,
+    {
+        let styles: Vec<CaseStyle> = styles.into_iter().collect();
+        if styles.is_empty() {
+            self.case_styles.remove(&convention);
+        } else {
+            self.case_styles.insert(convention, styles);
+        }
+    }
+
+    ///
+    /// Return the case styles configured for `convention`, or `None` if the convention's own
+    /// [styles](IdentifierCaseConvention::styles) are to be used.
+    ///
+    pub fn case_styles(&self, convention: IdentifierCaseConvention) -> Option<&[CaseStyle]> {
+        self.case_styles.get(&convention).map(Vec::as_slice)
+    }
+
+    ///
+    /// Apply these rules to `diagnostic`, returning `None` if its code is disabled or the
+    /// diagnostic with any severity override applied. Diagnostics without a known code are
+    /// returned unchanged.
+    ///
+    pub fn apply<'a>(&self, diagnostic: &'a Diagnostic) -> Option<Cow<'a, Diagnostic>> {
+        match diagnostic.code.as_deref().and_then(ErrorCode::from_code) {
+            Some(code) if !self.is_enabled(code) => None,
+            Some(code) if self.severity(code) != diagnostic.severity => {
+                let mut diagnostic = diagnostic.clone();
+                diagnostic.severity = self.severity(code);
+                Some(Cow::Owned(diagnostic))
+            }
+            _ => Some(Cow::Borrowed(diagnostic)),
+        }
+    }
+}
@@ -0,0 +1,217 @@
+/*!
+Provides [`Suggestion`], a text edit that would fix the condition reported by a [`Diagnostic`],
+and [`SuggestedDiagnostic`] which carries a diagnostic along with its suggestions.
+
+The functions that create diagnostics with suggestions return a [`SuggestedDiagnostic`], and
+these are reported with [`Reporter::emit_suggested`]. The labels of a diagnostic never include its
+suggestions; reporters that display diagnostics use [`Suggestion::to_label`] to show them, for
+example as ``fix: replace with `my_module` `` or `fix: remove`.
+
+# Example
+
+```rust
+use sdml_errors::diagnostics::functions::duplicate_module_import_with_removal;
+use sdml_errors::diagnostics::suggestions::Suggestion;
+
+let diagnostic = duplicate_module_import_with_removal(0, 9..12, 13..16, 12..16);
+
+assert_eq!(diagnostic.diagnostic().labels.len(), 2);
+assert_eq!(diagnostic.suggestions(), &[Suggestion::remove(0, 12..16)]);
+assert_eq!(
+    Suggestion::apply_all("import [ xsd xsd ]", diagnostic.suggestions()),
+    "import [ xsd ]"
+);
+```
+
+[`Reporter::emit_suggested`]: crate::diagnostics::reporter::Reporter::emit_suggested
+ */
+
+use crate::diagnostics::Diagnostic;
+use crate::{FileId, Span};
+use codespan_reporting::diagnostic::Label;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A suggested edit, replacing the text in `span` of the source file `file_id` with
+/// `replacement`. An empty replacement removes the text, and an empty span inserts the
+/// replacement.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Suggestion {
+    file_id: FileId,
+    span: Span,
+    replacement: String,
+}
+
+///
+/// A diagnostic along with the suggestions that would fix the condition it reports.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SuggestedDiagnostic {
+    diagnostic: Diagnostic,
+    suggestions: Vec<Suggestion>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Values
+// ------------------------------------------------------------------------------------------------
+
+const FIX_PREFIX: &str = "fix: ";
+const FIX_REMOVE: &str = "remove";
+const FIX_REPLACE: &str = "replace with `";
+const FIX_INSERT: &str = "insert `";
+const FIX_SUFFIX: &str = "`";
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl From<Diagnostic> for SuggestedDiagnostic {
+    fn from(diagnostic: Diagnostic) -> Self {
+        Self::new(diagnostic)
+    }
+}
+
+impl From<SuggestedDiagnostic> for Diagnostic {
+    fn from(value: SuggestedDiagnostic) -> Self {
+        value.diagnostic
+    }
+}
+
+impl SuggestedDiagnostic {
+    pub fn new(diagnostic: Diagnostic) -> Self {
+        Self {
+            diagnostic,
+            suggestions: Default::default(),
+        }
+    }
+
+    pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        if !self.suggestions.contains(&suggestion) {
+            self.suggestions.push(suggestion);
+        }
+        self
+    }
+
+    pub fn with_suggestions<I>(self, suggestions: I) -> Self
+    where
+        I: IntoIterator<Item = Suggestion>,
+    {
+        suggestions
+            .into_iter()
+            .fold(self, |diagnostic, suggestion| {
+                diagnostic.with_suggestion(suggestion)
+            })
+    }
+
+    pub fn diagnostic(&self) -> &Diagnostic {
+        &self.diagnostic
+    }
+
+    pub fn has_suggestions(&self) -> bool {
+        !self.suggestions.is_empty()
+    }
+
+    pub fn suggestions(&self) -> &[Suggestion] {
+        &self.suggestions
+    }
+
+    pub fn into_parts(self) -> (Diagnostic, Vec<Suggestion>) {
+        (self.diagnostic, self.suggestions)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Suggestion {
+    pub fn replace<S>(file_id: FileId, span: Span, replacement: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            file_id,
+            span,
+            replacement: replacement.into(),
+        }
+    }
+
+    pub fn remove(file_id: FileId, span: Span) -> Self {
+        Self::replace(file_id, span, String::new())
+    }
+
+    pub fn insert<S>(file_id: FileId, at: usize, text: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::replace(file_id, at..at, text)
+    }
+
+    pub fn file_id(&self) -> FileId {
+        self.file_id
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+
+    pub fn is_removal(&self) -> bool {
+        self.replacement.is_empty()
+    }
+
+    pub fn is_insertion(&self) -> bool {
+        self.span.is_empty()
+    }
+
+    ///
+    /// Apply `suggestions` to `source`, ignoring the file identifier of each. Suggestions are
+    /// applied from the end of `source`, and any suggestion overlapping one already applied, or
+    /// outside `source`, is skipped.
+    ///
+    pub fn apply_all(source: &str, suggestions: &[Suggestion]) -> String {
+        let mut suggestions: Vec<&Suggestion> = suggestions.iter().collect();
+        suggestions.sort_by(|lhs, rhs| {
+            rhs.span
+                .start
+                .cmp(&lhs.span.start)
+                .then(rhs.span.end.cmp(&lhs.span.end))
+        });
+        suggestions.dedup();
+
+        let mut result = source.to_string();
+        let mut limit = source.len();
+        for suggestion in suggestions {
+            let Span { start, end } = suggestion.span;
+            if start <= end
+                && end <= limit
+                && result.is_char_boundary(start)
+                && result.is_char_boundary(end)
+            {
+                result.replace_range(start..end, &suggestion.replacement);
+                limit = start;
+            }
+        }
+        result
+    }
+
+    ///
+    /// A secondary label showing this suggestion, with a fixed, untranslated, message; for
+    /// display only.
+    ///
+    pub fn to_label(&self) -> Label<FileId> {
+        let message = if self.is_removal() {
+            format!("{FIX_PREFIX}{FIX_REMOVE}")
+        } else if self.is_insertion() {
+            format!("{FIX_PREFIX}{FIX_INSERT}{}{FIX_SUFFIX}", self.replacement)
+        } else {
+            format!("{FIX_PREFIX}{FIX_REPLACE}{}{FIX_SUFFIX}", self.replacement)
+        };
+        Label::secondary(self.file_id, self.span.clone()).with_message(message)
+    }
+}
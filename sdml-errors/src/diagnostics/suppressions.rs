@@ -0,0 +1,163 @@
+/*!
+Provides [`Suppression`], a set of diagnostic codes that are not reported within some part of
+a source file.
+
+Suppressions are declared in module source with the `sdml:suppress` annotation on a module,
+definition, or member, and the scope of the suppression is the source span of the annotated
+element. A suppression on the module itself also applies to diagnostics that have no location,
+so long as they are reported for the module's source file. Only warnings and informational diagnostics may be suppressed, errors are always
+reported. The [`SuppressingReporter`](crate::diagnostics::reporter::SuppressingReporter) applies
+suppressions to the diagnostics it is given.
+
+# Example
+
+```rust
+use sdml_errors::diagnostics::functions::using_unconstrained_datatype;
+use sdml_errors::diagnostics::suppressions::Suppression;
+
+let suppression = Suppression::new(0, 10..80).with_code("I0504", Some(30..37));
+let diagnostic = using_unconstrained_datatype(0, Some(50..56), "string");
+
+assert!(suppression.suppresses(&diagnostic).is_some());
+```
+ */
+
+use crate::diagnostics::Diagnostic;
+use crate::{FileId, Span};
+use codespan_reporting::diagnostic::{LabelStyle, Severity};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The codes that are not reported for diagnostics whose primary label is within `scope` of the
+/// source file `file_id`. A module-scope suppression also applies to diagnostics without a
+/// primary label that are reported for the same source file.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Suppression {
+    file_id: FileId,
+    scope: Span,
+    module_scope: bool,
+    codes: Vec<SuppressedCode>,
+}
+
+///
+/// A single code within a [`Suppression`], the span is the location of the code in the
+/// annotation, if known.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SuppressedCode {
+    code: String,
+    span: Option<Span>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Suppression {
+    pub fn new(file_id: FileId, scope: Span) -> Self {
+        Self {
+            file_id,
+            scope,
+            module_scope: false,
+            codes: Default::default(),
+        }
+    }
+
+    pub fn with_module_scope(self) -> Self {
+        Self {
+            module_scope: true,
+            ..self
+        }
+    }
+
+    pub fn with_code<S>(mut self, code: S, span: Option<Span>) -> Self
+    where
+        S: Into<String>,
+    {
+        self.codes.push(SuppressedCode {
+            code: code.into(),
+            span,
+        });
+        self
+    }
+
+    pub fn file_id(&self) -> FileId {
+        self.file_id
+    }
+
+    pub fn scope(&self) -> &Span {
+        &self.scope
+    }
+
+    pub fn is_module_scope(&self) -> bool {
+        self.module_scope
+    }
+
+    pub fn has_codes(&self) -> bool {
+        !self.codes.is_empty()
+    }
+
+    pub fn codes(&self) -> impl Iterator<Item = &SuppressedCode> {
+        self.codes.iter()
+    }
+
+    ///
+    /// If `diagnostic` is suppressed, return the index of the matching code. A diagnostic is
+    /// suppressed if it is not an error, its code is one of this suppression's codes, and its
+    /// first primary label is within the scope of this suppression.
+    ///
+    pub fn suppresses(&self, diagnostic: &Diagnostic) -> Option<usize> {
+        self.suppresses_in_file(diagnostic, None)
+    }
+
+    ///
+    /// As [`Self::suppresses`], except that a diagnostic without a primary label is taken to be
+    /// from the source file `current_file`, and is suppressed if this is a module-scope
+    /// suppression in that file.
+    ///
+    pub fn suppresses_in_file(
+        &self,
+        diagnostic: &Diagnostic,
+        current_file: Option<FileId>,
+    ) -> Option<usize> {
+        if diagnostic.severity >= Severity::Error {
+            return None;
+        }
+        let code = diagnostic.code.as_ref()?;
+        let in_scope = match diagnostic
+            .labels
+            .iter()
+            .find(|label| label.style == LabelStyle::Primary)
+        {
+            Some(label) => {
+                label.file_id == self.file_id
+                    && label.range.start >= self.scope.start
+                    && label.range.end <= self.scope.end
+            }
+            None => self.module_scope && current_file == Some(self.file_id),
+        };
+        if in_scope {
+            self.codes
+                .iter()
+                .position(|suppressed| &suppressed.code == code)
+        } else {
+            None
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl SuppressedCode {
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn span(&self) -> Option<&Span> {
+        self.span.as_ref()
+    }
+}
@@ -58,6 +58,41 @@ pub enum Error {
         name: String,
         message: String,
     },
+    /// An error was signaled while reading, building, or resolving a model package.
+    PackageError {
+        name: String,
+        message: String,
+    },
+    /// An error was signaled while planning a refactoring of one or more modules.
+    RefactorError {
+        name: String,
+        message: String,
+    },
+    /// An error was signaled while parsing a model query.
+    QueryError {
+        query: String,
+        message: String,
+    },
+    /// An error was signaled while building a module with the programmatic builder API.
+    BuilderError {
+        name: String,
+        message: String,
+    },
+    /// An error was signaled while selecting the locale used for diagnostic messages.
+    LocaleError {
+        locale: String,
+        message: String,
+    },
+    /// An error was signaled while reading or parsing a lint configuration file.
+    LintConfigError {
+        file: String,
+        message: String,
+    },
+    /// An error was signaled while reading a deprecated term set.
+    TermSetError {
+        name: String,
+        message: String,
+    },
 }
 
 ///
@@ -110,6 +145,20 @@ impl Display for Error {
                     format!("An error occurred parsing a BCP-47 language tag; source: {source}"),
                 Self::GeneratorError { name, message } =>
                     format!("An error occurred in a generator named `{name}`: {message}"),
+                Self::PackageError { name, message } =>
+                    format!("An error occurred in the package `{name}`: {message}"),
+                Self::RefactorError { name, message } =>
+                    format!("An error occurred refactoring `{name}`: {message}"),
+                Self::QueryError { query, message } =>
+                    format!("An error occurred in the query `{query}`: {message}"),
+                Self::BuilderError { name, message } =>
+                    format!("An error occurred building `{name}`: {message}"),
+                Self::LocaleError { locale, message } =>
+                    format!("An error occurred selecting the locale `{locale}`: {message}"),
+                Self::LintConfigError { file, message } =>
+                    format!("An error occurred in the lint configuration `{file}`: {message}"),
+                Self::TermSetError { name, message } =>
+                    format!("An error occurred in the deprecated term set `{name}`: {message}"),
                 #[cfg(feature = "templates")]
                 Self::Template { source } =>
                     format!("An error occurred in the template generator; source: {source}"),
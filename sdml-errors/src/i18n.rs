@@ -1,10 +1,33 @@
 /*!
-Internal
+Provides the selection of the locale used for diagnostic messages and labels.
+
+Translations are embedded from the crate's `i18n` folder, one Fluent file per locale. By default
+the locale is chosen from those requested by the user's desktop environment, falling back to
+English for any locale without a bundled translation, and for any message missing from a
+translation.
+
+# Example
+
+```rust
+use sdml_errors::diagnostics::ErrorCode;
+use sdml_errors::i18n::{available_locales, current_locale, set_locale};
+
+assert!(available_locales().iter().any(|locale| locale.to_string() == "fr"));
+
+set_locale("fr-CA").unwrap();
+assert_eq!(current_locale().to_string(), "fr");
+assert_eq!(ErrorCode::ModuleNotFound.message(), "module introuvable");
+
+assert!(set_locale("xx").is_err());
+```
  */
 
+use crate::Error;
 use i18n_embed::{
     fluent::{fluent_language_loader, FluentLanguageLoader},
-    LanguageLoader,
+    select,
+    unic_langid::LanguageIdentifier,
+    DesktopLanguageRequester, LanguageLoader,
 };
 use rust_embed::RustEmbed;
 use std::sync::OnceLock;
@@ -30,6 +53,64 @@ macro_rules! i18n {
     }};
 }
 
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return the locales with a bundled translation, including the fallback, English.
+///
+pub fn available_locales() -> Vec<LanguageIdentifier> {
+    loader()
+        .available_languages(&Localizations)
+        .unwrap_or_default()
+}
+
+///
+/// Return the locale currently used for diagnostic messages.
+///
+pub fn current_locale() -> LanguageIdentifier {
+    loader().current_language()
+}
+
+///
+/// Use the bundled translation that best matches `locale`, a BCP-47 language tag such as `"de"`
+/// or `"fr-CA"`, for all subsequent diagnostic messages. Returns the locale selected, or an error
+/// if `locale` is not a valid tag or no bundled translation matches it.
+///
+pub fn set_locale(locale: &str) -> Result<LanguageIdentifier, Error> {
+    let requested: LanguageIdentifier = locale.parse().map_err(|e| Error::LocaleError {
+        locale: locale.to_string(),
+        message: format!("{e}"),
+    })?;
+    let available = available_locales();
+    if !available
+        .iter()
+        .any(|available| available.language == requested.language)
+    {
+        return Err(Error::LocaleError {
+            locale: locale.to_string(),
+            message: format!(
+                "no bundled translation, available locales are: {}",
+                available
+                    .iter()
+                    .map(|locale| locale.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        });
+    }
+    let selected =
+        select(loader(), &Localizations, &[requested]).map_err(|e| Error::LocaleError {
+            locale: locale.to_string(),
+            message: e.to_string(),
+        })?;
+    Ok(selected
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| loader().fallback_language().clone()))
+}
+
 // ------------------------------------------------------------------------------------------------
 // Language Translation
 // ------------------------------------------------------------------------------------------------
@@ -42,8 +123,97 @@ pub(crate) static LANGUAGE_LOADER: OnceLock<FluentLanguageLoader> = OnceLock::ne
 
 pub(crate) fn init_translations() -> FluentLanguageLoader {
     let loader: FluentLanguageLoader = fluent_language_loader!();
+    let requested = DesktopLanguageRequester::requested_languages();
+    if select(&loader, &Localizations, &requested)
+        .map(|selected| selected.is_empty())
+        .unwrap_or(true)
+    {
+        loader
+            .load_languages(&Localizations, &[loader.fallback_language().clone()])
+            .unwrap();
+    }
     loader
-        .load_languages(&Localizations, &[loader.fallback_language().clone()])
-        .unwrap();
-    loader
+}
+
+///
+/// Return the message `message_id` in the current locale; unlike the `i18n!` macro the
+/// identifier is not checked at compile time.
+///
+pub(crate) fn message(message_id: &str) -> String {
+    loader().get(message_id)
+}
+
+#[inline(always)]
+fn loader() -> &'static FluentLanguageLoader {
+    LANGUAGE_LOADER.get_or_init(init_translations)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::ErrorCode;
+    use std::collections::BTreeSet;
+
+    fn message_ids(locale: &LanguageIdentifier) -> BTreeSet<String> {
+        let loader: FluentLanguageLoader = fluent_language_loader!();
+        loader
+            .load_languages(&Localizations, std::slice::from_ref(locale))
+            .unwrap();
+        loader.with_message_iter(locale, |messages| {
+            messages
+                .map(|message| message.id.name.to_string())
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_bundled_locales() {
+        let locales: BTreeSet<String> = available_locales()
+            .iter()
+            .map(|locale| locale.to_string())
+            .collect();
+        for expected in ["de", "en", "es", "fr"] {
+            assert!(locales.contains(expected), "missing locale {expected}");
+        }
+    }
+
+    #[test]
+    fn test_every_error_code_has_a_message_in_every_locale() {
+        for locale in available_locales() {
+            let ids = message_ids(&locale);
+            let missing: Vec<String> = ErrorCode::ALL
+                .iter()
+                .filter(|code| !ids.contains(code.message_id()))
+                .map(|code| format!("{code} ({})", code.message_id()))
+                .collect();
+            assert!(
+                missing.is_empty(),
+                "locale `{locale}` has no message for: {}",
+                missing.join(", ")
+            );
+        }
+    }
+
+    #[test]
+    fn test_every_locale_has_every_message() {
+        let fallback = loader().fallback_language().clone();
+        let expected = message_ids(&fallback);
+        for locale in available_locales() {
+            let ids = message_ids(&locale);
+            let missing: Vec<&String> = expected.difference(&ids).collect();
+            let unknown: Vec<&String> = ids.difference(&expected).collect();
+            assert!(
+                missing.is_empty(),
+                "locale `{locale}` is missing messages: {missing:?}"
+            );
+            assert!(
+                unknown.is_empty(),
+                "locale `{locale}` has messages not in `{fallback}`: {unknown:?}"
+            );
+        }
+    }
 }
@@ -117,7 +117,7 @@ impl Source {
 // ------------------------------------------------------------------------------------------------
 
 #[macro_use]
-mod i18n;
+pub mod i18n;
 
 pub mod errors;
 pub use errors::Error;
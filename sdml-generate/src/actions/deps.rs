@@ -1,6 +1,9 @@
 /*!
 Generate a text-based dependency tree, or GraphViz-based dependency graph, starting from the supplied module.
 
+Both the text tree and GraphViz representations can optionally highlight import cycles, see
+[`DependencyViewOptions::with_highlight_cycles`].
+
 */
 
 use crate::color;
@@ -10,14 +13,15 @@ use crate::draw::OutputFormat;
 use crate::draw::DOT_PROGRAM;
 use crate::exec::exec_with_temp_input;
 use crate::Generator;
-use nu_ansi_term::Style;
+use nu_ansi_term::{Color, Style};
 use sdml_core::error::Error;
+use sdml_core::model::check::cycles::ImportGraph;
 use sdml_core::model::identifiers::Identifier;
 use sdml_core::model::modules::HeaderValue;
 use sdml_core::model::modules::Module;
 use sdml_core::model::HasName;
 use sdml_core::{stdlib::is_library_module, store::ModuleStore};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
 use std::path::PathBuf;
 use text_trees::{FormatCharacters, TreeFormatting, TreeNode};
@@ -37,6 +41,7 @@ pub struct DependencyViewGenerator {}
 pub struct DependencyViewOptions {
     depth: usize,
     representation: DependencyViewRepresentation,
+    highlight_cycles: bool,
 }
 
 ///
@@ -142,6 +147,11 @@ struct Node<'a> {
     children: Option<Vec<Node<'a>>>,
 }
 
+// Maps each module that is part of an import cycle to an index identifying that cycle, an import
+// is part of a cycle if both modules have the same index.
+#[derive(Debug, Default)]
+struct Cycles<'a>(HashMap<&'a Identifier, usize>);
+
 // ------------------------------------------------------------------------------------------------
 // Implementations
 // ------------------------------------------------------------------------------------------------
@@ -175,6 +185,17 @@ impl DependencyViewOptions {
     pub fn as_rdf_imports(self) -> Self {
         Self::with_representation(self, DependencyViewRepresentation::RdfImports)
     }
+
+    ///
+    /// If `true`, modules and imports that form an import cycle are highlighted in the text tree
+    /// and GraphViz representations.
+    ///
+    pub fn with_highlight_cycles(self, highlight_cycles: bool) -> Self {
+        Self {
+            highlight_cycles,
+            ..self
+        }
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -193,13 +214,18 @@ impl Generator for DependencyViewGenerator {
     where
         W: Write + Sized,
     {
+        let cycles = if options.highlight_cycles {
+            Cycles::from_graph(&ImportGraph::from_module(module, cache))
+        } else {
+            Cycles::default()
+        };
         match options.representation {
             DependencyViewRepresentation::TextTree => {
-                self.write_text_tree(module, cache, options.depth, writer)
+                self.write_text_tree(module, cache, options.depth, &cycles, writer)
             }
             DependencyViewRepresentation::DotGraph(inner_format) => {
                 let mut buffer = Vec::new();
-                self.write_dot_graph(module, cache, options.depth, &mut buffer)?;
+                self.write_dot_graph(module, cache, options.depth, &cycles, &mut buffer)?;
                 if inner_format == OutputFormat::Source {
                     writer.write_all(&buffer)?;
                 } else {
@@ -232,6 +258,7 @@ impl DependencyViewGenerator {
         module: &Module,
         cache: &impl ModuleStore,
         depth: usize,
+        cycles: &Cycles<'_>,
         writer: &mut W,
     ) -> Result<(), Error>
     where
@@ -243,7 +270,7 @@ impl DependencyViewGenerator {
         let tree = Node::from_module(module, None, &mut seen, cache, depth);
 
         // Convert from internal tree to TextTree
-        let new_tree = tree.make_text_tree(true);
+        let new_tree = tree.make_text_tree(None, cycles);
 
         // Write out text tree using it's write API
         new_tree.write_with_format(
@@ -263,6 +290,7 @@ impl DependencyViewGenerator {
         module: &Module,
         cache: &impl ModuleStore,
         depth: usize,
+        cycles: &Cycles<'_>,
         writer: &mut W,
     ) -> Result<(), Error>
     where
@@ -294,8 +322,13 @@ impl DependencyViewGenerator {
 
         if !seen.contains(module.name()) {
             writer.write_all(
-                self.write_gv_node(module.name(), true, is_library_module(module.name()))
-                    .as_bytes(),
+                self.write_gv_node(
+                    module.name(),
+                    true,
+                    is_library_module(module.name()),
+                    cycles.contains(module.name()),
+                )
+                .as_bytes(),
             )?;
         }
 
@@ -305,6 +338,7 @@ impl DependencyViewGenerator {
                     module_name,
                     module_name == module.name(),
                     is_library_module(module_name),
+                    cycles.contains(module_name),
                 )
                 .as_bytes(),
             )?;
@@ -312,7 +346,7 @@ impl DependencyViewGenerator {
 
         writer.write_all(b"\n")?;
 
-        self.write_graph_node(&tree, writer)?;
+        self.write_graph_node(&tree, cycles, writer)?;
 
         writer.write_all(b"}\n")?;
 
@@ -320,32 +354,71 @@ impl DependencyViewGenerator {
     }
 
     #[allow(clippy::only_used_in_recursion)]
-    fn write_graph_node<W>(&self, node: &Node<'_>, writer: &mut W) -> Result<(), Error>
+    fn write_graph_node<W>(
+        &self,
+        node: &Node<'_>,
+        cycles: &Cycles<'_>,
+        writer: &mut W,
+    ) -> Result<(), Error>
     where
         W: Write + Sized,
     {
+        const CYCLE_EDGE_ATTRIBUTES: &str = "color=\"red\"; fontcolor=\"red\"; style=\"bold\"";
         if let Some(children) = &node.children {
             for child in children {
-                if let Some(version_uri) = child.version_uri {
-                    writer.write_all(
+                let in_cycle = cycles.is_cycle_import(node.name, child.name);
+                match (child.version_uri, in_cycle) {
+                    (Some(version_uri), true) => writer.write_all(
+                        format!(
+                            "  {} -> {} [label=\"{}\"; {}];\n",
+                            node.name, child.name, version_uri, CYCLE_EDGE_ATTRIBUTES
+                        )
+                        .as_bytes(),
+                    )?,
+                    (Some(version_uri), false) => writer.write_all(
                         format!(
                             "  {} -> {} [label=\"{}\"];\n",
                             node.name, child.name, version_uri
                         )
                         .as_bytes(),
-                    )?;
-                } else {
-                    writer.write_all(format!("  {} -> {};\n", node.name, child.name).as_bytes())?;
+                    )?,
+                    (None, true) => writer.write_all(
+                        format!(
+                            "  {} -> {} [{}];\n",
+                            node.name, child.name, CYCLE_EDGE_ATTRIBUTES
+                        )
+                        .as_bytes(),
+                    )?,
+                    (None, false) => writer
+                        .write_all(format!("  {} -> {};\n", node.name, child.name).as_bytes())?,
                 }
-                self.write_graph_node(child, writer)?;
+                self.write_graph_node(child, cycles, writer)?;
             }
         }
 
         Ok(())
     }
 
-    fn write_gv_node(&self, name: &Identifier, is_subject: bool, is_library: bool) -> String {
+    fn write_gv_node(
+        &self,
+        name: &Identifier,
+        is_subject: bool,
+        is_library: bool,
+        in_cycle: bool,
+    ) -> String {
         const MODULE_STEREOTYPE: &str = "<FONT POINT-SIZE=\"9\">«module»</FONT><BR/>";
+        if in_cycle {
+            let label = match (is_subject, is_library) {
+                (true, true) => format!("<B><I>{}</I></B>", name),
+                (true, false) => format!("<B>{}</B>", name),
+                (false, true) => format!("<I>{}</I>", name),
+                (false, false) => name.to_string(),
+            };
+            return format!(
+                "  {} [label=<{}{}>; color=\"red\"; fontcolor=\"red\"];\n",
+                name, MODULE_STEREOTYPE, label
+            );
+        }
         match (is_subject, is_library) {
             (true, true) => format!(
                 "  {} [label=<{}<B><I>{}</I></B>>];\n",
@@ -497,19 +570,29 @@ impl<'a> Node<'a> {
         }
     }
 
-    fn make_text_tree(&'a self, is_root: bool) -> TreeNode<String> {
+    fn make_text_tree(
+        &'a self,
+        parent: Option<&Identifier>,
+        cycles: &Cycles<'_>,
+    ) -> TreeNode<String> {
         let children = if let Some(children) = &self.children {
             children
                 .iter()
-                .map(|node| node.make_text_tree(false))
+                .map(|node| node.make_text_tree(Some(self.name), cycles))
                 .collect::<Vec<TreeNode<_>>>()
         } else {
             Default::default()
         };
-        TreeNode::with_child_nodes(self.make_node_string(is_root), children.into_iter())
+        let in_cycle = parent
+            .map(|parent| cycles.is_cycle_import(parent, self.name))
+            .unwrap_or_default();
+        TreeNode::with_child_nodes(
+            self.make_node_string(parent.is_none(), in_cycle),
+            children.into_iter(),
+        )
     }
 
-    fn make_node_string(&self, is_root: bool) -> String {
+    fn make_node_string(&self, is_root: bool, in_cycle: bool) -> String {
         let node_string = format!(
             "{}{}",
             self.name,
@@ -528,10 +611,46 @@ impl<'a> Node<'a> {
             if is_library_module(self.name) {
                 style = style.dimmed().italic();
             }
+            if in_cycle {
+                style = style.fg(Color::Red);
+            }
 
-            style.paint(node_string).to_string()
+            let node_string = style.paint(node_string).to_string();
+            if in_cycle {
+                format!("{node_string} {}", Color::Red.paint("(cycle)"))
+            } else {
+                node_string
+            }
+        } else if in_cycle {
+            format!("{node_string} (cycle)")
         } else {
             node_string
         }
     }
 }
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a> Cycles<'a> {
+    fn from_graph(graph: &ImportGraph<'a>) -> Self {
+        Self(
+            graph
+                .cycles()
+                .iter()
+                .enumerate()
+                .flat_map(|(index, cycle)| cycle.modules().map(move |name| (name, index)))
+                .collect(),
+        )
+    }
+
+    fn contains(&self, name: &Identifier) -> bool {
+        self.0.contains_key(name)
+    }
+
+    fn is_cycle_import(&self, from: &Identifier, to: &Identifier) -> bool {
+        match (self.0.get(from), self.0.get(to)) {
+            (Some(from), Some(to)) => from == to,
+            _ => false,
+        }
+    }
+}
@@ -45,7 +45,7 @@ const HIGHLIGHT_NAMES: &[&str] = &[
     "variable.parameter",
 ];
 
-const DEFAULT_CSS: &str = include_str!("sdml-highlight.css");
+pub(crate) const DEFAULT_CSS: &str = include_str!("sdml-highlight.css");
 
 const HTML_HEADER: &str = r#"<!DOCTYPE html>
 <html>
@@ -12,4 +12,7 @@ pub mod deps;
 #[cfg(feature = "hl")]
 pub mod highlight;
 
+#[cfg(feature = "hl")]
+pub mod report;
+
 pub mod tags;
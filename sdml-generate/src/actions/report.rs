@@ -0,0 +1,897 @@
+/*!
+Write the diagnostics reported while loading and validating modules as a standalone document,
+either an HTML page or a Markdown file, suitable for attaching to a merge request.
+
+The [`DocumentReporter`] keeps every diagnostic emitted and writes the document when
+[`Reporter::done`] is called. The document starts with a summary of the [`ReportCounters`],
+followed by the diagnostics grouped by module and then by [`ErrorCode`]; each code links to its
+documentation, see [`ErrorCode::url_string`]. Each diagnostic with a source location includes an
+excerpt of the lines it spans. In HTML the excerpt is highlighted with
+[`write_highlighted_as_html`] and the span itself is marked, in Markdown the span is underlined.
+
+Diagnostics are grouped by the source file they refer to, unless the name of the module loaded
+from that file is provided with [`DocumentReporter::set_module_name`]. Clones of a reporter share
+the same diagnostics, so a clone may be given to a loader and the original used to name modules
+once they have been loaded.
+
+# Example
+
+```rust
+use sdml_errors::diagnostics::functions::unused_import;
+use sdml_errors::diagnostics::SeverityFilter;
+use sdml_errors::{Reporter, SourceFiles};
+use sdml_generate::actions::report::{DocumentReporter, ReportFormat};
+
+let mut sources = SourceFiles::new();
+let file_id = sources.add(
+    "example.sdm".to_string(),
+    "module example <https://example.com/> is\n  import xsd\nend\n"
+        .to_string()
+        .into(),
+);
+
+let reporter = DocumentReporter::new(std::io::sink(), ReportFormat::Markdown)
+    .with_severity_filter(SeverityFilter::Warning);
+reporter
+    .emit(&unused_import(file_id, Some(50..53), "xsd"), &sources)
+    .unwrap();
+reporter.set_module_name(file_id, "example");
+
+let counters = reporter.done(Some("example".to_string())).unwrap();
+assert_eq!(counters.warnings(), 1);
+```
+*/
+
+use crate::actions::highlight::{write_highlighted_as_html, DEFAULT_CSS};
+use codespan_reporting::diagnostic::{LabelStyle, Severity};
+use sdml_errors::diagnostics::reporter::ReportCounters;
+use sdml_errors::diagnostics::{ErrorCode, SeverityFilter};
+use sdml_errors::{Diagnostic, Error, FileId, Reporter, SourceFiles, Span};
+use std::cell::RefCell;
+use std::collections::{hash_map, BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::io::Write;
+use std::rc::Rc;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The kind of document written by a [`DocumentReporter`].
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ReportFormat {
+    /// A standalone HTML page, with styles included.
+    #[default]
+    Html,
+    /// A Markdown document.
+    Markdown,
+}
+
+///
+/// A reporter that collects all emitted diagnostics and writes them as a single HTML or Markdown
+/// document when [`Reporter::done`] is called. Clones share the same diagnostics, counters, and
+/// writer.
+///
+#[derive(Clone)]
+pub struct DocumentReporter {
+    writer: Rc<RefCell<Box<dyn Write>>>,
+    format: ReportFormat,
+    filter: SeverityFilter,
+    counters: Rc<RefCell<ReportCounters>>,
+    entries: Rc<RefCell<Vec<Entry>>>,
+    files: Rc<RefCell<HashMap<FileId, SourceFile>>>,
+    module_names: Rc<RefCell<HashMap<FileId, String>>>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug)]
+struct Entry {
+    code: String,
+    severity: Severity,
+    message: String,
+    location: Option<Location>,
+    notes: Vec<String>,
+}
+
+///
+/// Diagnostics are grouped by module, by file if the module loaded from the file is not known,
+/// and those without a location are grouped together.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Group {
+    Module(String),
+    File(FileId, String),
+    WithoutSource,
+}
+
+#[derive(Clone, Debug)]
+struct Location {
+    file_id: FileId,
+    range: Span,
+    message: String,
+}
+
+#[derive(Clone, Debug)]
+struct SourceFile {
+    name: String,
+    source: String,
+}
+
+///
+/// The lines of source spanned by a diagnostic, each with the byte range within the line that
+/// is covered by the span.
+///
+#[derive(Clone, Debug)]
+struct Excerpt {
+    file_name: String,
+    start_line: usize,
+    start_column: usize,
+    lines: Vec<(usize, Span)>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Debug for DocumentReporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DocumentReporter")
+            .field("format", &self.format)
+            .field("filter", &self.filter)
+            .field("counters", &self.counters)
+            .field("entries", &self.entries)
+            .field("module_names", &self.module_names)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for DocumentReporter {
+    fn default() -> Self {
+        Self::new(std::io::stdout(), Default::default())
+    }
+}
+
+impl Reporter for DocumentReporter {
+    fn emit(&self, diagnostic: &Diagnostic, sources: &SourceFiles) -> Result<(), Error> {
+        if self.is_enabled(diagnostic.severity) {
+            self.log(diagnostic);
+            self.counters.borrow_mut().report(diagnostic.severity);
+
+            let code = diagnostic.code.clone().unwrap_or_default();
+            let url = ErrorCode::from_code(&code).map(|code| code.url_string());
+            let location = diagnostic
+                .labels
+                .iter()
+                .find(|label| label.style == LabelStyle::Primary)
+                .and_then(|label| {
+                    let file = sources.get(label.file_id).ok()?;
+                    self.files
+                        .borrow_mut()
+                        .entry(label.file_id)
+                        .or_insert_with(|| SourceFile {
+                            name: file.name().clone(),
+                            source: file.source().to_string(),
+                        });
+                    Some(Location {
+                        file_id: label.file_id,
+                        range: label.range.clone(),
+                        message: label.message.clone(),
+                    })
+                });
+            // the help URL note is replaced by the link on each code.
+            let notes = diagnostic
+                .notes
+                .iter()
+                .filter(|note| url.as_ref().is_none_or(|url| !note.contains(url)))
+                .cloned()
+                .collect();
+
+            self.entries.borrow_mut().push(Entry {
+                code,
+                severity: diagnostic.severity,
+                message: diagnostic.message.clone(),
+                location,
+                notes,
+            });
+        }
+        Ok(())
+    }
+
+    fn counters(&self) -> ReportCounters {
+        *self.counters.borrow()
+    }
+
+    fn done(&self, module_name: Option<String>) -> Result<ReportCounters, Error> {
+        let counters = self.counters.replace(ReportCounters::default());
+        let entries = self.entries.replace(Default::default());
+        let files = self.files.replace(Default::default());
+        let module_names = self.module_names.replace(Default::default());
+
+        let groups = group_entries(&entries, &files, &module_names);
+        let mut writer = self.writer.borrow_mut();
+        match self.format {
+            ReportFormat::Html => write_html(
+                &mut *writer,
+                module_name.as_deref(),
+                &counters,
+                &groups,
+                &files,
+            )?,
+            ReportFormat::Markdown => write_markdown(
+                &mut *writer,
+                module_name.as_deref(),
+                &counters,
+                &groups,
+                &files,
+            )?,
+        }
+        writer.flush()?;
+
+        Ok(counters)
+    }
+
+    fn severity_filter(&self) -> SeverityFilter {
+        self.filter
+    }
+
+    fn set_severity_filter(&mut self, filter: SeverityFilter) {
+        self.filter = filter;
+    }
+}
+
+impl DocumentReporter {
+    ///
+    /// Create a new reporter that writes a document in `format` to `writer`.
+    ///
+    pub fn new<W>(writer: W, format: ReportFormat) -> Self
+    where
+        W: Write + 'static,
+    {
+        Self {
+            writer: Rc::new(RefCell::new(Box::new(writer))),
+            format,
+            filter: Default::default(),
+            counters: Default::default(),
+            entries: Default::default(),
+            files: Default::default(),
+            module_names: Default::default(),
+        }
+    }
+
+    pub fn with_severity_filter(self, filter: SeverityFilter) -> Self {
+        Self { filter, ..self }
+    }
+
+    pub fn format(&self) -> ReportFormat {
+        self.format
+    }
+
+    ///
+    /// Group the diagnostics for the source file `file_id` under `name`, rather than the file's
+    /// name.
+    ///
+    pub fn set_module_name<S>(&self, file_id: FileId, name: S)
+    where
+        S: Into<String>,
+    {
+        self.module_names.borrow_mut().insert(file_id, name.into());
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Excerpt {
+    fn new(file: &SourceFile, range: &Span) -> Self {
+        let source = file.source.as_str();
+        let start = floor_char_boundary(source, range.start);
+        let end = floor_char_boundary(source, range.end).max(start);
+
+        let start_line = source[..start].matches('\n').count();
+        let end_line = start_line
+            + source[start..end]
+                .trim_end_matches('\n')
+                .matches('\n')
+                .count();
+        let mut line_start = source[..start]
+            .rfind('\n')
+            .map(|i| i + 1)
+            .unwrap_or_default();
+        let start_column = source[line_start..start].chars().count() + 1;
+
+        let mut lines = Vec::default();
+        for (line, text) in source[line_start..].split('\n').enumerate() {
+            let line = start_line + line;
+            let line_end = line_start + text.len();
+            let from = start.max(line_start) - line_start;
+            let to = end.min(line_end).max(start.max(line_start)) - line_start;
+            lines.push((line, from..to));
+            if line == end_line {
+                break;
+            }
+            line_start = line_end + 1;
+        }
+
+        Self {
+            file_name: file.name.clone(),
+            start_line,
+            start_column,
+            lines,
+        }
+    }
+
+    fn position(&self) -> String {
+        format!(
+            "{}:{}:{}",
+            self.file_name,
+            self.start_line + 1,
+            self.start_column
+        )
+    }
+
+    fn gutter_width(&self) -> usize {
+        self.lines
+            .last()
+            .map(|(line, _)| (line + 1).to_string().len())
+            .unwrap_or(1)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+type Groups<'a> = Vec<(Group, BTreeMap<&'a str, Vec<&'a Entry>>)>;
+
+fn group_entries<'a>(
+    entries: &'a [Entry],
+    files: &HashMap<FileId, SourceFile>,
+    module_names: &HashMap<FileId, String>,
+) -> Groups<'a> {
+    let mut groups: Groups<'a> = Default::default();
+    for entry in entries {
+        let group = match &entry.location {
+            Some(location) => match module_names.get(&location.file_id) {
+                Some(name) => Group::Module(name.clone()),
+                None => Group::File(location.file_id, files[&location.file_id].name.clone()),
+            },
+            None => Group::WithoutSource,
+        };
+        let index = match groups.iter().position(|(other, _)| other == &group) {
+            Some(index) => index,
+            None => {
+                groups.push((group, Default::default()));
+                groups.len() - 1
+            }
+        };
+        groups[index]
+            .1
+            .entry(entry.code.as_str())
+            .or_default()
+            .push(entry);
+    }
+    groups
+}
+
+fn write_html(
+    w: &mut dyn Write,
+    module_name: Option<&str>,
+    counters: &ReportCounters,
+    groups: &Groups<'_>,
+    files: &HashMap<FileId, SourceFile>,
+) -> Result<(), Error> {
+    let title = match module_name {
+        Some(name) => format!("Diagnostics for module <code>{}</code>", escape_html(name)),
+        None => "Diagnostics".to_string(),
+    };
+    write!(
+        w,
+        "{}",
+        HTML_HEADER
+            .replace("{css}", DEFAULT_CSS)
+            .replace("{report_css}", REPORT_CSS)
+            .replace("{title}", &title)
+    )?;
+
+    writeln!(w, "      <section class=\"summary\">")?;
+    writeln!(w, "        <h2>Summary</h2>")?;
+    writeln!(w, "        <table>")?;
+    writeln!(
+        w,
+        "          <tr><th>Bugs</th><th>Errors</th><th>Warnings</th><th>Notes</th><th>Total</th></tr>"
+    )?;
+    writeln!(
+        w,
+        "          <tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+        counters.bugs(),
+        counters.errors(),
+        counters.warnings(),
+        counters.info(),
+        counters.total()
+    )?;
+    writeln!(w, "        </table>")?;
+    if groups.is_empty() {
+        writeln!(w, "        <p>No issues found.</p>")?;
+    }
+    writeln!(w, "      </section>")?;
+
+    let mut highlighted: HashMap<FileId, Vec<String>> = Default::default();
+    for (group, codes) in groups {
+        writeln!(w, "      <section class=\"module\">")?;
+        match group {
+            Group::Module(name) => writeln!(
+                w,
+                "        <h2>Module <code>{}</code></h2>",
+                escape_html(name)
+            )?,
+            Group::File(_, name) => writeln!(
+                w,
+                "        <h2>File <code>{}</code></h2>",
+                escape_html(name)
+            )?,
+            Group::WithoutSource => writeln!(w, "        <h2>Without source</h2>")?,
+        }
+        for (code, entries) in codes {
+            writeln!(w, "        <section class=\"code\">")?;
+            writeln!(w, "          <h3>{}</h3>", html_code_heading(code, entries))?;
+            for entry in entries {
+                writeln!(
+                    w,
+                    "          <div class=\"diagnostic {}\">",
+                    severity_name(entry.severity)
+                )?;
+                let excerpt = entry.location.as_ref().and_then(|location| {
+                    files
+                        .get(&location.file_id)
+                        .map(|file| (location, file, Excerpt::new(file, &location.range)))
+                });
+                match &excerpt {
+                    Some((_, _, excerpt)) => writeln!(
+                        w,
+                        "            <p><span class=\"severity\">{}</span>: {} <code>{}</code></p>",
+                        severity_name(entry.severity),
+                        escape_html(&entry.message),
+                        escape_html(&excerpt.position())
+                    )?,
+                    None => writeln!(
+                        w,
+                        "            <p><span class=\"severity\">{}</span>: {}</p>",
+                        severity_name(entry.severity),
+                        escape_html(&entry.message)
+                    )?,
+                }
+                if let Some((location, file, excerpt)) = excerpt {
+                    let lines = match highlighted.entry(location.file_id) {
+                        hash_map::Entry::Occupied(lines) => lines.into_mut(),
+                        hash_map::Entry::Vacant(lines) => {
+                            lines.insert(highlighted_lines(&file.source)?)
+                        }
+                    };
+                    write_html_excerpt(w, &excerpt, lines, &location.message)?;
+                }
+                if !entry.notes.is_empty() {
+                    writeln!(w, "            <ul>")?;
+                    for note in &entry.notes {
+                        writeln!(w, "              <li>{}</li>", escape_html(note))?;
+                    }
+                    writeln!(w, "            </ul>")?;
+                }
+                writeln!(w, "          </div>")?;
+            }
+            writeln!(w, "        </section>")?;
+        }
+        writeln!(w, "      </section>")?;
+    }
+
+    write!(w, "{}", HTML_FOOTER)?;
+    Ok(())
+}
+
+fn html_code_heading(code: &str, entries: &[&Entry]) -> String {
+    match ErrorCode::from_code(code) {
+        Some(error_code) => format!(
+            "<a href=\"{}\">{}</a>: {} ({})",
+            error_code.url_string(),
+            code,
+            escape_html(&error_code.message()),
+            entries.len()
+        ),
+        None => format!("{} ({})", escape_html(code), entries.len()),
+    }
+}
+
+fn write_html_excerpt(
+    w: &mut dyn Write,
+    excerpt: &Excerpt,
+    highlighted: &[String],
+    label: &str,
+) -> Result<(), Error> {
+    let width = excerpt.gutter_width();
+    write!(
+        w,
+        "            <pre class=\"excerpt\"><code class=\"sdml\">"
+    )?;
+    for (line, span) in &excerpt.lines {
+        let text = highlighted
+            .get(*line)
+            .map(String::as_str)
+            .unwrap_or_default();
+        writeln!(
+            w,
+            "<span class=\"line-number\">{:>width$}</span>{}",
+            line + 1,
+            mark_html_line(text, span),
+        )?;
+    }
+    writeln!(w, "</code></pre>")?;
+    if !label.is_empty() {
+        writeln!(
+            w,
+            "            <p class=\"label\">{}</p>",
+            escape_html(label)
+        )?;
+    }
+    Ok(())
+}
+
+///
+/// Highlight the whole of `source`, rather than each excerpt, so that every excerpt is
+/// highlighted in context, and return the HTML for each line. Elements open at the end of a line,
+/// for tokens such as multi-line strings, are closed there and reopened on the next line so that
+/// each line is balanced.
+///
+fn highlighted_lines(source: &str) -> Result<Vec<String>, Error> {
+    let mut buffer = Vec::default();
+    write_highlighted_as_html(source, &mut buffer, false)?;
+    let html = String::from_utf8_lossy(&buffer);
+
+    let body = html
+        .split_once(HTML_CODE_START)
+        .map(|(_, body)| body)
+        .unwrap_or_default();
+    let body = body
+        .rsplit_once(HTML_CODE_END)
+        .map(|(body, _)| body)
+        .unwrap_or(body);
+    let mut open: Vec<&str> = Default::default();
+    Ok(body
+        .lines()
+        .map(|line| {
+            let line = line.strip_prefix(HTML_CODE_INDENT).unwrap_or(line);
+            let mut balanced = open.concat();
+            balanced.push_str(line);
+            let mut rest = line;
+            while let Some(start) = rest.find('<') {
+                let end = rest[start..]
+                    .find('>')
+                    .map(|i| start + i + 1)
+                    .unwrap_or(rest.len());
+                let tag = &rest[start..end];
+                if tag.starts_with("</") {
+                    let _ = open.pop();
+                } else if !tag.ends_with("/>") {
+                    open.push(tag);
+                }
+                rest = &rest[end..];
+            }
+            for tag in open.iter().rev() {
+                let name = tag[1..]
+                    .split(|c: char| c.is_whitespace() || c == '>')
+                    .next()
+                    .unwrap_or_default();
+                balanced.push_str(&format!("</{name}>"));
+            }
+            balanced
+        })
+        .collect())
+}
+
+///
+/// Wrap the text within `html` that corresponds to the byte range `span` of the source line in
+/// `mark` elements. Marks are closed before, and reopened after, any tag so that they always
+/// nest correctly within the highlighting spans.
+///
+fn mark_html_line(html: &str, span: &Span) -> String {
+    let mut marked = String::with_capacity(html.len());
+    let mut offset = 0;
+    let mut open = false;
+    let mut rest = html;
+    while let Some(c) = rest.chars().next() {
+        let (unit, length) = match c {
+            '<' => {
+                let end = rest.find('>').map(|i| i + 1).unwrap_or(rest.len());
+                if open {
+                    marked.push_str("</mark>");
+                    open = false;
+                }
+                marked.push_str(&rest[..end]);
+                rest = &rest[end..];
+                continue;
+            }
+            // the renderer only escapes single-byte characters.
+            '&' => (&rest[..rest.find(';').map(|i| i + 1).unwrap_or(1)], 1),
+            _ => (&rest[..c.len_utf8()], c.len_utf8()),
+        };
+        let in_span = span.contains(&offset);
+        if in_span && !open {
+            marked.push_str("<mark>");
+            open = true;
+        } else if !in_span && open {
+            marked.push_str("</mark>");
+            open = false;
+        }
+        marked.push_str(unit);
+        offset += length;
+        rest = &rest[unit.len()..];
+    }
+    if open {
+        marked.push_str("</mark>");
+    }
+    marked
+}
+
+fn write_markdown(
+    w: &mut dyn Write,
+    module_name: Option<&str>,
+    counters: &ReportCounters,
+    groups: &Groups<'_>,
+    files: &HashMap<FileId, SourceFile>,
+) -> Result<(), Error> {
+    match module_name {
+        Some(name) => writeln!(w, "# Diagnostics for module `{}`", name)?,
+        None => writeln!(w, "# Diagnostics")?,
+    }
+    writeln!(w)?;
+    writeln!(w, "| Bugs | Errors | Warnings | Notes | Total |")?;
+    writeln!(w, "|-----:|-------:|---------:|------:|------:|")?;
+    writeln!(
+        w,
+        "| {} | {} | {} | {} | {} |",
+        counters.bugs(),
+        counters.errors(),
+        counters.warnings(),
+        counters.info(),
+        counters.total()
+    )?;
+    if groups.is_empty() {
+        writeln!(w)?;
+        writeln!(w, "No issues found.")?;
+    }
+
+    for (group, codes) in groups {
+        writeln!(w)?;
+        match group {
+            Group::Module(name) => writeln!(w, "## Module `{}`", name)?,
+            Group::File(_, name) => writeln!(w, "## File `{}`", name)?,
+            Group::WithoutSource => writeln!(w, "## Without source")?,
+        }
+        for (code, entries) in codes {
+            writeln!(w)?;
+            match ErrorCode::from_code(code) {
+                Some(error_code) => writeln!(
+                    w,
+                    "### [{}]({}): {} ({})",
+                    code,
+                    error_code.url_string(),
+                    escape_markdown(&error_code.message()),
+                    entries.len()
+                )?,
+                None => writeln!(w, "### {} ({})", escape_markdown(code), entries.len())?,
+            }
+            for entry in entries {
+                writeln!(w)?;
+                let excerpt = entry.location.as_ref().and_then(|location| {
+                    files
+                        .get(&location.file_id)
+                        .map(|file| (location, file, Excerpt::new(file, &location.range)))
+                });
+                match &excerpt {
+                    Some((_, _, excerpt)) => writeln!(
+                        w,
+                        "**{}**: {} `{}`",
+                        severity_name(entry.severity),
+                        escape_markdown(&entry.message),
+                        excerpt.position()
+                    )?,
+                    None => writeln!(
+                        w,
+                        "**{}**: {}",
+                        severity_name(entry.severity),
+                        escape_markdown(&entry.message)
+                    )?,
+                }
+                if let Some((location, file, excerpt)) = excerpt {
+                    writeln!(w)?;
+                    write_markdown_excerpt(w, &excerpt, &file.source, &location.message)?;
+                }
+                if !entry.notes.is_empty() {
+                    writeln!(w)?;
+                    for note in &entry.notes {
+                        writeln!(w, "* {}", escape_markdown(note))?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_markdown_excerpt(
+    w: &mut dyn Write,
+    excerpt: &Excerpt,
+    source: &str,
+    label: &str,
+) -> Result<(), Error> {
+    let width = excerpt.gutter_width();
+    let source_lines: Vec<&str> = source.split('\n').collect();
+    let last = excerpt.lines.len() - 1;
+    // the fence must be longer than any run of backticks within it.
+    let longest_run = excerpt
+        .lines
+        .iter()
+        .filter_map(|(line, _)| source_lines.get(*line))
+        .chain(std::iter::once(&label))
+        .flat_map(|text| text.split(|c| c != '`'))
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    writeln!(w, "{fence}text")?;
+    for (i, (line, span)) in excerpt.lines.iter().enumerate() {
+        let text = source_lines.get(*line).copied().unwrap_or_default();
+        writeln!(w, "{:>width$} │ {}", line + 1, text.trim_end_matches('\r'))?;
+        let indent = text[..span.start].chars().count();
+        let length = text[span.clone()].chars().count().max(1);
+        if i == last && !label.is_empty() {
+            writeln!(
+                w,
+                "{:width$} │ {}{} {}",
+                "",
+                " ".repeat(indent),
+                "^".repeat(length),
+                label
+            )?;
+        } else {
+            writeln!(
+                w,
+                "{:width$} │ {}{}",
+                "",
+                " ".repeat(indent),
+                "^".repeat(length)
+            )?;
+        }
+    }
+    writeln!(w, "{fence}")?;
+    Ok(())
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug => "bug",
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+        Severity::Help => "help",
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Escape the characters that Markdown would otherwise treat as inline markup, or as HTML.
+fn escape_markdown(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '~' | '&' | '#'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn floor_char_boundary(s: &str, index: usize) -> usize {
+    let mut index = index.min(s.len());
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Values
+// ------------------------------------------------------------------------------------------------
+
+const HTML_CODE_START: &str = "<code class=\"sdml\">\n";
+
+const HTML_CODE_END: &str = "  </code>";
+
+const HTML_CODE_INDENT: &str = "    ";
+
+const REPORT_CSS: &str = r#"
+    table {
+      border-collapse: collapse;
+    }
+    th, td {
+      border: 1px solid #e1e4e5;
+      padding: .3em .8em;
+      text-align: right;
+    }
+    div.diagnostic {
+      margin-bottom: 1.5em;
+    }
+    div.bug span.severity, div.error span.severity {
+      color: #d70000;
+      font-weight: bold;
+    }
+    div.warning span.severity {
+      color: #d78700;
+      font-weight: bold;
+    }
+    div.note span.severity, div.help span.severity {
+      color: #005fd7;
+      font-weight: bold;
+    }
+    pre.excerpt code.sdml {
+      display: block;
+    }
+    span.line-number {
+      color: #949494;
+      padding-right: 1em;
+      user-select: none;
+    }
+    mark {
+      background-color: #ffd7d7;
+      text-decoration: underline wavy #d70000;
+    }
+    p.label {
+      font-style: italic;
+    }
+"#;
+
+const HTML_HEADER: &str = r#"<!DOCTYPE html>
+<html>
+  <head>
+    <meta charset="utf-8" />
+    <meta name="viewport" content="width=device-width" />
+
+    <title>SDML Diagnostics</title>
+
+    <style type="text/css" media="screen">
+    body {
+      font-family: "Fira Sans",sans;
+    }
+{css}
+{report_css}
+    </style>
+  </head>
+
+  <body>
+
+    <header>
+      <h1>{title}</h1>
+    </header>
+
+    <main>
+"#;
+
+const HTML_FOOTER: &str = r#"    </main>
+
+    <footer>
+      <p>Generated by sdml <a href="https://github.com/johnstonskj/rust-sdml">command-line tool</a>.</p>
+    </footer>
+
+  </body>
+</html>
+"#;
@@ -365,10 +365,9 @@ impl SourceGenerator {
                         writer,
                         DEFINITION_ANNOTATION_INDENT,
                     )?;
-                    if body.has_members() {
-                        writer.write_all(EOL)?;
-                    }
+                    writer.write_all(EOL)?;
                 }
+                self.write_entity_identity(body.identity(), writer)?;
                 for member in body.members() {
                     self.write_member(member, writer)?;
                 }
@@ -490,6 +489,25 @@ impl SourceGenerator {
         Ok(())
     }
 
+    fn write_entity_identity(
+        &mut self,
+        defn: &Member,
+        writer: &mut dyn Write,
+    ) -> Result<(), Error> {
+        let indentation = self.options.indentation_str(DEFINITION_MEMBER_INDENT);
+        let prefix = format!("{indentation}{} ", keyword("identity"));
+        match defn.kind() {
+            MemberKind::Reference(v) => self.write_member_reference(v, &prefix, writer),
+            MemberKind::Definition(v) => self.write_member_definition_inner(
+                v,
+                &prefix,
+                MEMBER_ANNOTATION_INDENT,
+                &indentation,
+                writer,
+            ),
+        }
+    }
+
     fn write_member(&mut self, defn: &Member, writer: &mut dyn Write) -> Result<(), Error> {
         let indentation = self.options.indentation_str(DEFINITION_MEMBER_INDENT);
         match defn.kind() {
@@ -533,14 +551,18 @@ impl SourceGenerator {
             self.write_cardinality(defn.target_cardinality(), writer)?;
         }
         self.write_type_reference(defn.target_type(), writer)?;
-        if let Some(body) = defn.body() {
-            if body.has_annotations() && self.options.level.generate_member_bodies() {
-                writer.write_all(format!(" {}\n", keyword("is")).as_bytes())?;
-                self.write_annotations(body.annotations(), writer, annotation_indentation)?;
-                writer.write_all(format!("{end_indentation}{}\n", keyword("end")).as_bytes())?;
+        match defn.body() {
+            Some(body) if body.has_annotations() => {
+                if self.options.level.generate_member_bodies() {
+                    writer.write_all(format!(" {}\n", keyword("is")).as_bytes())?;
+                    self.write_annotations(body.annotations(), writer, annotation_indentation)?;
+                    writer
+                        .write_all(format!("{end_indentation}{}\n", keyword("end")).as_bytes())?;
+                } else {
+                    writer.write_all(ELIPPSIS.as_bytes())?;
+                }
             }
-        } else {
-            writer.write_all(EOL)?;
+            _ => writer.write_all(EOL)?,
         }
         Ok(())
     }
@@ -0,0 +1,194 @@
+use codespan_reporting::diagnostic::Label;
+use sdml_core::load::ModuleLoader;
+use sdml_core::model::check::usage::{module_set, validate_module_usage, Reachability};
+use sdml_core::model::identifiers::{Identifier, IdentifierReference};
+use sdml_core::model::HasName;
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_errors::diagnostics::SeverityFilter;
+use sdml_errors::{Diagnostic, Reporter, SourceFiles};
+use sdml_generate::actions::report::{DocumentReporter, ReportFormat};
+use sdml_parse::load::FsModuleLoader;
+use std::io::Cursor;
+use std::str::FromStr;
+use tempfile::NamedTempFile;
+
+const ADDRESSES: &str = r#"module addresses <https://example.com/addresses> is
+
+  structure Address is
+    street -> string
+  end
+
+  structure PostBox is
+    number -> string
+  end
+
+end
+"#;
+
+const CUSTOMERS: &str = r#"module customers <https://example.com/customers> is
+
+  import [ addresses xsd:string ]
+
+  entity Customer is
+    identity id -> string
+    address -> addresses:Address
+  end
+
+end
+"#;
+
+///
+/// Load both modules, report the unused `xsd:string` import and the unreachable `PostBox`, and
+/// return the report written.
+///
+fn report(format: ReportFormat, name_modules: bool) -> String {
+    let file = NamedTempFile::new().unwrap();
+    let reporter = DocumentReporter::new(file.reopen().unwrap(), format)
+        .with_severity_filter(SeverityFilter::Note);
+    let mut loader = FsModuleLoader::default().with_reporter(Box::new(reporter.clone()));
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    for source in [ADDRESSES, CUSTOMERS] {
+        loader
+            .load_from_reader(&mut Cursor::new(source.as_bytes()), &mut cache, false)
+            .unwrap();
+    }
+
+    let name = Identifier::from_str("customers").unwrap();
+    let module = cache.get(&name).unwrap();
+    let reachability = Reachability::from_roots(
+        [IdentifierReference::from_str("Customer").unwrap()],
+        &name,
+        &cache,
+    );
+    for module in module_set(module, &cache) {
+        validate_module_usage(module, Some(&reachability), &loader).unwrap();
+        if name_modules {
+            reporter.set_module_name(*module.file_id().unwrap(), module.name().to_string());
+        }
+    }
+
+    let counters = loader.reporter_done(Some(name.to_string())).unwrap();
+    assert_eq!(counters.warnings(), 1);
+    assert_eq!(counters.info(), 1);
+    assert_eq!(reporter.counters().total(), 0);
+
+    std::fs::read_to_string(file.path()).unwrap()
+}
+
+#[test]
+fn test_markdown_report() {
+    let report = report(ReportFormat::Markdown, true);
+
+    assert!(report.starts_with("# Diagnostics for module `customers`\n"));
+    assert!(report.contains("| 0 | 0 | 1 | 1 | 2 |"));
+    assert!(report.contains("## Module `customers`"));
+    assert!(report.contains("## Module `addresses`"));
+    assert!(report.contains("### [W0308](https://sdml.io/errors/#W0308): "));
+    assert!(report.contains("### [I0512](https://sdml.io/errors/#I0512): "));
+    assert!(report
+        .contains("3 │   import [ addresses xsd:string ]\n  │                      ^^^^^^^^^^ "));
+    assert!(report.contains("7 │   structure PostBox is\n  │             ^^^^^^^ "));
+    // the help URL note is replaced by the link on the code.
+    assert!(!report.contains("for more details"));
+}
+
+#[test]
+fn test_markdown_report_without_module_names() {
+    let report = report(ReportFormat::Markdown, false);
+
+    assert!(!report.contains("## Module"));
+    assert_eq!(report.matches("## File").count(), 2);
+}
+
+#[test]
+fn test_html_report() {
+    let report = report(ReportFormat::Html, true);
+
+    assert!(report.starts_with("<!DOCTYPE html>"));
+    assert!(report.contains("<h1>Diagnostics for module <code>customers</code></h1>"));
+    assert!(report.contains("<tr><td>0</td><td>0</td><td>1</td><td>1</td><td>2</td></tr>"));
+    assert!(report.contains("<h2>Module <code>addresses</code></h2>"));
+    assert!(report.contains("<a href=\"https://sdml.io/errors/#W0308\">W0308</a>"));
+    assert!(report.contains("<mark>xsd:string</mark>"));
+    assert!(report.contains("<mark>PostBox</mark>"));
+    assert!(report.contains("<span class=\"keyword\">structure</span>"));
+}
+
+#[test]
+fn test_empty_report() {
+    let file = NamedTempFile::new().unwrap();
+    let reporter = DocumentReporter::new(file.reopen().unwrap(), ReportFormat::Markdown);
+
+    let counters = reporter.done(None).unwrap();
+    assert_eq!(counters.total(), 0);
+
+    let report = std::fs::read_to_string(file.path()).unwrap();
+    assert!(report.starts_with("# Diagnostics\n"));
+    assert!(report.contains("No issues found."));
+}
+
+const FENCED: &str = r#"module example is
+
+  @skos:definition = "a ```fenced``` and
+  `quoted` <text>"
+
+end
+"#;
+
+///
+/// Report a diagnostic, with markup in its message, on the multi-line string in `FENCED`.
+///
+fn report_fenced(format: ReportFormat) -> String {
+    let file = NamedTempFile::new().unwrap();
+    let reporter = DocumentReporter::new(file.reopen().unwrap(), format)
+        .with_severity_filter(SeverityFilter::Warning);
+    let mut files = SourceFiles::new();
+    let file_id = files.add("example.sdm".to_string(), FENCED.to_string().into());
+    let start = FENCED.find("\"a").unwrap();
+    let end = FENCED.find(">\"").unwrap() + 2;
+    let diagnostic = Diagnostic::warning()
+        .with_code("W9999")
+        .with_message("use <b>bold</b> & *stars*")
+        .with_labels(vec![Label::primary(file_id, start..end)])
+        .with_notes(vec!["see [the docs]".to_string()]);
+    reporter.emit(&diagnostic, &files).unwrap();
+    reporter.done(None).unwrap();
+
+    std::fs::read_to_string(file.path()).unwrap()
+}
+
+#[test]
+fn test_markdown_report_escapes_text() {
+    let report = report_fenced(ReportFormat::Markdown);
+
+    assert!(
+        report.contains("**warning**: use \\<b\\>bold\\</b\\> \\& \\*stars\\* `example.sdm:3:22`")
+    );
+    assert!(report.contains("* see \\[the docs\\]"));
+    assert!(report.contains("\n````text\n3 │   @skos:definition = \"a ```fenced``` and\n"));
+    assert!(report.contains("\n````\n"));
+}
+
+#[test]
+fn test_html_report_balances_multi_line_tokens() {
+    let report = report_fenced(ReportFormat::Html);
+
+    let excerpt = report
+        .split_once("<pre class=\"excerpt\">")
+        .and_then(|(_, rest)| rest.split_once("</pre>"))
+        .map(|(excerpt, _)| excerpt)
+        .unwrap();
+    let lines: Vec<&str> = excerpt
+        .lines()
+        .filter(|line| line.contains("line-number"))
+        .collect();
+    assert_eq!(lines.len(), 2);
+    for line in lines {
+        assert_eq!(
+            line.matches("<span").count(),
+            line.matches("</span>").count(),
+            "{line}"
+        );
+    }
+    assert!(report.contains("&lt;text&gt;"));
+}
@@ -77,4 +77,9 @@ mod parse;
 
 pub use sdml_core::error;
 
+pub mod lint;
+
 pub mod load;
+
+#[cfg(feature = "archive")]
+pub mod package;
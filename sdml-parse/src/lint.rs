@@ -0,0 +1,291 @@
+/*!
+This module provides [`LintConfig`], the content of a project's lint configuration file.
+
+The configuration file, named [`SDML_LINT_FILE_NAME`], is discovered in the same way as the
+resolver catalog: in the current directory or any of its parents, unless the environment variable
+[`SDML_LINT_FILE_VARIABLE`] names a specific file. A configuration is applied to the diagnostics
+reported by a [`FsModuleLoader`](crate::load::FsModuleLoader) with
+[`with_lint_config`](crate::load::FsModuleLoader::with_lint_config), it is not discovered
+implicitly.
+
+The file may contain any of the following:
+
+* `codes`, a map from diagnostic code to one of `"off"`, `"on"`, `"error"`, `"warning"`,
+  `"note"`, or `"help"`; errors may not be turned off or reported as less than an error.
+* `naming`, a map from identifier usage to a list of allowed case styles, the first of which is
+  preferred. The usages are `module`, `member`, `imported_member`, `datatype`, `property`, `rdf`,
+  `type`, and `variant`; the styles are `snake_case`, `lowerCamelCase`, `UpperCamelCase`, and
+  `SHOUTY_SNAKE_CASE`.
+* `deprecated_terms`, the path, relative to the configuration file, of a deprecated term set to
+  use in place of the default; see [`load_term_set`] for the supported formats.
+
+```json
+{
+  "codes": {
+    "I0503": "off",
+    "W0305": "error"
+  },
+  "naming": {
+    "member": ["lowerCamelCase"],
+    "variant": ["SHOUTY_SNAKE_CASE"]
+  },
+  "deprecated_terms": "terms.json"
+}
+```
+
+# Example
+
+```rust
+use sdml_errors::diagnostics::ErrorCode;
+use sdml_parse::lint::LintConfig;
+use std::path::Path;
+
+let config = LintConfig::load_from_str(
+    r#"{ "codes": { "I0503": "off" } }"#,
+    Path::new("."),
+).unwrap();
+
+assert!(!config.rules().is_enabled(ErrorCode::StringWithoutLanguage));
+```
+*/
+
+use crate::load::{
+    FsModuleLoader, ModuleSourceResolver, SDML_FILE_EXTENSION, SDML_FILE_EXTENSION_LONG,
+};
+use codespan_reporting::diagnostic::Severity;
+use sdml_core::model::check::terms::TermSet;
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_errors::diagnostics::functions::{module_not_found, CaseStyle, IdentifierCaseConvention};
+use sdml_errors::diagnostics::rules::LintRules;
+use sdml_errors::diagnostics::ErrorCode;
+use sdml_errors::Error;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::path::{Path, PathBuf};
+use tracing::{info, trace, warn};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The name used for lint configuration files.
+pub const SDML_LINT_FILE_NAME: &str = "sdml-lint.json";
+
+/// The environment variable used to override lint configuration file location.
+pub const SDML_LINT_FILE_VARIABLE: &str = "SDML_LINT_FILE";
+
+///
+/// This type represents the content of a lint configuration file.
+///
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct LintConfig {
+    #[serde(skip)]
+    loaded_from: PathBuf,
+    #[serde(skip)]
+    rules: LintRules,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    codes: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    naming: BTreeMap<String, Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deprecated_terms: Option<PathBuf>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Load a deprecated term set from `path`; files with the extension `sdm` or `sdml` are parsed as
+/// an SDML module, see [`TermSet::from_module`], using `loader` and so reporting any diagnostics
+/// to its reporter; all others are read as JSON.
+///
+pub fn load_term_set<R>(path: &Path, loader: &mut FsModuleLoader<R>) -> Result<TermSet, Error>
+where
+    R: ModuleSourceResolver,
+{
+    trace!("load_term_set({path:?})");
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some(SDML_FILE_EXTENSION) | Some(SDML_FILE_EXTENSION_LONG) => {
+            let mut cache = InMemoryModuleCache::default().with_stdlib();
+            let name = loader.load_auxiliary_file(path.to_path_buf(), &mut cache)?;
+            let module = cache
+                .get(&name)
+                .ok_or_else(|| Error::from(module_not_found(name)))?;
+            TermSet::from_module(module)
+        }
+        _ => {
+            let source = std::fs::read_to_string(path)?;
+            serde_json::from_str(&source).map_err(|e| Error::TermSetError {
+                name: path.display().to_string(),
+                message: e.to_string(),
+            })
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl LintConfig {
+    ///
+    /// Load the lint configuration file named by the environment variable
+    /// [`SDML_LINT_FILE_VARIABLE`] if set, else from the current directory or any of its parents.
+    /// Returns `Ok(None)` if no configuration file is found, and an error if the file found
+    /// cannot be read or is not a valid configuration.
+    ///
+    pub fn discover() -> Result<Option<Self>, Error> {
+        trace!("LintConfig::discover()");
+        match env::var(SDML_LINT_FILE_VARIABLE) {
+            Ok(config_file) => Self::load_from_file(Path::new(&config_file)).map(Some),
+            _ => Self::load_from_current(true),
+        }
+    }
+
+    ///
+    /// Load a lint configuration file from the current directory.
+    ///
+    /// If the parameter `look_in_parents` is `true` this will check parent directories.
+    ///
+    pub fn load_from_current(look_in_parents: bool) -> Result<Option<Self>, Error> {
+        trace!("LintConfig::load_from_current({look_in_parents})");
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        Self::load_from(&cwd, look_in_parents)
+    }
+
+    ///
+    /// Load a lint configuration file from the `path`.
+    ///
+    /// If the parameter `look_in_parents` is `true` this will check parent directories.
+    ///
+    pub fn load_from(path: &Path, look_in_parents: bool) -> Result<Option<Self>, Error> {
+        trace!("LintConfig::load_from({path:?}, {look_in_parents})");
+        if path.is_file() {
+            Self::load_from_file(path).map(Some)
+        } else if path.is_dir() {
+            let file = path.join(SDML_LINT_FILE_NAME);
+            if file.is_file() {
+                Self::load_from_file(&file).map(Some)
+            } else if look_in_parents {
+                match path.parent() {
+                    Some(parent_path) => Self::load_from(parent_path, look_in_parents),
+                    None => Ok(None),
+                }
+            } else {
+                Ok(None)
+            }
+        } else {
+            warn!("The provided path was not a file or directory");
+            Ok(None)
+        }
+    }
+
+    fn load_from_file(file: &Path) -> Result<Self, Error> {
+        trace!("LintConfig::load_from_file({file:?})");
+        let invalid = |message: String| Error::LintConfigError {
+            file: file.display().to_string(),
+            message,
+        };
+        let source = std::fs::read_to_string(file).map_err(|e| invalid(e.to_string()))?;
+        let config = Self::from_json(&source, file.parent().unwrap_or_else(|| Path::new(".")))
+            .map_err(invalid)?;
+        info!("Loaded lint configuration, file: {file:?}");
+        Ok(config)
+    }
+
+    ///
+    /// Load a lint configuration from the JSON in `source`, the deprecated terms path is relative
+    /// to `loaded_from`. Unknown codes, usages, styles, or severities are returned as errors.
+    ///
+    pub fn load_from_str(source: &str, loaded_from: &Path) -> Result<Self, Error> {
+        Self::from_json(source, loaded_from).map_err(|message| Error::LintConfigError {
+            file: loaded_from.display().to_string(),
+            message,
+        })
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn loaded_from(&self) -> &PathBuf {
+        &self.loaded_from
+    }
+
+    pub fn rules(&self) -> &LintRules {
+        &self.rules
+    }
+
+    ///
+    /// Return the path of the configured deprecated term set, relative to the directory the
+    /// configuration was loaded from.
+    ///
+    pub fn deprecated_terms_path(&self) -> Option<PathBuf> {
+        self.deprecated_terms
+            .as_ref()
+            .map(|path| self.loaded_from.join(path))
+    }
+
+    ///
+    /// Load the configured deprecated term set, if any, see [`load_term_set`].
+    ///
+    pub fn deprecated_terms<R>(
+        &self,
+        loader: &mut FsModuleLoader<R>,
+    ) -> Result<Option<TermSet>, Error>
+    where
+        R: ModuleSourceResolver,
+    {
+        self.deprecated_terms_path()
+            .map(|path| load_term_set(&path, loader))
+            .transpose()
+    }
+
+    fn from_json(source: &str, loaded_from: &Path) -> Result<Self, String> {
+        let mut config = serde_json::from_str::<LintConfig>(source).map_err(|e| e.to_string())?;
+        config.rules = config.make_rules()?;
+        config.loaded_from = loaded_from.to_path_buf();
+        Ok(config)
+    }
+
+    fn make_rules(&self) -> Result<LintRules, String> {
+        let mut rules = LintRules::default();
+        for (code, setting) in &self.codes {
+            let code = ErrorCode::from_code(code)
+                .ok_or_else(|| format!("unknown diagnostic code `{code}`"))?;
+            let severity = match setting.as_str() {
+                "off" => None,
+                "on" => Some(code.severity()),
+                "error" => Some(Severity::Error),
+                "warning" => Some(Severity::Warning),
+                "note" => Some(Severity::Note),
+                "help" => Some(Severity::Help),
+                _ => return Err(format!("unknown setting `{setting}` for code `{code}`")),
+            };
+            if code.severity() >= Severity::Error
+                && severity.is_none_or(|severity| severity < code.severity())
+            {
+                return Err(format!("the error `{code}` may not be set to `{setting}`"));
+            }
+            match severity {
+                None => rules.disable(code),
+                Some(severity) if severity != code.severity() => rules.set_severity(code, severity),
+                _ => {}
+            }
+        }
+        for (usage, styles) in &self.naming {
+            let convention = IdentifierCaseConvention::from_name(usage)
+                .ok_or_else(|| format!("unknown identifier usage `{usage}`"))?;
+            let styles = styles
+                .iter()
+                .map(|style| {
+                    CaseStyle::from_name(style)
+                        .ok_or_else(|| format!("unknown case style `{style}` for `{usage}`"))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            rules.set_case_styles(convention, styles);
+        }
+        Ok(rules)
+    }
+}
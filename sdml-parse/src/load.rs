@@ -1,25 +1,53 @@
 /*!
 This module contains implementations of the [`ModuleResolver`] and [`ModuleLoader`] traits for
 file-system based modules.
+
+The loader reads module source through a [`ModuleSourceResolver`], by default a [`ResolverChain`]
+containing only the [`FsModuleResolver`]. Additional resolvers may be added to the chain, in
+priority order, to load modules from model packages (the `archive` feature), from a local git
+repository at a given reference (the `git` feature), or from a local mirror of the URLs in a
+catalog.
+
+# Example
+
+```rust,no_run
+use sdml_parse::load::mirror::MirrorModuleResolver;
+use sdml_parse::load::{FsModuleLoader, ModuleCatalog, ResolverChain};
+use std::path::Path;
+
+let catalog = ModuleCatalog::load_from(Path::new("./models"), false).unwrap();
+let chain = ResolverChain::default()
+    .with_resolver(MirrorModuleResolver::new("./mirror", catalog));
+let loader = FsModuleLoader::default().with_resolver(chain);
+```
 */
 
+use crate::lint::LintConfig;
 use crate::parse::parse_str;
 use codespan_reporting::files::SimpleFiles;
 use sdml_core::load::{ModuleLoader, ModuleResolver};
+use sdml_core::model::check::suppressions::module_suppressions;
 use sdml_core::model::identifiers::Identifier;
-use sdml_core::model::modules::HeaderValue;
+use sdml_core::model::modules::{HeaderValue, Module};
 use sdml_core::model::{HasName, HasSourceSpan};
 use sdml_core::stdlib;
 use sdml_core::store::ModuleStore;
-use sdml_errors::diagnostics::reporter::ReportCounters;
+use sdml_errors::diagnostics::functions::{
+    imported_module_not_found, module_version_mismatch, module_version_not_found,
+};
+use sdml_errors::diagnostics::reporter::{ReportCounters, SuppressingReporter};
+use sdml_errors::diagnostics::rules::LintRules;
+use sdml_errors::diagnostics::suggestions::SuggestedDiagnostic;
 use sdml_errors::diagnostics::SeverityFilter;
-use sdml_errors::diagnostics::{functions::imported_module_not_found, StandardStreamReporter};
+use sdml_errors::diagnostics::{ErrorCode, StandardStreamReporter};
 use sdml_errors::{Diagnostic, Reporter, Source, SourceFiles};
-use sdml_errors::{Error, FileId};
+use sdml_errors::{Error, FileId, Span};
 use search_path::SearchPath;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::env;
+use std::fmt::Debug;
 use std::fs::File;
 use std::io::Read;
 use std::path::{Path, PathBuf};
@@ -30,10 +58,61 @@ use url::Url;
 // Public Types
 // ------------------------------------------------------------------------------------------------
 
+///
+/// A source resolver locates, and reads, the source for a module. Unlike [`ModuleResolver`] the
+/// source need not be a file, it may be an entry in an archive or an object in a repository.
+///
+/// A resolver returns `Ok(None)` when it does not know the module so that a [`ResolverChain`]
+/// may try the next resolver; errors are reserved for failures reading a source it does know.
+///
+pub trait ModuleSourceResolver: Debug {
+    ///
+    /// Find and read the source for the version of module `name` identified by `version_uri`, or
+    /// the default version if `version_uri` is `None`.
+    ///
+    fn resolve(
+        &self,
+        name: &Identifier,
+        version_uri: Option<&Url>,
+    ) -> Result<Option<ResolvedSource>, Error>;
+
+    ///
+    /// Return the catalog used by this resolver, if any. The loader uses this to determine the
+    /// base URI for modules loaded directly from a file.
+    ///
+    fn catalog(&self) -> Option<&ModuleCatalog> {
+        None
+    }
+}
+
+///
+/// The source of a module, as found by a [`ModuleSourceResolver`].
+///
+#[derive(Clone, Debug)]
+pub struct ResolvedSource {
+    resource: Url,
+    location: PathBuf,
+    base_uri: Option<Url>,
+    source: String,
+}
+
+///
+/// A list of resolvers, consulted in priority order; the first resolver to find a module wins.
+///
+/// The default chain contains only a default [`FsModuleResolver`].
+///
+#[derive(Debug)]
+pub struct ResolverChain {
+    resolvers: Vec<Box<dyn ModuleSourceResolver>>,
+}
+
 ///
 /// The resolver implements the logic to map module identifiers to file system paths using the
 /// environment variable `SDML_PATH` to contain a search path.
 ///
+/// If a catalog is present it is consulted first, and where an import requests a specific version
+/// of a module the catalog's versions for that module are used to find the corresponding file.
+///
 #[derive(Clone, Debug)]
 pub struct FsModuleResolver {
     catalog: Option<ModuleCatalog>,
@@ -64,12 +143,23 @@ pub const SDML_CATALOG_FILE_VARIABLE: &str = "SDML_CATALOG_FILE";
 /// 2. parsing the source into an in-memory representation,
 /// 3. caching the loaded module, and it's source, for future use.
 ///
+/// When loading recursively, any imported module that cannot be found, or cannot be parsed, is
+/// reported and recorded as *unresolved* rather than causing the whole load to fail.
+///
+/// The loader is generic over the [`ModuleSourceResolver`] used to find module source, by default
+/// a [`ResolverChain`].
+///
 #[derive(Debug)]
-pub struct FsModuleLoader {
-    resolver: FsModuleResolver,
+pub struct FsModuleLoader<R = ResolverChain> {
+    resolver: R,
     module_file_ids: HashMap<Identifier, usize>,
     module_files: SourceFiles,
-    reporter: Box<dyn Reporter>,
+    unresolved_modules: HashSet<Identifier>,
+    attempted_versions: HashSet<(Identifier, Option<Url>)>,
+    loaded_files: HashSet<PathBuf>,
+    reported_mismatches: RefCell<HashSet<(FileId, Span)>>,
+    lint_config: Option<LintConfig>,
+    reporter: SuppressingReporter,
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -99,7 +189,7 @@ pub enum CatalogEntry {
 ///
 /// A resolver group allows the common configuration of multiple items.
 ///
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub struct Group {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -110,13 +200,29 @@ pub struct Group {
 }
 
 ///
-/// A specific resolver item.
+/// A specific resolver item. The item's own `relative_url` and `relative_path` identify the
+/// default version of the module, any additional versions are listed in `versions`.
 ///
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub struct Item {
     relative_url: String,
     relative_path: PathBuf,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    versions: Vec<ItemVersion>,
+}
+
+///
+/// A specific version of a resolver item, identified by the module's version URI. If no
+/// `relative_url` is provided the item's URL is used.
+///
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ItemVersion {
+    version_uri: Url,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    relative_url: Option<String>,
+    relative_path: PathBuf,
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -183,6 +289,39 @@ impl ModuleResolver for FsModuleResolver {
         Url::from_file_path(self.name_to_path(name, from)?)
             .map_err(|_| Error::UrlParseError { source: None })
     }
+
+    fn name_and_version_to_resource(
+        &self,
+        name: &Identifier,
+        version_uri: Option<&Url>,
+        from: Option<FileId>,
+    ) -> Result<Url, Error> {
+        Url::from_file_path(self.name_and_version_to_path(name, version_uri, from)?)
+            .map_err(|_| Error::UrlParseError { source: None })
+    }
+}
+
+impl ModuleSourceResolver for FsModuleResolver {
+    fn resolve(
+        &self,
+        name: &Identifier,
+        version_uri: Option<&Url>,
+    ) -> Result<Option<ResolvedSource>, Error> {
+        trace_entry!("ModuleSourceResolver", "resolve" => "{}, {:?}", name, version_uri);
+        match self.find_path(name, version_uri) {
+            Some(path) => {
+                let resource = Url::from_file_path(path.canonicalize()?)
+                    .map_err(|_| Error::UrlParseError { source: None })?;
+                let source = std::fs::read_to_string(&path)?;
+                Ok(Some(ResolvedSource::new(resource, path, source)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn catalog(&self) -> Option<&ModuleCatalog> {
+        self.catalog.as_ref()
+    }
 }
 
 impl FsModuleResolver {
@@ -196,57 +335,206 @@ impl FsModuleResolver {
         self.search_path.append(PathBuf::from(path));
     }
 
+    /// Return a file system path for the resource that /should/ contain the version of the named
+    /// module identified by `version_uri`. If the catalog does not list the requested version
+    /// this falls back to [`Self::name_to_path`].
+    pub fn name_and_version_to_path(
+        &self,
+        name: &Identifier,
+        version_uri: Option<&Url>,
+        from: Option<FileId>,
+    ) -> Result<PathBuf, Error> {
+        trace_entry!("ModuleResolver", "name_and_version_to_path" => "{}, {:?}", name, version_uri);
+        self.find_path(name, version_uri).ok_or_else(|| {
+            imported_module_not_found(
+                from.unwrap_or_default(),
+                name.source_span().map(|span| span.into()),
+                name,
+            )
+            .into()
+        })
+    }
+
     /// Return a file system path for the resource that /should/ contain the named module.
     pub fn name_to_path(&self, name: &Identifier, from: Option<FileId>) -> Result<PathBuf, Error> {
         trace_entry!("ModuleResolver", "name_to_path" => "{}", name);
+        self.find_path(name, None).ok_or_else(|| {
+            imported_module_not_found(
+                from.unwrap_or_default(),
+                name.source_span().map(|span| span.into()),
+                name,
+            )
+            .into()
+        })
+    }
+
+    fn find_path(&self, name: &Identifier, version_uri: Option<&Url>) -> Option<PathBuf> {
         if let Some(catalog) = &self.catalog {
             let name: String = name.to_string();
+            if let Some(version_uri) = version_uri {
+                if let Some(path) = catalog.resolve_version_local_path(&name, version_uri) {
+                    trace!("Found module version in catalog, path: {path:?}");
+                    return Some(path);
+                }
+                debug!("Version {version_uri} of module {name} not in catalog");
+            }
             if let Some(path) = catalog.resolve_local_path(&name) {
                 trace!("Found module in catalog, path: {path:?}");
-                return Ok(path);
+                return Some(path);
             }
         }
-        self.search_path
-            .find(format!("{}.{}", name, SDML_FILE_EXTENSION).as_ref())
-            .or_else(|| {
-                self.search_path
-                    .find(format!("{}/{}.{}", name, name, SDML_FILE_EXTENSION).as_ref())
-                    .or_else(|| {
-                        self.search_path
-                            .find(format!("{}.{}", name, SDML_FILE_EXTENSION_LONG).as_ref())
-                            .or_else(|| {
-                                self.search_path.find(
-                                    format!("{}/{}.{}", name, name, SDML_FILE_EXTENSION_LONG)
-                                        .as_ref(),
-                                )
-                            })
-                    })
-            })
-            .ok_or_else(|| {
-                imported_module_not_found(
-                    from.unwrap_or_default(),
-                    name.source_span().map(|span| span.into()),
-                    name,
-                )
-                .into()
-            })
+        module_file_names(name)
+            .into_iter()
+            .find_map(|file_name| self.search_path.find(file_name.as_ref()))
     }
 }
 
 // ------------------------------------------------------------------------------------------------
 
-impl Default for FsModuleLoader {
+impl Default for ResolverChain {
     fn default() -> Self {
         Self {
-            resolver: Default::default(),
-            module_file_ids: Default::default(),
-            module_files: SimpleFiles::new(),
-            reporter: Box::<StandardStreamReporter>::default(),
+            resolvers: vec![Box::new(FsModuleResolver::default())],
+        }
+    }
+}
+
+impl ModuleSourceResolver for ResolverChain {
+    fn resolve(
+        &self,
+        name: &Identifier,
+        version_uri: Option<&Url>,
+    ) -> Result<Option<ResolvedSource>, Error> {
+        for resolver in &self.resolvers {
+            if let Some(resolved) = resolver.resolve(name, version_uri)? {
+                return Ok(Some(resolved));
+            }
+        }
+        Ok(None)
+    }
+
+    fn catalog(&self) -> Option<&ModuleCatalog> {
+        self.resolvers
+            .iter()
+            .find_map(|resolver| resolver.catalog())
+    }
+}
+
+impl ModuleResolver for ResolverChain {
+    fn name_to_resource(&self, name: &Identifier, from: Option<FileId>) -> Result<Url, Error> {
+        self.name_and_version_to_resource(name, None, from)
+    }
+
+    fn name_and_version_to_resource(
+        &self,
+        name: &Identifier,
+        version_uri: Option<&Url>,
+        from: Option<FileId>,
+    ) -> Result<Url, Error> {
+        match self.resolve(name, version_uri)? {
+            Some(resolved) => Ok(resolved.resource),
+            None => Err(imported_module_not_found(
+                from.unwrap_or_default(),
+                name.source_span().map(|span| span.into()),
+                name,
+            )
+            .into()),
+        }
+    }
+}
+
+impl ResolverChain {
+    /// Construct a chain with no resolvers.
+    pub fn empty() -> Self {
+        Self {
+            resolvers: Default::default(),
+        }
+    }
+
+    /// Add `resolver` to the end of the chain, it will be consulted after any existing resolvers.
+    pub fn with_resolver(self, resolver: impl ModuleSourceResolver + 'static) -> Self {
+        let mut self_mut = self;
+        self_mut.append(resolver);
+        self_mut
+    }
+
+    /// Add `resolver` to the beginning of the chain, it will be consulted first.
+    pub fn prepend(&mut self, resolver: impl ModuleSourceResolver + 'static) {
+        self.resolvers.insert(0, Box::new(resolver));
+    }
+
+    /// Add `resolver` to the end of the chain, it will be consulted last.
+    pub fn append(&mut self, resolver: impl ModuleSourceResolver + 'static) {
+        self.resolvers.push(Box::new(resolver));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.resolvers.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.resolvers.len()
+    }
+
+    pub fn resolvers(&self) -> impl Iterator<Item = &dyn ModuleSourceResolver> {
+        self.resolvers.iter().map(|resolver| resolver.as_ref())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl ResolvedSource {
+    ///
+    /// Construct a new resolved source; `resource` identifies where the source was found and
+    /// `location` is the path recorded as the module's source file.
+    ///
+    pub fn new<P: Into<PathBuf>, S: Into<String>>(resource: Url, location: P, source: S) -> Self {
+        Self {
+            resource,
+            location: location.into(),
+            base_uri: None,
+            source: source.into(),
         }
     }
+
+    pub fn with_base_uri(self, base_uri: Url) -> Self {
+        Self {
+            base_uri: Some(base_uri),
+            ..self
+        }
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn resource(&self) -> &Url {
+        &self.resource
+    }
+
+    pub fn location(&self) -> &PathBuf {
+        &self.location
+    }
+
+    pub fn base_uri(&self) -> Option<&Url> {
+        self.base_uri.as_ref()
+    }
+
+    pub fn source(&self) -> &String {
+        &self.source
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Default for FsModuleLoader {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
 }
 
-impl ModuleLoader for FsModuleLoader {
+impl<R> ModuleLoader for FsModuleLoader<R>
+where
+    R: ModuleSourceResolver + ModuleResolver,
+{
     fn load(
         &mut self,
         name: &Identifier,
@@ -255,19 +543,7 @@ impl ModuleLoader for FsModuleLoader {
         recursive: bool,
     ) -> Result<Identifier, Error> {
         trace_entry!("ModuleLoader", "load" => "{}", name);
-        if stdlib::library_module(name).is_some() {
-            Ok(name.clone())
-        } else {
-            let file = match self.resolver.name_to_path(name, from) {
-                Ok(f) => f,
-                Err(Error::LanguageValidationError { source }) => {
-                    self.report(&source)?;
-                    return Err(source.into());
-                }
-                Err(e) => return Err(e),
-            };
-            self.load_from_file(file, cache, recursive)
-        }
+        self.load_version(name, None, from, cache, recursive)
     }
 
     fn resolver(&self) -> &impl ModuleResolver {
@@ -289,7 +565,11 @@ impl ModuleLoader for FsModuleLoader {
     }
 
     fn report(&self, diagnostic: &Diagnostic) -> Result<(), Error> {
-        self.reporter.emit(diagnostic, self.files())
+        self.emit(diagnostic)
+    }
+
+    fn report_suggested(&self, diagnostic: &SuggestedDiagnostic) -> Result<(), Error> {
+        self.emit_suggested(diagnostic)
     }
 
     fn reporter_done(&self, top_module_name: Option<String>) -> Result<ReportCounters, Error> {
@@ -299,15 +579,118 @@ impl ModuleLoader for FsModuleLoader {
     fn set_severity_filter(&mut self, filter: SeverityFilter) {
         self.reporter.set_severity_filter(filter);
     }
+
+    fn lint_rules(&self) -> Option<&LintRules> {
+        Some(self.reporter.rules())
+    }
 }
 
-impl FsModuleLoader {
-    pub fn with_resolver(self, resolver: FsModuleResolver) -> Self {
-        Self { resolver, ..self }
+impl<R: ModuleSourceResolver> FsModuleLoader<R> {
+    /// Construct a new loader using `resolver` to find module source.
+    pub fn new(resolver: R) -> Self {
+        Self {
+            resolver,
+            module_file_ids: Default::default(),
+            module_files: SimpleFiles::new(),
+            unresolved_modules: Default::default(),
+            attempted_versions: Default::default(),
+            loaded_files: Default::default(),
+            reported_mismatches: Default::default(),
+            lint_config: None,
+            reporter: SuppressingReporter::new(Box::<StandardStreamReporter>::default()),
+        }
     }
 
+    /// Replace the resolver used by this loader, the resolver may be of a different type.
+    pub fn with_resolver<R2: ModuleSourceResolver>(self, resolver: R2) -> FsModuleLoader<R2> {
+        FsModuleLoader {
+            resolver,
+            module_file_ids: self.module_file_ids,
+            module_files: self.module_files,
+            unresolved_modules: self.unresolved_modules,
+            attempted_versions: self.attempted_versions,
+            loaded_files: self.loaded_files,
+            reported_mismatches: self.reported_mismatches,
+            lint_config: self.lint_config,
+            reporter: self.reporter,
+        }
+    }
+
+    /// Replace the reporter used by this loader, diagnostics disabled by the loader's lint
+    /// configuration, or suppressed by `sdml:suppress` annotations in loaded modules, are not
+    /// passed to `reporter`. Diagnostics without a location are taken to be from the module most
+    /// recently loaded.
     pub fn with_reporter(self, reporter: Box<dyn Reporter>) -> Self {
-        Self { reporter, ..self }
+        let rules = self.reporter.rules().clone();
+        Self {
+            reporter: SuppressingReporter::new(reporter).with_rules(rules),
+            ..self
+        }
+    }
+
+    /// Replace the lint configuration used by this loader, the configuration's rules are applied
+    /// to all diagnostics reported from now on.
+    pub fn with_lint_config(mut self, lint_config: LintConfig) -> Self {
+        self.reporter.set_rules(lint_config.rules().clone());
+        Self {
+            lint_config: Some(lint_config),
+            ..self
+        }
+    }
+
+    /// Returns the lint configuration used by this loader, if any.
+    pub fn lint_config(&self) -> Option<&LintConfig> {
+        self.lint_config.as_ref()
+    }
+
+    /// Report every code in a `sdml:suppress` annotation, in the module loaded as `file_id`,
+    /// that did not suppress any diagnostic.
+    pub fn report_unused_suppressions(&self, file_id: FileId) -> Result<(), Error> {
+        for diagnostic in self.reporter.unused_suppressions(file_id) {
+            self.emit(&diagnostic)?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if any imported module could not be loaded, else `false`.
+    pub fn has_unresolved_modules(&self) -> bool {
+        !self.unresolved_modules.is_empty()
+    }
+
+    /// Returns `true` if the module named `name` was imported but could not be loaded.
+    pub fn is_unresolved_module(&self, name: &Identifier) -> bool {
+        self.unresolved_modules.contains(name)
+    }
+
+    /// Return an iterator over the names of all imported modules that could not be loaded.
+    pub fn unresolved_modules(&self) -> impl Iterator<Item = &Identifier> {
+        self.unresolved_modules.iter()
+    }
+
+    /// Load the version of the module named `name` identified by `version_uri`, or any version
+    /// if `version_uri` is `None`. As with [`ModuleLoader::load`] the module is not loaded if the
+    /// `cache` already contains the requested version.
+    pub fn load_version(
+        &mut self,
+        name: &Identifier,
+        version_uri: Option<&Url>,
+        from: Option<FileId>,
+        cache: &mut impl ModuleStore,
+        recursive: bool,
+    ) -> Result<Identifier, Error> {
+        trace_entry!("ModuleLoader", "load_version" => "{}, {:?}", name, version_uri);
+        if version_uri.is_some_and(|version_uri| cache.contains_version(name, version_uri)) {
+            return Ok(name.clone());
+        }
+        match self.resolve_dependency(name, version_uri, from) {
+            Ok(Some(resolved)) => self.load_from_resolved(resolved, cache, recursive),
+            Ok(None) => Ok(name.clone()),
+            Err(Error::LanguageValidationError { source }) => {
+                self.emit(&source)?;
+                Err(source.into())
+            }
+            Err(e) => Err(e),
+        }
     }
 
     /// Load a module from the source in `file`.
@@ -319,25 +702,39 @@ impl FsModuleLoader {
     ) -> Result<Identifier, Error> {
         trace_entry!("ModuleLoader", "load_from_file" => "{:?}", file);
         let mut reader = File::open(&file)?;
-        let catalog = self.resolver.catalog.clone();
-        let module_name = self.load_inner(&mut reader, Some(file.clone()), cache, recursive)?;
-        let module = cache.get_mut(&module_name).unwrap();
-        module.set_source_file(file.clone());
-        if !module.has_base_uri() {
-            if let Some(catalog) = catalog {
-                let name = module.name().to_string();
-                if let Some(url) = catalog.resolve_uri(&name) {
-                    module.set_base_uri(HeaderValue::from(url));
-                }
-            } else {
-                let file = file.canonicalize()?;
-                match Url::from_file_path(file) {
-                    Ok(base) => module.set_base_uri(HeaderValue::from(base)),
-                    Err(_) => warn!("Could not construct a base URI"),
-                }
-            }
-        }
-        Ok(module_name)
+        let mut source = String::new();
+        reader.read_to_string(&mut source)?;
+        self.load_inner(source, Some(file), None, cache, recursive)
+    }
+
+    // Load a module from `file` for its content alone, such as a term set; diagnostics without a
+    // location are still taken to be from the module loaded before it.
+    pub(crate) fn load_auxiliary_file(
+        &mut self,
+        file: PathBuf,
+        cache: &mut impl ModuleStore,
+    ) -> Result<Identifier, Error> {
+        let current_file = self.reporter.current_file();
+        let result = self.load_from_file(file, cache, false);
+        let _ = self.reporter.set_current_file(current_file);
+        result
+    }
+
+    /// Load a module from a source found by a [`ModuleSourceResolver`].
+    pub fn load_from_resolved(
+        &mut self,
+        resolved: ResolvedSource,
+        cache: &mut impl ModuleStore,
+        recursive: bool,
+    ) -> Result<Identifier, Error> {
+        trace_entry!("ModuleLoader", "load_from_resolved" => "{}", resolved.resource);
+        self.load_inner(
+            resolved.source,
+            Some(resolved.location),
+            resolved.base_uri,
+            cache,
+            recursive,
+        )
     }
 
     /// Load a module reading the source from `reader`.
@@ -348,54 +745,258 @@ impl FsModuleLoader {
         recursive: bool,
     ) -> Result<Identifier, Error> {
         trace_entry!("ModuleLoader", "load_from_reader");
-        self.load_inner(reader, None, cache, recursive)
+        let mut source = String::new();
+        reader.read_to_string(&mut source)?;
+        self.load_inner(source, None, None, cache, recursive)
     }
 
     fn load_inner(
         &mut self,
-        reader: &mut dyn Read,
+        source: String,
         file: Option<PathBuf>,
+        base_uri: Option<Url>,
         cache: &mut impl ModuleStore,
         recursive: bool,
     ) -> Result<Identifier, Error> {
-        trace!("ModuleLoader::load_inner(..., {file:?}, ..., {recursive})");
-        let mut source = String::new();
-        reader.read_to_string(&mut source)?;
+        trace!("ModuleLoader::load_inner(..., {file:?}, {base_uri:?}, ..., {recursive})");
         let file_name: String = file
+            .as_ref()
             .map(|p| p.to_string_lossy().into_owned())
             .unwrap_or_default();
         let file_id = self.module_files.add(file_name, source.into());
 
-        let module = parse_str(file_id, self)?;
+        // Stores that persist parsed modules return a copy for identical source, modules are only
+        // persisted if parsing reported no diagnostics as these are not repeated for the copy.
+        let source = self.module_files.get(file_id).unwrap().source().as_ref();
+        let (mut module, diagnostics) = match cache.get_parsed(source) {
+            Some(mut module) => {
+                debug!("found parsed module {} in store", module.name());
+                module.set_file_id(file_id);
+                (module, Vec::default())
+            }
+            None => {
+                let (module, diagnostics) = parse_str(file_id, &self.module_files, &self.reporter)?;
+                if diagnostics.is_empty() {
+                    cache.cache_parsed(source, &module);
+                }
+                (module, diagnostics)
+            }
+        };
+
+        // parser diagnostics are only reported once the module's suppressions are known, the
+        // module is the current file for diagnostics without a location until another is loaded.
+        let _ = self.reporter.set_current_file(Some(file_id));
+        for suppression in module_suppressions(&module) {
+            self.reporter.add_suppression(suppression);
+        }
+        for diagnostic in &diagnostics {
+            self.emit_suggested(diagnostic)?;
+        }
+        if let Some(file) = file {
+            let _ = self.loaded_files.insert(canonical_path(&file));
+            self.set_file_properties(&mut module, file, base_uri)?;
+        }
 
         let name = module.name().clone();
 
         let _ = self.module_file_ids.insert(name.clone(), file_id);
 
+        let dependencies = if recursive {
+            module
+                .imported_module_versions()
+                .into_iter()
+                .map(|(name, version_uri)| {
+                    (
+                        name.clone(),
+                        version_uri.map(|v| v.value().clone()),
+                        version_uri
+                            .and_then(|v| v.source_span())
+                            .map(|span| span.byte_range()),
+                    )
+                })
+                .collect::<Vec<(Identifier, Option<Url>, Option<Span>)>>()
+        } else {
+            Default::default()
+        };
+
         cache.insert(module);
 
         if recursive {
-            let dependencies = {
-                let module = cache.get(&name).unwrap();
-                module
-                    .imported_modules()
-                    .into_iter()
-                    .cloned()
-                    .collect::<Vec<Identifier>>()
-            };
-            for name in &dependencies {
-                if !cache.contains(name) {
-                    debug!("didn't find module {name} in cache, loading");
-                    // TODO: this bails on the first missing import, is that what we want?
-                    self.load(name, Some(file_id), cache, recursive)?;
+            for (name, version_uri, version_span) in &dependencies {
+                let cached = if let Some(version_uri) = version_uri {
+                    cache.contains_version(name, version_uri)
                 } else {
+                    cache.contains(name)
+                };
+                if cached {
                     debug!("found module {name} in cache");
+                } else if self.unresolved_modules.contains(name) {
+                    debug!("module {name} already known to be unresolved");
+                } else if !self
+                    .attempted_versions
+                    .insert((name.clone(), version_uri.clone()))
+                {
+                    // a previous attempt loaded a different version, don't try again.
+                    self.report_version_mismatch(file_id, name, version_uri, version_span, cache)?;
+                } else {
+                    debug!("didn't find module {name} in cache, loading");
+                    let result = self
+                        .resolve_dependency(name, version_uri.as_ref(), Some(file_id))
+                        .and_then(|resolved| match resolved {
+                            Some(resolved)
+                                if cache.contains(name)
+                                    && self
+                                        .loaded_files
+                                        .contains(&canonical_path(resolved.location())) =>
+                            {
+                                // the resolver found a file already loaded with another version.
+                                self.report_version_mismatch(
+                                    file_id,
+                                    name,
+                                    version_uri,
+                                    version_span,
+                                    cache,
+                                )
+                            }
+                            Some(resolved) => self
+                                .load_from_resolved(resolved, cache, recursive)
+                                .map(|_| ()),
+                            None => Ok(()),
+                        });
+                    if let Err(e) = result {
+                        warn!("could not load imported module {name}, error: {e}");
+                        self.emit(&imported_module_not_found(
+                            file_id,
+                            name.source_span().map(|span| span.byte_range()),
+                            name,
+                        ))?;
+                        let _ = self.unresolved_modules.insert(name.clone());
+                    }
+                    // loading the dependency changed the current file.
+                    let _ = self.reporter.set_current_file(Some(file_id));
                 }
             }
         }
         Ok(name)
     }
 
+    // Returns `Ok(None)` for library modules which need no loading, unlike `load` this does not
+    // report errors, the caller reports them against the import.
+    fn resolve_dependency(
+        &self,
+        name: &Identifier,
+        version_uri: Option<&Url>,
+        from: Option<FileId>,
+    ) -> Result<Option<ResolvedSource>, Error> {
+        if stdlib::library_module(name).is_some() {
+            Ok(None)
+        } else {
+            match self.resolver.resolve(name, version_uri)? {
+                Some(resolved) => Ok(Some(resolved)),
+                None => Err(imported_module_not_found(
+                    from.unwrap_or_default(),
+                    name.source_span().map(|span| span.into()),
+                    name,
+                )
+                .into()),
+            }
+        }
+    }
+
+    // Set the source file, and if necessary a base URI, from either the resolver, the catalog, or
+    // the file name.
+    fn set_file_properties(
+        &self,
+        module: &mut Module,
+        file: PathBuf,
+        base_uri: Option<Url>,
+    ) -> Result<(), Error> {
+        if !module.has_base_uri() {
+            if let Some(base_uri) = base_uri {
+                module.set_base_uri(HeaderValue::from(base_uri));
+            } else if let Some(catalog) = self.resolver.catalog() {
+                let name = module.name().to_string();
+                let url = module
+                    .version_uri()
+                    .and_then(|version_uri| catalog.resolve_version_uri(&name, version_uri.value()))
+                    .or_else(|| catalog.resolve_uri(&name));
+                if let Some(url) = url {
+                    module.set_base_uri(HeaderValue::from(url));
+                }
+            } else if file.is_file() {
+                match Url::from_file_path(file.canonicalize()?) {
+                    Ok(base) => module.set_base_uri(HeaderValue::from(base)),
+                    Err(_) => warn!("Could not construct a base URI"),
+                }
+            }
+        }
+        module.set_source_file(file);
+        Ok(())
+    }
+
+    // Report that the import of `name`, requesting `version_uri`, found a different version of the
+    // module already loaded; this is only reported for versioned imports.
+    fn report_version_mismatch(
+        &self,
+        file_id: FileId,
+        name: &Identifier,
+        version_uri: &Option<Url>,
+        version_span: &Option<Span>,
+        cache: &impl ModuleStore,
+    ) -> Result<(), Error> {
+        if let (Some(version_uri), Some(actual)) = (version_uri, cache.get(name)) {
+            let actual_file_id = actual.file_id().copied().unwrap_or_default();
+            let diagnostic = match actual.version_uri() {
+                Some(actual_version) => module_version_mismatch(
+                    file_id,
+                    version_span.clone(),
+                    version_uri.to_string(),
+                    actual_file_id,
+                    actual_version.source_span().map(|span| span.byte_range()),
+                    actual_version.as_ref().to_string(),
+                ),
+                None => module_version_not_found(
+                    file_id,
+                    version_span.clone(),
+                    version_uri.to_string(),
+                    actual_file_id,
+                    actual.source_span().map(|span| span.byte_range()),
+                    actual.name(),
+                ),
+            };
+            self.emit(&diagnostic)?;
+        }
+        Ok(())
+    }
+
+    fn emit(&self, diagnostic: &Diagnostic) -> Result<(), Error> {
+        if self.is_repeated_mismatch(diagnostic) {
+            Ok(())
+        } else {
+            self.reporter.emit(diagnostic, self.files())
+        }
+    }
+
+    fn emit_suggested(&self, diagnostic: &SuggestedDiagnostic) -> Result<(), Error> {
+        if self.is_repeated_mismatch(diagnostic.diagnostic()) {
+            Ok(())
+        } else {
+            self.reporter.emit_suggested(diagnostic, self.files())
+        }
+    }
+
+    // a version mismatch may be found by both the loader and module validation, it is only
+    // reported once for each import.
+    fn is_repeated_mismatch(&self, diagnostic: &Diagnostic) -> bool {
+        if diagnostic.code == Some(ErrorCode::ModuleVersionMismatch.to_string()) {
+            if let Some(label) = diagnostic.labels.first() {
+                let key = (label.file_id, label.range.clone());
+                return !self.reported_mismatches.borrow_mut().insert(key);
+            }
+        }
+        false
+    }
+
     #[inline(always)]
     pub(crate) fn files(&self) -> &SimpleFiles<String, Source> {
         &self.module_files
@@ -405,6 +1006,17 @@ impl FsModuleLoader {
 // ------------------------------------------------------------------------------------------------
 
 impl ModuleCatalog {
+    ///
+    /// Construct a new, empty, catalog; local paths in the catalog are relative to `loaded_from`.
+    ///
+    pub fn new(base: Url, loaded_from: &Path) -> Self {
+        Self {
+            base,
+            loaded_from: loaded_from.to_path_buf(),
+            entries: Default::default(),
+        }
+    }
+
     ///
     /// Load a resolver catalog file from the current directory.
     ///
@@ -453,17 +1065,13 @@ impl ModuleCatalog {
     fn load_from_file(file: &Path) -> Option<Self> {
         trace!("ModuleCatalog::load_from_file({file:?})");
         match std::fs::read_to_string(file) {
-            Ok(source) => match serde_json::from_str::<ModuleCatalog>(&source) {
-                Ok(mut catalog) => {
-                    catalog.loaded_from = file.parent().unwrap().to_path_buf();
+            Ok(source) => {
+                let catalog = Self::load_from_str(&source, file.parent().unwrap());
+                if catalog.is_some() {
                     info!("Loaded catalog, file: {file:?}");
-                    Some(catalog)
                 }
-                Err(e) => {
-                    error!("Error parsing catalog, file: {file:?}, error: {e}");
-                    None
-                }
-            },
+                catalog
+            }
             Err(e) => {
                 error!("Error reading catalog, file: {file:?}, error: {e}");
                 None
@@ -471,6 +1079,32 @@ impl ModuleCatalog {
         }
     }
 
+    ///
+    /// Write this catalog to the file `path` as JSON.
+    ///
+    pub fn save_to(&self, path: &Path) -> Result<(), Error> {
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::from)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    ///
+    /// Load a catalog from the JSON in `source`, local paths in the catalog are relative to
+    /// `loaded_from`.
+    ///
+    pub fn load_from_str(source: &str, loaded_from: &Path) -> Option<Self> {
+        match serde_json::from_str::<ModuleCatalog>(source) {
+            Ok(mut catalog) => {
+                catalog.loaded_from = loaded_from.to_path_buf();
+                Some(catalog)
+            }
+            Err(e) => {
+                error!("Error parsing catalog, loaded from: {loaded_from:?}, error: {e}");
+                None
+            }
+        }
+    }
+
     // --------------------------------------------------------------------------------------------
 
     pub fn base(&self) -> &Url {
@@ -487,6 +1121,10 @@ impl ModuleCatalog {
         &self.loaded_from
     }
 
+    pub fn set_loaded_from(&mut self, loaded_from: &Path) {
+        self.loaded_from = loaded_from.to_path_buf();
+    }
+
     // --------------------------------------------------------------------------------------------
 
     pub fn has_entries(&self) -> bool {
@@ -513,6 +1151,10 @@ impl ModuleCatalog {
         self.entries.values()
     }
 
+    pub fn insert_entry(&mut self, key: String, entry: CatalogEntry) {
+        self.entries.insert(key, entry);
+    }
+
     // --------------------------------------------------------------------------------------------
 
     pub fn groups(&self) -> impl Iterator<Item = (&String, &Group)> {
@@ -550,6 +1192,37 @@ impl ModuleCatalog {
                 .unwrap_or_default()
         }
     }
+
+    pub fn resolve_version_uri(&self, module: &String, version_uri: &Url) -> Option<Url> {
+        if let Some(CatalogEntry::Item(item)) = self.get_entry(module) {
+            item.get_version(version_uri).map(|version| {
+                self.base
+                    .join(version.relative_url().unwrap_or(item.relative_url()))
+                    .unwrap()
+            })
+        } else {
+            self.groups()
+                .find(|(_, g)| g.entries_contains_key(module))
+                .map(|(_, g)| g.resolve_version_uri(&self.base, module, version_uri))
+                .unwrap_or_default()
+        }
+    }
+
+    pub fn resolve_version_local_path(
+        &self,
+        module: &String,
+        version_uri: &Url,
+    ) -> Option<PathBuf> {
+        if let Some(CatalogEntry::Item(item)) = self.get_entry(module) {
+            item.get_version(version_uri)
+                .map(|version| self.loaded_from.join(version.relative_path()))
+        } else {
+            self.groups()
+                .find(|(_, g)| g.entries_contains_key(module))
+                .map(|(_, g)| g.resolve_version_local_path(&self.loaded_from, module, version_uri))
+                .unwrap_or_default()
+        }
+    }
 }
 
 impl From<Group> for CatalogEntry {
@@ -589,6 +1262,26 @@ impl CatalogEntry {
 }
 
 impl Group {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_relative_path<P: Into<PathBuf>>(self, relative_path: P) -> Self {
+        Self {
+            relative_path: Some(relative_path.into()),
+            ..self
+        }
+    }
+
+    pub fn with_relative_url<S: Into<String>>(self, relative_url: S) -> Self {
+        Self {
+            relative_url: Some(relative_url.into()),
+            ..self
+        }
+    }
+
+    // --------------------------------------------------------------------------------------------
+
     pub fn relative_path(&self) -> Option<&PathBuf> {
         self.relative_path.as_ref()
     }
@@ -637,30 +1330,79 @@ impl Group {
         self.entries.values()
     }
 
+    pub fn insert_entry(&mut self, key: String, item: Item) {
+        self.entries.insert(key, item);
+    }
+
     // --------------------------------------------------------------------------------------------
 
     pub fn resolve_uri(&self, base: &Url, module: &String) -> Option<Url> {
-        let base = if let Some(relative_url) = &self.relative_url {
+        self.get_entry(module).map(|item| {
+            self.url_base(base)
+                .join(item.relative_url().as_str())
+                .unwrap()
+        })
+    }
+
+    pub fn resolve_local_path(&self, base: &Path, module: &String) -> Option<PathBuf> {
+        self.get_entry(module)
+            .map(|item| self.local_base(base).join(item.relative_path()))
+    }
+
+    pub fn resolve_version_uri(
+        &self,
+        base: &Url,
+        module: &String,
+        version_uri: &Url,
+    ) -> Option<Url> {
+        self.get_entry(module).and_then(|item| {
+            item.get_version(version_uri).map(|version| {
+                self.url_base(base)
+                    .join(version.relative_url().unwrap_or(item.relative_url()))
+                    .unwrap()
+            })
+        })
+    }
+
+    pub fn resolve_version_local_path(
+        &self,
+        base: &Path,
+        module: &String,
+        version_uri: &Url,
+    ) -> Option<PathBuf> {
+        self.get_entry(module)
+            .and_then(|item| item.get_version(version_uri))
+            .map(|version| self.local_base(base).join(version.relative_path()))
+    }
+
+    fn url_base(&self, base: &Url) -> Url {
+        if let Some(relative_url) = &self.relative_url {
             base.join(relative_url.as_str()).unwrap()
         } else {
             base.clone()
-        };
-        self.get_entry(module)
-            .map(|item| base.join(item.relative_url().as_str()).unwrap())
+        }
     }
 
-    pub fn resolve_local_path(&self, base: &Path, module: &String) -> Option<PathBuf> {
-        let base = if let Some(group_base) = &self.relative_path {
+    fn local_base(&self, base: &Path) -> PathBuf {
+        if let Some(group_base) = &self.relative_path {
             base.join(group_base)
         } else {
             base.to_path_buf()
-        };
-        self.get_entry(module)
-            .map(|item| base.join(item.relative_url().as_str()))
+        }
     }
 }
 
 impl Item {
+    pub fn new<S: Into<String>, P: Into<PathBuf>>(relative_url: S, relative_path: P) -> Self {
+        Self {
+            relative_url: relative_url.into(),
+            relative_path: relative_path.into(),
+            versions: Default::default(),
+        }
+    }
+
+    // --------------------------------------------------------------------------------------------
+
     pub fn relative_path(&self) -> &PathBuf {
         &self.relative_path
     }
@@ -678,8 +1420,140 @@ impl Item {
     pub fn set_relative_url(&mut self, relative_url: String) {
         self.relative_url = relative_url;
     }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn has_versions(&self) -> bool {
+        !self.versions.is_empty()
+    }
+
+    pub fn versions(&self) -> impl Iterator<Item = &ItemVersion> {
+        self.versions.iter()
+    }
+
+    pub fn add_to_versions(&mut self, version: ItemVersion) {
+        self.versions.push(version);
+    }
+
+    pub fn get_version(&self, version_uri: &Url) -> Option<&ItemVersion> {
+        self.versions
+            .iter()
+            .find(|v| v.version_uri() == version_uri)
+    }
+}
+
+impl ItemVersion {
+    pub fn new(version_uri: Url, relative_path: PathBuf) -> Self {
+        Self {
+            version_uri,
+            relative_url: None,
+            relative_path,
+        }
+    }
+
+    pub fn with_relative_url(self, relative_url: String) -> Self {
+        Self {
+            relative_url: Some(relative_url),
+            ..self
+        }
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn version_uri(&self) -> &Url {
+        &self.version_uri
+    }
+
+    pub fn set_version_uri(&mut self, version_uri: Url) {
+        self.version_uri = version_uri;
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn relative_path(&self) -> &PathBuf {
+        &self.relative_path
+    }
+
+    pub fn set_relative_path(&mut self, relative_path: PathBuf) {
+        self.relative_path = relative_path;
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn relative_url(&self) -> Option<&String> {
+        self.relative_url.as_ref()
+    }
+
+    pub fn set_relative_url(&mut self, relative_url: String) {
+        self.relative_url = Some(relative_url);
+    }
+
+    pub fn unset_relative_url(&mut self) {
+        self.relative_url = None;
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+// Paths are compared canonically where possible, resolvers for archives and repositories return
+// locations that do not exist on the file system.
+fn canonical_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+// The file names, in priority order, that may contain the module `name`.
+fn module_file_names(name: &Identifier) -> [String; 4] {
+    [
+        format!("{name}.{SDML_FILE_EXTENSION}"),
+        format!("{name}/{name}.{SDML_FILE_EXTENSION}"),
+        format!("{name}.{SDML_FILE_EXTENSION_LONG}"),
+        format!("{name}/{name}.{SDML_FILE_EXTENSION_LONG}"),
+    ]
+}
+
+// The paths, in priority order and relative to the root of a package or repository, that may
+// contain the requested version of module `name`, along with any base URI the catalog provides.
+// The catalog is expected to have been loaded with an empty `loaded_from` path.
+#[cfg(any(feature = "archive", feature = "git"))]
+fn module_candidate_paths(
+    catalog: Option<&ModuleCatalog>,
+    name: &Identifier,
+    version_uri: Option<&Url>,
+) -> Vec<(PathBuf, Option<Url>)> {
+    let mut paths = Vec::default();
+    if let Some(catalog) = catalog {
+        let module_name = name.to_string();
+        if let Some(version_uri) = version_uri {
+            if let Some(path) = catalog.resolve_version_local_path(&module_name, version_uri) {
+                paths.push((path, catalog.resolve_version_uri(&module_name, version_uri)));
+            }
+        }
+        if let Some(path) = catalog.resolve_local_path(&module_name) {
+            paths.push((path, catalog.resolve_uri(&module_name)));
+        }
+    }
+    paths.extend(
+        module_file_names(name)
+            .into_iter()
+            .map(|file_name| (PathBuf::from(file_name), None)),
+    );
+    paths
 }
 
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "archive")]
+pub mod archive;
+
+#[cfg(feature = "git")]
+pub mod git;
+
+pub mod mirror;
+
 // ------------------------------------------------------------------------------------------------
 // Unit Tests
 // ------------------------------------------------------------------------------------------------
@@ -698,6 +1572,7 @@ mod tests {
                 CatalogEntry::Item(Item {
                     relative_url: String::from("rentals/v1/"),
                     relative_path: PathBuf::from("examples/rentals.sdm"),
+                    versions: Default::default(),
                 }),
             )]
             .into_iter()
@@ -747,4 +1622,48 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn test_resolve_catalog_versions() {
+        let mut catalog: ModuleCatalog = serde_json::from_str(
+            r#"{
+  "base": "https://example.org/rentals/",
+  "entries": {
+    "vehicle": {
+      "item": {
+        "relative_url": "vehicle#",
+        "relative_path": "vehicle-v2.sdm",
+        "versions": [
+          {
+            "version_uri": "https://example.org/rentals/v1/vehicle",
+            "relative_url": "v1/vehicle#",
+            "relative_path": "vehicle-v1.sdm"
+          }
+        ]
+      }
+    }
+  }
+}"#,
+        )
+        .unwrap();
+        catalog.loaded_from = PathBuf::from("/models");
+
+        let name = String::from("vehicle");
+        let v1 = Url::parse("https://example.org/rentals/v1/vehicle").unwrap();
+        let v3 = Url::parse("https://example.org/rentals/v3/vehicle").unwrap();
+
+        assert_eq!(
+            catalog.resolve_local_path(&name),
+            Some(PathBuf::from("/models/vehicle-v2.sdm"))
+        );
+        assert_eq!(
+            catalog.resolve_version_local_path(&name, &v1),
+            Some(PathBuf::from("/models/vehicle-v1.sdm"))
+        );
+        assert_eq!(
+            catalog.resolve_version_uri(&name, &v1),
+            Some(Url::parse("https://example.org/rentals/v1/vehicle#").unwrap())
+        );
+        assert_eq!(catalog.resolve_version_local_path(&name, &v3), None);
+    }
 }
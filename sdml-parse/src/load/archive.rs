@@ -0,0 +1,203 @@
+/*!
+Provides a [`ModuleSourceResolver`] that reads modules from a *model package*, a zip or tar
+archive of module files.
+
+If the archive contains a catalog file, named [`SDML_CATALOG_FILE_NAME`], at its root it is used
+to map module names, and versions, to entries in the archive; otherwise the archive is searched
+using the same file naming rules as the file-system resolver.
+
+# Example
+
+```rust,no_run
+use sdml_parse::load::archive::ArchiveModuleResolver;
+use sdml_parse::load::{FsModuleLoader, ResolverChain};
+
+let chain = ResolverChain::default()
+    .with_resolver(ArchiveModuleResolver::open("rentals-1.0.zip").unwrap());
+let loader = FsModuleLoader::default().with_resolver(chain);
+```
+*/
+
+use crate::load::{
+    module_candidate_paths, ModuleCatalog, ModuleSourceResolver, ResolvedSource,
+    SDML_CATALOG_FILE_NAME,
+};
+use flate2::read::GzDecoder;
+use sdml_core::model::identifiers::Identifier;
+use sdml_errors::Error;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tracing::{debug, trace};
+use url::Url;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The archive formats supported by [`ArchiveModuleResolver`].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+///
+/// A resolver that reads module source from entries in a zip or tar archive.
+///
+#[derive(Clone, Debug)]
+pub struct ArchiveModuleResolver {
+    archive: PathBuf,
+    kind: ArchiveKind,
+    catalog: Option<ModuleCatalog>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl ArchiveKind {
+    ///
+    /// Determine the kind of archive from the extension of `path`, returns `None` if the
+    /// extension is not one of `zip`, `tar`, `tar.gz`, or `tgz`.
+    ///
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_string_lossy().to_lowercase();
+        if file_name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if file_name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else if file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else {
+            None
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl ModuleSourceResolver for ArchiveModuleResolver {
+    fn resolve(
+        &self,
+        name: &Identifier,
+        version_uri: Option<&Url>,
+    ) -> Result<Option<ResolvedSource>, Error> {
+        trace!("ArchiveModuleResolver::resolve({name}, {version_uri:?})");
+        for (path, base_uri) in module_candidate_paths(self.catalog.as_ref(), name, version_uri) {
+            if let Some(source) = self.read_entry(&path)? {
+                debug!(
+                    "Found module {name} in archive {:?}, entry {path:?}",
+                    self.archive
+                );
+                let mut resource = Url::from_file_path(self.archive.canonicalize()?)
+                    .map_err(|_| Error::UrlParseError { source: None })?;
+                resource.set_fragment(Some(&path.to_string_lossy()));
+                let resolved = ResolvedSource::new(resource, self.archive.join(&path), source);
+                return Ok(Some(match base_uri {
+                    Some(base_uri) => resolved.with_base_uri(base_uri),
+                    None => resolved,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    fn catalog(&self) -> Option<&ModuleCatalog> {
+        self.catalog.as_ref()
+    }
+}
+
+impl ArchiveModuleResolver {
+    ///
+    /// Open the archive at `path`, the kind of archive is determined from the file extension.
+    ///
+    pub fn open<P: Into<PathBuf>>(path: P) -> Result<Self, Error> {
+        let archive = path.into();
+        let kind = ArchiveKind::from_path(&archive).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("unsupported archive type: {archive:?}"),
+            )
+        })?;
+        Self::open_as(archive, kind)
+    }
+
+    ///
+    /// Open the archive at `path` as the archive type `kind`.
+    ///
+    pub fn open_as<P: Into<PathBuf>>(path: P, kind: ArchiveKind) -> Result<Self, Error> {
+        let mut resolver = Self {
+            archive: path.into(),
+            kind,
+            catalog: None,
+        };
+        resolver.catalog = resolver
+            .read_entry(Path::new(SDML_CATALOG_FILE_NAME))?
+            .and_then(|source| ModuleCatalog::load_from_str(&source, Path::new("")));
+        Ok(resolver)
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn archive(&self) -> &PathBuf {
+        &self.archive
+    }
+
+    pub fn kind(&self) -> ArchiveKind {
+        self.kind
+    }
+
+    pub fn has_catalog(&self) -> bool {
+        self.catalog.is_some()
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Read the entry at `path` in the archive, returns `None` if there is no such entry.
+    ///
+    pub fn read_entry(&self, path: &Path) -> Result<Option<String>, Error> {
+        let file = File::open(&self.archive)?;
+        match self.kind {
+            ArchiveKind::Zip => {
+                let mut archive = zip::ZipArchive::new(file).map_err(std::io::Error::other)?;
+                let result = archive.by_name(&path.to_string_lossy());
+                match result {
+                    Ok(mut entry) => {
+                        let mut source = String::new();
+                        entry.read_to_string(&mut source)?;
+                        Ok(Some(source))
+                    }
+                    Err(zip::result::ZipError::FileNotFound) => Ok(None),
+                    Err(e) => Err(std::io::Error::other(e).into()),
+                }
+            }
+            ArchiveKind::Tar => read_tar_entry(tar::Archive::new(file), path),
+            ArchiveKind::TarGz => read_tar_entry(tar::Archive::new(GzDecoder::new(file)), path),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn read_tar_entry<R: Read>(
+    mut archive: tar::Archive<R>,
+    path: &Path,
+) -> Result<Option<String>, Error> {
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let entry_path = entry.path()?.into_owned();
+        if entry_path.strip_prefix(".").unwrap_or(&entry_path) == path {
+            let mut source = String::new();
+            entry.read_to_string(&mut source)?;
+            return Ok(Some(source));
+        }
+    }
+    Ok(None)
+}
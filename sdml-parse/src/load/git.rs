@@ -0,0 +1,155 @@
+/*!
+Provides a [`ModuleSourceResolver`] that reads modules from a local git repository as of a given
+reference, such as a branch, tag, or commit, without checking it out.
+
+The repository is only read, no network access is performed. If the tree at the reference
+contains a catalog file, named [`SDML_CATALOG_FILE_NAME`], at its root it is used to map module
+names, and versions, to paths in the tree; otherwise the tree is searched using the same file
+naming rules as the file-system resolver.
+
+# Example
+
+```rust,no_run
+use sdml_parse::load::git::GitModuleResolver;
+use sdml_parse::load::{FsModuleLoader, ResolverChain};
+
+let chain = ResolverChain::default()
+    .with_resolver(GitModuleResolver::open("../models", "v1.0").unwrap());
+let loader = FsModuleLoader::default().with_resolver(chain);
+```
+*/
+
+use crate::load::{
+    module_candidate_paths, ModuleCatalog, ModuleSourceResolver, ResolvedSource,
+    SDML_CATALOG_FILE_NAME,
+};
+use git2::{ErrorCode, Repository};
+use sdml_core::model::identifiers::Identifier;
+use sdml_errors::Error;
+use std::fmt::Debug;
+use std::path::Path;
+use tracing::{debug, trace};
+use url::Url;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A resolver that reads module source from the tree of a local git repository at a given
+/// reference.
+///
+pub struct GitModuleResolver {
+    repository: Repository,
+    reference: String,
+    catalog: Option<ModuleCatalog>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Debug for GitModuleResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GitModuleResolver")
+            .field("repository", &self.repository.path())
+            .field("reference", &self.reference)
+            .field("catalog", &self.catalog)
+            .finish()
+    }
+}
+
+impl ModuleSourceResolver for GitModuleResolver {
+    fn resolve(
+        &self,
+        name: &Identifier,
+        version_uri: Option<&Url>,
+    ) -> Result<Option<ResolvedSource>, Error> {
+        trace!("GitModuleResolver::resolve({name}, {version_uri:?})");
+        for (path, base_uri) in module_candidate_paths(self.catalog.as_ref(), name, version_uri) {
+            if let Some(source) = self.read_blob(&path)? {
+                debug!("Found module {name} at {}:{path:?}", self.reference);
+                let root = self.root();
+                let location = format!("{}:{}", self.reference, path.to_string_lossy());
+                let mut resource = Url::from_directory_path(root.canonicalize()?)
+                    .map_err(|_| Error::UrlParseError { source: None })?;
+                resource.set_fragment(Some(&location));
+                // The location names the blob at the reference, as `{reference}:{path}`, so that
+                // file properties are never taken from the working tree.
+                let resolved = ResolvedSource::new(resource, root.join(location), source);
+                return Ok(Some(match base_uri {
+                    Some(base_uri) => resolved.with_base_uri(base_uri),
+                    None => resolved,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    fn catalog(&self) -> Option<&ModuleCatalog> {
+        self.catalog.as_ref()
+    }
+}
+
+impl GitModuleResolver {
+    ///
+    /// Open the repository containing `path` and resolve modules from the tree at `reference`,
+    /// which may be any revision expression understood by git, for example `main`, `v1.0`, or
+    /// `HEAD~2`.
+    ///
+    pub fn open<P: AsRef<Path>, S: Into<String>>(path: P, reference: S) -> Result<Self, Error> {
+        let repository = Repository::discover(path).map_err(std::io::Error::other)?;
+        let mut resolver = Self {
+            repository,
+            reference: reference.into(),
+            catalog: None,
+        };
+        // Ensure the reference exists before any module is requested.
+        let _ = resolver.tree()?;
+        resolver.catalog = resolver
+            .read_blob(Path::new(SDML_CATALOG_FILE_NAME))?
+            .and_then(|source| ModuleCatalog::load_from_str(&source, Path::new("")));
+        Ok(resolver)
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn reference(&self) -> &String {
+        &self.reference
+    }
+
+    pub fn has_catalog(&self) -> bool {
+        self.catalog.is_some()
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    fn root(&self) -> &Path {
+        self.repository
+            .workdir()
+            .unwrap_or_else(|| self.repository.path())
+    }
+
+    fn tree(&self) -> Result<git2::Tree<'_>, Error> {
+        self.repository
+            .revparse_single(&self.reference)
+            .and_then(|object| object.peel_to_tree())
+            .map_err(|e| std::io::Error::other(e).into())
+    }
+
+    fn read_blob(&self, path: &Path) -> Result<Option<String>, Error> {
+        let tree = self.tree()?;
+        let entry = match tree.get_path(path) {
+            Ok(entry) => entry,
+            Err(e) if e.code() == ErrorCode::NotFound => return Ok(None),
+            Err(e) => return Err(std::io::Error::other(e).into()),
+        };
+        let object = entry
+            .to_object(&self.repository)
+            .map_err(std::io::Error::other)?;
+        match object.as_blob() {
+            Some(blob) => Ok(Some(String::from_utf8(blob.content().to_vec())?)),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+/*!
+Provides a [`ModuleSourceResolver`] that reads modules from a local mirror of the URLs described
+by a catalog.
+
+A catalog maps each module to a URL, the catalog's `base` joined with an item's `relative_url`.
+A mirror is a directory that holds a copy of these resources laid out as `host/path`, so that for
+a catalog with the base `https://example.org/rentals/` the module with the relative URL
+`vehicle#` is read from `{mirror}/example.org/rentals/vehicle.sdm`. Where the URL path ends in `/`
+the module's file name is appended to it.
+
+# Example
+
+```rust,no_run
+use sdml_parse::load::mirror::MirrorModuleResolver;
+use sdml_parse::load::{FsModuleLoader, ModuleCatalog, ResolverChain};
+use std::path::Path;
+
+let catalog = ModuleCatalog::load_from(Path::new("."), true).unwrap();
+let chain = ResolverChain::default()
+    .with_resolver(MirrorModuleResolver::new("/var/cache/sdml", catalog));
+let loader = FsModuleLoader::default().with_resolver(chain);
+```
+*/
+
+use crate::load::{
+    ModuleCatalog, ModuleSourceResolver, ResolvedSource, SDML_FILE_EXTENSION,
+    SDML_FILE_EXTENSION_LONG,
+};
+use sdml_core::model::identifiers::Identifier;
+use sdml_errors::Error;
+use std::path::PathBuf;
+use tracing::{debug, trace};
+use url::Url;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A resolver that reads module source from a directory mirroring the URLs in a catalog.
+///
+#[derive(Clone, Debug)]
+pub struct MirrorModuleResolver {
+    root: PathBuf,
+    catalog: ModuleCatalog,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl ModuleSourceResolver for MirrorModuleResolver {
+    fn resolve(
+        &self,
+        name: &Identifier,
+        version_uri: Option<&Url>,
+    ) -> Result<Option<ResolvedSource>, Error> {
+        trace!("MirrorModuleResolver::resolve({name}, {version_uri:?})");
+        let module_name = name.to_string();
+        let url = version_uri
+            .and_then(|version_uri| self.catalog.resolve_version_uri(&module_name, version_uri))
+            .or_else(|| self.catalog.resolve_uri(&module_name));
+        if let Some(url) = url {
+            for path in self.url_to_paths(&url, &module_name) {
+                if path.is_file() {
+                    debug!("Found module {name} for {url} in mirror, path: {path:?}");
+                    let resource = Url::from_file_path(path.canonicalize()?)
+                        .map_err(|_| Error::UrlParseError { source: None })?;
+                    let source = std::fs::read_to_string(&path)?;
+                    return Ok(Some(
+                        ResolvedSource::new(resource, path, source).with_base_uri(url),
+                    ));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn catalog(&self) -> Option<&ModuleCatalog> {
+        Some(&self.catalog)
+    }
+}
+
+impl MirrorModuleResolver {
+    ///
+    /// Construct a new resolver for the mirror directory `root` using `catalog` to map module
+    /// names to URLs.
+    ///
+    pub fn new<P: Into<PathBuf>>(root: P, catalog: ModuleCatalog) -> Self {
+        Self {
+            root: root.into(),
+            catalog,
+        }
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn root(&self) -> &PathBuf {
+        &self.root
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Return the mirror paths, in priority order, that may hold the resource at `url`.
+    ///
+    pub fn url_to_paths(&self, url: &Url, module_name: &str) -> Vec<PathBuf> {
+        let mut base = self.root.clone();
+        if let Some(host) = url.host_str() {
+            match url.port() {
+                Some(port) => base.push(format!("{host}:{port}")),
+                None => base.push(host),
+            }
+        }
+        let path = url.path();
+        for segment in path.split('/').filter(|s| !s.is_empty()) {
+            base.push(segment);
+        }
+        if path.ends_with('/') || path.is_empty() {
+            vec![
+                base.join(format!("{module_name}.{SDML_FILE_EXTENSION}")),
+                base.join(format!("{module_name}.{SDML_FILE_EXTENSION_LONG}")),
+            ]
+        } else {
+            let file = base.to_string_lossy();
+            vec![
+                PathBuf::from(format!("{file}.{SDML_FILE_EXTENSION}")),
+                PathBuf::from(format!("{file}.{SDML_FILE_EXTENSION_LONG}")),
+                base.clone(),
+            ]
+        }
+    }
+}
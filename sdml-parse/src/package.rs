@@ -0,0 +1,879 @@
+/*!
+This module provides *model packages*, a set of modules shared as a unit and described by a
+package manifest.
+
+A manifest, named [`SDML_PACKAGE_FILE_NAME`], lists the package's modules, its version, the
+modules it exports to other packages, and its dependencies on other packages with a range of
+acceptable versions. All dependencies are local, either a directory containing a manifest or a
+package archive produced by [`PackageManifest::build`].
+
+```json
+{
+  "name": "rentals",
+  "version": "1.2.0",
+  "base": "https://example.org/rentals/",
+  "modules": {
+    "rentals": { "path": "src/rentals.sdm" },
+    "vehicle": { "path": "src/vehicle.sdm", "relative_url": "vehicle#" }
+  },
+  "exports": ["rentals"],
+  "dependencies": {
+    "common": { "version": "^1.0", "path": "../common" }
+  }
+}
+```
+
+The operations on a package are:
+
+1. **build**, write a zip archive containing the manifest, the module files, and a generated
+   catalog so that the archive can be read by [`ArchiveModuleResolver`],
+2. **lock**, resolve all dependencies, transitively, and record their exact versions and
+   checksums in a lockfile, named [`SDML_PACKAGE_LOCK_FILE_NAME`],
+3. **vendor**, copy the modules of all dependencies into a local directory and generate a
+   catalog so that the standard resolver will find them.
+
+# Example
+
+```rust,no_run
+use sdml_parse::package::PackageManifest;
+use std::path::Path;
+
+let manifest = PackageManifest::load_from(Path::new(".")).unwrap();
+let archive = manifest.build(None).unwrap();
+println!("package written to {archive:?}");
+```
+
+*/
+
+use crate::load::archive::{ArchiveKind, ArchiveModuleResolver};
+use crate::load::{FsModuleLoader, Group, Item, ModuleCatalog, SDML_CATALOG_FILE_NAME};
+use sdml_core::model::HasName;
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_errors::Error;
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::Display;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, trace};
+use url::Url;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The name used for package manifest files.
+pub const SDML_PACKAGE_FILE_NAME: &str = "sdml-package.json";
+
+/// The name used for package lock files.
+pub const SDML_PACKAGE_LOCK_FILE_NAME: &str = "sdml-package.lock";
+
+/// The default directory, relative to the package, into which dependencies are vendored.
+pub const SDML_VENDOR_DIRECTORY_NAME: &str = "vendor";
+
+/// The current version of the lock file format.
+pub const SDML_PACKAGE_LOCK_FORMAT_VERSION: u32 = 1;
+
+///
+/// This type represents the content of a package manifest file.
+///
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PackageManifest {
+    name: String,
+    version: Version,
+    base: Url,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    modules: BTreeMap<String, PackageModule>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    exports: BTreeSet<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    dependencies: BTreeMap<String, PackageDependency>,
+    #[serde(skip)]
+    loaded_from: PathBuf,
+}
+
+///
+/// A module in a package, the path is relative to the package directory. If no `relative_url` is
+/// provided the module's URL, relative to the package base, is its name followed by `#`.
+///
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PackageModule {
+    path: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    relative_url: Option<String>,
+}
+
+///
+/// A dependency on another package; the path, relative to the package directory, is either a
+/// package directory or a package archive.
+///
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PackageDependency {
+    version: VersionReq,
+    path: PathBuf,
+}
+
+///
+/// A package found on the local file system, either as a directory or an archive.
+///
+#[derive(Clone, Debug)]
+pub struct Package {
+    manifest: PackageManifest,
+    source: PackageSource,
+}
+
+///
+/// Where the content of a package is read from.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PackageSource {
+    Directory(PathBuf),
+    Archive(PathBuf),
+}
+
+///
+/// This type represents the content of a package lock file, the exact version of every package
+/// a package depends on, directly or indirectly.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PackageLock {
+    format_version: u32,
+    packages: Vec<LockedPackage>,
+}
+
+///
+/// A single locked package, the checksum is calculated over the package's name, version, and the
+/// content of its modules.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct LockedPackage {
+    name: String,
+    version: Version,
+    source: PathBuf,
+    checksum: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    dependencies: BTreeMap<String, Version>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl PackageManifest {
+    ///
+    /// Construct a new, empty, manifest for a package located in the directory `loaded_from`.
+    ///
+    pub fn new<S: Into<String>>(name: S, version: Version, base: Url, loaded_from: &Path) -> Self {
+        Self {
+            name: name.into(),
+            version,
+            base,
+            description: None,
+            modules: Default::default(),
+            exports: Default::default(),
+            dependencies: Default::default(),
+            loaded_from: loaded_from.to_path_buf(),
+        }
+    }
+
+    ///
+    /// Load a manifest from `path`, which may be the manifest file itself or the package
+    /// directory containing it.
+    ///
+    pub fn load_from(path: &Path) -> Result<Self, Error> {
+        trace!("PackageManifest::load_from({path:?})");
+        let file = if path.is_dir() {
+            path.join(SDML_PACKAGE_FILE_NAME)
+        } else {
+            path.to_path_buf()
+        };
+        let source = std::fs::read_to_string(&file)?;
+        let loaded_from = file
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+        Self::load_from_str(&source, &loaded_from)
+    }
+
+    ///
+    /// Load a manifest from the JSON in `source`, module and dependency paths are relative to
+    /// `loaded_from`.
+    ///
+    pub fn load_from_str(source: &str, loaded_from: &Path) -> Result<Self, Error> {
+        let mut manifest: Self =
+            serde_json::from_str(source).map_err(|e| package_error(loaded_from.display(), e))?;
+        manifest.loaded_from = loaded_from.to_path_buf();
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    ///
+    /// Write this manifest to `path` as JSON.
+    ///
+    pub fn save_to(&self, path: &Path) -> Result<(), Error> {
+        write_json(path, self, &self.name)
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    pub fn base(&self) -> &Url {
+        &self.base
+    }
+
+    pub fn loaded_from(&self) -> &PathBuf {
+        &self.loaded_from
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn description(&self) -> Option<&String> {
+        self.description.as_ref()
+    }
+
+    pub fn set_description<S: Into<String>>(&mut self, description: S) {
+        self.description = Some(description.into());
+    }
+
+    pub fn unset_description(&mut self) {
+        self.description = None;
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn has_modules(&self) -> bool {
+        !self.modules.is_empty()
+    }
+
+    pub fn modules(&self) -> impl Iterator<Item = (&String, &PackageModule)> {
+        self.modules.iter()
+    }
+
+    pub fn get_module(&self, name: &str) -> Option<&PackageModule> {
+        self.modules.get(name)
+    }
+
+    pub fn add_to_modules<S: Into<String>>(&mut self, name: S, module: PackageModule) {
+        self.modules.insert(name.into(), module);
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Returns `true` if the module named `name` may be imported by other packages. If the
+    /// manifest lists no exports then all modules are exported.
+    ///
+    pub fn is_exported(&self, name: &str) -> bool {
+        self.modules.contains_key(name) && (self.exports.is_empty() || self.exports.contains(name))
+    }
+
+    pub fn exports(&self) -> impl Iterator<Item = &String> {
+        self.exports.iter()
+    }
+
+    pub fn add_to_exports<S: Into<String>>(&mut self, name: S) {
+        self.exports.insert(name.into());
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn has_dependencies(&self) -> bool {
+        !self.dependencies.is_empty()
+    }
+
+    pub fn dependencies(&self) -> impl Iterator<Item = (&String, &PackageDependency)> {
+        self.dependencies.iter()
+    }
+
+    pub fn add_to_dependencies<S: Into<String>>(&mut self, name: S, dependency: PackageDependency) {
+        self.dependencies.insert(name.into(), dependency);
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Check that every export names a module in this package.
+    ///
+    pub fn validate(&self) -> Result<(), Error> {
+        if let Some(export) = self.exports.iter().find(|e| !self.modules.contains_key(*e)) {
+            return Err(package_error(
+                &self.name,
+                format!("exported module `{export}` is not a module of the package"),
+            ));
+        }
+        Ok(())
+    }
+
+    ///
+    /// Generate a catalog for the modules in this package, with paths relative to the package.
+    ///
+    pub fn catalog(&self) -> ModuleCatalog {
+        let mut catalog = ModuleCatalog::new(self.base.clone(), &self.loaded_from);
+        for (name, module) in &self.modules {
+            catalog.insert_entry(name.clone(), module.catalog_item(name).into());
+        }
+        catalog
+    }
+
+    ///
+    /// The file name of the archive written by [`Self::build`], `{name}-{version}.zip`.
+    ///
+    pub fn archive_file_name(&self) -> String {
+        format!("{}-{}.zip", self.name, self.version)
+    }
+
+    ///
+    /// Build a package archive, if `output` is `None` the archive is written into the package
+    /// directory using the name from [`Self::archive_file_name`]. Returns the path of the archive
+    /// written.
+    ///
+    pub fn build(&self, output: Option<&Path>) -> Result<PathBuf, Error> {
+        let output = output
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| self.loaded_from.join(self.archive_file_name()));
+        info!("Building package {} into {output:?}", self.name);
+
+        let mut writer = zip::ZipWriter::new(File::create(&output)?);
+        let options = zip::write::SimpleFileOptions::default();
+
+        let mut add_file = |path: &str, content: &[u8]| -> Result<(), Error> {
+            writer
+                .start_file(path, options)
+                .map_err(std::io::Error::other)?;
+            writer.write_all(content)?;
+            Ok(())
+        };
+
+        add_file(
+            SDML_PACKAGE_FILE_NAME,
+            to_json(self, &self.name)?.as_bytes(),
+        )?;
+        let mut catalog = self.catalog();
+        catalog.set_loaded_from(Path::new(""));
+        add_file(
+            SDML_CATALOG_FILE_NAME,
+            to_json(&catalog, &self.name)?.as_bytes(),
+        )?;
+        for module in self.modules.values() {
+            let source = std::fs::read(self.loaded_from.join(&module.path))?;
+            add_file(&module.archive_path(), &source)?;
+        }
+
+        writer.finish().map_err(std::io::Error::other)?;
+        Ok(output)
+    }
+
+    ///
+    /// Find all the packages this package depends on, directly or indirectly. Each dependency's
+    /// version must satisfy the range required by every package that depends on it.
+    ///
+    pub fn resolve_dependencies(&self) -> Result<Vec<Package>, Error> {
+        let mut resolved: BTreeMap<String, Package> = Default::default();
+        let mut queue: VecDeque<(String, PackageDependency, PathBuf, String)> = self
+            .dependencies
+            .iter()
+            .map(|(name, dep)| {
+                (
+                    name.clone(),
+                    dep.clone(),
+                    self.loaded_from.clone(),
+                    self.name.clone(),
+                )
+            })
+            .collect();
+
+        while let Some((name, dependency, relative_to, required_by)) = queue.pop_front() {
+            if let Some(existing) = resolved.get(&name) {
+                if !dependency.version.matches(existing.version()) {
+                    return Err(package_error(
+                        &required_by,
+                        format!(
+                            "requires {name} {}, but version {} is already required",
+                            dependency.version,
+                            existing.version()
+                        ),
+                    ));
+                }
+                continue;
+            }
+            let package = Package::open(&relative_to.join(&dependency.path))?;
+            if package.name() != &name {
+                return Err(package_error(
+                    &required_by,
+                    format!(
+                        "dependency {name} at {:?} contains the package {}",
+                        dependency.path,
+                        package.name()
+                    ),
+                ));
+            }
+            if !dependency.version.matches(package.version()) {
+                return Err(package_error(
+                    &required_by,
+                    format!(
+                        "requires {name} {}, but found version {}",
+                        dependency.version,
+                        package.version()
+                    ),
+                ));
+            }
+            debug!("Resolved {name} {} for {required_by}", package.version());
+            let package_directory = package.directory();
+            queue.extend(package.manifest.dependencies.iter().map(|(dep_name, dep)| {
+                (
+                    dep_name.clone(),
+                    dep.clone(),
+                    package_directory.clone(),
+                    name.clone(),
+                )
+            }));
+            resolved.insert(name, package);
+        }
+
+        Ok(resolved.into_values().collect())
+    }
+
+    ///
+    /// Resolve all dependencies and produce a lock describing them.
+    ///
+    pub fn lock(&self) -> Result<PackageLock, Error> {
+        let packages = self.resolve_dependencies()?;
+        PackageLock::from_packages(&packages)
+    }
+
+    ///
+    /// Copy the modules of every package this package depends on into `directory`, relative to
+    /// the package directory, with each package in a sub-directory of its own. Returns a catalog
+    /// for this package's modules, and the vendored modules, suitable to write into the package
+    /// directory.
+    ///
+    /// If `lock` is provided the resolved dependencies must match it exactly. Diagnostics from
+    /// loading this package's modules are reported by `loader`.
+    ///
+    pub fn vendor(
+        &self,
+        directory: &Path,
+        lock: Option<&PackageLock>,
+        loader: &mut FsModuleLoader,
+    ) -> Result<ModuleCatalog, Error> {
+        let packages = self.resolve_dependencies()?;
+        if let Some(lock) = lock {
+            lock.verify(&packages)?;
+        }
+        self.check_imports(&packages, loader)?;
+
+        let mut catalog = self.catalog();
+        for package in &packages {
+            let package_directory = directory.join(package.name());
+            let target = self.loaded_from.join(&package_directory);
+            info!(
+                "Vendoring {} {} into {target:?}",
+                package.name(),
+                package.version()
+            );
+            let mut group = Group::new()
+                .with_relative_path(package_directory)
+                .with_relative_url(package.manifest.base.to_string());
+            for (name, module) in package.manifest.modules() {
+                let module_target = target.join(&module.path);
+                if let Some(parent) = module_target.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&module_target, package.read_module(module)?)?;
+                group.insert_entry(name.clone(), module.catalog_item(name));
+            }
+            package
+                .manifest
+                .save_to(&target.join(SDML_PACKAGE_FILE_NAME))?;
+            catalog.insert_entry(package.name().clone(), group.into());
+        }
+        Ok(catalog)
+    }
+
+    ///
+    /// Check that modules in this package only import modules that are exported by the packages
+    /// it depends on. Diagnostics from loading this package's modules are reported by `loader`.
+    ///
+    pub fn check_imports(
+        &self,
+        packages: &[Package],
+        loader: &mut FsModuleLoader,
+    ) -> Result<(), Error> {
+        let mut cache = InMemoryModuleCache::default();
+        for (name, module) in &self.modules {
+            let module_name =
+                loader.load_from_file(self.loaded_from.join(&module.path), &mut cache, false)?;
+            let loaded = cache.get(&module_name).unwrap();
+            for imported in loaded.imported_modules() {
+                let imported = imported.to_string();
+                if let Some(package) = packages
+                    .iter()
+                    .find(|package| package.manifest.modules.contains_key(&imported))
+                {
+                    if !package.manifest.is_exported(&imported) {
+                        return Err(package_error(
+                            &self.name,
+                            format!(
+                                "module `{name}` imports `{imported}` which is not exported by {}",
+                                package.name()
+                            ),
+                        ));
+                    }
+                }
+            }
+            debug!("Checked imports of module {}", loaded.name());
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl PackageModule {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            relative_url: None,
+        }
+    }
+
+    pub fn with_relative_url<S: Into<String>>(self, relative_url: S) -> Self {
+        Self {
+            relative_url: Some(relative_url.into()),
+            ..self
+        }
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    pub fn relative_url(&self) -> Option<&String> {
+        self.relative_url.as_ref()
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    fn catalog_item(&self, name: &str) -> Item {
+        Item::new(
+            self.relative_url
+                .clone()
+                .unwrap_or_else(|| format!("{name}#")),
+            PathBuf::from(self.archive_path()),
+        )
+    }
+
+    // Paths in archives, and catalogs, always use `/` as a separator.
+    fn archive_path(&self) -> String {
+        self.path
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl PackageDependency {
+    pub fn new<P: Into<PathBuf>>(version: VersionReq, path: P) -> Self {
+        Self {
+            version,
+            path: path.into(),
+        }
+    }
+
+    pub fn version(&self) -> &VersionReq {
+        &self.version
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for PackageSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Directory(path) | Self::Archive(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+impl Package {
+    ///
+    /// Open the package at `path`, which is either a package archive or a directory containing
+    /// a package manifest.
+    ///
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        trace!("Package::open({path:?})");
+        if ArchiveKind::from_path(path).is_some() && path.is_file() {
+            let archive = ArchiveModuleResolver::open(path)?;
+            let source = archive
+                .read_entry(Path::new(SDML_PACKAGE_FILE_NAME))?
+                .ok_or_else(|| {
+                    package_error(
+                        path.display(),
+                        "archive does not contain a package manifest",
+                    )
+                })?;
+            let loaded_from = path.parent().unwrap_or(Path::new("."));
+            Ok(Self {
+                manifest: PackageManifest::load_from_str(&source, loaded_from)?,
+                source: PackageSource::Archive(path.to_path_buf()),
+            })
+        } else {
+            Ok(Self {
+                manifest: PackageManifest::load_from(path)?,
+                source: PackageSource::Directory(path.to_path_buf()),
+            })
+        }
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn manifest(&self) -> &PackageManifest {
+        &self.manifest
+    }
+
+    pub fn name(&self) -> &String {
+        self.manifest.name()
+    }
+
+    pub fn version(&self) -> &Version {
+        self.manifest.version()
+    }
+
+    pub fn source(&self) -> &PackageSource {
+        &self.source
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Read the source of `module`, which must be one of this package's modules.
+    ///
+    pub fn read_module(&self, module: &PackageModule) -> Result<String, Error> {
+        match &self.source {
+            PackageSource::Directory(directory) => {
+                Ok(std::fs::read_to_string(directory.join(&module.path))?)
+            }
+            PackageSource::Archive(archive) => ArchiveModuleResolver::open(archive)?
+                .read_entry(Path::new(&module.archive_path()))?
+                .ok_or_else(|| {
+                    package_error(
+                        self.name(),
+                        format!("archive does not contain the module file {:?}", module.path),
+                    )
+                }),
+        }
+    }
+
+    ///
+    /// Calculate a SHA-256 checksum over the package's name, version and module content. Each
+    /// field is prefixed with its length so that moving bytes between adjacent fields changes the
+    /// checksum.
+    ///
+    pub fn checksum(&self) -> Result<String, Error> {
+        let mut hasher = Sha256::new();
+        update_field(&mut hasher, self.name());
+        update_field(&mut hasher, &self.version().to_string());
+        for (name, module) in self.manifest.modules() {
+            update_field(&mut hasher, name);
+            update_field(&mut hasher, &self.read_module(module)?);
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    // The directory against which this package's own dependency paths are resolved.
+    fn directory(&self) -> PathBuf {
+        match &self.source {
+            PackageSource::Directory(directory) => directory.clone(),
+            PackageSource::Archive(_) => self.manifest.loaded_from.clone(),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl PackageLock {
+    ///
+    /// Construct a lock from a set of resolved packages.
+    ///
+    pub fn from_packages(packages: &[Package]) -> Result<Self, Error> {
+        let mut locked = packages
+            .iter()
+            .map(|package| {
+                Ok(LockedPackage {
+                    name: package.name().clone(),
+                    version: package.version().clone(),
+                    source: match &package.source {
+                        PackageSource::Directory(path) | PackageSource::Archive(path) => {
+                            path.clone()
+                        }
+                    },
+                    checksum: package.checksum()?,
+                    dependencies: package
+                        .manifest
+                        .dependencies()
+                        .filter_map(|(name, _)| {
+                            packages
+                                .iter()
+                                .find(|p| p.name() == name)
+                                .map(|p| (name.clone(), p.version().clone()))
+                        })
+                        .collect(),
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        locked.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+        Ok(Self {
+            format_version: SDML_PACKAGE_LOCK_FORMAT_VERSION,
+            packages: locked,
+        })
+    }
+
+    ///
+    /// Load a lock file from `path`.
+    ///
+    pub fn load_from(path: &Path) -> Result<Self, Error> {
+        let source = std::fs::read_to_string(path)?;
+        serde_json::from_str(&source).map_err(|e| package_error(path.display(), e))
+    }
+
+    ///
+    /// Write this lock to `path` as JSON.
+    ///
+    pub fn save_to(&self, path: &Path) -> Result<(), Error> {
+        write_json(path, self, path.display())
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn format_version(&self) -> u32 {
+        self.format_version
+    }
+
+    pub fn packages(&self) -> impl Iterator<Item = &LockedPackage> {
+        self.packages.iter()
+    }
+
+    pub fn get_package(&self, name: &str) -> Option<&LockedPackage> {
+        self.packages.iter().find(|p| p.name == name)
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Ensure that `packages` are exactly those in this lock, with the same versions and
+    /// checksums.
+    ///
+    pub fn verify(&self, packages: &[Package]) -> Result<(), Error> {
+        let current = Self::from_packages(packages)?;
+        for locked in &self.packages {
+            match current.get_package(&locked.name) {
+                None => {
+                    return Err(package_error(
+                        &locked.name,
+                        "locked package is no longer a dependency",
+                    ))
+                }
+                Some(found) if found.version != locked.version => {
+                    return Err(package_error(
+                        &locked.name,
+                        format!(
+                            "locked version {} does not match the resolved version {}",
+                            locked.version, found.version
+                        ),
+                    ))
+                }
+                Some(found) if found.checksum != locked.checksum => {
+                    return Err(package_error(
+                        &locked.name,
+                        "package content has changed since it was locked",
+                    ))
+                }
+                _ => {}
+            }
+        }
+        if let Some(unlocked) = current
+            .packages
+            .iter()
+            .find(|p| self.get_package(&p.name).is_none())
+        {
+            return Err(package_error(
+                &unlocked.name,
+                "dependency is not in the lock file",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl LockedPackage {
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    pub fn source(&self) -> &PathBuf {
+        &self.source
+    }
+
+    pub fn checksum(&self) -> &String {
+        &self.checksum
+    }
+
+    pub fn dependencies(&self) -> impl Iterator<Item = (&String, &Version)> {
+        self.dependencies.iter()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn package_error<S1: Display, S2: Display>(name: S1, message: S2) -> Error {
+    Error::PackageError {
+        name: name.to_string(),
+        message: message.to_string(),
+    }
+}
+
+fn to_json<T: Serialize, S: Display>(value: &T, name: S) -> Result<String, Error> {
+    serde_json::to_string_pretty(value).map_err(|e| package_error(name, e))
+}
+
+fn write_json<T: Serialize, S: Display>(path: &Path, value: &T, name: S) -> Result<(), Error> {
+    let mut file = File::create(path)?;
+    file.write_all(to_json(value, name)?.as_bytes())?;
+    Ok(())
+}
+
+fn update_field(hasher: &mut Sha256, field: &str) {
+    hasher.update((field.len() as u64).to_le_bytes());
+    hasher.update(field.as_bytes());
+}
@@ -3,7 +3,6 @@ use crate::parse::constraints::formal::sentences::parse_constraint_sentence;
 use crate::parse::constraints::formal::values::parse_predicate_value;
 use crate::parse::identifiers::parse_identifier;
 use crate::parse::ParseContext;
-use sdml_core::load::ModuleLoader as ModuleLoaderTrait;
 use sdml_core::model::constraints::EnvironmentDef;
 use sdml_core::model::identifiers::Identifier;
 use sdml_core::syntax::{
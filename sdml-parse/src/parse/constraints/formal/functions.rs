@@ -2,7 +2,6 @@ use crate::parse::constraints::formal::sentences::parse_constraint_sentence;
 use crate::parse::identifiers::{parse_identifier, parse_identifier_reference};
 use crate::parse::members::parse_mapping_type;
 use crate::parse::ParseContext;
-use sdml_core::load::ModuleLoader as ModuleLoaderTrait;
 use sdml_core::model::constraints::{
     FunctionCardinality, FunctionDef, FunctionParameter, FunctionSignature, FunctionType,
     FunctionTypeReference, FunctionTypeReferenceInner,
@@ -1,7 +1,6 @@
 use super::terms::parse_term;
 use crate::parse::identifiers::parse_identifier;
 use crate::parse::ParseContext;
-use sdml_core::load::ModuleLoader as ModuleLoaderTrait;
 use sdml_core::model::constraints::{
     AtomicSentence, BinaryBooleanSentence, BooleanSentence, ConstraintSentence, Equation,
     InequalityRelation, Inequation, QuantifiedSentence, QuantifiedVariable,
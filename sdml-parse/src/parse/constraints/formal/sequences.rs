@@ -1,7 +1,6 @@
 use crate::parse::constraints::formal::parse_quantified_sentence;
 use crate::parse::identifiers::parse_identifier;
 use crate::parse::ParseContext;
-use sdml_core::load::ModuleLoader as ModuleLoaderTrait;
 use sdml_core::model::constraints::{MappingVariable, NamedVariables, SequenceBuilder, Variables};
 use sdml_core::model::identifiers::Identifier;
 use sdml_core::syntax::{
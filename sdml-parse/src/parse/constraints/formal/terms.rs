@@ -1,7 +1,6 @@
 use crate::parse::constraints::{parse_predicate_value, parse_sequence_builder};
 use crate::parse::identifiers::{parse_identifier, parse_identifier_reference};
 use crate::parse::ParseContext;
-use sdml_core::load::ModuleLoader as ModuleLoaderTrait;
 use sdml_core::model::constraints::{FunctionComposition, FunctionalTerm, Subject, Term};
 use sdml_core::model::identifiers::Identifier;
 use sdml_core::syntax::{
@@ -1,7 +1,6 @@
 use crate::parse::members::parse_member_def;
 use crate::parse::ParseContext;
 use sdml_core::error::Error;
-use sdml_core::load::ModuleLoader;
 use sdml_core::model::definitions::PropertyDef;
 use sdml_core::model::HasSourceSpan;
 use sdml_core::syntax::{FIELD_NAME_MEMBER, NODE_KIND_MEMBER_DEF};
@@ -3,8 +3,8 @@
 // ------------------------------------------------------------------------------------------------
 
 macro_rules! emit_diagnostic {
-    ($loader: expr, $diagnostic: expr) => {
-        $loader.report($diagnostic)?;
+    ($context: expr, $diagnostic: expr) => {
+        $context.report($diagnostic)?;
     };
 }
 
@@ -20,7 +20,7 @@ macro_rules! unexpected_node {
             expected,
             $node.kind()
         );
-        emit_diagnostic!($context.loader, &diagnostic);
+        emit_diagnostic!($context, &diagnostic);
 
         return Err(diagnostic.into())
     };
@@ -32,7 +32,7 @@ macro_rules! unexpected_node {
             $expected,
             $node.kind()
         );
-        emit_diagnostic!($context.loader, &diagnostic);
+        emit_diagnostic!($context, &diagnostic);
 
         return Err(diagnostic.into())
     };
@@ -47,7 +47,7 @@ macro_rules! invalid_value_for_node_type {
             $node.kind(),
             $error,
         );
-        emit_diagnostic!($context.loader, &diagnostic);
+        emit_diagnostic!($context, &diagnostic);
 
         return Err(diagnostic.into())
     };
@@ -62,7 +62,7 @@ macro_rules! missing_node {
             $expecting,
             Some($field_name),
         );
-        emit_diagnostic!($context.loader, &diagnostic);
+        emit_diagnostic!($context, &diagnostic);
 
         return Err(diagnostic.into())
     };
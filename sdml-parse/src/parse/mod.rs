@@ -9,23 +9,23 @@ YYYYY
 
 */
 
-use crate::load::FsModuleLoader;
 use crate::parse::modules::parse_module;
-use sdml_core::load::ModuleLoader as ModuleLoaderTrait;
 use sdml_core::model::identifiers::Identifier;
 use sdml_core::model::modules::{Import, Module};
 use sdml_core::model::HasSourceSpan;
 use sdml_core::syntax::NODE_KIND_MODULE;
 use sdml_errors::diagnostics::functions::{
-    duplicate_definition, duplicate_definition_import, duplicate_member, duplicate_module_import,
-    duplicate_variant, found_error_node,
+    duplicate_definition, duplicate_definition_import_with_removal, duplicate_member,
+    duplicate_module_import_with_removal, duplicate_variant, found_error_node,
 };
-use sdml_errors::Error;
-use sdml_errors::{FileId, Source};
+use sdml_errors::diagnostics::suggestions::SuggestedDiagnostic;
+use sdml_errors::{Diagnostic, Error, Reporter};
+use sdml_errors::{FileId, Source, SourceFiles, Span};
+use std::cell::RefCell;
 use std::collections::HashSet;
 use tracing::trace;
-use tree_sitter::Node;
 use tree_sitter::Parser;
+use tree_sitter::{Node, Tree};
 use tree_sitter_sdml::language;
 
 // ------------------------------------------------------------------------------------------------
@@ -43,11 +43,16 @@ mod macros;
 // Public Functions
 // ------------------------------------------------------------------------------------------------
 
-// This should only be called by `ModuleLoader`
-pub(crate) fn parse_str(file_id: FileId, loader: &FsModuleLoader) -> Result<Module, Error> {
+// This should only be called by `ModuleLoader`. Diagnostics found while parsing are returned,
+// rather than reported, so that the loader may apply the module's suppressions to them; if
+// parsing fails they are reported to `reporter` before the error is returned.
+pub(crate) fn parse_str(
+    file_id: FileId,
+    files: &SourceFiles,
+    reporter: &dyn Reporter,
+) -> Result<(Module, Vec<SuggestedDiagnostic>), Error> {
     trace!("parse_str({file_id}, ...)");
-    let file_cache = loader.files();
-    let source = file_cache.get(file_id).unwrap().source();
+    let source = files.get(file_id).unwrap().source();
     let mut parser = Parser::new();
     parser
         .set_language(&language())
@@ -56,13 +61,33 @@ pub(crate) fn parse_str(file_id: FileId, loader: &FsModuleLoader) -> Result<Modu
     let tree = parser.parse(source, None).unwrap();
     let node = tree.root_node();
 
-    let mut context = ParseContext::new(file_id, loader);
+    let mut context = ParseContext::new(file_id, files);
+    match parse_root(&mut context, &tree, node) {
+        Ok(module) => Ok((module, context.diagnostics.into_inner())),
+        Err(e) => {
+            for diagnostic in context.diagnostics.borrow().iter() {
+                reporter.emit_suggested(diagnostic, context.files)?;
+            }
+            Err(e)
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn parse_root<'a>(
+    context: &mut ParseContext<'a>,
+    tree: &'a Tree,
+    node: Node<'a>,
+) -> Result<Module, Error> {
     context.check_if_error(&node, "module")?;
 
     if node.kind() == NODE_KIND_MODULE {
         let mut cursor = tree.walk();
-        let mut module = parse_module(&mut context, &mut cursor)?;
-        module.set_file_id(file_id);
+        let mut module = parse_module(context, &mut cursor)?;
+        module.set_file_id(context.file_id);
         Ok(module)
     } else {
         unexpected_node!(context, "parse_str", node, NODE_KIND_MODULE);
@@ -79,7 +104,8 @@ pub(crate) fn parse_str(file_id: FileId, loader: &FsModuleLoader) -> Result<Modu
 
 #[derive(Debug)]
 pub(crate) struct ParseContext<'a> {
-    loader: &'a FsModuleLoader,
+    files: &'a SourceFiles,
+    diagnostics: RefCell<Vec<SuggestedDiagnostic>>,
     file_id: FileId,
     source: Source,
     is_library: bool,
@@ -94,12 +120,12 @@ pub(crate) struct ParseContext<'a> {
 // ------------------------------------------------------------------------------------------------
 
 impl<'a> ParseContext<'a> {
-    fn new(file_id: FileId, loader: &'a FsModuleLoader) -> Self {
-        let file_cache = loader.files();
-        let file = file_cache.get(file_id).unwrap();
+    fn new(file_id: FileId, files: &'a SourceFiles) -> Self {
+        let file = files.get(file_id).unwrap();
         Self {
             file_id,
-            loader,
+            files,
+            diagnostics: Default::default(),
             source: file.source().clone(),
             module: Default::default(),
             is_library: false,
@@ -109,6 +135,15 @@ impl<'a> ParseContext<'a> {
         }
     }
 
+    fn report(&self, diagnostic: &Diagnostic) -> Result<(), Error> {
+        self.report_suggested(SuggestedDiagnostic::new(diagnostic.clone()))
+    }
+
+    fn report_suggested(&self, diagnostic: SuggestedDiagnostic) -> Result<(), Error> {
+        self.diagnostics.borrow_mut().push(diagnostic);
+        Ok(())
+    }
+
     fn node_source(&'a self, node: &'a Node<'a>) -> Result<&'a str, Error> {
         Ok(node.utf8_text(self.source.as_ref())?)
     }
@@ -116,29 +151,32 @@ impl<'a> ParseContext<'a> {
     fn check_if_error(&self, node: &Node<'a>, rule: &str) -> Result<(), Error> {
         if node.is_error() {
             let diagnostic = found_error_node(self.file_id, node.byte_range(), rule);
-            self.loader.report(&diagnostic).unwrap();
+            self.report(&diagnostic).unwrap();
             Err(diagnostic.into())
         } else {
             Ok(())
         }
     }
 
-    fn add_import(&mut self, import: &Import) -> Result<(), Error> {
+    // `removal` is the span of source to remove if `import` is a duplicate.
+    fn add_import(&mut self, import: &Import, removal: Span) -> Result<(), Error> {
         if let Some(previous) = self.imports.get(import) {
             let diagnostic = if matches!(previous, Import::Module(_)) {
-                duplicate_module_import(
+                duplicate_module_import_with_removal(
                     self.file_id,
                     previous.source_span().unwrap().byte_range(),
                     import.source_span().unwrap().byte_range(),
+                    removal,
                 )
             } else {
-                duplicate_definition_import(
+                duplicate_definition_import_with_removal(
                     self.file_id,
                     previous.source_span().unwrap().byte_range(),
                     import.source_span().unwrap().byte_range(),
+                    removal,
                 )
             };
-            self.loader.report(&diagnostic).unwrap();
+            self.report_suggested(diagnostic).unwrap();
         } else {
             self.imports.insert(import.clone());
         }
@@ -152,7 +190,7 @@ impl<'a> ParseContext<'a> {
                 name.source_span().unwrap().byte_range(),
                 type_defn.source_span().unwrap().byte_range(),
             );
-            self.loader.report(&diagnostic).unwrap();
+            self.report(&diagnostic).unwrap();
         } else {
             self.type_names.insert(name.clone());
         }
@@ -166,7 +204,7 @@ impl<'a> ParseContext<'a> {
                 member.source_span().unwrap().byte_range(),
                 name.source_span().unwrap().byte_range(),
             );
-            self.loader.report(&diagnostic).unwrap();
+            self.report(&diagnostic).unwrap();
         } else {
             self.member_names.insert(name.clone());
         }
@@ -180,7 +218,7 @@ impl<'a> ParseContext<'a> {
                 member.source_span().unwrap().byte_range(),
                 name.source_span().unwrap().byte_range(),
             );
-            self.loader.report(&diagnostic).unwrap();
+            self.report(&diagnostic).unwrap();
         } else {
             self.member_names.insert(name.clone());
         }
@@ -5,7 +5,6 @@ use crate::parse::annotations::parse_annotation;
 use crate::parse::definitions::parse_definition;
 use crate::parse::identifiers::{parse_identifier, parse_qualified_identifier};
 use sdml_core::error::Error;
-use sdml_core::load::ModuleLoader as ModuleLoaderTrait;
 use sdml_core::model::annotations::HasAnnotations;
 use sdml_core::model::identifiers::Identifier;
 use sdml_core::model::modules::{HeaderValue, Import, ImportStatement, ModuleImport};
@@ -17,6 +16,7 @@ use sdml_core::syntax::{
     NODE_KIND_IMPORT_STATEMENT, NODE_KIND_LINE_COMMENT, NODE_KIND_MEMBER_IMPORT,
     NODE_KIND_MODULE_BODY, NODE_KIND_MODULE_IMPORT,
 };
+use sdml_errors::Span;
 use tree_sitter::{Node, TreeCursor};
 use url::Url;
 
@@ -136,9 +136,20 @@ fn parse_import_statement<'a>(
 ) -> Result<ImportStatement, Error> {
     rule_fn!("import_statement", cursor.node());
 
-    let mut import = ImportStatement::default().with_source_span(cursor.node().into());
-
-    for node in cursor.node().named_children(cursor) {
+    let statement = cursor.node();
+    let mut import = ImportStatement::default().with_source_span(statement.into());
+    let is_single_import = statement
+        .named_children(cursor)
+        .filter(|node| {
+            matches!(
+                node.kind(),
+                NODE_KIND_MODULE_IMPORT | NODE_KIND_MEMBER_IMPORT
+            )
+        })
+        .count()
+        == 1;
+
+    for node in statement.named_children(cursor) {
         context.check_if_error(&node, RULE_NAME)?;
         match node.kind() {
             NODE_KIND_MODULE_IMPORT => {
@@ -155,15 +166,17 @@ fn parse_import_statement<'a>(
 
                 let imported: Import = imported.into();
 
-                context.add_import(&imported)?;
+                let removal = import_removal_span(context, &statement, &node, is_single_import);
+                context.add_import(&imported, removal)?;
                 import.add_to_imports(imported);
             }
             NODE_KIND_MEMBER_IMPORT => {
+                let removal = import_removal_span(context, &statement, &node, is_single_import);
                 let node = node.child_by_field_name(FIELD_NAME_NAME).unwrap();
                 context.check_if_error(&node, RULE_NAME)?;
                 let imported: Import =
                     parse_qualified_identifier(context, &mut node.walk())?.into();
-                context.add_import(&imported)?;
+                context.add_import(&imported, removal)?;
                 import.add_to_imports(imported);
             }
             NODE_KIND_LINE_COMMENT => {}
@@ -179,3 +192,31 @@ fn parse_import_statement<'a>(
     }
     Ok(import)
 }
+
+// The source to remove if the import `node` is a duplicate; the whole statement, and its line,
+// if it is the only import in the statement, otherwise the import and any preceding whitespace.
+fn import_removal_span(
+    context: &ParseContext<'_>,
+    statement: &Node<'_>,
+    node: &Node<'_>,
+    is_single_import: bool,
+) -> Span {
+    let source: &str = context.source.as_ref();
+    let is_blank = |c: char| c == ' ' || c == '\t';
+    if is_single_import {
+        let range = statement.byte_range();
+        let before = &source[..range.start];
+        let after = &source[range.end..];
+        let line_start = before.trim_end_matches(is_blank);
+        let line_end = after.trim_start_matches(is_blank);
+        if (line_start.is_empty() || line_start.ends_with('\n')) && line_end.starts_with('\n') {
+            line_start.len()..(source.len() - line_end.len() + 1)
+        } else {
+            range
+        }
+    } else {
+        let range = node.byte_range();
+        let before = source[..range.start].trim_end_matches(char::is_whitespace);
+        before.len()..range.end
+    }
+}
@@ -1,10 +1,19 @@
+use sdml_core::load::{ModuleLoader, ModuleResolver};
 use sdml_core::model::annotations::{AnnotationProperty, HasAnnotations};
 use sdml_core::model::definitions::Definition;
+use sdml_core::model::identifiers::Identifier;
 use sdml_core::model::modules::ImportStatement;
 use sdml_core::model::values::{SequenceMember, SimpleValue, Value};
 use sdml_core::model::{HasBody, HasName, HasNameReference};
+use sdml_core::store::disk::DiskModuleCache;
 use sdml_core::store::{InMemoryModuleCache, ModuleStore};
-use sdml_parse::load::FsModuleLoader;
+use sdml_errors::diagnostics::reporter::CollectingReporter;
+use sdml_errors::diagnostics::ErrorCode;
+use sdml_errors::{Error, FileId};
+use sdml_parse::load::{
+    FsModuleLoader, FsModuleResolver, ModuleSourceResolver, ResolvedSource, ResolverChain,
+};
+use std::collections::HashMap;
 use std::io::Cursor;
 use std::str::FromStr;
 use url::Url;
@@ -57,6 +66,106 @@ end"#
     assert_eq!(imported, ["foo"]);
 }
 
+#[test]
+fn test_load_module_with_missing_imports() {
+    let source = r#"module foo is
+
+  import [ xsd missing_one missing_two:Thing ]
+
+end"#;
+    let reporter = CollectingReporter::default();
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let mut loader = FsModuleLoader::default().with_reporter(Box::new(reporter.clone()));
+    let module = loader.load_from_reader(&mut Cursor::new(source.as_bytes()), &mut cache, true);
+    assert!(module.is_ok());
+    assert!(cache.contains(&module.unwrap()));
+
+    assert!(loader.has_unresolved_modules());
+    let mut unresolved: Vec<String> = loader.unresolved_modules().map(|n| n.to_string()).collect();
+    unresolved.sort();
+    assert_eq!(unresolved, ["missing_one", "missing_two"]);
+
+    let diagnostics = reporter.take_diagnostics();
+    assert_eq!(diagnostics.len(), 2);
+    for (diagnostic, name) in diagnostics.iter().zip(["missing_one", "missing_two"]) {
+        let start = source.find(name).unwrap();
+        assert_eq!(
+            diagnostic.code,
+            Some(ErrorCode::ImportedModuleNotFound.to_string())
+        );
+        assert_eq!(diagnostic.labels.len(), 1);
+        assert_eq!(diagnostic.labels[0].range, start..start + name.len());
+    }
+}
+
+#[test]
+fn test_load_multiple_module_versions() {
+    let mut cache = InMemoryModuleCache::default();
+    let mut loader = FsModuleLoader::default();
+    for version in ["v1", "v2"] {
+        let source = format!(
+            "module foo <https://example.org/foo#> version <https://example.org/{version}/foo> is end"
+        );
+        let module =
+            loader.load_from_reader(&mut Cursor::new(source.as_bytes()), &mut cache, false);
+        assert!(module.is_ok());
+    }
+
+    let name = Identifier::from_str("foo").unwrap();
+    assert_eq!(cache.module_versions(&name).count(), 2);
+
+    let v1 = Url::parse("https://example.org/v1/foo").unwrap();
+    let v2 = Url::parse("https://example.org/v2/foo").unwrap();
+    assert!(cache.contains_version(&name, &v1));
+    assert!(cache.contains_version(&name, &v2));
+    assert!(!cache.contains_version(&name, &Url::parse("https://example.org/v3/foo").unwrap()));
+}
+
+#[test]
+fn test_load_from_disk_cache() {
+    let directory = tempfile::tempdir().unwrap();
+    let file = directory.path().join("foo.sdm");
+    std::fs::write(
+        &file,
+        "module foo <https://example.org/foo#> is import xsd datatype Name <- xsd:string end",
+    )
+    .unwrap();
+    let cache_directory = directory.path().join("cache");
+    let name = Identifier::from_str("foo").unwrap();
+
+    let load = || {
+        let mut cache = DiskModuleCache::open(&cache_directory)
+            .unwrap()
+            .with_stdlib();
+        let mut loader = FsModuleLoader::default();
+        let loaded = loader
+            .load_from_file(file.clone(), &mut cache, true)
+            .unwrap();
+        assert_eq!(loaded, name);
+        assert!(cache.contains(&Identifier::from_str("xsd").unwrap()));
+        let module = cache.get(&name).unwrap();
+        assert_eq!(module.source_file(), Some(&file));
+        assert!(loader.get_file_id(&name).is_some());
+        assert_eq!(module.file_id(), loader.get_file_id(&name).as_ref());
+        module.body().definitions().count()
+    };
+
+    // The first load parses and persists, the second reads the persisted module.
+    assert_eq!(load(), 1);
+    assert_eq!(std::fs::read_dir(&cache_directory).unwrap().count(), 1);
+    assert_eq!(load(), 1);
+    assert_eq!(std::fs::read_dir(&cache_directory).unwrap().count(), 1);
+
+    // Changing the source invalidates the persisted module.
+    std::fs::write(
+        &file,
+        "module foo <https://example.org/foo#> is import xsd datatype Name <- xsd:string datatype Code <- xsd:string end",
+    )
+    .unwrap();
+    assert_eq!(load(), 2);
+    assert_eq!(std::fs::read_dir(&cache_directory).unwrap().count(), 2);
+}
+
 #[test]
 fn test_parse_module_with_annotations() {
     let mut cache = InMemoryModuleCache::default();
@@ -173,3 +282,95 @@ end"#
         panic!();
     }
 }
+
+#[test]
+fn test_load_mutual_version_mismatch() {
+    let directory = tempfile::tempdir().unwrap();
+    for (name, other) in [("foo", "bar"), ("bar", "foo")] {
+        std::fs::write(
+            directory.path().join(format!("{name}.sdm")),
+            format!(
+                r#"module {name} <https://example.com/{name}/> version "2" <https://example.com/v2/{name}/> is
+
+  import {other} <https://example.com/v1/{other}/>
+
+end
+"#
+            ),
+        )
+        .unwrap();
+    }
+
+    let mut resolver = FsModuleResolver::default();
+    resolver.prepend_to_search_path(directory.path());
+    let reporter = CollectingReporter::default();
+    let mut loader = FsModuleLoader::default()
+        .with_resolver(ResolverChain::empty().with_resolver(resolver))
+        .with_reporter(Box::new(reporter.clone()));
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+
+    // each module requests a version of the other that does not exist, this must neither
+    // reload the modules forever nor fail.
+    let name = loader
+        .load_from_file(directory.path().join("foo.sdm"), &mut cache, true)
+        .unwrap();
+    assert_eq!(name.as_ref(), "foo");
+    assert!(cache.contains(&Identifier::from_str("bar").unwrap()));
+
+    let codes: Vec<String> = reporter
+        .take_diagnostics()
+        .into_iter()
+        .filter_map(|diagnostic| diagnostic.code)
+        .collect();
+    assert_eq!(codes, vec!["E0103"]);
+}
+
+#[test]
+fn test_unresolved_in_empty_chain() {
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let mut loader = FsModuleLoader::default()
+        .with_resolver(ResolverChain::empty())
+        .with_reporter(Box::new(CollectingReporter::default()));
+    let name = Identifier::from_str("rentals").unwrap();
+    assert!(loader.load(&name, None, &mut cache, true).is_err());
+}
+
+// A resolver for modules held in memory, keyed by name.
+#[derive(Debug, Default)]
+struct InMemoryResolver(HashMap<String, String>);
+
+impl ModuleSourceResolver for InMemoryResolver {
+    fn resolve(&self, name: &Identifier, _: Option<&Url>) -> Result<Option<ResolvedSource>, Error> {
+        Ok(self.0.get(name.as_ref()).map(|source| {
+            ResolvedSource::new(
+                self.name_to_resource(name, None).unwrap(),
+                format!("{name}.sdm"),
+                source.as_str(),
+            )
+        }))
+    }
+}
+
+impl ModuleResolver for InMemoryResolver {
+    fn name_to_resource(&self, name: &Identifier, _: Option<FileId>) -> Result<Url, Error> {
+        Ok(Url::parse(&format!("memory:{name}")).unwrap())
+    }
+}
+
+#[test]
+fn test_load_with_custom_resolver() {
+    let resolver = InMemoryResolver(HashMap::from([
+        (
+            "rentals".to_string(),
+            "module rentals is\n  import fleet\nend\n".to_string(),
+        ),
+        ("fleet".to_string(), "module fleet is\nend\n".to_string()),
+    ]));
+    let mut loader = FsModuleLoader::new(resolver);
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let name = Identifier::from_str("rentals").unwrap();
+
+    assert_eq!(loader.load(&name, None, &mut cache, true).unwrap(), name);
+    assert!(cache.contains(&Identifier::from_str("fleet").unwrap()));
+    assert!(!loader.has_unresolved_modules());
+}
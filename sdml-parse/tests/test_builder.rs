@@ -0,0 +1,267 @@
+use sdml_core::builder::{
+    DatatypeBuilder, EntityBuilder, EnumBuilder, EventBuilder, MemberBuilder, ModuleBuilder,
+    StructureBuilder, UnionBuilder, VariantBuilder,
+};
+use sdml_core::diff::ModuleDiff;
+use sdml_core::error::Error;
+use sdml_core::model::definitions::Definition;
+use sdml_core::model::identifiers::Identifier;
+use sdml_core::model::members::{Cardinality, Ordering, Uniqueness};
+use sdml_core::model::{HasBody, HasName};
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_parse::load::FsModuleLoader;
+use std::io::Cursor;
+
+const RENTALS: &str = r#"module rentals <https://example.org/rentals/>
+  version "1.0" <https://example.org/rentals/1.0> is
+  import [ skos xsd ]
+
+  @skos:prefLabel = "Rentals"
+
+  datatype VIN <- xsd:string is
+    @xsd:length = 17
+  end
+
+  property name -> string
+
+  entity Vehicle is
+    @skos:definition = "a vehicle available for rent"
+
+    identity vin -> VIN
+
+    ref name
+    color -> Color
+    features -> {unordered unique 0..} xsd:string
+    mileage -> (xsd:date -> xsd:integer)
+  end
+
+  structure Address is
+    street -> xsd:string
+    city -> {0..1} xsd:string
+  end
+
+  enum Color of
+    Red
+    Blue is
+      @skos:prefLabel = "Blue"
+    end
+  end
+
+  union Location of
+    Address
+    xsd:anyURI as Link
+  end
+
+  event Returned source Vehicle is
+    when -> xsd:dateTime
+  end
+end"#;
+
+fn rentals_builder() -> ModuleBuilder {
+    ModuleBuilder::new("rentals")
+        .with_base_uri("https://example.org/rentals/")
+        .with_version_info("1.0")
+        .with_version_uri("https://example.org/rentals/1.0")
+        .with_import("skos")
+        .with_import("xsd")
+        .with_annotation("skos:prefLabel", "Rentals")
+        .with_datatype(
+            DatatypeBuilder::new("VIN", "xsd:string").with_annotation("xsd:length", 17u32),
+        )
+        .with_property(MemberBuilder::new("name", "string"))
+        .with_entity(
+            EntityBuilder::new("Vehicle")
+                .with_annotation("skos:definition", "a vehicle available for rent")
+                .with_identity(MemberBuilder::new("vin", "VIN"))
+                .with_member(MemberBuilder::new_reference("name"))
+                .with_member(MemberBuilder::new("color", "Color"))
+                .with_member(
+                    MemberBuilder::new("features", "xsd:string").with_cardinality(
+                        Cardinality::zero_or_more(
+                            Some(Ordering::Unordered),
+                            Some(Uniqueness::Unique),
+                        ),
+                    ),
+                )
+                .with_member(MemberBuilder::new_mapping(
+                    "mileage",
+                    "xsd:date",
+                    "xsd:integer",
+                )),
+        )
+        .with_structure(
+            StructureBuilder::new("Address")
+                .with_member(MemberBuilder::new("street", "xsd:string"))
+                .with_member(
+                    MemberBuilder::new("city", "xsd:string")
+                        .with_cardinality(Cardinality::zero_or_one()),
+                ),
+        )
+        .with_enum(
+            EnumBuilder::new("Color").with_variant("Red").with_variant(
+                VariantBuilder::new("Blue").with_annotation("skos:prefLabel", "Blue"),
+            ),
+        )
+        .with_union(
+            UnionBuilder::new("Location")
+                .with_variant("Address")
+                .with_variant(VariantBuilder::new("xsd:anyURI").with_rename("Link")),
+        )
+        .with_event(
+            EventBuilder::new("Returned", "Vehicle")
+                .with_member(MemberBuilder::new("when", "xsd:dateTime")),
+        )
+}
+
+fn builder_error(result: Result<sdml_core::model::modules::Module, Error>) -> (String, String) {
+    match result {
+        Err(Error::BuilderError { name, message }) => (name, message),
+        result => panic!("expected a builder error, not {result:?}"),
+    }
+}
+
+#[test]
+fn test_builder_matches_parsed_module() {
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let mut loader = FsModuleLoader::default();
+    let name = loader
+        .load_from_reader(&mut Cursor::new(RENTALS.as_bytes()), &mut cache, false)
+        .unwrap();
+    let parsed = cache.get(&name).unwrap();
+
+    let built = rentals_builder().build().unwrap();
+
+    let diff = ModuleDiff::compare(parsed, &built);
+    assert!(
+        diff.is_empty(),
+        "unexpected changes: {:?}",
+        diff.changes().map(|c| c.to_string()).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        built.base_uri().unwrap().value().as_str(),
+        "https://example.org/rentals/"
+    );
+    assert_eq!(built.version_info().unwrap().value(), "1.0");
+    assert!(!built.is_library_module());
+}
+
+#[test]
+fn test_builder_output_is_complete() {
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let built = rentals_builder().build().unwrap();
+    assert_eq!(built.body().definitions_len(), 7);
+    assert!(matches!(
+        built
+            .body()
+            .get_definition(&Identifier::new_unchecked("name")),
+        Some(Definition::Property(_))
+    ));
+    cache.insert(built);
+    let module = cache.get(&Identifier::new_unchecked("rentals")).unwrap();
+    assert!(!module.is_incomplete(&cache));
+    assert_eq!(module.name().as_ref(), "rentals");
+}
+
+#[test]
+fn test_builder_rejects_invalid_identifiers() {
+    let (name, message) = builder_error(ModuleBuilder::new("9lives").build());
+    assert_eq!(name, "9lives");
+    assert!(message.contains("`9lives`"));
+
+    let (name, _) = builder_error(
+        ModuleBuilder::new("rentals")
+            .with_structure(
+                StructureBuilder::new("Address").with_member(MemberBuilder::new("street", "xsd:")),
+            )
+            .build(),
+    );
+    assert_eq!(name, "rentals:Address.street");
+
+    let (name, _) = builder_error(
+        ModuleBuilder::new("rentals")
+            .with_annotation("skos:pref label", "Rentals")
+            .build(),
+    );
+    assert_eq!(name, "rentals");
+
+    // keywords are not valid identifiers
+    assert!(ModuleBuilder::new("rentals")
+        .with_enum(EnumBuilder::new("Status").with_variant("end"))
+        .build()
+        .is_err());
+}
+
+#[test]
+fn test_builder_rejects_duplicate_names() {
+    let (name, message) = builder_error(
+        ModuleBuilder::new("rentals")
+            .with_structure(StructureBuilder::new("Address"))
+            .with_enum(EnumBuilder::new("Address"))
+            .build(),
+    );
+    assert_eq!(name, "rentals");
+    assert_eq!(message, "duplicate definition name `Address`");
+
+    let (name, message) = builder_error(
+        ModuleBuilder::new("rentals")
+            .with_entity(
+                EntityBuilder::new("Vehicle")
+                    .with_identity(MemberBuilder::new("vin", "xsd:string"))
+                    .with_member(MemberBuilder::new("vin", "xsd:string")),
+            )
+            .build(),
+    );
+    assert_eq!(name, "rentals:Vehicle");
+    assert_eq!(message, "duplicate member name `vin`");
+
+    let (_, message) = builder_error(
+        ModuleBuilder::new("rentals")
+            .with_enum(
+                EnumBuilder::new("Color")
+                    .with_variant("Red")
+                    .with_variant("Red"),
+            )
+            .build(),
+    );
+    assert_eq!(message, "duplicate variant name `Red`");
+
+    let (_, message) = builder_error(
+        ModuleBuilder::new("rentals")
+            .with_union(
+                UnionBuilder::new("Location")
+                    .with_variant("Link")
+                    .with_variant(VariantBuilder::new("xsd:anyURI").with_rename("Link")),
+            )
+            .build(),
+    );
+    assert_eq!(message, "duplicate variant name `Link`");
+}
+
+#[test]
+fn test_builder_rejects_malformed_definitions() {
+    let (name, _) = builder_error(
+        ModuleBuilder::new("rentals")
+            .with_entity(
+                EntityBuilder::new("Vehicle").with_member(MemberBuilder::new("vin", "xsd:string")),
+            )
+            .build(),
+    );
+    assert_eq!(name, "rentals:Vehicle");
+
+    assert!(ModuleBuilder::new("rentals")
+        .with_property(MemberBuilder::new_reference("name"))
+        .build()
+        .is_err());
+
+    assert!(ModuleBuilder::new("rentals")
+        .with_enum(
+            EnumBuilder::new("Color").with_variant(VariantBuilder::new("Red").with_rename("Rouge"))
+        )
+        .build()
+        .is_err());
+
+    assert!(ModuleBuilder::new("rentals")
+        .with_base_uri("not a uri")
+        .build()
+        .is_err());
+}
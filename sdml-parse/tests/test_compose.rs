@@ -0,0 +1,228 @@
+use sdml_core::compose::{Merge, Split};
+use sdml_core::model::annotations::HasAnnotations;
+use sdml_core::model::definitions::{Definition, HasMembers};
+use sdml_core::model::identifiers::{Identifier, IdentifierReference};
+use sdml_core::model::modules::Module;
+use sdml_core::model::{HasBody, HasName, HasOptionalBody};
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_errors::diagnostics::ErrorCode;
+use sdml_parse::load::FsModuleLoader;
+use std::io::Cursor;
+use std::str::FromStr;
+
+const FLEET: &str = r#"module fleet <https://example.org/fleet/> is
+  import [ skos xsd billing ]
+
+  @skos:prefLabel = "Fleet"
+
+  entity Vehicle is
+    @skos:prefLabel = "Vehicle"
+    identity vin -> xsd:string
+    rate -> billing:Rate
+  end
+
+  structure Address is
+    street -> xsd:string
+  end
+end"#;
+
+const BILLING: &str = r#"module billing <https://example.org/billing/> is
+  import [ skos xsd fleet ]
+
+  @skos:prefLabel = "Fleet"
+
+  structure Rate is
+    amount -> xsd:decimal
+    vehicle -> fleet:Vehicle
+  end
+
+  structure Address is
+    @skos:definition = "a billing address"
+    street -> xsd:string
+  end
+end"#;
+
+const CONFLICTING: &str = r#"module other is
+  import xsd
+
+  structure Address is
+    street -> xsd:string
+    city -> xsd:string
+  end
+end"#;
+
+const TAGGED: &str = r#"module rentals is
+  import [ dc xsd ]
+
+  entity Vehicle is
+    @dc:subject = "fleet"
+    identity vin -> xsd:string
+    rate -> Rate
+  end
+
+  structure Rate is
+    @dc:subject = "Billing Rules"
+    amount -> xsd:decimal
+  end
+
+  datatype Code <- xsd:string
+
+  enum Color of
+    Red
+    Blue
+  end
+end"#;
+
+fn load(cache: &mut InMemoryModuleCache, sources: &[&str]) -> Vec<Identifier> {
+    let mut loader = FsModuleLoader::default();
+    sources
+        .iter()
+        .map(|source| {
+            loader
+                .load_from_reader(&mut Cursor::new(source.as_bytes()), cache, false)
+                .unwrap()
+        })
+        .collect()
+}
+
+fn modules<'a>(cache: &'a InMemoryModuleCache, names: &[Identifier]) -> Vec<&'a Module> {
+    names.iter().map(|name| cache.get(name).unwrap()).collect()
+}
+
+fn imported(module: &Module) -> Vec<String> {
+    let mut imported: Vec<String> = module
+        .imported_modules()
+        .into_iter()
+        .map(|name| name.to_string())
+        .collect();
+    imported.sort();
+    imported
+}
+
+#[test]
+fn test_merge_localizes_references() {
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let names = load(&mut cache, &[FLEET, BILLING]);
+
+    let merged = Merge::new(Identifier::new_unchecked("rentals"))
+        .with_base_uri(url::Url::parse("https://example.org/rentals/").unwrap())
+        .merge(modules(&cache, &names))
+        .unwrap();
+    assert!(!merged.has_conflicts());
+
+    let module = merged.module();
+    assert_eq!(module.name().as_ref(), "rentals");
+    assert_eq!(module.body().definitions_len(), 3);
+    assert_eq!(imported(module), vec!["skos", "xsd"]);
+    // the identical module annotations are not duplicated
+    assert_eq!(module.body().annotations().count(), 1);
+
+    let Some(Definition::Structure(rate)) = module
+        .body()
+        .get_definition(&Identifier::new_unchecked("Rate"))
+    else {
+        panic!("expected structure Rate");
+    };
+    let vehicle = rate
+        .body()
+        .unwrap()
+        .members()
+        .find(|member| member.name().as_ref() == "vehicle")
+        .unwrap();
+    assert_eq!(
+        vehicle.as_definition().unwrap().target_type().to_string(),
+        "Vehicle"
+    );
+}
+
+#[test]
+fn test_merge_combines_same_shape_definitions() {
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let names = load(&mut cache, &[FLEET, BILLING]);
+
+    let merged = Merge::new(Identifier::new_unchecked("rentals"))
+        .merge(modules(&cache, &names))
+        .unwrap();
+    let Some(Definition::Structure(address)) = merged
+        .module()
+        .body()
+        .get_definition(&Identifier::new_unchecked("Address"))
+    else {
+        panic!("expected structure Address");
+    };
+    assert_eq!(address.body().unwrap().annotations().count(), 1);
+}
+
+#[test]
+fn test_merge_reports_conflicts() {
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let names = load(&mut cache, &[FLEET, CONFLICTING]);
+
+    let merged = Merge::new(Identifier::new_unchecked("rentals"))
+        .merge(modules(&cache, &names))
+        .unwrap();
+    assert!(merged.has_conflicts());
+
+    let conflicts: Vec<_> = merged.conflicts().collect();
+    assert_eq!(conflicts.len(), 1);
+    let conflict = conflicts[0];
+    assert_eq!(conflict.definition().as_ref(), "Address");
+    assert_eq!(conflict.kept_from().as_ref(), "fleet");
+    assert_eq!(conflict.dropped_from().as_ref(), "other");
+    assert!(conflict.differences().count() > 0);
+
+    let diagnostic = conflict.to_diagnostic();
+    assert_eq!(
+        diagnostic.code,
+        Some(ErrorCode::ConflictingDefinition.to_string())
+    );
+    assert_eq!(diagnostic.labels.len(), 2);
+}
+
+#[test]
+fn test_merge_rejects_empty_input() {
+    assert!(Merge::new(Identifier::new_unchecked("rentals"))
+        .merge([])
+        .is_err());
+}
+
+#[test]
+fn test_split_by_kind() {
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let names = load(&mut cache, &[TAGGED]);
+    let module = cache.get(&names[0]).unwrap();
+
+    let parts = Split::by_kind().split(module).unwrap();
+    let part_names: Vec<String> = parts.iter().map(|part| part.name().to_string()).collect();
+    assert_eq!(
+        part_names,
+        vec![
+            "rentals_datatype",
+            "rentals_entity",
+            "rentals_enum",
+            "rentals_structure"
+        ]
+    );
+
+    assert_eq!(imported(&parts[0]), vec!["xsd"]);
+    assert_eq!(imported(&parts[1]), vec!["dc", "rentals_structure", "xsd"]);
+    assert!(imported(&parts[2]).is_empty());
+}
+
+#[test]
+fn test_split_by_annotation() {
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let names = load(&mut cache, &[TAGGED]);
+    let module = cache.get(&names[0]).unwrap();
+
+    let parts = Split::by_annotation(IdentifierReference::from_str("dc:subject").unwrap())
+        .split(module)
+        .unwrap();
+    let part_names: Vec<String> = parts.iter().map(|part| part.name().to_string()).collect();
+    assert_eq!(
+        part_names,
+        vec!["rentals", "rentals_billing_rules", "rentals_fleet"]
+    );
+    assert_eq!(parts[0].body().definitions_len(), 2);
+    assert!(imported(&parts[2]).contains(&"rentals_billing_rules".to_string()));
+}
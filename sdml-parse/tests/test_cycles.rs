@@ -0,0 +1,114 @@
+use sdml_core::model::check::cycles::{validate_module_import_cycles, ImportGraph};
+use sdml_core::model::identifiers::Identifier;
+use sdml_core::model::modules::{ImportStatement, Module, ModuleBody};
+use sdml_core::model::HasName;
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_errors::diagnostics::reporter::CollectingReporter;
+use sdml_parse::load::FsModuleLoader;
+use std::collections::HashSet;
+use std::io::Cursor;
+use std::str::FromStr;
+
+fn id(name: &str) -> Identifier {
+    Identifier::from_str(name).unwrap()
+}
+
+fn module_source(name: &str, imports: &[&str]) -> String {
+    let imports = if imports.is_empty() {
+        String::default()
+    } else {
+        format!("  import [ {} ]\n\n", imports.join(" "))
+    };
+    format!("module {name} <https://example.com/{name}> is\n\n{imports}end\n")
+}
+
+///
+/// Load each `(name, imports)` module and validate cycles from the first, returning the paths of
+/// the cycles reported.
+///
+fn reported_cycles(modules: &[(&str, &[&str])], allowed: &[&str]) -> Vec<Vec<String>> {
+    let reporter = CollectingReporter::default();
+    let mut loader = FsModuleLoader::default().with_reporter(Box::new(reporter.clone()));
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    for (name, imports) in modules {
+        loader
+            .load_from_reader(
+                &mut Cursor::new(module_source(name, imports)),
+                &mut cache,
+                false,
+            )
+            .unwrap();
+    }
+    let _ = reporter.take_diagnostics();
+
+    let module = cache.get(&id(modules[0].0)).unwrap();
+    let allowed: HashSet<Identifier> = allowed.iter().map(|name| id(name)).collect();
+    validate_module_import_cycles(module, &cache, &loader, &allowed).unwrap();
+
+    let diagnostics = reporter.take_diagnostics();
+    assert!(diagnostics
+        .iter()
+        .all(|diagnostic| diagnostic.code.as_deref() == Some("W0306")));
+
+    let graph = ImportGraph::from_module(module, &cache);
+    graph
+        .cycles()
+        .into_iter()
+        .filter(|cycle| !cycle.is_allowed(&allowed))
+        .take(diagnostics.len())
+        .map(|cycle| {
+            cycle
+                .starting_from(module.name())
+                .path()
+                .iter()
+                .map(|name| name.to_string())
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_user_module_cycle() {
+    let cycles = reported_cycles(&[("a", &["b"]), ("b", &["c"]), ("c", &["a"])], &[]);
+    assert_eq!(cycles, vec![vec!["a", "b", "c", "a"]]);
+}
+
+#[test]
+fn test_self_import() {
+    let cycles = reported_cycles(&[("a", &["a"])], &[]);
+    assert_eq!(cycles, vec![vec!["a", "a"]]);
+}
+
+#[test]
+fn test_allowed_cycle() {
+    let modules: &[(&str, &[&str])] = &[("a", &["b"]), ("b", &["a"])];
+    assert!(reported_cycles(modules, &["a", "b"]).is_empty());
+    // a cycle is only allowed if all of its modules are.
+    assert_eq!(reported_cycles(modules, &["a"]).len(), 1);
+}
+
+#[test]
+fn test_acyclic_diamond() {
+    let modules: &[(&str, &[&str])] =
+        &[("a", &["b", "c"]), ("b", &["d"]), ("c", &["d"]), ("d", &[])];
+    assert!(reported_cycles(modules, &[]).is_empty());
+}
+
+#[test]
+fn test_long_import_chain() {
+    const LENGTH: usize = 50_000;
+    let mut cache = InMemoryModuleCache::default();
+    for index in 0..LENGTH {
+        let mut body = ModuleBody::default();
+        body.add_to_imports(ImportStatement::new_module(id(&format!(
+            "m{}",
+            (index + 1) % LENGTH
+        ))));
+        cache.insert(Module::new(id(&format!("m{index}")), body));
+    }
+
+    let graph = ImportGraph::from_store(&cache);
+    let cycles = graph.cycles();
+    assert_eq!(cycles.len(), 1);
+    assert_eq!(cycles[0].modules().count(), LENGTH);
+}
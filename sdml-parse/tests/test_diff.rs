@@ -0,0 +1,321 @@
+use sdml_core::diff::compatibility::Compatibility;
+use sdml_core::diff::{Change, HeaderField, ModuleDiff};
+use sdml_core::model::modules::Module;
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_parse::load::FsModuleLoader;
+use std::io::Cursor;
+
+const OLD: &str = r#"module rentals <https://example.org/rentals/>
+  version "1.0" <https://example.org/rentals/1.0> is
+  import xsd
+
+  @skos:prefLabel = "Rentals"
+
+  entity Customer is
+    identity id -> xsd:string
+
+    name -> xsd:string
+    age -> {0..1} xsd:integer
+  end
+
+  entity Booking is
+    identity id -> xsd:string
+
+    days -> xsd:integer
+  end
+
+  enum Status of
+    Open
+    Closed
+    Cancelled
+  end
+
+  datatype Code <- xsd:string
+end"#;
+
+const NEW: &str = r#"module rentals <https://example.org/rentals/>
+  version "1.1" <https://example.org/rentals/1.1> is
+  import [ xsd dc ]
+
+  @skos:prefLabel = "Car Rentals"
+
+  entity Customer is
+    identity id -> xsd:string
+
+    name -> xsd:string
+    email -> {0..1} xsd:string
+  end
+
+  entity Booking is
+    identity id -> xsd:string
+
+    days -> {1..5} xsd:integer
+  end
+
+  enum Status of
+    Open
+    Closed
+    Pending
+  end
+
+  structure Address is
+    street -> xsd:string
+  end
+end"#;
+
+// The same module as OLD, with a different layout and order of definitions.
+const OLD_REFORMATTED: &str = r#"module rentals <https://example.org/rentals/>
+  version "1.0" <https://example.org/rentals/1.0> is
+  import xsd
+  @skos:prefLabel = "Rentals"
+  datatype Code <- xsd:string
+  enum Status of Open Closed Cancelled end
+  entity Booking is identity id -> xsd:string days -> {1} xsd:integer end
+  entity Customer is
+    identity id -> xsd:string
+    ;; the customer's age, if known
+    age -> {0..1} xsd:integer
+    name -> xsd:string
+  end
+end"#;
+
+fn load(source: &str) -> Module {
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let mut loader = FsModuleLoader::default();
+    let name = loader
+        .load_from_reader(&mut Cursor::new(source.as_bytes()), &mut cache, false)
+        .unwrap();
+    cache.get(&name).unwrap().clone()
+}
+
+fn changes(old: &str, new: &str) -> Vec<String> {
+    ModuleDiff::compare(&load(old), &load(new))
+        .changes()
+        .map(|change| change.to_string())
+        .collect()
+}
+
+#[test]
+fn test_diff_identical_modules() {
+    let diff = ModuleDiff::compare(&load(OLD), &load(OLD_REFORMATTED));
+    assert!(
+        diff.is_empty(),
+        "unexpected changes: {:?}",
+        diff.changes().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_diff_header_and_imports() {
+    let changes = changes(OLD, NEW);
+    assert!(changes.contains(&"~ module version info changed from 1.0 to 1.1".to_string()));
+    assert!(changes.contains(&"+ import dc".to_string()));
+}
+
+#[test]
+fn test_diff_definitions() {
+    let changes = changes(OLD, NEW);
+    assert!(changes.contains(&"+ structure Address".to_string()));
+    assert!(changes.contains(&"- datatype Code".to_string()));
+}
+
+#[test]
+fn test_diff_members() {
+    let changes = changes(OLD, NEW);
+    assert!(changes.contains(&"+ member Customer.email -> {0..1} xsd:string".to_string()));
+    assert!(changes.contains(&"- member Customer.age".to_string()));
+    assert!(changes
+        .contains(&"~ member Booking.days cardinality changed from {1..1} to {1..5}".to_string()));
+}
+
+#[test]
+fn test_diff_variants() {
+    let diff = ModuleDiff::compare(&load(OLD), &load(NEW));
+    let variants: Vec<&Change> = diff
+        .changes()
+        .filter(|change| {
+            matches!(
+                change,
+                Change::VariantAdded { .. } | Change::VariantRemoved { .. }
+            )
+        })
+        .collect();
+    assert_eq!(variants.len(), 2);
+    assert!(variants
+        .iter()
+        .any(|change| change.to_string() == "+ variant Status.Pending"));
+    assert!(variants
+        .iter()
+        .any(|change| change.to_string() == "- variant Status.Cancelled"));
+}
+
+#[test]
+fn test_diff_annotations() {
+    let changes = changes(OLD, NEW);
+    assert!(changes.contains(
+        &r#"~ module @skos:prefLabel changed from "Rentals" to "Car Rentals""#.to_string()
+    ));
+}
+
+#[test]
+fn test_diff_type_classes_not_compared() {
+    // type classes are only allowed in library modules.
+    let old = r#"module sdml is
+  class Any (T) is
+    def new -> T
+  end
+end"#;
+    let new = r#"module sdml is
+  class Any (T) is
+    @skos:prefLabel = "any"
+    def new -> T
+    def is_entity(v -> T) -> boolean
+  end
+end"#;
+    let diff = ModuleDiff::compare(&load(old), &load(new));
+    let changes: Vec<String> = diff.changes().map(|change| change.to_string()).collect();
+    assert_eq!(
+        changes,
+        vec![
+            r#"+ Any @skos:prefLabel = "any""#.to_string(),
+            "~ class Any not compared, class definitions are not supported".to_string(),
+        ]
+    );
+    assert_eq!(diff.compatibility(), Compatibility::PotentiallyBreaking);
+}
+
+// ------------------------------------------------------------------------------------------------
+// Compatibility
+// ------------------------------------------------------------------------------------------------
+
+const FACETS_OLD: &str = r#"module facets <https://example.org/facets/>
+  version "1" <https://example.org/facets/1> is
+  import xsd
+
+  datatype Code <- xsd:string is
+    @xsd:minLength = 2
+    @xsd:maxLength = 10
+  end
+end"#;
+
+const FACETS_NEW: &str = r#"module facets <https://example.org/facets/>
+  version "2" <https://example.org/facets/1> is
+  import xsd
+
+  datatype Code <- xsd:string is
+    @xsd:minLength = 1
+    @xsd:maxLength = 8
+  end
+end"#;
+
+#[test]
+fn test_compatibility_of_changes() {
+    let diff = ModuleDiff::compare(&load(OLD), &load(NEW));
+    let classified: Vec<(String, Compatibility)> = diff
+        .changes()
+        .map(|change| (change.description(), change.compatibility()))
+        .collect();
+    let compatibility_of = |description: &str| {
+        classified
+            .iter()
+            .find(|(d, _)| d == description)
+            .map(|(_, c)| *c)
+            .unwrap()
+    };
+    assert_eq!(
+        compatibility_of("member Customer.email -> {0..1} xsd:string"),
+        Compatibility::NonBreaking
+    );
+    assert_eq!(
+        compatibility_of("member Customer.age"),
+        Compatibility::Breaking
+    );
+    assert_eq!(
+        compatibility_of("member Booking.days cardinality changed from {1..1} to {1..5}"),
+        Compatibility::PotentiallyBreaking
+    );
+    assert_eq!(
+        compatibility_of("variant Status.Cancelled"),
+        Compatibility::Breaking
+    );
+    assert_eq!(
+        compatibility_of("variant Status.Pending"),
+        Compatibility::PotentiallyBreaking
+    );
+    assert_eq!(
+        compatibility_of("structure Address"),
+        Compatibility::NonBreaking
+    );
+    assert_eq!(diff.compatibility(), Compatibility::Breaking);
+}
+
+#[test]
+fn test_compatibility_of_narrowed_cardinality() {
+    let diff = ModuleDiff::compare(&load(NEW), &load(OLD));
+    let change = diff
+        .changes()
+        .find(|change| matches!(change, Change::MemberCardinalityChanged { .. }))
+        .unwrap();
+    assert_eq!(change.compatibility(), Compatibility::Breaking);
+}
+
+#[test]
+fn test_compatibility_of_facets() {
+    let diff = ModuleDiff::compare(&load(FACETS_OLD), &load(FACETS_NEW));
+    let facets: Vec<(String, Compatibility)> = diff
+        .changes()
+        .filter(|change| matches!(change, Change::FacetChanged { .. }))
+        .map(|change| (change.description(), change.compatibility()))
+        .collect();
+    assert_eq!(
+        facets,
+        vec![
+            (
+                "Code @xsd:maxLength changed from 10 to 8".to_string(),
+                Compatibility::Breaking
+            ),
+            (
+                "Code @xsd:minLength changed from 2 to 1".to_string(),
+                Compatibility::NonBreaking
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_missing_version_changes() {
+    // The version URI changes along with the breaking changes.
+    let diff = ModuleDiff::compare(&load(OLD), &load(NEW));
+    assert!(diff.missing_version_changes().is_empty());
+
+    // The version info changes, but the version URI does not.
+    let diff = ModuleDiff::compare(&load(FACETS_OLD), &load(FACETS_NEW));
+    assert_eq!(
+        diff.missing_version_changes(),
+        vec![HeaderField::VersionUri]
+    );
+
+    // Neither changes.
+    let diff = ModuleDiff::compare(
+        &load(FACETS_OLD),
+        &load(&FACETS_NEW.replace("\"2\"", "\"1\"")),
+    );
+    assert_eq!(
+        diff.missing_version_changes(),
+        vec![HeaderField::VersionInfo, HeaderField::VersionUri]
+    );
+
+    // The version info decreases, and narrowing the minimum length is breaking.
+    let diff = ModuleDiff::compare(&load(FACETS_NEW), &load(FACETS_OLD));
+    assert_eq!(
+        diff.missing_version_changes(),
+        vec![HeaderField::VersionInfo, HeaderField::VersionUri]
+    );
+
+    // The version URI decreases.
+    let diff = ModuleDiff::compare(&load(NEW), &load(OLD));
+    assert_eq!(
+        diff.missing_version_changes(),
+        vec![HeaderField::VersionInfo, HeaderField::VersionUri]
+    );
+}
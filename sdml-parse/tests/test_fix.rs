@@ -0,0 +1,144 @@
+use sdml_core::model::identifiers::Identifier;
+use sdml_core::model::values::LanguageTag;
+use sdml_core::refactor::fix::{Fix, FixKind};
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_errors::diagnostics::reporter::CollectingReporter;
+use sdml_parse::load::FsModuleLoader;
+use std::io::Cursor;
+use std::str::FromStr;
+
+const OTHER: &str = r#"module other is
+  import xsd
+
+  datatype Code <- xsd:string
+end
+"#;
+
+const EXAMPLE: &str = r#"module example is
+
+  import [ xsd xsd skos ]
+
+  @skos:prefLabel = "Example"
+
+  entity access_record is
+    identity ID -> xsd:string
+    code -> other:Code
+  end
+
+  structure Thing is
+    record -> access_record
+  end
+
+end
+"#;
+
+fn fix_modules(fix: Fix, sources: &[&str]) -> (Vec<String>, Vec<String>) {
+    let reporter = CollectingReporter::default();
+    let mut loader = FsModuleLoader::default().with_reporter(Box::new(reporter.clone()));
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let names: Vec<Identifier> = sources
+        .iter()
+        .map(|source| {
+            loader
+                .load_from_reader(&mut Cursor::new(source.as_bytes()), &mut cache, true)
+                .unwrap()
+        })
+        .collect();
+    for name in &names {
+        cache.get(name).unwrap().validate(&cache, &loader, false);
+    }
+
+    let plan = fix
+        .plan(
+            &names,
+            &reporter.take_suggested_diagnostics(),
+            &cache,
+            &loader,
+        )
+        .unwrap();
+    let fixed = names
+        .iter()
+        .zip(sources)
+        .map(|(name, source)| plan.apply(name, source))
+        .collect();
+    (fixed, plan.skipped().to_vec())
+}
+
+#[test]
+fn test_fix_all() {
+    let (fixed, skipped) = fix_modules(Fix::default(), &[OTHER, EXAMPLE]);
+
+    assert_eq!(fixed[0], OTHER);
+    assert_eq!(
+        fixed[1],
+        r#"module example is
+
+  import [ xsd skos ]
+  import other
+
+  @skos:prefLabel = "Example"@en
+
+  entity AccessRecord is
+    identity id -> xsd:string
+    code -> other:Code
+  end
+
+  structure Thing is
+    record -> AccessRecord
+  end
+
+end
+"#
+    );
+    assert!(skipped.is_empty());
+}
+
+#[test]
+fn test_fix_only_language_tag() {
+    let fix = Fix::default()
+        .only([FixKind::LanguageTag])
+        .with_language(LanguageTag::from_str("en-GB").unwrap());
+    let (fixed, _) = fix_modules(fix, &[OTHER, EXAMPLE]);
+
+    assert_eq!(
+        fixed[1],
+        EXAMPLE.replace(r#""Example""#, r#""Example"@en-GB"#)
+    );
+}
+
+#[test]
+fn test_fix_missing_import_without_imports() {
+    let source = r#"module example is
+  structure Thing is
+    code -> other:Code
+  end
+end
+"#;
+    let (fixed, _) = fix_modules(
+        Fix::default().only([FixKind::MissingImport]),
+        &[OTHER, source],
+    );
+
+    assert_eq!(
+        fixed[1],
+        r#"module example is
+  import other
+
+  structure Thing is
+    code -> other:Code
+  end
+end
+"#
+    );
+}
+
+#[test]
+fn test_fix_module_name_is_skipped() {
+    let source = r#"module Example is
+end
+"#;
+    let (fixed, skipped) = fix_modules(Fix::default(), &[source]);
+
+    assert_eq!(fixed[0], source);
+    assert_eq!(skipped.len(), 1);
+}
@@ -0,0 +1,193 @@
+use sdml_core::model::definitions::DefinitionKind;
+use sdml_core::model::identifiers::QualifiedIdentifier;
+use sdml_core::model::values::{LanguageString, SimpleValue, Value};
+use sdml_core::store::index::ModuleIndex;
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_parse::load::FsModuleLoader;
+use std::io::Cursor;
+use std::str::FromStr;
+
+const OTHER: &str = r#"module other is
+  import xsd
+
+  datatype Code <- xsd:string
+
+  datatype ShortCode <- Code
+
+  entity Thing is
+    identity id -> Code
+
+    code -> Code
+  end
+end"#;
+
+const EXAMPLE: &str = r#"module example is
+  import [ other:Thing other skos xsd ]
+
+  entity Example is
+    @skos:definition = "an example"
+
+    identity id -> other:Code
+
+    thing -> Thing
+    created -> xsd:date
+  end
+
+  structure Address is
+    @skos:definition = "an address"
+
+    moved -> xsd:date
+    lookup -> {0..1} (Thing -> Address)
+  end
+
+  event Moved source Example is
+    address -> Address
+  end
+end"#;
+
+fn index() -> ModuleIndex {
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let mut loader = FsModuleLoader::default();
+    let mut names = Vec::default();
+    for source in [OTHER, EXAMPLE] {
+        names.push(
+            loader
+                .load_from_reader(&mut Cursor::new(source.as_bytes()), &mut cache, false)
+                .unwrap(),
+        );
+    }
+    ModuleIndex::from_modules(names.iter().map(|name| cache.get(name).unwrap()), &cache)
+}
+
+fn qualified(name: &str) -> QualifiedIdentifier {
+    QualifiedIdentifier::from_str(name).unwrap()
+}
+
+fn strings<T: ToString>(values: impl Iterator<Item = T>) -> Vec<String> {
+    values.map(|value| value.to_string()).collect()
+}
+
+#[test]
+fn test_index_definitions_of_kind() {
+    let index = index();
+    assert_eq!(
+        strings(index.definitions_of_kind(DefinitionKind::Entity)),
+        ["example:Example", "other:Thing"]
+    );
+    assert_eq!(
+        strings(index.definitions_of_kind(DefinitionKind::Datatype)),
+        ["other:Code", "other:ShortCode"]
+    );
+    assert_eq!(
+        index.kind_of(&qualified("example:Moved")),
+        Some(DefinitionKind::Event)
+    );
+    assert!(index
+        .definitions_of_kind(DefinitionKind::Union)
+        .next()
+        .is_none());
+}
+
+#[test]
+fn test_index_members_of_type() {
+    let index = index();
+    assert_eq!(
+        strings(index.members_of_type(&qualified("xsd:date"))),
+        ["example:Address.moved", "example:Example.created"]
+    );
+    // Unqualified references are qualified by member imports, and mapping types are included.
+    assert_eq!(
+        strings(index.members_of_type(&qualified("other:Thing"))),
+        ["example:Address.lookup", "example:Example.thing"]
+    );
+    assert_eq!(
+        strings(index.members_of_type(&qualified("other:Code"))),
+        ["example:Example.id", "other:Thing.code", "other:Thing.id"]
+    );
+}
+
+#[test]
+fn test_index_references_to() {
+    let index = index();
+    assert_eq!(
+        strings(index.references_to(&qualified("example:Address"))),
+        ["example:Moved"]
+    );
+    assert_eq!(
+        strings(index.references_to(&qualified("other:Thing"))),
+        ["example:Address", "example:Example"]
+    );
+    assert_eq!(
+        strings(index.references_to(&qualified("skos:definition"))),
+        ["example:Address", "example:Example"]
+    );
+}
+
+#[test]
+fn test_index_annotated_with() {
+    let index = index();
+    let definition = qualified("skos:definition");
+    assert_eq!(
+        strings(index.annotated_with(&definition, None)),
+        ["example:Address", "example:Example"]
+    );
+    let value = Value::from(SimpleValue::from(LanguageString::from("an address")));
+    assert_eq!(
+        strings(index.annotated_with(&definition, Some(&value))),
+        ["example:Address"]
+    );
+}
+
+#[test]
+fn test_index_subtypes_and_instances() {
+    let index = index();
+    assert_eq!(
+        strings(index.subtypes_of(&qualified("other:Code"))),
+        ["other:ShortCode"]
+    );
+    assert_eq!(
+        strings(index.all_subtypes_of(&qualified("xsd:string")).into_iter()),
+        ["other:Code", "other:ShortCode"]
+    );
+}
+
+#[test]
+fn test_index_library_classes() {
+    // RDF definitions are only allowed in library modules.
+    let index = InMemoryModuleCache::default().with_stdlib().index();
+    assert!(index
+        .subtypes_of(&qualified("rdfs:Resource"))
+        .any(|name| name == &qualified("rdfs:Literal")));
+    assert!(index
+        .instances_of(&qualified("rdfs:Class"))
+        .any(|name| name == &qualified("rdfs:Datatype")));
+}
+
+#[test]
+fn test_index_leaves_unknown_references_unresolved() {
+    let source = r#"module example is
+  structure Address is
+    street -> string
+    moved -> Unknown
+  end
+end"#;
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let mut loader = FsModuleLoader::default();
+    let name = loader
+        .load_from_reader(&mut Cursor::new(source.as_bytes()), &mut cache, false)
+        .unwrap();
+    let index = ModuleIndex::from_modules([cache.get(&name).unwrap()], &cache);
+    // Builtin types are qualified by the sdml module, other unknown names are not qualified.
+    assert_eq!(
+        strings(index.members_of_type(&qualified("sdml:string"))),
+        ["example:Address.street"]
+    );
+    assert_eq!(
+        index.members_of_type(&qualified("example:Unknown")).count(),
+        0
+    );
+    assert_eq!(
+        index.references_to(&qualified("example:Unknown")).count(),
+        0
+    );
+}
@@ -0,0 +1,141 @@
+use sdml_core::load::ModuleLoader;
+use sdml_core::model::HasName;
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_errors::diagnostics::reporter::JsonLinesReporter;
+use sdml_errors::diagnostics::suggestions::Suggestion;
+use sdml_errors::diagnostics::SeverityFilter;
+use sdml_parse::load::FsModuleLoader;
+use serde_json::Value;
+use std::cell::RefCell;
+use std::io::{Cursor, Write};
+use std::rc::Rc;
+
+const DUPLICATE_IMPORTS: &str = r#"module example is
+  import xsd
+  import [ skos xsd skos:Concept skos:Concept ]
+  import xsd
+
+  structure Foo is
+    name -> xsd:string
+  end
+end
+"#;
+
+const NOT_PREFERRED_CASE: &str = r#"module Example is
+  structure access_record is
+    name -> string
+  end
+end
+"#;
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn validate_to_records(source: &str) -> Vec<Value> {
+    let buffer = SharedBuffer::default();
+    let reporter =
+        JsonLinesReporter::new(buffer.clone()).with_severity_filter(SeverityFilter::Help);
+    let mut loader = FsModuleLoader::default().with_reporter(Box::new(reporter));
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let name = loader
+        .load_from_reader(&mut Cursor::new(source.as_bytes()), &mut cache, true)
+        .unwrap();
+    let module = cache.get(&name).unwrap();
+    module.validate(&cache, &loader, false);
+    let _ = loader
+        .reporter_done(Some(module.name().to_string()))
+        .unwrap();
+
+    let output = buffer.0.borrow();
+    String::from_utf8(output.clone())
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+fn suggestions(records: &[Value], code: &str) -> Vec<Suggestion> {
+    records
+        .iter()
+        .filter(|record| record["code"] == code)
+        .flat_map(|record| record["suggestions"].as_array().unwrap())
+        .map(|suggestion| {
+            Suggestion::replace(
+                0,
+                suggestion["span"]["start"].as_u64().unwrap() as usize
+                    ..suggestion["span"]["end"].as_u64().unwrap() as usize,
+                suggestion["replacement"].as_str().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_json_record_structure() {
+    let records = validate_to_records(DUPLICATE_IMPORTS);
+    let record = records
+        .iter()
+        .find(|record| record["code"] == "W0302")
+        .unwrap();
+    assert_eq!(record["severity"], "warning");
+    assert_eq!(record["help"], "https://sdml.io/errors/#W0302");
+    // the help URL is not repeated in the notes.
+    assert!(record["notes"].as_array().unwrap().is_empty());
+
+    let labels = record["labels"].as_array().unwrap();
+    assert_eq!(labels.len(), 2);
+    assert_eq!(labels[0]["style"], "primary");
+    assert_eq!(labels[0]["start"]["line"], 3);
+    assert_eq!(labels[0]["start"]["column"], 34);
+    assert_eq!(labels[1]["style"], "secondary");
+    assert_eq!(labels[1]["start"]["column"], 21);
+}
+
+#[test]
+fn test_duplicate_import_removal() {
+    let records = validate_to_records(DUPLICATE_IMPORTS);
+    let mut fixes = suggestions(&records, "W0301");
+    fixes.extend(suggestions(&records, "W0302"));
+    assert_eq!(fixes.len(), 3);
+
+    assert_eq!(
+        Suggestion::apply_all(DUPLICATE_IMPORTS, &fixes),
+        r#"module example is
+  import xsd
+  import [ skos skos:Concept ]
+
+  structure Foo is
+    name -> xsd:string
+  end
+end
+"#
+    );
+}
+
+#[test]
+fn test_preferred_case_replacement() {
+    let records = validate_to_records(NOT_PREFERRED_CASE);
+    let fixes = suggestions(&records, "I0506");
+    let replacements: Vec<&str> = fixes.iter().map(|fix| fix.replacement()).collect();
+    assert_eq!(replacements, vec!["example", "AccessRecord"]);
+
+    assert_eq!(
+        Suggestion::apply_all(NOT_PREFERRED_CASE, &fixes),
+        r#"module example is
+  structure AccessRecord is
+    name -> string
+  end
+end
+"#
+    );
+}
@@ -0,0 +1,165 @@
+use codespan_reporting::diagnostic::Severity;
+use sdml_core::model::check::terms::validate_module_terms;
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_errors::diagnostics::reporter::CollectingReporter;
+use sdml_errors::{Diagnostic, Error};
+use sdml_parse::lint::{LintConfig, SDML_LINT_FILE_NAME};
+use sdml_parse::load::FsModuleLoader;
+use std::io::Cursor;
+use std::path::Path;
+
+const EXAMPLE: &str = r#"module example is
+
+  import [ skos ]
+
+  @skos:prefLabel = "Example"
+
+  structure Thing is
+    first_name -> string
+    lastName -> string
+  end
+
+  enum Color of
+    Red
+    DARK_BLUE
+  end
+
+end
+"#;
+
+fn validate_with(config: LintConfig, source: &str) -> Vec<Diagnostic> {
+    let reporter = CollectingReporter::default();
+    let mut loader = FsModuleLoader::default()
+        .with_reporter(Box::new(reporter.clone()))
+        .with_lint_config(config);
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let name = loader
+        .load_from_reader(&mut Cursor::new(source.as_bytes()), &mut cache, true)
+        .unwrap();
+    let module = cache.get(&name).unwrap();
+    module.validate(&cache, &loader, false);
+    let config = loader.lint_config().unwrap().clone();
+    if let Some(term_set) = config.deprecated_terms(&mut loader).unwrap() {
+        validate_module_terms(module, &term_set, &loader);
+    }
+    reporter.take_diagnostics()
+}
+
+fn codes(diagnostics: &[Diagnostic]) -> Vec<&str> {
+    diagnostics
+        .iter()
+        .filter_map(|diagnostic| diagnostic.code.as_deref())
+        .collect()
+}
+
+#[test]
+fn test_codes_off_and_severity() {
+    let source = EXAMPLE.replace("Thing", "thing");
+    let default = validate_with(LintConfig::default(), &source);
+    assert!(codes(&default).contains(&"I0503"));
+
+    let config = LintConfig::load_from_str(
+        r#"{ "codes": { "I0503": "off", "I0506": "warning" } }"#,
+        Path::new("."),
+    )
+    .unwrap();
+    let diagnostics = validate_with(config, &source);
+
+    assert!(!codes(&diagnostics).contains(&"I0503"));
+    let case_diagnostics: Vec<&Diagnostic> = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.code.as_deref() == Some("I0506"))
+        .collect();
+    assert!(!case_diagnostics.is_empty());
+    assert!(case_diagnostics
+        .iter()
+        .all(|diagnostic| diagnostic.severity == Severity::Warning));
+}
+
+#[test]
+fn test_naming_conventions() {
+    let default = validate_with(LintConfig::default(), EXAMPLE);
+    // the defaults allow both member names and both variant names.
+    assert!(!codes(&default).contains(&"I0506"));
+
+    let config = LintConfig::load_from_str(
+        r#"{ "naming": { "member": ["lowerCamelCase"], "variant": ["SHOUTY_SNAKE_CASE"] } }"#,
+        Path::new("."),
+    )
+    .unwrap();
+    let diagnostics = validate_with(config, EXAMPLE);
+    let notes: Vec<&String> = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.code.as_deref() == Some("I0506"))
+        .flat_map(|diagnostic| diagnostic.notes.first())
+        .collect();
+    assert_eq!(notes.len(), 2);
+    assert!(notes[0].contains("lowerCamelCase"));
+    assert!(notes[1].contains("SHOUTY_SNAKE_CASE"));
+}
+
+#[test]
+fn test_invalid_configurations() {
+    for source in [
+        r#"{ "codes": { "X9999": "off" } }"#,
+        r#"{ "codes": { "E0114": "off" } }"#,
+        r#"{ "codes": { "E0114": "warning" } }"#,
+        r#"{ "codes": { "I0506": "loud" } }"#,
+        r#"{ "naming": { "member": ["kebab-case"] } }"#,
+        r#"{ "naming": { "widget": ["snake_case"] } }"#,
+    ] {
+        assert!(
+            LintConfig::load_from_str(source, Path::new(".")).is_err(),
+            "{source}"
+        );
+    }
+}
+
+#[test]
+fn test_deprecated_terms_from_file() {
+    let directory = tempfile::tempdir().unwrap();
+    std::fs::write(
+        directory.path().join(SDML_LINT_FILE_NAME),
+        r#"{ "deprecated_terms": "terms.json" }"#,
+    )
+    .unwrap();
+    std::fs::write(
+        directory.path().join("terms.json"),
+        r#"{
+  "name": "Project Terms",
+  "terms": {
+    "thing": { "regex": "(?i)\\bthing\\b", "alternative_terms": ["Item"] }
+  }
+}"#,
+    )
+    .unwrap();
+
+    let config = LintConfig::load_from(directory.path(), false)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        config.deprecated_terms_path(),
+        Some(directory.path().join("terms.json"))
+    );
+
+    let diagnostics = validate_with(config, EXAMPLE);
+    assert!(codes(&diagnostics).contains(&"W0305"));
+}
+
+#[test]
+fn test_invalid_configuration_file() {
+    let directory = tempfile::tempdir().unwrap();
+    assert!(LintConfig::load_from(directory.path(), false)
+        .unwrap()
+        .is_none());
+
+    std::fs::write(
+        directory.path().join(SDML_LINT_FILE_NAME),
+        r#"{ "codes": { "X9999": "off" } }"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        LintConfig::load_from(directory.path(), false),
+        Err(Error::LintConfigError { .. })
+    ));
+}
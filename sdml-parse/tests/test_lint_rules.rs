@@ -0,0 +1,167 @@
+use sdml_core::model::check::lint::rules::UnusedImport;
+use sdml_core::model::check::lint::{LintContext, LintRegistry, LintRule};
+use sdml_core::model::definitions::Definition;
+use sdml_core::model::HasBody;
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_errors::diagnostics::reporter::CollectingReporter;
+use sdml_errors::diagnostics::ErrorCode;
+use sdml_errors::Diagnostic;
+use sdml_parse::lint::LintConfig;
+use sdml_parse::load::FsModuleLoader;
+use std::io::Cursor;
+use std::path::Path;
+
+const EXAMPLE: &str = r#"module example is
+
+  import [ dc skos xsd ]
+
+  datatype Name <- xsd:string is
+    @skos:definition = "A person's name."@en
+  end
+
+  datatype Unused <- xsd:string is
+    @dc:description = "Not used by anything."@en
+  end
+
+  entity Person is
+    @skos:definition = "A person."@en
+
+    identity id -> {0..} string
+
+    name -> Name
+    nickname -> unknown
+  end
+
+  entity Placeholder
+
+  enum Status of
+    Active
+  end
+
+end
+"#;
+
+fn lint_with(registry: &LintRegistry, config: LintConfig, source: &str) -> Vec<Diagnostic> {
+    let reporter = CollectingReporter::default();
+    let mut loader = FsModuleLoader::default()
+        .with_reporter(Box::new(reporter.clone()))
+        .with_lint_config(config);
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let name = loader
+        .load_from_reader(&mut Cursor::new(source.as_bytes()), &mut cache, true)
+        .unwrap();
+    let module = cache.get(&name).unwrap();
+    registry.check_and_report(module, &cache, &loader).unwrap();
+    reporter.take_diagnostics()
+}
+
+fn codes(diagnostics: &[Diagnostic]) -> Vec<&str> {
+    let mut codes: Vec<&str> = diagnostics
+        .iter()
+        .filter_map(|diagnostic| diagnostic.code.as_deref())
+        .collect();
+    codes.sort();
+    codes
+}
+
+#[test]
+fn test_standard_rules() {
+    let diagnostics = lint_with(&LintRegistry::standard(), LintConfig::default(), EXAMPLE);
+    assert_eq!(
+        codes(&diagnostics),
+        vec![
+            "I0507", // Placeholder has no identity
+            "I0508", // Placeholder has no description
+            "I0508", // Status has no description
+            "I0509", // Unused is not used
+            "I0510", // Person's identity is {0..}
+            "I0511", // Status has a single variant
+            "W0309", // nickname is unknown
+        ]
+    );
+}
+
+#[test]
+fn test_unused_imports() {
+    let source = r#"module example is
+
+  import [ dc skos:prefLabel xsd:string rdfs ]
+
+  @skos:prefLabel = "Example"@en
+
+  structure Thing is
+    @rdfs:comment = "A thing."@en
+
+    name -> string
+  end
+
+end
+"#;
+    let registry = LintRegistry::default().with_rule(UnusedImport);
+    let diagnostics = lint_with(&registry, LintConfig::default(), source);
+    let codes: Vec<&str> = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.code.as_deref().unwrap())
+        .collect();
+    // `dc`, and `xsd:string` as the member type `string` is `sdml:string`.
+    assert_eq!(codes, vec!["W0308", "W0308"]);
+}
+
+#[test]
+fn test_disabled_codes_are_not_run() {
+    let config = LintConfig::load_from_str(
+        r#"{ "codes": { "I0508": "off", "I0511": "off" } }"#,
+        Path::new("."),
+    )
+    .unwrap();
+    let diagnostics = lint_with(&LintRegistry::standard(), config, EXAMPLE);
+    let codes = codes(&diagnostics);
+    assert!(!codes.contains(&"I0508"));
+    assert!(!codes.contains(&"I0511"));
+    assert!(codes.contains(&"I0507"));
+}
+
+#[derive(Debug)]
+struct NoEvents;
+
+impl LintRule for NoEvents {
+    fn name(&self) -> &'static str {
+        "no-events"
+    }
+
+    fn code(&self) -> ErrorCode {
+        ErrorCode::IncompleteModule
+    }
+
+    fn description(&self) -> &'static str {
+        "a module with no event definitions"
+    }
+
+    fn check(&self, context: &LintContext<'_>) -> Vec<Diagnostic> {
+        if context
+            .module()
+            .body()
+            .definitions()
+            .any(|definition| matches!(definition, Definition::Event(_)))
+        {
+            Vec::default()
+        } else {
+            vec![Diagnostic::note()
+                .with_code(self.code().to_string())
+                .with_message(self.description())]
+        }
+    }
+}
+
+#[test]
+fn test_custom_rule() {
+    let mut registry = LintRegistry::standard().with_rule(NoEvents);
+    assert_eq!(registry.len(), 8);
+    assert!(registry.unregister("single-variant-enum"));
+    assert!(registry.get("single-variant-enum").is_none());
+
+    let diagnostics = lint_with(&registry, LintConfig::default(), EXAMPLE);
+    let codes = codes(&diagnostics);
+    assert!(codes.contains(&"I0500"));
+    assert!(!codes.contains(&"I0511"));
+}
@@ -45,7 +45,9 @@ where
 fn test_load_without_catalogue() {
     with_env_variable(SDML_CATALOG_FILE_VARIABLE, None, || {
         let mut cache = ::sdml_core::store::InMemoryModuleCache::default().with_stdlib();
-        let mut loader = ::sdml_parse::load::FsModuleLoader::default();
+        let mut loader = ::sdml_parse::load::FsModuleLoader::default().with_reporter(Box::new(
+            ::sdml_errors::diagnostics::reporter::CollectingReporter::default(),
+        ));
         let module_name = Identifier::from_str(MODULE_NAME).unwrap();
 
         loader
@@ -0,0 +1,214 @@
+#![cfg(feature = "archive")]
+
+use sdml_core::load::ModuleLoader;
+use sdml_core::model::identifiers::Identifier;
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_parse::load::archive::ArchiveModuleResolver;
+use sdml_parse::load::{FsModuleLoader, ResolverChain};
+use sdml_parse::package::{Package, PackageManifest, PackageSource};
+use std::path::Path;
+use std::str::FromStr;
+
+const COMMON_MANIFEST: &str = r#"{
+  "name": "common",
+  "version": "1.0.3",
+  "base": "https://example.org/common/",
+  "modules": {
+    "money": { "path": "src/money.sdm" },
+    "internal": { "path": "src/internal.sdm" }
+  },
+  "exports": ["money"]
+}"#;
+
+const MONEY: &str = r#"module money <https://example.org/common/money#> is
+  import internal
+  datatype Amount <- xsd:decimal
+end
+"#;
+
+const INTERNAL: &str = r#"module internal <https://example.org/common/internal#> is
+  import xsd
+  datatype Code <- xsd:string
+end
+"#;
+
+fn write_files(root: &Path, files: &[(&str, &str)]) {
+    for (path, content) in files {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+}
+
+fn rentals_manifest(version_range: &str, dependency_path: &str) -> String {
+    format!(
+        r#"{{
+  "name": "rentals",
+  "version": "0.1.0",
+  "base": "https://example.org/rentals/",
+  "modules": {{ "rentals": {{ "path": "rentals.sdm" }} }},
+  "dependencies": {{ "common": {{ "version": "{version_range}", "path": "{dependency_path}" }} }}
+}}"#
+    )
+}
+
+fn rentals_module(import: &str) -> String {
+    format!("module rentals <https://example.org/rentals/rentals#> is import {import} end\n")
+}
+
+#[test]
+fn test_build_package_archive() {
+    let dir = tempfile::tempdir().unwrap();
+    let common = dir.path().join("common");
+    write_files(
+        &common,
+        &[
+            ("sdml-package.json", COMMON_MANIFEST),
+            ("src/money.sdm", MONEY),
+            ("src/internal.sdm", INTERNAL),
+        ],
+    );
+
+    let manifest = PackageManifest::load_from(&common).unwrap();
+    assert!(manifest.is_exported("money"));
+    assert!(!manifest.is_exported("internal"));
+
+    let archive = manifest.build(None).unwrap();
+    assert_eq!(archive, common.join("common-1.0.3.zip"));
+
+    let package = Package::open(&archive).unwrap();
+    assert_eq!(package.source(), &PackageSource::Archive(archive.clone()));
+    assert_eq!(package.version().to_string(), "1.0.3");
+
+    // The generated catalog allows the archive to be used directly by the loader.
+    let resolver = ArchiveModuleResolver::open(&archive).unwrap();
+    assert!(resolver.has_catalog());
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let mut loader =
+        FsModuleLoader::default().with_resolver(ResolverChain::empty().with_resolver(resolver));
+    let money = Identifier::from_str("money").unwrap();
+    loader.load(&money, None, &mut cache, true).unwrap();
+    assert!(cache.contains(&Identifier::from_str("internal").unwrap()));
+}
+
+#[test]
+fn test_lock_and_vendor_dependencies() {
+    let dir = tempfile::tempdir().unwrap();
+    write_files(
+        &dir.path().join("common"),
+        &[
+            ("sdml-package.json", COMMON_MANIFEST),
+            ("src/money.sdm", MONEY),
+            ("src/internal.sdm", INTERNAL),
+        ],
+    );
+    let rentals = dir.path().join("rentals");
+    write_files(
+        &rentals,
+        &[
+            ("sdml-package.json", &rentals_manifest("^1.0", "../common")),
+            ("rentals.sdm", &rentals_module("money")),
+        ],
+    );
+
+    let manifest = PackageManifest::load_from(&rentals).unwrap();
+    let lock = manifest.lock().unwrap();
+    let locked = lock.get_package("common").unwrap();
+    assert_eq!(locked.version().to_string(), "1.0.3");
+
+    let catalog = manifest
+        .vendor(
+            Path::new("vendor"),
+            Some(&lock),
+            &mut FsModuleLoader::default(),
+        )
+        .unwrap();
+    assert!(rentals.join("vendor/common/src/money.sdm").is_file());
+    assert_eq!(
+        catalog.resolve_local_path(&String::from("money")),
+        Some(rentals.join("vendor/common/src/money.sdm"))
+    );
+    assert_eq!(
+        catalog
+            .resolve_uri(&String::from("money"))
+            .unwrap()
+            .as_str(),
+        "https://example.org/common/money#"
+    );
+
+    // Changing a dependency invalidates the lock.
+    std::fs::write(dir.path().join("common/src/money.sdm"), INTERNAL).unwrap();
+    assert!(manifest
+        .vendor(
+            Path::new("vendor"),
+            Some(&lock),
+            &mut FsModuleLoader::default()
+        )
+        .is_err());
+}
+
+#[test]
+fn test_dependency_version_mismatch() {
+    let dir = tempfile::tempdir().unwrap();
+    write_files(
+        &dir.path().join("common"),
+        &[
+            ("sdml-package.json", COMMON_MANIFEST),
+            ("src/money.sdm", MONEY),
+            ("src/internal.sdm", INTERNAL),
+        ],
+    );
+    let rentals = dir.path().join("rentals");
+    write_files(
+        &rentals,
+        &[
+            ("sdml-package.json", &rentals_manifest("^2.0", "../common")),
+            ("rentals.sdm", &rentals_module("money")),
+        ],
+    );
+
+    let manifest = PackageManifest::load_from(&rentals).unwrap();
+    assert!(manifest.resolve_dependencies().is_err());
+}
+
+#[test]
+fn test_import_of_unexported_module() {
+    let dir = tempfile::tempdir().unwrap();
+    write_files(
+        &dir.path().join("common"),
+        &[
+            ("sdml-package.json", COMMON_MANIFEST),
+            ("src/money.sdm", MONEY),
+            ("src/internal.sdm", INTERNAL),
+        ],
+    );
+    let rentals = dir.path().join("rentals");
+    write_files(
+        &rentals,
+        &[
+            ("sdml-package.json", &rentals_manifest("^1.0", "../common")),
+            ("rentals.sdm", &rentals_module("internal")),
+        ],
+    );
+
+    let manifest = PackageManifest::load_from(&rentals).unwrap();
+    let packages = manifest.resolve_dependencies().unwrap();
+    assert!(manifest
+        .check_imports(&packages, &mut FsModuleLoader::default())
+        .is_err());
+}
+
+#[test]
+fn test_export_must_be_a_module() {
+    let result = PackageManifest::load_from_str(
+        r#"{
+  "name": "broken",
+  "version": "1.0.0",
+  "base": "https://example.org/broken/",
+  "modules": {},
+  "exports": ["missing"]
+}"#,
+        Path::new("."),
+    );
+    assert!(result.is_err());
+}
@@ -0,0 +1,157 @@
+use sdml_core::model::annotations::HasAnnotations;
+use sdml_core::model::definitions::Definition;
+use sdml_core::model::definitions::HasMembers;
+use sdml_core::model::identifiers::Identifier;
+use sdml_core::model::members::{MemberKind, TypeReference};
+use sdml_core::model::modules::Module;
+use sdml_core::model::walk::passes::{
+    InlineImports, ModulePass, PassChain, QualifyReferences, StripAnnotations,
+};
+use sdml_core::model::{HasBody, HasOptionalBody};
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_parse::load::FsModuleLoader;
+use std::io::Cursor;
+
+const OTHER: &str = r#"module other is
+  import xsd
+
+  datatype Code <- xsd:string
+
+  entity Thing is
+    identity id -> Code
+
+    code -> Code
+  end
+end"#;
+
+const EXAMPLE: &str = r#"module example is
+  import [ dc other:Thing other ]
+
+  @dc:description = "an example"
+
+  entity Example is
+    @dc:description = "the example"
+    assert not_empty = "the name is not empty"
+
+    identity id -> Name
+
+    name -> Name
+    thing -> Thing
+  end
+
+  datatype Name <- sdml:string
+end"#;
+
+fn load(sources: &[&str]) -> (Module, InMemoryModuleCache) {
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let mut loader = FsModuleLoader::default();
+    let mut last = None;
+    for source in sources {
+        last = Some(
+            loader
+                .load_from_reader(&mut Cursor::new(source.as_bytes()), &mut cache, false)
+                .unwrap(),
+        );
+    }
+    let module = cache.get(&last.unwrap()).unwrap().clone();
+    (module, cache)
+}
+
+fn member_type<'a>(module: &'a Module, entity: &str, member: &str) -> &'a TypeReference {
+    let Some(Definition::Entity(entity)) = module.resolve_local(&Identifier::new_unchecked(entity))
+    else {
+        panic!("no entity {entity}");
+    };
+    let member = entity
+        .body()
+        .unwrap()
+        .members()
+        .find(|m| m.name().as_ref() == member)
+        .unwrap();
+    match member.kind() {
+        MemberKind::Definition(def) => def.target_type(),
+        MemberKind::Reference(_) => panic!("member {member:?} is a reference"),
+    }
+}
+
+#[test]
+fn test_strip_annotations() {
+    let (mut module, _) = load(&[OTHER, EXAMPLE]);
+    StripAnnotations::default().apply(&mut module).unwrap();
+
+    assert!(!module.body().has_annotations());
+    let Some(Definition::Entity(entity)) =
+        module.resolve_local(&Identifier::new_unchecked("Example"))
+    else {
+        panic!();
+    };
+    assert!(!entity.body().unwrap().has_annotations());
+}
+
+#[test]
+fn test_strip_annotations_keep_constraints() {
+    let (mut module, _) = load(&[OTHER, EXAMPLE]);
+    StripAnnotations::default()
+        .with_keep_constraints(true)
+        .apply(&mut module)
+        .unwrap();
+
+    assert!(!module.body().has_annotations());
+    let Some(Definition::Entity(entity)) =
+        module.resolve_local(&Identifier::new_unchecked("Example"))
+    else {
+        panic!();
+    };
+    let body = entity.body().unwrap();
+    assert_eq!(body.annotation_properties().count(), 0);
+    assert_eq!(body.annotation_constraints().count(), 1);
+}
+
+#[test]
+fn test_qualify_references() {
+    let (mut module, _) = load(&[OTHER, EXAMPLE]);
+    QualifyReferences::default().apply(&mut module).unwrap();
+
+    assert_eq!(
+        member_type(&module, "Example", "name").to_string(),
+        "example:Name"
+    );
+    assert_eq!(
+        member_type(&module, "Example", "thing").to_string(),
+        "other:Thing"
+    );
+}
+
+#[test]
+fn test_inline_imports() {
+    let (mut module, cache) = load(&[OTHER, EXAMPLE]);
+    let mut passes = PassChain::default()
+        .with_pass(QualifyReferences::default())
+        .with_pass(InlineImports::new(&cache));
+    assert_eq!(
+        passes.pass_names().collect::<Vec<_>>(),
+        ["qualify-references", "inline-imports"]
+    );
+    passes.apply(&mut module).unwrap();
+
+    assert!(module
+        .resolve_local(&Identifier::new_unchecked("Thing"))
+        .is_some());
+    assert_eq!(
+        member_type(&module, "Example", "thing").to_string(),
+        "Thing"
+    );
+    assert_eq!(
+        member_type(&module, "Thing", "code").to_string(),
+        "other:Code"
+    );
+
+    let imported: Vec<String> = module
+        .body()
+        .imports()
+        .flat_map(|s| s.imports())
+        .map(|i| i.to_string())
+        .collect();
+    assert!(!imported.contains(&"other:Thing".to_string()));
+    assert!(imported.contains(&"other".to_string()));
+}
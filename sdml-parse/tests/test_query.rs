@@ -0,0 +1,112 @@
+use sdml_core::query::Query;
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_parse::load::FsModuleLoader;
+use std::io::Cursor;
+use std::str::FromStr;
+
+const RENTALS: &str = r#"module rentals is
+  import [ skos xsd ]
+
+  entity Customer is
+    identity id -> xsd:string
+
+    name -> xsd:string
+    born -> {0..1} xsd:date
+  end
+
+  entity Vehicle is
+    @skos:definition = "a vehicle available for rent"
+
+    identity vin -> xsd:string
+  end
+
+  structure Address is
+    @skos:prefLabel = "Postal Address"
+    assert valid = "the move date is in the past"
+
+    moved -> xsd:date
+    street -> xsd:string
+  end
+
+  enum Status of
+    Open
+    Closed
+  end
+end"#;
+
+fn query(query: &str) -> Vec<String> {
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let mut loader = FsModuleLoader::default();
+    let name = loader
+        .load_from_reader(&mut Cursor::new(RENTALS.as_bytes()), &mut cache, false)
+        .unwrap();
+    Query::from_str(query)
+        .unwrap()
+        .execute([cache.get(&name).unwrap()], &cache)
+        .unwrap()
+        .into_iter()
+        .map(|row| row.path())
+        .collect()
+}
+
+#[test]
+fn test_query_definitions_by_kind() {
+    assert_eq!(query("entity"), ["rentals:Customer", "rentals:Vehicle"]);
+    assert_eq!(
+        query("definition[name ~ e]"),
+        ["rentals:Customer", "rentals:Vehicle", "rentals:Address"]
+    );
+    assert_eq!(query("definition[kind = enum]"), ["rentals:Status"]);
+}
+
+#[test]
+fn test_query_missing_annotation() {
+    assert_eq!(query("entity[!@skos:definition]"), ["rentals:Customer"]);
+    assert_eq!(
+        query(r#"definition[@skos:prefLabel = "Postal Address"]"#),
+        ["rentals:Address"]
+    );
+}
+
+#[test]
+fn test_query_members_by_type() {
+    assert_eq!(
+        query("member[type = xsd:date]"),
+        ["rentals:Customer.born", "rentals:Address.moved"]
+    );
+    assert_eq!(
+        query("structure/member[type = xsd:date]"),
+        ["rentals:Address.moved"]
+    );
+    assert_eq!(
+        query("entity/member[cardinality != {1..1}]"),
+        ["rentals:Customer.born"]
+    );
+}
+
+#[test]
+fn test_query_annotations_and_variants() {
+    assert_eq!(
+        query("annotation[value ~ Postal]"),
+        ["rentals:Address@skos:prefLabel"]
+    );
+    assert_eq!(query("constraint"), ["rentals:Address@valid"]);
+    assert_eq!(
+        query("enum/variant"),
+        ["rentals:Status.Open", "rentals:Status.Closed"]
+    );
+}
+
+#[test]
+fn test_query_syntax_errors() {
+    for query in [
+        "",
+        "entities",
+        "entity[name]",
+        "entity[colour = red]",
+        "entity[@skos:definition",
+        "entity/",
+    ] {
+        assert!(Query::from_str(query).is_err(), "expected error: {query}");
+    }
+}
@@ -0,0 +1,150 @@
+use sdml_core::load::ModuleLoader;
+use sdml_core::model::identifiers::Identifier;
+use sdml_core::refactor::rename::{Rename, RenameTarget};
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_parse::load::FsModuleLoader;
+use std::io::Cursor;
+use std::str::FromStr;
+
+const RENTALS: &str = r#"module rentals is
+  import xsd
+
+  property note -> xsd:string
+
+  ;; The customer renting a vehicle.
+  entity Customer is
+    identity id -> xsd:string
+
+    name -> xsd:string
+  end
+
+  structure Booking is
+    @rentals:note = "a booking"
+    customer -> Customer
+    other -> rentals:Customer
+  end
+
+  union Party of
+    Customer
+    Booking as Reservation
+  end
+end"#;
+
+const BILLING: &str = r#"module billing is
+  import [ rentals:Customer rentals ]
+
+  structure Invoice is
+    ;; who pays
+    payer -> Customer
+    also -> rentals:Customer
+  end
+end"#;
+
+fn load(sources: &[&str]) -> (InMemoryModuleCache, FsModuleLoader) {
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let mut loader = FsModuleLoader::default();
+    for source in sources {
+        let _ = loader
+            .load_from_reader(&mut Cursor::new(source.as_bytes()), &mut cache, false)
+            .unwrap();
+    }
+    (cache, loader)
+}
+
+fn rename(target: &str, new_name: &str) -> Rename {
+    Rename::new(
+        RenameTarget::from_str(target).unwrap(),
+        Identifier::from_str(new_name).unwrap(),
+    )
+}
+
+fn renamed(
+    cache: &InMemoryModuleCache,
+    loader: &FsModuleLoader,
+    rename: &Rename,
+    module: &str,
+) -> String {
+    let plan = rename.plan(cache).unwrap();
+    let name = Identifier::new_unchecked(module);
+    let file_id = cache.get(&name).unwrap().file_id().unwrap();
+    let source = loader.get_source(*file_id).unwrap();
+    plan.apply(&name, source.as_ref())
+}
+
+#[test]
+fn test_rename_definition() {
+    let (cache, loader) = load(&[RENTALS, BILLING]);
+    let rename = rename("rentals:Customer", "Client");
+
+    let plan = rename.plan(&cache).unwrap();
+    assert_eq!(
+        plan.module_names()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>(),
+        ["billing", "rentals"]
+    );
+    assert_eq!(plan.len(), 7);
+    assert_eq!(plan.missing_spans(), 0);
+
+    assert_eq!(
+        renamed(&cache, &loader, &rename, "rentals"),
+        RENTALS.replace("Customer", "Client")
+    );
+    assert_eq!(
+        renamed(&cache, &loader, &rename, "billing"),
+        BILLING.replace("Customer", "Client")
+    );
+}
+
+#[test]
+fn test_rename_annotation_property() {
+    let (cache, loader) = load(&[RENTALS, BILLING]);
+    let rename = rename("rentals:note", "comment");
+
+    assert_eq!(
+        renamed(&cache, &loader, &rename, "rentals"),
+        RENTALS
+            .replace("property note", "property comment")
+            .replace("@rentals:note", "@rentals:comment")
+    );
+}
+
+#[test]
+fn test_rename_member() {
+    let (cache, loader) = load(&[RENTALS, BILLING]);
+    let rename = rename("rentals:Customer.name", "fullName");
+
+    let plan = rename.plan(&cache).unwrap();
+    assert_eq!(plan.len(), 1);
+    assert_eq!(
+        renamed(&cache, &loader, &rename, "rentals"),
+        RENTALS.replace("    name -> xsd:string", "    fullName -> xsd:string")
+    );
+}
+
+#[test]
+fn test_rename_type_variants() {
+    let (cache, loader) = load(&[RENTALS, BILLING]);
+
+    let rename_existing = rename("rentals:Party.Reservation", "Order");
+    assert_eq!(
+        renamed(&cache, &loader, &rename_existing, "rentals"),
+        RENTALS.replace("Booking as Reservation", "Booking as Order")
+    );
+
+    let rename_new = rename("rentals:Party.Customer", "Person");
+    assert_eq!(
+        renamed(&cache, &loader, &rename_new, "rentals"),
+        RENTALS.replace("    Customer\n", "    Customer as Person\n")
+    );
+}
+
+#[test]
+fn test_rename_conflicts() {
+    let (cache, _) = load(&[RENTALS, BILLING]);
+
+    assert!(rename("rentals:Customer", "Booking").plan(&cache).is_err());
+    assert!(rename("rentals:Customer.name", "id").plan(&cache).is_err());
+    assert!(rename("rentals:Unknown", "Other").plan(&cache).is_err());
+    assert!(rename("xsd:string", "text").plan(&cache).is_err());
+}
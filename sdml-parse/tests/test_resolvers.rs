@@ -0,0 +1,218 @@
+#![cfg(all(feature = "archive", feature = "git"))]
+
+use sdml_core::load::ModuleLoader;
+use sdml_core::model::identifiers::Identifier;
+use sdml_core::model::{HasBody, HasName};
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_parse::load::archive::ArchiveModuleResolver;
+use sdml_parse::load::git::GitModuleResolver;
+use sdml_parse::load::mirror::MirrorModuleResolver;
+use sdml_parse::load::{FsModuleLoader, ModuleCatalog, ResolverChain};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use url::Url;
+
+const VEHICLE: &str = r#"module vehicle <https://example.org/rentals/vehicle#> is
+
+  import xsd
+
+  entity Vehicle
+
+end
+"#;
+
+const RENTALS: &str = r#"module rentals <https://example.org/rentals/rentals#> is
+
+  import vehicle
+
+  entity Rental
+
+end
+"#;
+
+fn load_rentals(chain: ResolverChain) -> (FsModuleLoader, InMemoryModuleCache) {
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let mut loader = FsModuleLoader::default().with_resolver(chain);
+    let name = Identifier::from_str("rentals").unwrap();
+    let loaded = loader.load(&name, None, &mut cache, true).unwrap();
+    assert_eq!(loaded, name);
+    assert!(!loader.has_unresolved_modules());
+    (loader, cache)
+}
+
+#[test]
+fn test_resolve_from_zip_archive() {
+    let dir = tempfile::tempdir().unwrap();
+    let archive = dir.path().join("rentals.zip");
+    let mut writer = zip::ZipWriter::new(File::create(&archive).unwrap());
+    let options =
+        zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    writer.start_file("rentals.sdm", options).unwrap();
+    writer.write_all(RENTALS.as_bytes()).unwrap();
+    writer.start_file("vehicle/vehicle.sdm", options).unwrap();
+    writer.write_all(VEHICLE.as_bytes()).unwrap();
+    writer.finish().unwrap();
+
+    let chain =
+        ResolverChain::empty().with_resolver(ArchiveModuleResolver::open(&archive).unwrap());
+    let (_, cache) = load_rentals(chain);
+
+    let vehicle = cache
+        .get(&Identifier::from_str("vehicle").unwrap())
+        .unwrap();
+    assert_eq!(
+        vehicle.source_file(),
+        Some(&archive.join("vehicle/vehicle.sdm"))
+    );
+}
+
+#[test]
+fn test_resolve_from_tar_archive_with_catalog() {
+    let dir = tempfile::tempdir().unwrap();
+    let archive = dir.path().join("rentals.tar.gz");
+    let encoder = flate2::write::GzEncoder::new(
+        File::create(&archive).unwrap(),
+        flate2::Compression::default(),
+    );
+    let mut builder = tar::Builder::new(encoder);
+    let catalog = r#"{
+  "base": "https://example.org/rentals/",
+  "entries": {
+    "vehicle": { "item": { "relative_url": "vehicle#", "relative_path": "src/vehicle-v1.sdm" } }
+  }
+}"#;
+    for (path, content) in [
+        ("sdml-catalog.json", catalog),
+        ("rentals.sdm", RENTALS),
+        ("src/vehicle-v1.sdm", VEHICLE),
+    ] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, path, content.as_bytes())
+            .unwrap();
+    }
+    builder.into_inner().unwrap().finish().unwrap();
+
+    let resolver = ArchiveModuleResolver::open(&archive).unwrap();
+    assert!(resolver.has_catalog());
+    let chain = ResolverChain::empty().with_resolver(resolver);
+    let (_, cache) = load_rentals(chain);
+
+    let vehicle = cache
+        .get(&Identifier::from_str("vehicle").unwrap())
+        .unwrap();
+    assert_eq!(
+        vehicle.source_file(),
+        Some(&archive.join("src/vehicle-v1.sdm"))
+    );
+}
+
+#[test]
+fn test_resolve_from_git_reference() {
+    let dir = tempfile::tempdir().unwrap();
+    let repository = git2::Repository::init(dir.path()).unwrap();
+    let signature = git2::Signature::now("test", "test@example.org").unwrap();
+
+    let commit = |files: &[(&str, &str)], message: &str| {
+        let mut index = repository.index().unwrap();
+        for (path, content) in files {
+            let full_path = dir.path().join(path);
+            std::fs::create_dir_all(full_path.parent().unwrap()).unwrap();
+            std::fs::write(&full_path, content).unwrap();
+            index.add_path(Path::new(path)).unwrap();
+        }
+        index.write().unwrap();
+        let tree = repository.find_tree(index.write_tree().unwrap()).unwrap();
+        let parents = repository
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit<'_>> = parents.iter().collect();
+        repository
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                message,
+                &tree,
+                &parents,
+            )
+            .unwrap()
+    };
+
+    let first = commit(
+        &[("rentals.sdm", RENTALS), ("vehicle.sdm", VEHICLE)],
+        "first",
+    );
+    repository
+        .tag_lightweight("v1", &repository.find_object(first, None).unwrap(), false)
+        .unwrap();
+    // The working tree, and HEAD, no longer contain a valid vehicle module.
+    commit(&[("vehicle.sdm", "module vehicle is")], "second");
+
+    let chain =
+        ResolverChain::empty().with_resolver(GitModuleResolver::open(dir.path(), "v1").unwrap());
+    let (_, cache) = load_rentals(chain);
+
+    let vehicle = cache
+        .get(&Identifier::from_str("vehicle").unwrap())
+        .unwrap();
+    assert_eq!(vehicle.body().definitions().count(), 1);
+    assert_eq!(
+        vehicle.source_file(),
+        Some(&dir.path().join("v1:vehicle.sdm"))
+    );
+
+    assert!(GitModuleResolver::open(dir.path(), "no-such-tag").is_err());
+}
+
+#[test]
+fn test_resolve_from_mirror_in_priority_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let mirror = dir.path().join("mirror");
+    let rentals_dir = mirror.join("example.org/rentals");
+    std::fs::create_dir_all(&rentals_dir).unwrap();
+    std::fs::write(rentals_dir.join("rentals.sdm"), RENTALS).unwrap();
+    std::fs::write(rentals_dir.join("vehicle.sdm"), VEHICLE).unwrap();
+
+    let catalog = ModuleCatalog::load_from_str(
+        r#"{
+  "base": "https://example.org/rentals/",
+  "entries": {
+    "rentals": { "item": { "relative_url": "rentals", "relative_path": "rentals.sdm" } },
+    "vehicle": { "item": { "relative_url": "vehicle#", "relative_path": "vehicle.sdm" } }
+  }
+}"#,
+        dir.path(),
+    )
+    .unwrap();
+
+    // An empty archive earlier in the chain must not prevent the mirror being consulted.
+    let archive = dir.path().join("empty.zip");
+    zip::ZipWriter::new(File::create(&archive).unwrap())
+        .finish()
+        .unwrap();
+
+    let chain = ResolverChain::empty()
+        .with_resolver(ArchiveModuleResolver::open(&archive).unwrap())
+        .with_resolver(MirrorModuleResolver::new(&mirror, catalog));
+    let (_, cache) = load_rentals(chain);
+
+    let vehicle = cache
+        .get(&Identifier::from_str("vehicle").unwrap())
+        .unwrap();
+    assert_eq!(vehicle.name().as_ref(), "vehicle");
+    assert_eq!(
+        vehicle.base_uri().map(|uri| uri.value()),
+        Some(&Url::parse("https://example.org/rentals/vehicle#").unwrap())
+    );
+    assert_eq!(
+        vehicle.source_file(),
+        Some(&rentals_dir.join("vehicle.sdm"))
+    );
+}
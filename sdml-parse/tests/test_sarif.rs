@@ -0,0 +1,120 @@
+use sdml_core::load::ModuleLoader;
+use sdml_core::model::HasName;
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_errors::diagnostics::reporter::SarifReporter;
+use sdml_errors::diagnostics::SeverityFilter;
+use sdml_parse::load::FsModuleLoader;
+use serde_json::Value;
+use std::cell::RefCell;
+use std::io::{Cursor, Write};
+use std::rc::Rc;
+
+const DUPLICATE_MEMBER: &str = r#"module example <https://example.com/api> is
+
+  structure Foo is
+    foo -> string
+    bar -> integer
+    foo -> boolean
+  end
+
+end"#;
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn validate_to_sarif(source: &str) -> Value {
+    let buffer = SharedBuffer::default();
+    let reporter = SarifReporter::new(buffer.clone())
+        .with_tool("sdml", "1.2.3")
+        .with_severity_filter(SeverityFilter::Help);
+    let mut loader = FsModuleLoader::default().with_reporter(Box::new(reporter));
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let name = loader
+        .load_from_reader(&mut Cursor::new(source.as_bytes()), &mut cache, true)
+        .unwrap();
+    let module = cache.get(&name).unwrap();
+    module.validate(&cache, &loader, false);
+    let counters = loader
+        .reporter_done(Some(module.name().to_string()))
+        .unwrap();
+    assert_eq!(counters.errors(), 1);
+
+    let output = buffer.0.borrow();
+    serde_json::from_slice(&output).unwrap()
+}
+
+#[test]
+fn test_sarif_log_structure() {
+    let log = validate_to_sarif(DUPLICATE_MEMBER);
+    assert_eq!(log["version"], "2.1.0");
+
+    let runs = log["runs"].as_array().unwrap();
+    assert_eq!(runs.len(), 1);
+    let driver = &runs[0]["tool"]["driver"];
+    assert_eq!(driver["name"], "sdml");
+    assert_eq!(driver["version"], "1.2.3");
+
+    let rules = driver["rules"].as_array().unwrap();
+    let rule = rules.iter().find(|rule| rule["id"] == "E0105").unwrap();
+    assert_eq!(rule["name"], "DuplicateMemberName");
+    assert_eq!(rule["helpUri"], "https://sdml.io/errors/#E0105");
+    assert_eq!(rule["defaultConfiguration"]["level"], "error");
+    assert_eq!(rule["properties"]["number"], 105);
+}
+
+#[test]
+fn test_sarif_result_locations() {
+    let log = validate_to_sarif(DUPLICATE_MEMBER);
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    let result = results
+        .iter()
+        .find(|result| result["ruleId"] == "E0105")
+        .unwrap();
+    assert_eq!(result["level"], "error");
+
+    let region = &result["locations"][0]["physicalLocation"]["region"];
+    assert_eq!(region["startLine"], 6);
+    assert_eq!(region["startColumn"], 5);
+    assert_eq!(region["endLine"], 6);
+    assert_eq!(region["endColumn"], 8);
+    assert_eq!(region["byteLength"], 3);
+
+    let related = &result["relatedLocations"][0]["physicalLocation"]["region"];
+    assert_eq!(related["startLine"], 4);
+}
+
+#[test]
+fn test_sarif_result_fixes() {
+    let log = validate_to_sarif(
+        r#"module example is
+  import [ xsd xsd ]
+
+  structure Foo is
+    foo -> xsd:string
+    foo -> xsd:string
+  end
+end"#,
+    );
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    let result = results
+        .iter()
+        .find(|result| result["ruleId"] == "W0301")
+        .unwrap();
+    // suggestions are not also reported as related locations.
+    assert_eq!(result["relatedLocations"].as_array().unwrap().len(), 1);
+
+    let replacement = &result["fixes"][0]["artifactChanges"][0]["replacements"][0];
+    assert_eq!(replacement["deletedRegion"]["byteOffset"], 32);
+    assert_eq!(replacement["deletedRegion"]["byteLength"], 4);
+    assert_eq!(replacement["insertedContent"]["text"], "");
+}
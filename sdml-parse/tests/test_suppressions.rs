@@ -0,0 +1,143 @@
+use sdml_core::load::ModuleLoader;
+use sdml_core::model::check::suppressions::module_suppressions;
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_errors::diagnostics::functions::{
+    identifier_not_preferred_case, string_without_language, IdentifierCaseConvention,
+};
+use sdml_errors::diagnostics::reporter::CollectingReporter;
+use sdml_parse::load::FsModuleLoader;
+use std::io::Cursor;
+
+const LEGACY: &str = r#"module Legacy is
+
+  import [ sdml xsd xsd ]
+
+  @sdml:suppress = [ "W0301" "I0503" ]
+
+  entity access_record is
+    @sdml:suppress = "I0506"
+    identity ID -> string
+  end
+
+  structure other_thing is
+    Name -> string is
+      @sdml:suppress = "I0506"
+    end
+  end
+
+end
+"#;
+
+fn validate_codes(source: &str, report_unused: bool) -> Vec<String> {
+    let reporter = CollectingReporter::default();
+    let mut loader = FsModuleLoader::default().with_reporter(Box::new(reporter.clone()));
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let name = loader
+        .load_from_reader(&mut Cursor::new(source.as_bytes()), &mut cache, true)
+        .unwrap();
+    let module = cache.get(&name).unwrap();
+    module.validate(&cache, &loader, false);
+    if report_unused {
+        loader
+            .report_unused_suppressions(*module.file_id().unwrap())
+            .unwrap();
+    }
+
+    reporter
+        .take_diagnostics()
+        .into_iter()
+        .filter_map(|diagnostic| diagnostic.code)
+        .collect()
+}
+
+#[test]
+fn test_module_suppressions() {
+    let reporter = CollectingReporter::default();
+    let mut loader = FsModuleLoader::default().with_reporter(Box::new(reporter));
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let name = loader
+        .load_from_reader(&mut Cursor::new(LEGACY.as_bytes()), &mut cache, true)
+        .unwrap();
+    let suppressions = module_suppressions(cache.get(&name).unwrap());
+
+    let codes: Vec<Vec<&str>> = suppressions
+        .iter()
+        .map(|suppression| suppression.codes().map(|code| code.code()).collect())
+        .collect();
+    assert_eq!(
+        codes,
+        vec![vec!["W0301", "I0503"], vec!["I0506"], vec!["I0506"]]
+    );
+}
+
+#[test]
+fn test_suppressed_in_scope() {
+    let codes = validate_codes(LEGACY, false);
+
+    // the parser's duplicate import warning is suppressed by the module annotation.
+    assert!(!codes.contains(&"W0301".to_string()));
+    // the entity, its identity member, and the structure member names are suppressed; the
+    // module and structure names are not.
+    assert_eq!(
+        codes.iter().filter(|code| code.as_str() == "I0506").count(),
+        2
+    );
+}
+
+#[test]
+fn test_errors_not_suppressed() {
+    let source = r#"module example is
+  @sdml:suppress = "E0114"
+
+  structure Thing is
+    name -> Missing
+  end
+end
+"#;
+    let codes = validate_codes(source, false);
+
+    assert!(codes.contains(&"E0114".to_string()));
+}
+
+#[test]
+fn test_report_unused_suppressions() {
+    let codes = validate_codes(LEGACY, true);
+
+    // there are no string values for I0503 to suppress.
+    assert_eq!(
+        codes.iter().filter(|code| code.as_str() == "W0307").count(),
+        1
+    );
+}
+
+#[test]
+fn test_module_scope_suppresses_unlocated() {
+    let reporter = CollectingReporter::default();
+    let mut loader = FsModuleLoader::default().with_reporter(Box::new(reporter.clone()));
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let name = loader
+        .load_from_reader(&mut Cursor::new(LEGACY.as_bytes()), &mut cache, true)
+        .unwrap();
+    let file_id = *cache.get(&name).unwrap().file_id().unwrap();
+    let _ = reporter.take_diagnostics();
+
+    // the module suppresses I0503 and I0506 only within the entity, neither has a location.
+    loader
+        .report(&string_without_language(file_id, None, "legacy"))
+        .unwrap();
+    loader
+        .report(&identifier_not_preferred_case(
+            file_id,
+            None,
+            "AccessRecord",
+            IdentifierCaseConvention::TypeDefinition,
+        ))
+        .unwrap();
+
+    let codes: Vec<String> = reporter
+        .take_diagnostics()
+        .into_iter()
+        .filter_map(|diagnostic| diagnostic.code)
+        .collect();
+    assert_eq!(codes, vec!["I0506".to_string()]);
+}
@@ -0,0 +1,189 @@
+use sdml_core::model::check::terms::{default_term_set, validate_module_terms, Term, TermSet};
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_errors::diagnostics::reporter::CollectingReporter;
+use sdml_errors::Error;
+use sdml_parse::lint::load_term_set;
+use sdml_parse::load::FsModuleLoader;
+use std::io::Cursor;
+use std::path::Path;
+
+const TERM_MODULE: &str = r#"module project_terms <https://example.com/terms/>
+  version "2" <https://example.com/terms/v2/> is
+
+  import [ dc skos ]
+
+  @skos:prefLabel = "Project Terms"@en
+  @dc:description = "Terms the project has retired."@en
+
+  structure Widget is
+    @skos:altLabel = [ "Component"@en "Part"@en ]
+    @skos:note = "Too vague."@en
+  end
+
+  structure Thingy is
+    @skos:prefLabel = "thing"@en
+    @skos:hiddenLabel = "(?i)\bthing(y)?\b"
+    @skos:altLabel = "Item"@en
+  end
+
+  structure Unrelated is
+    @skos:note = "Not a term, it has no alternatives."@en
+  end
+
+end
+"#;
+
+fn load_module(
+    source: &str,
+    cache: &mut InMemoryModuleCache,
+) -> sdml_core::model::identifiers::Identifier {
+    FsModuleLoader::default()
+        .with_reporter(Box::new(CollectingReporter::default()))
+        .load_from_reader(&mut Cursor::new(source.as_bytes()), cache, false)
+        .unwrap()
+}
+
+#[test]
+fn test_term_set_from_module() {
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let name = load_module(TERM_MODULE, &mut cache);
+    let term_set = TermSet::from_module(cache.get(&name).unwrap()).unwrap();
+
+    assert_eq!(term_set.name(), "Project Terms");
+    assert_eq!(
+        term_set.description().map(String::as_str),
+        Some("Terms the project has retired.")
+    );
+    assert_eq!(term_set.version().map(String::as_str), Some("2"));
+    assert_eq!(term_set.len(), 2);
+
+    let widget = term_set.get("Widget").unwrap();
+    assert_eq!(widget.alternative_terms(), &vec!["Component", "Part"]);
+    assert_eq!(widget.reason().map(String::as_str), Some("Too vague."));
+    assert!(widget.regex().is_none());
+
+    let thing = term_set.get("thing").unwrap();
+    assert_eq!(thing.alternative_terms(), &vec!["Item"]);
+    assert!(thing.regex().unwrap().is_match("a Thingy"));
+}
+
+#[test]
+fn test_term_set_from_module_invalid_regex() {
+    let source = TERM_MODULE.replace(r"(?i)\bthing(y)?\b", "(thing");
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let name = load_module(&source, &mut cache);
+    assert!(TermSet::from_module(cache.get(&name).unwrap()).is_err());
+}
+
+#[test]
+fn test_merge_term_sets() {
+    let first = TermSet::new("first")
+        .with_term("widget", Term::new(["component"]))
+        .with_term("gadget", Term::new(["device"]));
+    let second = TermSet::new("second")
+        .with_version("1")
+        .with_term("widget", Term::new(["part"]).with_reason("replaced"))
+        .with_term("doohickey", Term::new(["tool"]));
+
+    let merged = TermSet::merged([first, second]).unwrap();
+    assert_eq!(merged.name(), "first");
+    assert!(merged.version().is_none());
+    assert_eq!(merged.len(), 3);
+    assert_eq!(
+        merged.get("widget").unwrap().alternative_terms(),
+        &vec!["part"]
+    );
+    assert!(merged.get("gadget").is_some());
+
+    assert!(TermSet::merged(Vec::default()).is_none());
+
+    let mut with_default = default_term_set().unwrap();
+    let default_len = with_default.len();
+    with_default.merge(TermSet::new("empty"));
+    assert_eq!(with_default.len(), default_len);
+}
+
+#[test]
+fn test_load_term_sets_from_files() {
+    let directory = tempfile::tempdir().unwrap();
+    let json_path = directory.path().join("terms.json");
+    std::fs::write(
+        &json_path,
+        r#"{ "name": "JSON Terms", "terms": { "gizmo": { "alternative_terms": ["Device"] } } }"#,
+    )
+    .unwrap();
+    let sdml_path = directory.path().join("project_terms.sdm");
+    std::fs::write(&sdml_path, TERM_MODULE).unwrap();
+
+    let reporter = CollectingReporter::default();
+    let mut loader = FsModuleLoader::default().with_reporter(Box::new(reporter.clone()));
+    let json_terms = load_term_set(&json_path, &mut loader).unwrap();
+    assert_eq!(json_terms.name(), "JSON Terms");
+    let sdml_terms = load_term_set(&sdml_path, &mut loader).unwrap();
+    assert_eq!(sdml_terms.name(), "Project Terms");
+    assert!(load_term_set(Path::new("no/such/terms.json"), &mut loader).is_err());
+
+    let term_set = TermSet::merged([json_terms, sdml_terms]).unwrap();
+    assert_eq!(term_set.len(), 3);
+
+    let reporter = CollectingReporter::default();
+    let mut loader = FsModuleLoader::default().with_reporter(Box::new(reporter.clone()));
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let name = loader
+        .load_from_reader(
+            &mut Cursor::new(
+                "module example is\n  structure Gizmo is\n    part -> Widget\n  end\n  structure Widget\nend\n"
+                    .as_bytes(),
+            ),
+            &mut cache,
+            false,
+        )
+        .unwrap();
+    validate_module_terms(cache.get(&name).unwrap(), &term_set, &loader);
+    let deprecated = reporter
+        .take_diagnostics()
+        .into_iter()
+        .filter(|diagnostic| diagnostic.code.as_deref() == Some("W0305"))
+        .count();
+    assert!(deprecated >= 2);
+}
+
+#[test]
+fn test_load_invalid_term_sets() {
+    let directory = tempfile::tempdir().unwrap();
+    let sdml_path = directory.path().join("bad_terms.sdm");
+    std::fs::write(
+        &sdml_path,
+        TERM_MODULE.replace(r#"(?i)\bthing(y)?\b"#, "(thing"),
+    )
+    .unwrap();
+    let json_path = directory.path().join("bad_terms.json");
+    std::fs::write(&json_path, r#"{ "terms": {} }"#).unwrap();
+
+    let reporter = CollectingReporter::default();
+    let mut loader = FsModuleLoader::default().with_reporter(Box::new(reporter.clone()));
+    match load_term_set(&sdml_path, &mut loader) {
+        Err(Error::TermSetError { name, message }) => {
+            assert_eq!(name, "Project Terms");
+            assert!(message.contains("`Thingy`"));
+        }
+        result => panic!("expected a term set error, not {result:?}"),
+    }
+    assert!(matches!(
+        load_term_set(&json_path, &mut loader),
+        Err(Error::TermSetError { .. })
+    ));
+
+    // diagnostics from loading the module go to the loader's reporter.
+    std::fs::write(
+        &sdml_path,
+        "module bad_terms is\n  import [ skos skos ]\nend\n",
+    )
+    .unwrap();
+    let term_set = load_term_set(&sdml_path, &mut loader).unwrap();
+    assert!(term_set.is_empty());
+    assert!(reporter
+        .take_diagnostics()
+        .iter()
+        .any(|diagnostic| diagnostic.code.as_deref() == Some("W0301")));
+}
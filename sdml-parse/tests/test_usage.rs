@@ -0,0 +1,193 @@
+use sdml_core::model::check::usage::{
+    module_set, prune_module, unused_imports, validate_module_usage, Reachability,
+};
+use sdml_core::model::identifiers::{Identifier, IdentifierReference, QualifiedIdentifier};
+use sdml_core::model::{HasBody, HasName};
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_errors::diagnostics::reporter::CollectingReporter;
+use sdml_parse::load::FsModuleLoader;
+use std::io::Cursor;
+use std::str::FromStr;
+
+const ADDRESSES: &str = r#"module addresses <https://example.com/addresses> is
+
+  structure Address is
+    street -> string
+    country -> Country
+  end
+
+  structure Country is
+    code -> string
+  end
+
+  structure PostBox is
+    number -> string
+  end
+
+end
+"#;
+
+const CUSTOMERS: &str = r#"module customers <https://example.com/customers> is
+
+  import [ addresses dc skos xsd:string rdfs:Class ]
+
+  @skos:prefLabel = "Customers"@en
+
+  entity Customer is
+    @dc:description = "A customer"@en
+    identity id -> string
+    address -> addresses:Address
+    tier -> Tier
+  end
+
+  enum Tier of
+    Gold
+    Silver
+  end
+
+  union Payment of
+    Card
+    Cash
+  end
+
+  structure Card
+  structure Cash
+
+  event CustomerMoved source Customer
+
+end
+"#;
+
+fn load(loader: &mut FsModuleLoader, cache: &mut InMemoryModuleCache, source: &str) -> Identifier {
+    loader
+        .load_from_reader(&mut Cursor::new(source.as_bytes()), cache, false)
+        .unwrap()
+}
+
+fn loaded() -> (
+    FsModuleLoader,
+    CollectingReporter,
+    InMemoryModuleCache,
+    Identifier,
+) {
+    let reporter = CollectingReporter::default();
+    let mut loader = FsModuleLoader::default().with_reporter(Box::new(reporter.clone()));
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    load(&mut loader, &mut cache, ADDRESSES);
+    let name = load(&mut loader, &mut cache, CUSTOMERS);
+    let _ = reporter.take_diagnostics();
+    (loader, reporter, cache, name)
+}
+
+fn root(name: &str) -> IdentifierReference {
+    IdentifierReference::from_str(name).unwrap()
+}
+
+fn names(definitions: Vec<&sdml_core::model::definitions::Definition>) -> Vec<String> {
+    definitions
+        .into_iter()
+        .map(|definition| definition.name().to_string())
+        .collect()
+}
+
+#[test]
+fn test_unused_imports() {
+    let (_, _, cache, name) = loaded();
+    let module = cache.get(&name).unwrap();
+
+    let unused: Vec<String> = unused_imports(module)
+        .into_iter()
+        .map(|import| import.to_string())
+        .collect();
+    // `string` is the built-in `sdml:string`, so the member import of `xsd:string` is unused.
+    assert_eq!(unused, vec!["xsd:string", "rdfs:Class"]);
+}
+
+#[test]
+fn test_module_set() {
+    let (_, _, cache, name) = loaded();
+    let module = cache.get(&name).unwrap();
+
+    let set: Vec<String> = module_set(module, &cache)
+        .into_iter()
+        .map(|module| module.name().to_string())
+        .collect();
+    assert_eq!(set, vec!["customers", "addresses"]);
+}
+
+#[test]
+fn test_reachability_across_modules() {
+    let (_, _, cache, name) = loaded();
+    let customers = cache.get(&name).unwrap();
+    let addresses = cache
+        .get(&Identifier::from_str("addresses").unwrap())
+        .unwrap();
+
+    let reachability = Reachability::from_roots([root("CustomerMoved")], &name, &cache);
+    assert_eq!(reachability.unresolved_roots().count(), 0);
+    assert!(reachability.is_reachable(&QualifiedIdentifier::from_str("addresses:Country").unwrap()));
+    assert!(reachability.is_reachable(&QualifiedIdentifier::from_str("dc:description").unwrap()));
+
+    assert_eq!(
+        names(reachability.unreachable_definitions(customers)),
+        vec!["Payment", "Card", "Cash"]
+    );
+    assert_eq!(
+        names(reachability.unreachable_definitions(addresses)),
+        vec!["PostBox"]
+    );
+
+    let reachability = Reachability::from_roots([root("Payment")], &name, &cache);
+    assert!(reachability.is_reachable(&QualifiedIdentifier::from_str("customers:Cash").unwrap()));
+
+    let reachability =
+        Reachability::from_roots([root("Missing"), root("addresses:Nope")], &name, &cache);
+    assert_eq!(reachability.unresolved_roots().count(), 2);
+    assert_eq!(reachability.reached().count(), 0);
+}
+
+#[test]
+fn test_validate_module_usage() {
+    let (loader, reporter, cache, name) = loaded();
+    let module = cache.get(&name).unwrap();
+
+    let reachability = Reachability::from_roots([root("Customer")], &name, &cache);
+    for module in module_set(module, &cache) {
+        validate_module_usage(module, Some(&reachability), &loader).unwrap();
+    }
+    let codes: Vec<String> = reporter
+        .take_diagnostics()
+        .into_iter()
+        .filter_map(|diagnostic| diagnostic.code)
+        .collect();
+    assert_eq!(codes.iter().filter(|code| *code == "W0308").count(), 2);
+    // Payment, Card, Cash, CustomerMoved, and PostBox.
+    assert_eq!(codes.iter().filter(|code| *code == "I0512").count(), 5);
+}
+
+#[test]
+fn test_prune_module() {
+    let (_, _, cache, name) = loaded();
+    let module = cache.get(&name).unwrap();
+
+    let pruned = prune_module(module, None);
+    assert_eq!(
+        pruned.body().definitions_len(),
+        module.body().definitions_len()
+    );
+    assert!(unused_imports(&pruned).is_empty());
+
+    let reachability = Reachability::from_roots([root("Customer")], &name, &cache);
+    let pruned = prune_module(module, Some(&reachability));
+    assert_eq!(
+        names(pruned.body().definitions().collect()),
+        vec!["Customer", "Tier"]
+    );
+    let imported: Vec<String> = pruned
+        .body()
+        .imports()
+        .flat_map(|statement| statement.imports())
+        .map(|import| import.to_string())
+        .collect();
+    assert_eq!(imported, vec!["addresses", "dc", "skos"]);
+}
@@ -8,10 +8,10 @@ More detailed description, with
 
  */
 
-use rdftk_core::model::graph::{Graph, PrefixMapping};
+use rdftk_core::model::graph::Graph;
 use rdftk_core::model::literal::{DataType, Literal};
 use rdftk_core::model::statement::{BlankNode, Statement, SubjectNode};
-use rdftk_iri::Name;
+use rdftk_iri::{IriPrefixMap, Name};
 use sdml_core::model::annotations::{Annotation, AnnotationProperty, HasAnnotations};
 use sdml_core::model::constraints::Constraint;
 use sdml_core::model::definitions::{
@@ -63,8 +63,7 @@ pub fn module_to_graph_with_options(
     cache: &impl ModuleStore,
     options: RdfGeneratorOptions,
 ) -> Result<Graph, ApiError> {
-    println!("{module:#?}");
-    let mappings = PrefixMapping::common().with(
+    let mappings = IriPrefixMap::common().with(
         Name::from_str(MODULE_NAME).unwrap(),
         Url::from_str(MODULE_URL)?,
     );
@@ -75,6 +74,24 @@ pub fn module_to_graph_with_options(
     Ok(graph)
 }
 
+///
+/// Project every module in `store` that has a base URI into a single, union, graph. Modules
+/// without a base URI have no IRIs for their definitions and are skipped.
+///
+pub fn store_to_graph(store: &impl ModuleStore) -> Result<Graph, ApiError> {
+    let mappings = IriPrefixMap::common().with(
+        Name::from_str(MODULE_NAME).unwrap(),
+        Url::from_str(MODULE_URL)?,
+    );
+    let mut graph = Graph::default().with_mappings(mappings);
+
+    for module in store.modules().filter(|module| module.base_uri().is_some()) {
+        add_module_to_graph(module, store, &mut graph)?;
+    }
+
+    Ok(graph)
+}
+
 pub fn add_module_to_graph(
     module: &Module,
     cache: &impl ModuleStore,
@@ -79,3 +79,5 @@ mod errors;
 pub mod parse;
 
 pub mod generate;
+
+pub mod sparql;
@@ -0,0 +1,1261 @@
+/*!
+Provides an embedded SPARQL query engine over the RDF projection of loaded modules.
+
+The engine implements the `SELECT`, `ASK`, and `CONSTRUCT` query forms of SPARQL 1.1 over a
+single in-memory [`Graph`], usually the union graph of every module in a store created with
+[`store_to_graph`]. The following subset of the query language is supported:
+
+* `BASE` and `PREFIX` declarations.
+* `SELECT` with `DISTINCT`, or `REDUCED`, and either `*` or a list of variables.
+* `CONSTRUCT` with a template, or the short form `CONSTRUCT WHERE { ... }`.
+* Basic graph patterns, with the `;` and `,` abbreviations, `a` for `rdf:type`, and blank nodes
+  as `_:label` or `[]`.
+* Group graph patterns with `OPTIONAL`, `UNION`, `MINUS`, and `FILTER`.
+* Filter expressions with `||`, `&&`, `!`, the comparison operators, and the functions `BOUND`,
+  `isIRI`, `isURI`, `isBlank`, `isLiteral`, `STR`, `LANG`, `DATATYPE`, `sameTerm`,
+  `langMatches`, `CONTAINS`, `STRSTARTS`, `STRENDS`, `STRLEN`, `LCASE`, `UCASE`, and `REGEX`;
+  the pattern and flags of `REGEX` must be simple literals, and are compiled when the query is
+  parsed.
+* The solution modifiers `ORDER BY`, `LIMIT`, and `OFFSET`.
+
+Property paths, aggregates, sub-queries, `BIND`, `VALUES`, and datasets (`FROM`) are not
+supported and are reported as errors when the query is parsed.
+
+# Example
+
+```rust
+use sdml_core::model::identifiers::Identifier;
+use sdml_core::model::modules::Module;
+use sdml_core::store::{InMemoryModuleCache, ModuleStore};
+use sdml_rdf::generate::store_to_graph;
+use sdml_rdf::sparql::{Query, QueryResults};
+use std::str::FromStr;
+use url::Url;
+
+let mut module = Module::empty(Identifier::new_unchecked("example"));
+module.set_base_uri(Url::parse("https://example.org/example/").unwrap().into());
+let mut store = InMemoryModuleCache::default();
+store.insert(module);
+
+let graph = store_to_graph(&store).unwrap();
+let query = Query::from_str(
+    "PREFIX owl: <http://www.w3.org/2002/07/owl#>
+     SELECT ?ontology WHERE { ?ontology a owl:Ontology }",
+)
+.unwrap();
+
+if let QueryResults::Solutions { solutions, .. } = query.execute(&graph).unwrap() {
+    assert_eq!(
+        solutions[0].get("ontology").unwrap().to_string(),
+        "<https://example.org/example/example>"
+    );
+}
+```
+
+[`store_to_graph`]: crate::generate::store_to_graph
+*/
+
+use rdftk_core::model::graph::Graph;
+use rdftk_core::model::literal::{LanguageTag, Literal};
+use rdftk_core::model::statement::{BlankNode, ObjectNode, Statement, SubjectNode};
+use regex::{Regex, RegexBuilder};
+use sdml_errors::Error;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Display;
+use std::str::FromStr;
+use url::Url;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A parsed SPARQL query, see the module documentation for the supported subset.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Query {
+    source: String,
+    form: QueryForm,
+    pattern: GroupPattern,
+    modifiers: Modifiers,
+}
+
+///
+/// An RDF term, as bound to a variable in a solution.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Term {
+    Iri(Url),
+    BlankNode(String),
+    Literal {
+        lexical_form: String,
+        data_type: Option<Url>,
+        language: Option<String>,
+    },
+}
+
+///
+/// A single solution, the bindings of variable names, without `?`, to terms.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Solution(BTreeMap<String, Term>);
+
+///
+/// The results of executing a query, which depend on the query form.
+///
+#[derive(Debug)]
+pub enum QueryResults {
+    /// The results of a `SELECT` query, the projected variables and the solutions in order.
+    Solutions {
+        variables: Vec<String>,
+        solutions: Vec<Solution>,
+    },
+    /// The result of an `ASK` query.
+    Boolean(bool),
+    /// The result of a `CONSTRUCT` query.
+    Graph(Graph),
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+enum QueryForm {
+    Select {
+        distinct: bool,
+        variables: Option<Vec<String>>,
+    },
+    Ask,
+    Construct {
+        template: Vec<TriplePattern>,
+    },
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Modifiers {
+    order_by: Vec<OrderCondition>,
+    limit: Option<usize>,
+    offset: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct OrderCondition {
+    expression: Expression,
+    descending: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct GroupPattern(Vec<PatternElement>);
+
+#[derive(Clone, Debug, PartialEq)]
+enum PatternElement {
+    Triple(Box<TriplePattern>),
+    Group(GroupPattern),
+    Optional(GroupPattern),
+    Union(Vec<GroupPattern>),
+    Minus(GroupPattern),
+    Filter(Expression),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct TriplePattern {
+    subject: TermPattern,
+    predicate: TermPattern,
+    object: TermPattern,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum TermPattern {
+    Variable(String),
+    Term(Term),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expression {
+    Or(Box<Expression>, Box<Expression>),
+    And(Box<Expression>, Box<Expression>),
+    Not(Box<Expression>),
+    Compare(Comparison, Box<Expression>, Box<Expression>),
+    Function(Function, Vec<Expression>),
+    Regex(Box<Expression>, Box<RegexPattern>),
+    Variable(String),
+    Term(Term),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Function {
+    Bound,
+    IsIri,
+    IsBlank,
+    IsLiteral,
+    Str,
+    Lang,
+    Datatype,
+    SameTerm,
+    LangMatches,
+    Contains,
+    StrStarts,
+    StrEnds,
+    StrLen,
+    LCase,
+    UCase,
+    Regex,
+}
+
+// The pattern of a REGEX call, compiled when the query is parsed.
+#[derive(Clone, Debug)]
+struct RegexPattern {
+    pattern: String,
+    flags: String,
+    regex: Regex,
+}
+
+// The graph's statements as terms, statements with collection or statement objects are ignored.
+// Each statement is indexed by its subject, predicate, and object.
+struct Triples {
+    triples: Vec<[Term; 3]>,
+    index: [HashMap<Term, Vec<usize>>; 3],
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Query {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl FromStr for Query {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::parse_query(s)
+    }
+}
+
+impl Query {
+    #[inline(always)]
+    pub fn is_select(&self) -> bool {
+        matches!(self.form, QueryForm::Select { .. })
+    }
+
+    #[inline(always)]
+    pub fn is_ask(&self) -> bool {
+        matches!(self.form, QueryForm::Ask)
+    }
+
+    #[inline(always)]
+    pub fn is_construct(&self) -> bool {
+        matches!(self.form, QueryForm::Construct { .. })
+    }
+
+    ///
+    /// Execute this query over `graph`.
+    ///
+    pub fn execute(&self, graph: &Graph) -> Result<QueryResults, Error> {
+        let triples = Triples::from(graph);
+        let mut solutions = self.pattern.evaluate(&triples);
+
+        if let QueryForm::Ask = self.form {
+            return Ok(QueryResults::Boolean(!solutions.is_empty()));
+        }
+
+        if !self.modifiers.order_by.is_empty() {
+            let keys: Vec<Vec<Option<Term>>> = solutions
+                .iter()
+                .map(|solution| {
+                    self.modifiers
+                        .order_by
+                        .iter()
+                        .map(|condition| condition.expression.evaluate(solution))
+                        .collect()
+                })
+                .collect();
+            let mut indexed: Vec<(Vec<Option<Term>>, Solution)> =
+                keys.into_iter().zip(solutions).collect();
+            indexed.sort_by(|(lhs, _), (rhs, _)| {
+                lhs.iter()
+                    .zip(rhs.iter())
+                    .zip(self.modifiers.order_by.iter())
+                    .map(|((lhs, rhs), condition)| {
+                        let ordering = order_terms(lhs.as_ref(), rhs.as_ref());
+                        if condition.descending {
+                            ordering.reverse()
+                        } else {
+                            ordering
+                        }
+                    })
+                    .find(|ordering| *ordering != Ordering::Equal)
+                    .unwrap_or(Ordering::Equal)
+            });
+            solutions = indexed.into_iter().map(|(_, solution)| solution).collect();
+        }
+
+        match &self.form {
+            QueryForm::Select {
+                distinct,
+                variables,
+            } => {
+                let variables = variables
+                    .clone()
+                    .unwrap_or_else(|| self.pattern.variables());
+                let mut solutions: Vec<Solution> = solutions
+                    .into_iter()
+                    .map(|solution| solution.project(&variables))
+                    .collect();
+                if *distinct {
+                    let mut seen = HashSet::new();
+                    solutions.retain(|solution| seen.insert(solution.clone()));
+                }
+                let solutions = self.modifiers.slice(solutions);
+                Ok(QueryResults::Solutions {
+                    variables,
+                    solutions,
+                })
+            }
+            QueryForm::Construct { template } => {
+                let mut used = triples.blank_node_labels();
+                let mut graph = Graph::default();
+                for solution in self.modifiers.slice(solutions) {
+                    // template blank nodes are fresh for each solution, and distinct from those
+                    // in the graph.
+                    let mut blank_nodes: HashMap<String, String> = HashMap::default();
+                    let mut blank_node = |label: &str| {
+                        blank_nodes
+                            .entry(label.to_string())
+                            .or_insert_with(|| fresh_blank_node_label(&mut used, label))
+                            .clone()
+                    };
+                    for pattern in template {
+                        if let Some(statement) = pattern.instantiate(&solution, &mut blank_node) {
+                            graph.insert(statement);
+                        }
+                    }
+                }
+                Ok(QueryResults::Graph(graph))
+            }
+            QueryForm::Ask => unreachable!(),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Term {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Iri(iri) => write!(f, "<{iri}>"),
+            Self::BlankNode(label) => write!(f, "_:{label}"),
+            Self::Literal {
+                lexical_form,
+                data_type,
+                language,
+            } => {
+                write!(f, "\"{}\"", escape(lexical_form))?;
+                if let Some(language) = language {
+                    write!(f, "@{language}")
+                } else if let Some(data_type) = data_type {
+                    write!(f, "^^<{data_type}>")
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+impl From<Url> for Term {
+    fn from(value: Url) -> Self {
+        Self::Iri(value)
+    }
+}
+
+impl Term {
+    ///
+    /// Construct a simple literal, with neither data type nor language.
+    ///
+    pub fn plain<S: Into<String>>(value: S) -> Self {
+        Self::Literal {
+            lexical_form: value.into(),
+            data_type: None,
+            language: None,
+        }
+    }
+
+    ///
+    /// Construct a typed literal, a data type of `xsd:string` is the same as a simple literal.
+    ///
+    pub fn typed<S: Into<String>>(value: S, data_type: Url) -> Self {
+        Self::Literal {
+            lexical_form: value.into(),
+            data_type: if data_type.as_str() == XSD_STRING {
+                None
+            } else {
+                Some(data_type)
+            },
+            language: None,
+        }
+    }
+
+    pub fn boolean(value: bool) -> Self {
+        Self::typed(value.to_string(), xsd_url(XSD_BOOLEAN))
+    }
+
+    pub fn integer(value: i64) -> Self {
+        Self::typed(value.to_string(), xsd_url(XSD_INTEGER))
+    }
+
+    #[inline(always)]
+    pub fn is_iri(&self) -> bool {
+        matches!(self, Self::Iri(_))
+    }
+
+    #[inline(always)]
+    pub fn is_blank_node(&self) -> bool {
+        matches!(self, Self::BlankNode(_))
+    }
+
+    #[inline(always)]
+    pub fn is_literal(&self) -> bool {
+        matches!(self, Self::Literal { .. })
+    }
+
+    ///
+    /// The IRI, blank node label, or literal lexical form of this term.
+    ///
+    pub fn value(&self) -> &str {
+        match self {
+            Self::Iri(iri) => iri.as_str(),
+            Self::BlankNode(label) => label,
+            Self::Literal { lexical_form, .. } => lexical_form,
+        }
+    }
+
+    pub fn from_subject(subject: &SubjectNode) -> Option<Self> {
+        if let Some(iri) = subject.as_resource() {
+            Some(Self::Iri(iri.clone()))
+        } else {
+            subject
+                .as_blank()
+                .map(|blank| Self::BlankNode(blank.to_string()))
+        }
+    }
+
+    pub fn from_object(object: &ObjectNode) -> Option<Self> {
+        if let Some(iri) = object.as_resource() {
+            Some(Self::Iri(iri.clone()))
+        } else if let Some(blank) = object.as_blank() {
+            Some(Self::BlankNode(blank.to_string()))
+        } else {
+            object.as_literal().map(|literal| {
+                let lexical_form = unescape(literal.lexical_form());
+                if let Some(language) = literal.language() {
+                    Self::Literal {
+                        lexical_form,
+                        data_type: None,
+                        language: Some(language.to_string()),
+                    }
+                } else if let Some(data_type) = literal.data_type() {
+                    Self::typed(lexical_form, data_type.as_iri().clone())
+                } else {
+                    Self::plain(lexical_form)
+                }
+            })
+        }
+    }
+
+    pub fn to_subject(&self) -> Option<SubjectNode> {
+        match self {
+            Self::Iri(iri) => Some(SubjectNode::from(iri)),
+            Self::BlankNode(label) => BlankNode::from_str(label).ok().map(SubjectNode::from),
+            Self::Literal { .. } => None,
+        }
+    }
+
+    pub fn to_object(&self) -> Option<ObjectNode> {
+        match self {
+            Self::Iri(iri) => Some(ObjectNode::from(iri)),
+            Self::BlankNode(label) => BlankNode::from_str(label).ok().map(ObjectNode::from),
+            Self::Literal {
+                lexical_form,
+                data_type,
+                language,
+            } => {
+                let literal = match (language, data_type) {
+                    (Some(language), _) => {
+                        Literal::with_language(lexical_form, LanguageTag::parse(language).ok()?)
+                    }
+                    (None, Some(data_type)) => {
+                        Literal::with_data_type_iri(lexical_form, data_type.clone())
+                    }
+                    (None, None) => Literal::plain(lexical_form),
+                };
+                Some(ObjectNode::from(literal))
+            }
+        }
+    }
+
+    fn language(&self) -> Option<&str> {
+        match self {
+            Self::Literal { language, .. } => language.as_deref(),
+            _ => None,
+        }
+    }
+
+    fn is_string(&self) -> bool {
+        matches!(
+            self,
+            Self::Literal {
+                data_type: None,
+                ..
+            }
+        )
+    }
+
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            Self::Literal {
+                lexical_form,
+                data_type: Some(data_type),
+                ..
+            } if is_numeric_type(data_type) => lexical_form.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn as_boolean(&self) -> Option<bool> {
+        match self {
+            Self::Literal {
+                lexical_form,
+                data_type: Some(data_type),
+                ..
+            } if data_type.as_str() == XSD_BOOLEAN => match lexical_form.as_str() {
+                "true" | "1" => Some(true),
+                "false" | "0" => Some(false),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    // A literal with the same language, or data type, as this one.
+    fn with_value(&self, value: String) -> Self {
+        match self {
+            Self::Literal {
+                data_type,
+                language,
+                ..
+            } => Self::Literal {
+                lexical_form: value,
+                data_type: data_type.clone(),
+                language: language.clone(),
+            },
+            _ => Self::plain(value),
+        }
+    }
+
+    // The effective boolean value, or `None` if this is an error.
+    fn effective_boolean_value(&self) -> Option<bool> {
+        if let Some(value) = self.as_boolean() {
+            Some(value)
+        } else if let Some(value) = self.as_number() {
+            Some(value != 0.0 && !value.is_nan())
+        } else if self.is_string() || self.language().is_some() {
+            Some(!self.value().is_empty())
+        } else {
+            None
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Solution {
+    pub fn get(&self, variable: &str) -> Option<&Term> {
+        self.0.get(variable)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Term)> {
+        self.0.iter()
+    }
+
+    fn project(mut self, variables: &[String]) -> Self {
+        self.0.retain(|name, _| variables.contains(name));
+        self
+    }
+
+    fn is_compatible(&self, other: &Self) -> bool {
+        self.0
+            .iter()
+            .all(|(name, term)| other.0.get(name).map(|t| t == term).unwrap_or(true))
+    }
+
+    fn shares_variables(&self, other: &Self) -> bool {
+        self.0.keys().any(|name| other.0.contains_key(name))
+    }
+
+    fn merge(&self, other: &Self) -> Self {
+        let mut solution = self.clone();
+        solution.0.extend(
+            other
+                .0
+                .iter()
+                .map(|(name, term)| (name.clone(), term.clone())),
+        );
+        solution
+    }
+
+    fn bind(&self, pattern: &TermPattern, term: &Term) -> Option<Self> {
+        match pattern {
+            TermPattern::Term(expected) => (expected == term).then(|| self.clone()),
+            TermPattern::Variable(name) => match self.0.get(name) {
+                Some(bound) => (bound == term).then(|| self.clone()),
+                None => {
+                    let mut solution = self.clone();
+                    let _ = solution.0.insert(name.clone(), term.clone());
+                    Some(solution)
+                }
+            },
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl QueryResults {
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Solutions { solutions, .. } => solutions.is_empty(),
+            Self::Boolean(value) => !value,
+            Self::Graph(graph) => graph.is_empty(),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Modifiers {
+    fn slice(&self, solutions: Vec<Solution>) -> Vec<Solution> {
+        let solutions = solutions.into_iter().skip(self.offset);
+        match self.limit {
+            Some(limit) => solutions.take(limit).collect(),
+            None => solutions.collect(),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl GroupPattern {
+    // Evaluate this group on its own, the filters in the group apply only to its solutions.
+    fn evaluate(&self, triples: &Triples) -> Vec<Solution> {
+        let mut solutions = self.evaluate_unfiltered(triples);
+        for expression in self.filters() {
+            solutions.retain(|solution| expression.is_true(solution));
+        }
+        solutions
+    }
+
+    fn evaluate_unfiltered(&self, triples: &Triples) -> Vec<Solution> {
+        let mut solutions = vec![Solution::default()];
+        for element in &self.0 {
+            solutions = match element {
+                // extending each solution is the same as joining with the matches of the triple.
+                PatternElement::Triple(pattern) => solutions
+                    .iter()
+                    .flat_map(|solution| pattern.evaluate(triples, solution))
+                    .collect(),
+                PatternElement::Group(group) => join(&solutions, &group.evaluate(triples)),
+                PatternElement::Union(groups) => {
+                    let alternatives: Vec<Solution> = groups
+                        .iter()
+                        .flat_map(|group| group.evaluate(triples))
+                        .collect();
+                    join(&solutions, &alternatives)
+                }
+                PatternElement::Optional(group) => {
+                    // filters in the optional group are the condition of the left join.
+                    let optional = group.evaluate_unfiltered(triples);
+                    let filters: Vec<&Expression> = group.filters().collect();
+                    left_join(&solutions, &optional, &filters)
+                }
+                PatternElement::Minus(group) => {
+                    let removed = group.evaluate(triples);
+                    solutions
+                        .into_iter()
+                        .filter(|solution| {
+                            !removed.iter().any(|other| {
+                                solution.shares_variables(other) && solution.is_compatible(other)
+                            })
+                        })
+                        .collect()
+                }
+                PatternElement::Filter(_) => solutions,
+            };
+        }
+        solutions
+    }
+
+    fn filters(&self) -> impl Iterator<Item = &Expression> {
+        self.0.iter().filter_map(|element| match element {
+            PatternElement::Filter(expression) => Some(expression),
+            _ => None,
+        })
+    }
+
+    // The variables in the order they first appear, excluding blank nodes.
+    fn variables(&self) -> Vec<String> {
+        let mut variables = Vec::default();
+        self.add_variables(&mut variables);
+        variables
+    }
+
+    fn add_variables(&self, variables: &mut Vec<String>) {
+        for element in &self.0 {
+            match element {
+                PatternElement::Triple(pattern) => {
+                    for term in [&pattern.subject, &pattern.predicate, &pattern.object] {
+                        if let TermPattern::Variable(name) = term {
+                            if !name.starts_with(BLANK_VARIABLE_PREFIX) && !variables.contains(name)
+                            {
+                                variables.push(name.clone());
+                            }
+                        }
+                    }
+                }
+                PatternElement::Group(group) | PatternElement::Optional(group) => {
+                    group.add_variables(variables)
+                }
+                PatternElement::Union(groups) => groups
+                    .iter()
+                    .for_each(|group| group.add_variables(variables)),
+                PatternElement::Minus(_) | PatternElement::Filter(_) => {}
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl TriplePattern {
+    fn evaluate(&self, triples: &Triples, solution: &Solution) -> Vec<Solution> {
+        triples
+            .matching([
+                self.subject.bound(solution),
+                self.predicate.bound(solution),
+                self.object.bound(solution),
+            ])
+            .filter_map(|[subject, predicate, object]| {
+                solution
+                    .bind(&self.subject, subject)
+                    .and_then(|solution| solution.bind(&self.predicate, predicate))
+                    .and_then(|solution| solution.bind(&self.object, object))
+            })
+            .collect()
+    }
+
+    fn instantiate(
+        &self,
+        solution: &Solution,
+        blank_node: &mut impl FnMut(&str) -> String,
+    ) -> Option<Statement> {
+        let mut term = |pattern: &TermPattern| match pattern {
+            TermPattern::Term(term) => Some(term.clone()),
+            TermPattern::Variable(name) => match name.strip_prefix(BLANK_VARIABLE_PREFIX) {
+                Some(label) => Some(Term::BlankNode(blank_node(label))),
+                None => solution.get(name).cloned(),
+            },
+        };
+        let Some(Term::Iri(predicate)) = term(&self.predicate) else {
+            return None;
+        };
+        Some(Statement::new(
+            term(&self.subject)?.to_subject()?,
+            predicate,
+            term(&self.object)?.to_object()?,
+        ))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl TermPattern {
+    // The term this pattern matches in `solution`, or `None` if it matches any term.
+    fn bound<'a>(&'a self, solution: &'a Solution) -> Option<&'a Term> {
+        match self {
+            Self::Term(term) => Some(term),
+            Self::Variable(name) => solution.get(name),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Expression {
+    fn is_true(&self, solution: &Solution) -> bool {
+        self.evaluate(solution)
+            .and_then(|term| term.effective_boolean_value())
+            .unwrap_or(false)
+    }
+
+    // Evaluate this expression, `None` represents both an unbound variable and an error.
+    fn evaluate(&self, solution: &Solution) -> Option<Term> {
+        match self {
+            Self::Or(lhs, rhs) => {
+                let lhs = lhs
+                    .evaluate(solution)
+                    .and_then(|t| t.effective_boolean_value());
+                let rhs = rhs
+                    .evaluate(solution)
+                    .and_then(|t| t.effective_boolean_value());
+                match (lhs, rhs) {
+                    (Some(true), _) | (_, Some(true)) => Some(Term::boolean(true)),
+                    (Some(false), Some(false)) => Some(Term::boolean(false)),
+                    _ => None,
+                }
+            }
+            Self::And(lhs, rhs) => {
+                let lhs = lhs
+                    .evaluate(solution)
+                    .and_then(|t| t.effective_boolean_value());
+                let rhs = rhs
+                    .evaluate(solution)
+                    .and_then(|t| t.effective_boolean_value());
+                match (lhs, rhs) {
+                    (Some(false), _) | (_, Some(false)) => Some(Term::boolean(false)),
+                    (Some(true), Some(true)) => Some(Term::boolean(true)),
+                    _ => None,
+                }
+            }
+            Self::Not(expression) => expression
+                .evaluate(solution)
+                .and_then(|t| t.effective_boolean_value())
+                .map(|value| Term::boolean(!value)),
+            Self::Compare(comparison, lhs, rhs) => {
+                let lhs = lhs.evaluate(solution)?;
+                let rhs = rhs.evaluate(solution)?;
+                comparison.compare(&lhs, &rhs).map(Term::boolean)
+            }
+            Self::Function(function, arguments) => function.call(arguments, solution),
+            Self::Regex(text, pattern) => {
+                let text = text.evaluate(solution)?;
+                (text.is_string() || text.language().is_some())
+                    .then(|| Term::boolean(pattern.regex.is_match(text.value())))
+            }
+            Self::Variable(name) => solution.get(name).cloned(),
+            Self::Term(term) => Some(term.clone()),
+        }
+    }
+}
+
+impl Comparison {
+    fn compare(&self, lhs: &Term, rhs: &Term) -> Option<bool> {
+        let ordering = match (lhs.as_number(), rhs.as_number()) {
+            (Some(lhs), Some(rhs)) => lhs.partial_cmp(&rhs),
+            _ => match (lhs, rhs) {
+                (
+                    Term::Literal {
+                        lexical_form: lhs_value,
+                        data_type: lhs_type,
+                        language: lhs_language,
+                    },
+                    Term::Literal {
+                        lexical_form: rhs_value,
+                        data_type: rhs_type,
+                        language: rhs_language,
+                    },
+                ) if lhs_type == rhs_type && lhs_language == rhs_language => {
+                    Some(lhs_value.cmp(rhs_value))
+                }
+                _ => {
+                    // Other terms may only be tested for equality.
+                    return match self {
+                        Self::Equal => Some(lhs == rhs),
+                        Self::NotEqual => Some(lhs != rhs),
+                        _ => None,
+                    };
+                }
+            },
+        }?;
+        Some(match self {
+            Self::Equal => ordering == Ordering::Equal,
+            Self::NotEqual => ordering != Ordering::Equal,
+            Self::Less => ordering == Ordering::Less,
+            Self::LessOrEqual => ordering != Ordering::Greater,
+            Self::Greater => ordering == Ordering::Greater,
+            Self::GreaterOrEqual => ordering != Ordering::Less,
+        })
+    }
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_uppercase().as_str() {
+            "BOUND" => Self::Bound,
+            "ISIRI" | "ISURI" => Self::IsIri,
+            "ISBLANK" => Self::IsBlank,
+            "ISLITERAL" => Self::IsLiteral,
+            "STR" => Self::Str,
+            "LANG" => Self::Lang,
+            "DATATYPE" => Self::Datatype,
+            "SAMETERM" => Self::SameTerm,
+            "LANGMATCHES" => Self::LangMatches,
+            "CONTAINS" => Self::Contains,
+            "STRSTARTS" => Self::StrStarts,
+            "STRENDS" => Self::StrEnds,
+            "STRLEN" => Self::StrLen,
+            "LCASE" => Self::LCase,
+            "UCASE" => Self::UCase,
+            "REGEX" => Self::Regex,
+            _ => return None,
+        })
+    }
+
+    fn arity(&self) -> (usize, usize) {
+        match self {
+            Self::SameTerm
+            | Self::LangMatches
+            | Self::Contains
+            | Self::StrStarts
+            | Self::StrEnds => (2, 2),
+            Self::Regex => (2, 3),
+            _ => (1, 1),
+        }
+    }
+
+    fn call(&self, arguments: &[Expression], solution: &Solution) -> Option<Term> {
+        if let Self::Bound = self {
+            return match arguments.first() {
+                Some(Expression::Variable(name)) => {
+                    Some(Term::boolean(solution.get(name).is_some()))
+                }
+                _ => None,
+            };
+        }
+        let arguments = arguments
+            .iter()
+            .map(|argument| argument.evaluate(solution))
+            .collect::<Option<Vec<Term>>>()?;
+        let first = arguments.first()?;
+        let string_argument = |index: usize| -> Option<&str> {
+            arguments
+                .get(index)
+                .filter(|term| term.is_literal() && (term.is_string() || term.language().is_some()))
+                .map(|term| term.value())
+        };
+        match self {
+            Self::Bound => unreachable!(),
+            Self::IsIri => Some(Term::boolean(first.is_iri())),
+            Self::IsBlank => Some(Term::boolean(first.is_blank_node())),
+            Self::IsLiteral => Some(Term::boolean(first.is_literal())),
+            Self::Str => (!first.is_blank_node()).then(|| Term::plain(first.value())),
+            Self::Lang => first
+                .is_literal()
+                .then(|| Term::plain(first.language().unwrap_or_default())),
+            Self::Datatype => match first {
+                Term::Literal {
+                    data_type: Some(data_type),
+                    ..
+                } => Some(Term::Iri(data_type.clone())),
+                Term::Literal { language: None, .. } => Some(Term::Iri(xsd_url(XSD_STRING))),
+                Term::Literal { .. } => Some(Term::Iri(Url::parse(RDF_LANG_STRING).ok()?)),
+                _ => None,
+            },
+            Self::SameTerm => Some(Term::boolean(first == arguments.get(1)?)),
+            Self::LangMatches => {
+                let language = string_argument(0)?.to_lowercase();
+                let range = string_argument(1)?.to_lowercase();
+                Some(Term::boolean(if range == "*" {
+                    !language.is_empty()
+                } else {
+                    language == range || language.starts_with(&format!("{range}-"))
+                }))
+            }
+            Self::Contains => Some(Term::boolean(
+                string_argument(0)?.contains(string_argument(1)?),
+            )),
+            Self::StrStarts => Some(Term::boolean(
+                string_argument(0)?.starts_with(string_argument(1)?),
+            )),
+            Self::StrEnds => Some(Term::boolean(
+                string_argument(0)?.ends_with(string_argument(1)?),
+            )),
+            Self::StrLen => Some(Term::integer(
+                string_argument(0)?.chars().count().try_into().ok()?,
+            )),
+            Self::LCase => Some(first.with_value(string_argument(0)?.to_lowercase())),
+            Self::UCase => Some(first.with_value(string_argument(0)?.to_uppercase())),
+            // compiled into `Expression::Regex` when the query is parsed.
+            Self::Regex => unreachable!(),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl PartialEq for RegexPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern && self.flags == other.flags
+    }
+}
+
+impl RegexPattern {
+    // Compile `pattern` with the SPARQL flags `i`, `m`, `s`, and `x`.
+    fn new(pattern: &str, flags: &str) -> Result<Self, String> {
+        if let Some(flag) = flags.chars().find(|c| !"imsx".contains(*c)) {
+            return Err(format!(
+                "the regular expression flag `{flag}` is not supported"
+            ));
+        }
+        let regex = RegexBuilder::new(pattern)
+            .case_insensitive(flags.contains('i'))
+            .multi_line(flags.contains('m'))
+            .dot_matches_new_line(flags.contains('s'))
+            .ignore_whitespace(flags.contains('x'))
+            .build()
+            .map_err(|e| format!("the regular expression `{pattern}` is not valid: {e}"))?;
+        Ok(Self {
+            pattern: pattern.to_string(),
+            flags: flags.to_string(),
+            regex,
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl From<&Graph> for Triples {
+    fn from(graph: &Graph) -> Self {
+        let triples: Vec<[Term; 3]> = graph
+            .statements()
+            .filter_map(|statement| {
+                Some([
+                    Term::from_subject(statement.subject())?,
+                    Term::Iri(statement.predicate().clone()),
+                    Term::from_object(statement.object())?,
+                ])
+            })
+            .collect();
+        let mut index: [HashMap<Term, Vec<usize>>; 3] = Default::default();
+        for (position, triple) in triples.iter().enumerate() {
+            for (index, term) in index.iter_mut().zip(triple) {
+                index.entry(term.clone()).or_default().push(position);
+            }
+        }
+        Self { triples, index }
+    }
+}
+
+impl Triples {
+    // The triples that may match the bound subject, predicate, and object terms, using the
+    // smallest index of those that are bound.
+    fn matching(&self, bound: [Option<&Term>; 3]) -> Box<dyn Iterator<Item = &[Term; 3]> + '_> {
+        let mut smallest: Option<&[usize]> = None;
+        for (index, term) in self.index.iter().zip(bound) {
+            if let Some(term) = term {
+                let positions = index.get(term).map(Vec::as_slice).unwrap_or_default();
+                if smallest.is_none_or(|smallest| positions.len() < smallest.len()) {
+                    smallest = Some(positions);
+                }
+            }
+        }
+        match smallest {
+            Some(positions) => Box::new(positions.iter().map(|position| &self.triples[*position])),
+            None => Box::new(self.triples.iter()),
+        }
+    }
+
+    fn blank_node_labels(&self) -> HashSet<String> {
+        self.triples
+            .iter()
+            .flatten()
+            .filter_map(|term| match term {
+                Term::BlankNode(label) => Some(label.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+const XSD_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema#";
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const RDF_LANG_STRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString";
+
+// Blank nodes in a query pattern are variables that may not be selected.
+const BLANK_VARIABLE_PREFIX: &str = "_:";
+
+fn join(lhs: &[Solution], rhs: &[Solution]) -> Vec<Solution> {
+    lhs.iter()
+        .flat_map(|lhs| {
+            rhs.iter()
+                .filter(|rhs| lhs.is_compatible(rhs))
+                .map(|rhs| lhs.merge(rhs))
+        })
+        .collect()
+}
+
+// Each solution on the left is extended by the compatible solutions on the right that satisfy
+// every filter, or is kept unchanged if there are none.
+fn left_join(lhs: &[Solution], rhs: &[Solution], filters: &[&Expression]) -> Vec<Solution> {
+    lhs.iter()
+        .flat_map(|lhs| {
+            let extended: Vec<Solution> = rhs
+                .iter()
+                .filter(|rhs| lhs.is_compatible(rhs))
+                .map(|rhs| lhs.merge(rhs))
+                .filter(|solution| filters.iter().all(|filter| filter.is_true(solution)))
+                .collect();
+            if extended.is_empty() {
+                vec![lhs.clone()]
+            } else {
+                extended
+            }
+        })
+        .collect()
+}
+
+// A blank node label, based on `label`, that is not yet used.
+fn fresh_blank_node_label(used: &mut HashSet<String>, label: &str) -> String {
+    let mut index = used.len();
+    loop {
+        let candidate = format!("{label}{index}");
+        if used.insert(candidate.clone()) {
+            return candidate;
+        }
+        index += 1;
+    }
+}
+
+fn xsd_url(iri: &str) -> Url {
+    Url::parse(iri).expect("invalid XML Schema IRI")
+}
+
+fn is_numeric_type(data_type: &Url) -> bool {
+    data_type
+        .as_str()
+        .strip_prefix(XSD_NAMESPACE)
+        .is_some_and(|name| {
+            matches!(
+                name,
+                "integer"
+                    | "decimal"
+                    | "double"
+                    | "float"
+                    | "long"
+                    | "int"
+                    | "short"
+                    | "byte"
+                    | "nonNegativeInteger"
+                    | "nonPositiveInteger"
+                    | "negativeInteger"
+                    | "positiveInteger"
+                    | "unsignedLong"
+                    | "unsignedInt"
+                    | "unsignedShort"
+                    | "unsignedByte"
+            )
+        })
+}
+
+// Unbound values sort first, then blank nodes, IRIs, and literals.
+fn order_terms(lhs: Option<&Term>, rhs: Option<&Term>) -> Ordering {
+    let rank = |term: Option<&Term>| match term {
+        None => 0,
+        Some(Term::BlankNode(_)) => 1,
+        Some(Term::Iri(_)) => 2,
+        Some(Term::Literal { .. }) => 3,
+    };
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) if lhs.is_literal() && rhs.is_literal() => {
+            match (lhs.as_number(), rhs.as_number()) {
+                (Some(l), Some(r)) => l.partial_cmp(&r).unwrap_or(Ordering::Equal),
+                _ => lhs.value().cmp(rhs.value()),
+            }
+        }
+        (Some(lhs), Some(rhs)) if rank(Some(lhs)) == rank(Some(rhs)) => {
+            lhs.value().cmp(rhs.value())
+        }
+        _ => rank(lhs).cmp(&rank(rhs)),
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Literals in the graph hold their lexical form escaped as by the `Debug` format of `str`.
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('t') => unescaped.push('\t'),
+            Some('0') => unescaped.push('\0'),
+            Some('u') => {
+                let code: String = chars
+                    .by_ref()
+                    .skip_while(|c| *c == '{')
+                    .take_while(|c| *c != '}')
+                    .collect();
+                if let Some(c) = u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                    unescaped.push(c);
+                }
+            }
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
+
+mod parser;
@@ -0,0 +1,866 @@
+/*!
+Provides the tokenizer and recursive descent parser for the supported subset of SPARQL.
+*/
+
+use super::{
+    Comparison, Expression, Function, GroupPattern, Modifiers, OrderCondition, PatternElement,
+    Query, QueryForm, RegexPattern, Term, TermPattern, TriplePattern, BLANK_VARIABLE_PREFIX,
+    RDF_TYPE, XSD_BOOLEAN, XSD_NAMESPACE,
+};
+use sdml_errors::Error;
+use std::collections::HashMap;
+use url::Url;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+pub(super) fn parse_query(source: &str) -> Result<Query, Error> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser {
+        source,
+        tokens,
+        position: 0,
+        base: None,
+        prefixes: Default::default(),
+        anonymous: 0,
+    };
+    parser.query()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    IriRef(String),
+    PrefixedName(String, String),
+    Variable(String),
+    BlankNode(String),
+    String(String),
+    LanguageTag(String),
+    Integer(String),
+    Decimal(String),
+    Double(String),
+    Word(String),
+    Punctuation(&'static str),
+}
+
+#[derive(Clone, Debug)]
+struct Located {
+    token: Token,
+    line: usize,
+    column: usize,
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Located>,
+    position: usize,
+    base: Option<Url>,
+    prefixes: HashMap<String, String>,
+    anonymous: usize,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Parser<'_> {
+    fn query(&mut self) -> Result<Query, Error> {
+        self.prologue()?;
+        let form = if self.is_keyword("SELECT") {
+            self.select()?
+        } else if self.is_keyword("ASK") {
+            self.advance();
+            QueryForm::Ask
+        } else if self.is_keyword("CONSTRUCT") {
+            self.advance();
+            if self.is_keyword("WHERE") {
+                // the short form, the pattern is also the template.
+                QueryForm::Construct {
+                    template: Vec::default(),
+                }
+            } else {
+                self.expect_punctuation("{")?;
+                let template = self.triples_template()?;
+                QueryForm::Construct { template }
+            }
+        } else if self.is_keyword("DESCRIBE") {
+            return Err(self.error("DESCRIBE queries are not supported"));
+        } else {
+            return Err(self.error("expecting one of SELECT, ASK, or CONSTRUCT"));
+        };
+
+        if self.is_keyword("FROM") {
+            return Err(self.error("datasets, FROM and FROM NAMED, are not supported"));
+        }
+        let _ = self.accept_keyword("WHERE");
+        let pattern = self.group_pattern()?;
+
+        let form = match form {
+            QueryForm::Construct { template } if template.is_empty() => QueryForm::Construct {
+                template: pattern
+                    .0
+                    .iter()
+                    .map(|element| match element {
+                        PatternElement::Triple(triple) => Ok(triple.as_ref().clone()),
+                        _ => Err(self
+                            .error("the short form of CONSTRUCT may only contain triple patterns")),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            },
+            form => form,
+        };
+
+        let modifiers = self.modifiers()?;
+        match self.peek() {
+            None => Ok(Query {
+                source: self.source.to_string(),
+                form,
+                pattern,
+                modifiers,
+            }),
+            Some(_) => Err(self.error("unexpected content after the end of the query")),
+        }
+    }
+
+    fn prologue(&mut self) -> Result<(), Error> {
+        loop {
+            if self.accept_keyword("BASE") {
+                let iri = self.iri_ref()?;
+                self.base = Some(iri);
+            } else if self.accept_keyword("PREFIX") {
+                let prefix = match self.next() {
+                    Some(Token::PrefixedName(prefix, local)) if local.is_empty() => prefix,
+                    _ => return Err(self.error("expecting a prefix name, such as `rdfs:`")),
+                };
+                let iri = self.iri_ref()?;
+                let _ = self.prefixes.insert(prefix, iri.to_string());
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn select(&mut self) -> Result<QueryForm, Error> {
+        self.advance();
+        let distinct = self.accept_keyword("DISTINCT") || self.accept_keyword("REDUCED");
+        let variables = if self.accept_punctuation("*") {
+            None
+        } else {
+            let mut variables = Vec::default();
+            while let Some(Token::Variable(name)) = self.peek() {
+                variables.push(name.clone());
+                self.advance();
+            }
+            if self.is_punctuation("(") {
+                return Err(self.error("expressions in the SELECT clause are not supported"));
+            }
+            if variables.is_empty() {
+                return Err(self.error("expecting `*` or one or more variables"));
+            }
+            Some(variables)
+        };
+        Ok(QueryForm::Select {
+            distinct,
+            variables,
+        })
+    }
+
+    fn modifiers(&mut self) -> Result<Modifiers, Error> {
+        let mut modifiers = Modifiers::default();
+        if self.is_keyword("GROUP") || self.is_keyword("HAVING") {
+            return Err(self.error("aggregates are not supported"));
+        }
+        if self.accept_keyword("ORDER") {
+            if !self.accept_keyword("BY") {
+                return Err(self.error("expecting BY after ORDER"));
+            }
+            loop {
+                let descending = if self.accept_keyword("ASC") {
+                    Some(false)
+                } else if self.accept_keyword("DESC") {
+                    Some(true)
+                } else {
+                    None
+                };
+                let expression = match descending {
+                    Some(_) => self.bracketted_expression()?,
+                    None => match self.peek() {
+                        Some(Token::Variable(name)) => {
+                            let name = name.clone();
+                            self.advance();
+                            Expression::Variable(name)
+                        }
+                        Some(Token::Punctuation("(")) => self.bracketted_expression()?,
+                        Some(Token::Word(_)) if !self.is_modifier_keyword() => {
+                            self.function_call()?
+                        }
+                        _ => break,
+                    },
+                };
+                modifiers.order_by.push(OrderCondition {
+                    expression,
+                    descending: descending.unwrap_or_default(),
+                });
+            }
+            if modifiers.order_by.is_empty() {
+                return Err(self.error("expecting one or more order conditions"));
+            }
+        }
+        loop {
+            if self.accept_keyword("LIMIT") {
+                modifiers.limit = Some(self.count()?);
+            } else if self.accept_keyword("OFFSET") {
+                modifiers.offset = self.count()?;
+            } else {
+                return Ok(modifiers);
+            }
+        }
+    }
+
+    fn count(&mut self) -> Result<usize, Error> {
+        match self.next() {
+            Some(Token::Integer(value)) => value
+                .parse()
+                .map_err(|_| self.error("expecting a non-negative integer")),
+            _ => Err(self.error("expecting a non-negative integer")),
+        }
+    }
+
+    fn group_pattern(&mut self) -> Result<GroupPattern, Error> {
+        self.expect_punctuation("{")?;
+        if self.is_keyword("SELECT") {
+            return Err(self.error("sub-queries are not supported"));
+        }
+        let mut elements = Vec::default();
+        loop {
+            if self.accept_punctuation("}") {
+                return Ok(GroupPattern(elements));
+            } else if self.accept_punctuation(".") {
+                continue;
+            } else if self.accept_keyword("OPTIONAL") {
+                elements.push(PatternElement::Optional(self.group_pattern()?));
+            } else if self.accept_keyword("MINUS") {
+                elements.push(PatternElement::Minus(self.group_pattern()?));
+            } else if self.accept_keyword("FILTER") {
+                let expression = if self.is_punctuation("(") {
+                    self.bracketted_expression()?
+                } else {
+                    self.function_call()?
+                };
+                elements.push(PatternElement::Filter(expression));
+            } else if self.is_punctuation("{") {
+                let mut groups = vec![self.group_pattern()?];
+                while self.accept_keyword("UNION") {
+                    groups.push(self.group_pattern()?);
+                }
+                if groups.len() == 1 {
+                    elements.push(PatternElement::Group(groups.remove(0)));
+                } else {
+                    elements.push(PatternElement::Union(groups));
+                }
+            } else if let Some(Token::Word(word)) = self.peek() {
+                if word != "a" && !self.is_boolean() {
+                    let message = format!("{} is not supported", word.to_uppercase());
+                    return Err(self.error(message));
+                }
+                self.triples_same_subject(&mut elements)?;
+            } else if self.peek().is_none() {
+                return Err(self.error("expecting `}` to end the group"));
+            } else {
+                self.triples_same_subject(&mut elements)?;
+            }
+        }
+    }
+
+    fn triples_template(&mut self) -> Result<Vec<TriplePattern>, Error> {
+        let mut elements = Vec::default();
+        loop {
+            if self.accept_punctuation("}") {
+                break;
+            } else if self.accept_punctuation(".") {
+                continue;
+            } else if self.peek().is_none() {
+                return Err(self.error("expecting `}` to end the template"));
+            }
+            self.triples_same_subject(&mut elements)?;
+        }
+        Ok(elements
+            .into_iter()
+            .filter_map(|element| match element {
+                PatternElement::Triple(triple) => Some(*triple),
+                _ => None,
+            })
+            .collect())
+    }
+
+    fn triples_same_subject(&mut self, elements: &mut Vec<PatternElement>) -> Result<(), Error> {
+        let subject = self.term_pattern()?;
+        if let TermPattern::Term(term) = &subject {
+            if term.is_literal() {
+                return Err(self.error("a literal may not be the subject of a triple"));
+            }
+        }
+        loop {
+            let predicate = self.verb()?;
+            loop {
+                let object = self.term_pattern()?;
+                elements.push(PatternElement::Triple(Box::new(TriplePattern {
+                    subject: subject.clone(),
+                    predicate: predicate.clone(),
+                    object,
+                })));
+                if !self.accept_punctuation(",") {
+                    break;
+                }
+            }
+            if !self.accept_punctuation(";") {
+                return Ok(());
+            }
+            while self.accept_punctuation(";") {}
+            if self.is_punctuation(".") || self.is_punctuation("}") {
+                return Ok(());
+            }
+        }
+    }
+
+    fn verb(&mut self) -> Result<TermPattern, Error> {
+        if self.accept_keyword_exact("a") {
+            return Ok(TermPattern::Term(Term::Iri(Url::parse(RDF_TYPE).unwrap())));
+        }
+        match self.term_pattern()? {
+            pattern @ TermPattern::Variable(_) if !is_blank_variable(&pattern) => Ok(pattern),
+            pattern @ TermPattern::Term(Term::Iri(_)) => Ok(pattern),
+            _ => Err(self.error("a predicate must be a variable or an IRI")),
+        }
+    }
+
+    fn term_pattern(&mut self) -> Result<TermPattern, Error> {
+        match self.peek() {
+            Some(Token::Variable(name)) => {
+                let name = name.clone();
+                self.advance();
+                Ok(TermPattern::Variable(name))
+            }
+            Some(Token::BlankNode(label)) => {
+                let name = format!("{BLANK_VARIABLE_PREFIX}{label}");
+                self.advance();
+                Ok(TermPattern::Variable(name))
+            }
+            Some(Token::Punctuation("[")) => {
+                self.advance();
+                if !self.accept_punctuation("]") {
+                    return Err(self.error("blank node property lists are not supported"));
+                }
+                self.anonymous += 1;
+                Ok(TermPattern::Variable(format!(
+                    "{BLANK_VARIABLE_PREFIX}anon{}",
+                    self.anonymous
+                )))
+            }
+            Some(Token::Punctuation("(")) => Err(self.error("collections are not supported")),
+            _ => Ok(TermPattern::Term(self.term()?)),
+        }
+    }
+
+    fn term(&mut self) -> Result<Term, Error> {
+        let negative = if self.accept_punctuation("-") {
+            true
+        } else {
+            let _ = self.accept_punctuation("+");
+            false
+        };
+        let sign = if negative { "-" } else { "" };
+        match self.next() {
+            Some(Token::IriRef(iri)) => self.resolve(&iri).map(Term::Iri),
+            Some(Token::PrefixedName(prefix, local)) => self.expand(&prefix, &local).map(Term::Iri),
+            Some(Token::String(value)) => match self.peek() {
+                Some(Token::LanguageTag(language)) => {
+                    let language = language.clone();
+                    self.advance();
+                    Ok(Term::Literal {
+                        lexical_form: value,
+                        data_type: None,
+                        language: Some(language),
+                    })
+                }
+                Some(Token::Punctuation("^^")) => {
+                    self.advance();
+                    let data_type = match self.next() {
+                        Some(Token::IriRef(iri)) => self.resolve(&iri)?,
+                        Some(Token::PrefixedName(prefix, local)) => self.expand(&prefix, &local)?,
+                        _ => return Err(self.error("expecting a data type IRI after `^^`")),
+                    };
+                    Ok(Term::typed(value, data_type))
+                }
+                _ => Ok(Term::plain(value)),
+            },
+            Some(Token::Integer(value)) => {
+                Ok(Term::typed(format!("{sign}{value}"), xsd_type("integer")))
+            }
+            Some(Token::Decimal(value)) => {
+                Ok(Term::typed(format!("{sign}{value}"), xsd_type("decimal")))
+            }
+            Some(Token::Double(value)) => {
+                Ok(Term::typed(format!("{sign}{value}"), xsd_type("double")))
+            }
+            Some(Token::Word(word)) if !negative && (word == "true" || word == "false") => {
+                Ok(Term::typed(word, Url::parse(XSD_BOOLEAN).unwrap()))
+            }
+            _ => {
+                self.back();
+                Err(self.error("expecting a variable, IRI, or literal"))
+            }
+        }
+    }
+
+    fn bracketted_expression(&mut self) -> Result<Expression, Error> {
+        self.expect_punctuation("(")?;
+        let expression = self.or_expression()?;
+        self.expect_punctuation(")")?;
+        Ok(expression)
+    }
+
+    fn or_expression(&mut self) -> Result<Expression, Error> {
+        let mut lhs = self.and_expression()?;
+        while self.accept_punctuation("||") {
+            let rhs = self.and_expression()?;
+            lhs = Expression::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn and_expression(&mut self) -> Result<Expression, Error> {
+        let mut lhs = self.relational_expression()?;
+        while self.accept_punctuation("&&") {
+            let rhs = self.relational_expression()?;
+            lhs = Expression::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn relational_expression(&mut self) -> Result<Expression, Error> {
+        let lhs = self.unary_expression()?;
+        let comparison = match self.peek() {
+            Some(Token::Punctuation("=")) => Comparison::Equal,
+            Some(Token::Punctuation("!=")) => Comparison::NotEqual,
+            Some(Token::Punctuation("<")) => Comparison::Less,
+            Some(Token::Punctuation("<=")) => Comparison::LessOrEqual,
+            Some(Token::Punctuation(">")) => Comparison::Greater,
+            Some(Token::Punctuation(">=")) => Comparison::GreaterOrEqual,
+            _ => return Ok(lhs),
+        };
+        self.advance();
+        let rhs = self.unary_expression()?;
+        Ok(Expression::Compare(
+            comparison,
+            Box::new(lhs),
+            Box::new(rhs),
+        ))
+    }
+
+    fn unary_expression(&mut self) -> Result<Expression, Error> {
+        if self.accept_punctuation("!") {
+            Ok(Expression::Not(Box::new(self.unary_expression()?)))
+        } else {
+            self.primary_expression()
+        }
+    }
+
+    fn primary_expression(&mut self) -> Result<Expression, Error> {
+        match self.peek() {
+            Some(Token::Punctuation("(")) => self.bracketted_expression(),
+            Some(Token::Variable(name)) => {
+                let name = name.clone();
+                self.advance();
+                Ok(Expression::Variable(name))
+            }
+            Some(Token::Word(_)) if !self.is_boolean() => self.function_call(),
+            _ => Ok(Expression::Term(self.term()?)),
+        }
+    }
+
+    fn function_call(&mut self) -> Result<Expression, Error> {
+        let name = match self.next() {
+            Some(Token::Word(name)) => name,
+            _ => {
+                self.back();
+                return Err(self.error("expecting a function call"));
+            }
+        };
+        let function = Function::from_name(&name).ok_or_else(|| {
+            self.back();
+            self.error(format!("the function `{name}` is not supported"))
+        })?;
+        self.expect_punctuation("(")?;
+        let mut arguments = Vec::default();
+        if !self.accept_punctuation(")") {
+            loop {
+                arguments.push(self.or_expression()?);
+                if self.accept_punctuation(")") {
+                    break;
+                }
+                self.expect_punctuation(",")?;
+            }
+        }
+        let (min, max) = function.arity();
+        if arguments.len() < min || arguments.len() > max {
+            return Err(self.error(format!(
+                "the function `{name}` has the wrong number of arguments"
+            )));
+        }
+        if function == Function::Bound && !matches!(arguments[0], Expression::Variable(_)) {
+            return Err(self.error("the argument to BOUND must be a variable"));
+        }
+        if function == Function::Regex {
+            let mut arguments = arguments.into_iter();
+            let text = arguments.next().unwrap();
+            let mut strings = arguments.map(|argument| match argument {
+                Expression::Term(term) if term.is_string() => Ok(term.value().to_string()),
+                _ => Err(self.error("the pattern and flags of REGEX must be simple literals")),
+            });
+            let pattern = strings.next().unwrap()?;
+            let flags = strings.next().transpose()?.unwrap_or_default();
+            let pattern = RegexPattern::new(&pattern, &flags).map_err(|e| self.error(e))?;
+            return Ok(Expression::Regex(Box::new(text), Box::new(pattern)));
+        }
+        Ok(Expression::Function(function, arguments))
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    fn iri_ref(&mut self) -> Result<Url, Error> {
+        match self.next() {
+            Some(Token::IriRef(iri)) => self.resolve(&iri),
+            _ => Err(self.error("expecting an IRI, such as `<http://example.org/>`")),
+        }
+    }
+
+    fn resolve(&self, iri: &str) -> Result<Url, Error> {
+        match &self.base {
+            Some(base) => base.join(iri),
+            None => Url::parse(iri),
+        }
+        .map_err(|e| self.error(format!("the IRI `{iri}` is not valid: {e}")))
+    }
+
+    fn expand(&self, prefix: &str, local: &str) -> Result<Url, Error> {
+        match self.prefixes.get(prefix) {
+            Some(namespace) => Url::parse(&format!("{namespace}{local}"))
+                .map_err(|e| self.error(format!("the IRI `{prefix}:{local}` is not valid: {e}"))),
+            None => Err(self.error(format!("the prefix `{prefix}:` has not been declared"))),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|located| &located.token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.advance();
+        token
+    }
+
+    fn advance(&mut self) {
+        self.position += 1;
+    }
+
+    fn back(&mut self) {
+        self.position -= 1;
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn is_boolean(&self) -> bool {
+        matches!(self.peek(), Some(Token::Word(word)) if word == "true" || word == "false")
+    }
+
+    fn is_modifier_keyword(&self) -> bool {
+        self.is_keyword("LIMIT") || self.is_keyword("OFFSET")
+    }
+
+    fn accept_keyword(&mut self, keyword: &str) -> bool {
+        let accepted = self.is_keyword(keyword);
+        if accepted {
+            self.advance();
+        }
+        accepted
+    }
+
+    fn accept_keyword_exact(&mut self, keyword: &str) -> bool {
+        let accepted = matches!(self.peek(), Some(Token::Word(word)) if word == keyword);
+        if accepted {
+            self.advance();
+        }
+        accepted
+    }
+
+    fn is_punctuation(&self, punctuation: &str) -> bool {
+        matches!(self.peek(), Some(Token::Punctuation(p)) if *p == punctuation)
+    }
+
+    fn accept_punctuation(&mut self, punctuation: &str) -> bool {
+        let accepted = self.is_punctuation(punctuation);
+        if accepted {
+            self.advance();
+        }
+        accepted
+    }
+
+    fn expect_punctuation(&mut self, punctuation: &str) -> Result<(), Error> {
+        if self.accept_punctuation(punctuation) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expecting `{punctuation}`")))
+        }
+    }
+
+    fn error<S: Into<String>>(&self, message: S) -> Error {
+        let message = message.into();
+        let message = match self.tokens.get(self.position) {
+            Some(located) => format!(
+                "{message}, at line {}, column {}",
+                located.line, located.column
+            ),
+            None => format!("{message}, at the end of the query"),
+        };
+        query_error(self.source, message)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+const PUNCTUATION: [&str; 21] = [
+    "^^", "&&", "||", "!=", "<=", ">=", "{", "}", "(", ")", "[", "]", ".", ",", ";", "*", "=", "<",
+    ">", "!", "-",
+];
+
+fn tokenize(source: &str) -> Result<Vec<Located>, Error> {
+    let mut tokens = Vec::default();
+    let chars: Vec<char> = source.chars().collect();
+    let (mut index, mut line, mut column) = (0, 1, 1);
+
+    let error = |line: usize, column: usize, message: &str| {
+        query_error(
+            source,
+            format!("{message}, at line {line}, column {column}"),
+        )
+    };
+
+    while index < chars.len() {
+        let c = chars[index];
+        let start = (line, column);
+        let mut advance = |count: usize, index: &mut usize| {
+            for _ in 0..count {
+                if chars[*index] == '\n' {
+                    line += 1;
+                    column = 1;
+                } else {
+                    column += 1;
+                }
+                *index += 1;
+            }
+        };
+
+        if c.is_whitespace() {
+            advance(1, &mut index);
+            continue;
+        }
+        if c == '#' {
+            while index < chars.len() && chars[index] != '\n' {
+                advance(1, &mut index);
+            }
+            continue;
+        }
+
+        let rest: String = chars[index..].iter().take(3).collect();
+        let token = if c == '<' && is_iri_ref(&chars[index..]) {
+            let end = chars[index..].iter().position(|c| *c == '>').unwrap();
+            let iri: String = chars[index + 1..index + end].iter().collect();
+            advance(end + 1, &mut index);
+            Token::IriRef(iri)
+        } else if c == '?' || c == '$' {
+            let length = name_length(&chars[index + 1..]);
+            if length == 0 {
+                return Err(error(start.0, start.1, "expecting a variable name"));
+            }
+            let name: String = chars[index + 1..index + 1 + length].iter().collect();
+            advance(length + 1, &mut index);
+            Token::Variable(name)
+        } else if rest.starts_with("_:") {
+            let length = name_length(&chars[index + 2..]);
+            if length == 0 {
+                return Err(error(start.0, start.1, "expecting a blank node label"));
+            }
+            let label: String = chars[index + 2..index + 2 + length].iter().collect();
+            advance(length + 2, &mut index);
+            Token::BlankNode(label)
+        } else if c == '"' || c == '\'' {
+            if rest == "\"\"\"" || rest == "'''" {
+                return Err(error(start.0, start.1, "long strings are not supported"));
+            }
+            let mut value = String::new();
+            let mut end = index + 1;
+            loop {
+                match chars.get(end) {
+                    None | Some('\n') => {
+                        return Err(error(start.0, start.1, "unterminated string"));
+                    }
+                    Some(q) if *q == c => break,
+                    Some('\\') => {
+                        end += 1;
+                        match chars.get(end) {
+                            Some('n') => value.push('\n'),
+                            Some('r') => value.push('\r'),
+                            Some('t') => value.push('\t'),
+                            Some(c) => value.push(*c),
+                            None => return Err(error(start.0, start.1, "unterminated string")),
+                        }
+                    }
+                    Some(c) => value.push(*c),
+                }
+                end += 1;
+            }
+            advance(end + 1 - index, &mut index);
+            Token::String(value)
+        } else if c == '@' {
+            let length = chars[index + 1..]
+                .iter()
+                .take_while(|c| c.is_ascii_alphanumeric() || **c == '-')
+                .count();
+            let language: String = chars[index + 1..index + 1 + length].iter().collect();
+            advance(length + 1, &mut index);
+            Token::LanguageTag(language)
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.get(index + 1).is_some_and(|c| c.is_ascii_digit()))
+        {
+            let mut length = chars[index..]
+                .iter()
+                .take_while(|c| c.is_ascii_digit())
+                .count();
+            let mut token = Token::Integer(String::new());
+            if chars.get(index + length) == Some(&'.')
+                && chars
+                    .get(index + length + 1)
+                    .is_some_and(|c| c.is_ascii_digit())
+            {
+                length += 1 + chars[index + length + 1..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_digit())
+                    .count();
+                token = Token::Decimal(String::new());
+            }
+            if matches!(chars.get(index + length), Some('e' | 'E')) {
+                let mut exponent = 1;
+                if matches!(chars.get(index + length + 1), Some('+' | '-')) {
+                    exponent += 1;
+                }
+                let digits = chars[(index + length + exponent).min(chars.len())..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_digit())
+                    .count();
+                if digits > 0 {
+                    length += exponent + digits;
+                    token = Token::Double(String::new());
+                }
+            }
+            let value: String = chars[index..index + length].iter().collect();
+            advance(length, &mut index);
+            match token {
+                Token::Integer(_) => Token::Integer(value),
+                Token::Decimal(_) => Token::Decimal(value),
+                _ => Token::Double(value),
+            }
+        } else if c.is_alphabetic() || c == '_' || c == ':' {
+            let prefix_length = if c == ':' {
+                0
+            } else {
+                name_length(&chars[index..])
+            };
+            if chars.get(index + prefix_length) == Some(&':') {
+                let prefix: String = chars[index..index + prefix_length].iter().collect();
+                let local_length = name_length(&chars[index + prefix_length + 1..]);
+                let local: String = chars
+                    [index + prefix_length + 1..index + prefix_length + 1 + local_length]
+                    .iter()
+                    .collect();
+                advance(prefix_length + 1 + local_length, &mut index);
+                Token::PrefixedName(prefix, local)
+            } else {
+                let word: String = chars[index..index + prefix_length].iter().collect();
+                advance(prefix_length, &mut index);
+                Token::Word(word)
+            }
+        } else if let Some(punctuation) = PUNCTUATION
+            .iter()
+            .find(|punctuation| rest.starts_with(**punctuation))
+        {
+            advance(punctuation.len(), &mut index);
+            Token::Punctuation(punctuation)
+        } else if c == '+' {
+            advance(1, &mut index);
+            Token::Punctuation("+")
+        } else {
+            return Err(error(
+                start.0,
+                start.1,
+                &format!("unexpected character `{c}`"),
+            ));
+        };
+        tokens.push(Located {
+            token,
+            line: start.0,
+            column: start.1,
+        });
+    }
+    Ok(tokens)
+}
+
+// An IRI reference has no whitespace, or characters not allowed in an IRI, before the `>`.
+fn is_iri_ref(chars: &[char]) -> bool {
+    for c in chars.iter().skip(1) {
+        match c {
+            '>' => return true,
+            '<' | '"' | '{' | '}' | '|' | '^' | '`' | '\\' => return false,
+            c if c.is_whitespace() => return false,
+            _ => {}
+        }
+    }
+    false
+}
+
+// Names may contain `.` but not end with one.
+fn name_length(chars: &[char]) -> usize {
+    let length = chars
+        .iter()
+        .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        .count();
+    chars[..length]
+        .iter()
+        .rposition(|c| *c != '.')
+        .map(|last| last + 1)
+        .unwrap_or(0)
+}
+
+fn is_blank_variable(pattern: &TermPattern) -> bool {
+    matches!(pattern, TermPattern::Variable(name) if name.starts_with(BLANK_VARIABLE_PREFIX))
+}
+
+fn xsd_type(name: &str) -> Url {
+    Url::parse(&format!("{XSD_NAMESPACE}{name}")).unwrap()
+}
+
+fn query_error<S: Into<String>>(query: &str, message: S) -> Error {
+    Error::QueryError {
+        query: query.to_string(),
+        message: message.into(),
+    }
+}
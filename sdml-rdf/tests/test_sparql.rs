@@ -0,0 +1,223 @@
+use rdftk_core::model::graph::Graph;
+use sdml_core::store::InMemoryModuleCache;
+use sdml_parse::load::FsModuleLoader;
+use sdml_rdf::generate::store_to_graph;
+use sdml_rdf::sparql::{Query, QueryResults, Term};
+use std::collections::HashSet;
+use std::io::Cursor;
+use std::str::FromStr;
+
+const RENTALS: &str = r#"module rentals <https://example.org/rentals/>
+  version "1.0" <https://example.org/rentals/1.0> is
+  import [ skos xsd ]
+
+  @skos:prefLabel = "Rentals"@en
+  @skos:altLabel = "Car Rentals"
+end"#;
+
+const BOOKINGS: &str = r#"module bookings <https://example.org/bookings/> is
+  import [ rentals skos ]
+
+  @skos:prefLabel = "Bookings"
+end"#;
+
+const PREFIXES: &str = r#"PREFIX owl: <http://www.w3.org/2002/07/owl#>
+PREFIX skos: <http://www.w3.org/2004/02/skos/>
+"#;
+
+fn graph() -> Graph {
+    let mut cache = InMemoryModuleCache::default().with_stdlib();
+    let mut loader = FsModuleLoader::default();
+    for source in [RENTALS, BOOKINGS] {
+        let _ = loader
+            .load_from_reader(&mut Cursor::new(source.as_bytes()), &mut cache, false)
+            .unwrap();
+    }
+    store_to_graph(&cache).unwrap()
+}
+
+fn execute(query: &str) -> QueryResults {
+    Query::from_str(&format!("{PREFIXES}{query}"))
+        .unwrap()
+        .execute(&graph())
+        .unwrap()
+}
+
+fn select(query: &str) -> Vec<Vec<String>> {
+    match execute(query) {
+        QueryResults::Solutions {
+            variables,
+            solutions,
+        } => solutions
+            .iter()
+            .map(|solution| {
+                variables
+                    .iter()
+                    .map(|variable| {
+                        solution
+                            .get(variable)
+                            .map(Term::to_string)
+                            .unwrap_or_default()
+                    })
+                    .collect()
+            })
+            .collect(),
+        results => panic!("expected solutions, not {results:?}"),
+    }
+}
+
+#[test]
+fn test_select_with_filter() {
+    assert_eq!(
+        select(
+            r#"SELECT ?module ?label WHERE {
+                 ?module a owl:Ontology ; skos:prefLabel ?label .
+                 FILTER(STRSTARTS(STR(?module), "https://example.org/"))
+               } ORDER BY ?label"#
+        ),
+        [
+            ["<https://example.org/bookings/bookings>", "\"Bookings\"",],
+            ["<https://example.org/rentals/rentals>", "\"Rentals\"@en",],
+        ]
+    );
+    assert_eq!(
+        select(
+            r#"SELECT ?label WHERE {
+                 ?module skos:prefLabel ?label FILTER(langMatches(LANG(?label), "en"))
+                 FILTER(regex(?label, "^rent", "i"))
+               }"#
+        ),
+        [["\"Rentals\"@en"]]
+    );
+}
+
+#[test]
+fn test_select_optional_and_union() {
+    assert_eq!(
+        select(
+            r#"SELECT ?module ?version WHERE {
+                 ?module skos:prefLabel ?label .
+                 FILTER(CONTAINS(STR(?module), "example.org"))
+                 OPTIONAL { ?module owl:versionInfo ?version }
+               } ORDER BY DESC(?module)"#
+        ),
+        [
+            ["<https://example.org/rentals/rentals>", "\"1.0\""],
+            ["<https://example.org/bookings/bookings>", ""],
+        ]
+    );
+    assert_eq!(
+        select(
+            r#"SELECT DISTINCT ?label WHERE {
+                 { <https://example.org/rentals/rentals> skos:prefLabel ?label }
+                 UNION
+                 { <https://example.org/rentals/rentals> skos:altLabel ?label }
+               }"#
+        )
+        .len(),
+        2
+    );
+}
+
+#[test]
+fn test_select_filters_scoped_to_group() {
+    assert!(select(
+        r#"SELECT ?label WHERE {
+             ?module skos:prefLabel ?label
+             { FILTER(BOUND(?label)) }
+           }"#
+    )
+    .is_empty());
+    assert_eq!(
+        select(
+            r#"SELECT ?module ?version WHERE {
+                 ?module skos:prefLabel ?label .
+                 FILTER(STRSTARTS(STR(?module), "https://example.org/"))
+                 OPTIONAL { ?module owl:versionInfo ?version FILTER(?label = "Bookings") }
+               } ORDER BY ?module"#
+        ),
+        [
+            ["<https://example.org/bookings/bookings>", ""],
+            ["<https://example.org/rentals/rentals>", ""],
+        ]
+    );
+}
+
+#[test]
+fn test_select_limit_and_offset() {
+    assert_eq!(
+        select(
+            r#"SELECT ?imported WHERE {
+                 <https://example.org/bookings/bookings> owl:imports ?imported
+               } ORDER BY ?imported LIMIT 1 OFFSET 1"#
+        )
+        .len(),
+        1
+    );
+}
+
+#[test]
+fn test_ask() {
+    assert!(matches!(
+        execute("ASK { <https://example.org/bookings/bookings> owl:imports ?module }"),
+        QueryResults::Boolean(true)
+    ));
+    assert!(matches!(
+        execute(r#"ASK { ?module skos:prefLabel "Hire"@en }"#),
+        QueryResults::Boolean(false)
+    ));
+}
+
+#[test]
+fn test_construct() {
+    let QueryResults::Graph(graph) = execute(
+        r#"CONSTRUCT { ?module skos:hiddenLabel ?label }
+           WHERE { ?module a owl:Ontology ; skos:altLabel ?label }"#,
+    ) else {
+        panic!("expected a graph");
+    };
+    assert_eq!(graph.len(), 1);
+    let statement = graph.statements().next().unwrap();
+    assert_eq!(
+        statement.predicate().as_str(),
+        "http://www.w3.org/2004/02/skos/hiddenLabel"
+    );
+}
+
+#[test]
+fn test_construct_fresh_blank_nodes() {
+    let QueryResults::Graph(graph) = execute(
+        r#"CONSTRUCT { _:note skos:note ?label . _:note skos:related ?module }
+           WHERE {
+             ?module skos:prefLabel ?label
+             FILTER(STRSTARTS(STR(?module), "https://example.org/"))
+           }"#,
+    ) else {
+        panic!("expected a graph");
+    };
+    assert_eq!(graph.len(), 4);
+    let subjects: HashSet<String> = graph
+        .statements()
+        .map(|statement| statement.subject().to_string())
+        .collect();
+    assert_eq!(subjects.len(), 2);
+}
+
+#[test]
+fn test_query_syntax_errors() {
+    for query in [
+        "SELECT WHERE { ?s ?p ?o }",
+        "SELECT * WHERE { ?s ?p ?o ",
+        "SELECT * WHERE { ?s undeclared:p ?o }",
+        "SELECT * WHERE { ?s ?p ?o FILTER(NOW()) }",
+        "SELECT * WHERE { ?s ?p ?o } GROUP BY ?s",
+        "SELECT * FROM <https://example.org/> WHERE { ?s ?p ?o }",
+        "DESCRIBE <https://example.org/>",
+        "SELECT * WHERE { \"s\" ?p ?o }",
+        "SELECT * WHERE { ?s ?p ?o FILTER(REGEX(?o, \"(\")) }",
+        "SELECT * WHERE { ?s ?p ?o FILTER(REGEX(?o, \"a\", \"q\")) }",
+        "SELECT * WHERE { ?s ?p ?o FILTER(REGEX(?o, ?p)) }",
+    ] {
+        assert!(Query::from_str(query).is_err(), "expected error: {query}");
+    }
+}